@@ -13,8 +13,8 @@ use zksync_prometheus_exporter::run_prometheus_exporter;
 fn api_client_from_env() -> client::ApiClient {
     let server_api_url = parse_env("API_PROVER_URL");
     let request_timout = Duration::from_secs(parse_env::<u64>("PROVER_PROVER_REQUEST_TIMEOUT"));
-    let secret = get_env("API_PROVER_SECRET_AUTH");
-    client::ApiClient::new(&server_api_url, request_timout, &secret)
+    let auth_token = get_env("PROVER_PROVER_AUTH_TOKEN");
+    client::ApiClient::new(&server_api_url, request_timout, &auth_token)
 }
 
 #[derive(StructOpt)]
@@ -5,44 +5,44 @@ use anyhow::format_err;
 use backoff::future::retry_notify;
 use backoff::Error::{Permanent, Transient};
 use futures::Future;
-use reqwest::Url;
+use reqwest::{StatusCode, Url};
+use serde::{de::DeserializeOwned, Serialize};
 use vlog::*;
 // Workspace deps
-use crate::auth_utils::AuthTokenGenerator;
 use zksync_prover_utils::api::{
-    ProverInputRequest, ProverInputResponse, ProverOutputRequest, ProverStopped, WorkingOn,
+    v2::{
+        ApiError, ErrorCode, HeartbeatRequest, HeartbeatResponse, Job, JobHeartbeat, JobsRequest,
+        JobsResponse,
+    },
+    ProverOutputRequest,
 };
 
+/// Client of the prover server API v2.
 #[derive(Debug, Clone)]
 pub struct ApiClient {
-    get_job_url: Url,
-    working_on_url: Url,
+    jobs_url: Url,
+    heartbeat_url: Url,
     publish_url: Url,
     stopped_url: Url,
     // Client keeps connection pool inside, so it is recommended to reuse it (see docstring for reqwest::Client).
     http_client: reqwest::Client,
-    // A generator that create the authentication token upon request to any endpoint.
-    auth_token_generator: AuthTokenGenerator,
+    // Personal token of the prover, registered on the server.
+    auth_token: String,
 }
 
 impl ApiClient {
-    // The time for which the authorization token will be valid.
-    const AUTH_TOKEN_LIFETIME: Duration = Duration::from_secs(10);
-
-    pub fn new(base_url: &Url, req_server_timeout: Duration, secret: &str) -> Self {
+    pub fn new(base_url: &Url, req_server_timeout: Duration, auth_token: &str) -> Self {
         let http_client = reqwest::ClientBuilder::new()
             .timeout(req_server_timeout)
             .build()
             .expect("Failed to create request client");
-        let auth_token_generator =
-            AuthTokenGenerator::new(secret.to_string(), Self::AUTH_TOKEN_LIFETIME);
         Self {
-            get_job_url: base_url.join("/get_job").unwrap(),
-            working_on_url: base_url.join("/working_on").unwrap(),
-            publish_url: base_url.join("/publish").unwrap(),
-            stopped_url: base_url.join("/stopped").unwrap(),
+            jobs_url: base_url.join("/api/v2/jobs").unwrap(),
+            heartbeat_url: base_url.join("/api/v2/heartbeat").unwrap(),
+            publish_url: base_url.join("/api/v2/publish").unwrap(),
+            stopped_url: base_url.join("/api/v2/stopped").unwrap(),
             http_client,
-            auth_token_generator,
+            auth_token: auth_token.to_string(),
         }
     }
 
@@ -84,114 +84,75 @@ impl ApiClient {
         }
     }
 
-    fn get_encoded_token(&self) -> anyhow::Result<String> {
-        self.auth_token_generator
-            .encode()
-            .map_err(|e| format_err!("failed generate authorization token: {}", e))
-    }
-}
-
-#[async_trait::async_trait]
-impl crate::ApiClient for ApiClient {
-    async fn get_job(&self, req: ProverInputRequest) -> anyhow::Result<ProverInputResponse> {
+    /// Sends the request to the endpoint, retrying it unless the server rejects it.
+    async fn post<Req, Resp>(&self, url: &Url, request: &Req) -> anyhow::Result<Resp>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned,
+    {
         let operation = || async {
-            vlog::trace!("get prover job");
-
             let response = self
                 .http_client
-                .get(self.get_job_url.clone())
-                .bearer_auth(&self.get_encoded_token()?)
-                .json(&req)
+                .post(url.clone())
+                .bearer_auth(&self.auth_token)
+                .json(request)
                 .send()
                 .await
-                .map_err(|e| format_err!("failed to send get job request: {}", e))?;
-
-            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-                return Err(Permanent(format_err!("authorization error")));
+                .map_err(|e| Transient(format_err!("failed to send request to {}: {}", url, e)))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let error = response
+                    .json::<ApiError>()
+                    .await
+                    .map_err(|_| Transient(format_err!("request to {} failed: {}", url, status)))?;
+                return match error.code {
+                    ErrorCode::StorageError => Err(Transient(error.into())),
+                    _ => Err(Permanent(error.into())),
+                };
             }
 
-            response
-                .json()
-                .await
-                .map_err(|e| Transient(format_err!("failed parse json on get job request: {}", e)))
+            let body = response.bytes().await.map_err(|e| {
+                Transient(format_err!("failed to read response from {}: {}", url, e))
+            })?;
+            // Endpoints without output return an empty body.
+            let body = if body.is_empty() {
+                b"null".as_ref()
+            } else {
+                &body[..]
+            };
+            serde_json::from_slice(body).map_err(|e| {
+                Transient(format_err!(
+                    "failed parse json response from {}: {}",
+                    url,
+                    e
+                ))
+            })
         };
 
         self.with_retries(operation).await
     }
+}
 
-    async fn working_on(&self, job_id: i32, prover_name: &str) -> anyhow::Result<()> {
-        let operation = || async {
-            log::trace!(
-                "sending working_on job_id: {}, prover_name: {}",
-                job_id,
-                prover_name
-            );
-
-            let response = self
-                .http_client
-                .post(self.working_on_url.clone())
-                .bearer_auth(&self.get_encoded_token()?)
-                .json(&WorkingOn {
-                    job_id,
-                    prover_name: prover_name.to_string(),
-                })
-                .send()
-                .await
-                .map_err(|e| Transient(format_err!("failed to send working_on request: {}", e)))?;
-
-            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-                return Err(Permanent(format_err!("authorization error")));
-            }
-
-            Ok(())
-        };
+#[async_trait::async_trait]
+impl crate::ApiClient for ApiClient {
+    async fn get_jobs(&self, req: JobsRequest) -> anyhow::Result<Vec<Job>> {
+        vlog::trace!("get prover jobs");
+        let response: JobsResponse = self.post(&self.jobs_url, &req).await?;
+        Ok(response.jobs)
+    }
 
-        self.with_retries(operation).await
+    async fn heartbeat(&self, jobs: Vec<JobHeartbeat>) -> anyhow::Result<HeartbeatResponse> {
+        log::trace!("sending heartbeat for jobs: {:?}", jobs);
+        self.post(&self.heartbeat_url, &HeartbeatRequest { jobs })
+            .await
     }
 
     async fn publish(&self, data: ProverOutputRequest) -> anyhow::Result<()> {
-        let operation = || async {
-            let response = self
-                .http_client
-                .post(self.publish_url.clone())
-                .bearer_auth(&self.get_encoded_token()?)
-                .json(&data)
-                .send()
-                .await
-                .map_err(|e| Transient(format_err!("failed to send publish request: {}", e)))?;
-
-            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-                return Err(Permanent(format_err!("authorization error")));
-            }
-
-            Ok(())
-        };
-
-        self.with_retries(operation).await
+        self.post(&self.publish_url, &data).await
     }
 
-    async fn prover_stopped(&self, prover_name: String) -> anyhow::Result<()> {
-        let operation = || async {
-            let response = self
-                .http_client
-                .post(self.stopped_url.clone())
-                .bearer_auth(&self.get_encoded_token()?)
-                .json(&ProverStopped {
-                    prover_name: prover_name.clone(),
-                })
-                .send()
-                .await
-                .map_err(|e| {
-                    Transient(format_err!("failed to send prover_stopped request: {}", e))
-                })?;
-
-            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-                return Err(Permanent(format_err!("authorization error")));
-            }
-
-            Ok(())
-        };
-
-        self.with_retries(operation).await
+    async fn prover_stopped(&self) -> anyhow::Result<()> {
+        self.post(&self.stopped_url, &()).await
     }
 }
@@ -6,10 +6,11 @@ pub mod plonk_step_by_step_prover;
 
 // Built-in deps
 use futures::{pin_mut, FutureExt};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::{
-    atomic::{AtomicBool, AtomicI32, Ordering},
-    Arc,
+    atomic::{AtomicBool, AtomicI32, AtomicU8, Ordering},
+    Arc, Mutex,
 };
 use std::time::Duration;
 use tokio::sync::oneshot;
@@ -21,8 +22,8 @@ use zksync_crypto::rand::{
 // Workspace deps
 use zksync_config::ProverConfig as EnvProverConfig;
 use zksync_prover_utils::api::{
-    JobRequestData, JobResultData, ProverInputRequest, ProverInputRequestAuxData,
-    ProverInputResponse, ProverOutputRequest,
+    v2::{HeartbeatResponse, Job, JobHeartbeat, JobsRequest, ProverCapabilities},
+    JobRequestData, JobResultData, ProverOutputRequest,
 };
 
 const ABSENT_PROVER_ID: i32 = -1;
@@ -79,31 +80,81 @@ pub trait ProverImpl {
     type Config: ProverConfig;
     /// Creates prover from config and API client.
     fn create_from_config(config: Self::Config) -> Self;
-    fn get_request_aux_data(&self) -> ProverInputRequestAuxData {
+    /// Returns the jobs the prover is able to prove, so the server doesn't hand out the other ones.
+    fn capabilities(&self) -> ProverCapabilities {
         Default::default()
-        // TODO: Add the ability to define different config (ZKS-283).
     }
     /// Resource heavy operation
     fn create_proof(&self, data: JobRequestData) -> anyhow::Result<JobResultData>;
+    /// Same as `create_proof`, but reports the progress of the proof (in percent) to `progress`.
+    /// By default, only the completion of the proof is reported.
+    fn create_proof_with_progress(
+        &self,
+        data: JobRequestData,
+        progress: &AtomicU8,
+    ) -> anyhow::Result<JobResultData> {
+        let proof = self.create_proof(data)?;
+        progress.store(100, Ordering::SeqCst);
+        Ok(proof)
+    }
 }
 #[async_trait::async_trait]
 pub trait ApiClient: Debug {
-    async fn get_job(&self, req: ProverInputRequest) -> anyhow::Result<ProverInputResponse>;
-    async fn working_on(&self, job_id: i32, prover_name: &str) -> anyhow::Result<()>;
+    async fn get_jobs(&self, req: JobsRequest) -> anyhow::Result<Vec<Job>>;
+    async fn heartbeat(&self, jobs: Vec<JobHeartbeat>) -> anyhow::Result<HeartbeatResponse>;
     async fn publish(&self, data: ProverOutputRequest) -> anyhow::Result<()>;
-    async fn prover_stopped(&self, prover_name: String) -> anyhow::Result<()>;
+    async fn prover_stopped(&self) -> anyhow::Result<()>;
+}
+
+/// Jobs of the current batch whose leases are renewed by the heartbeats, with their progress.
+/// Jobs are removed once their proofs are published or their leases are lost.
+#[derive(Debug, Clone)]
+struct LeasedJobs(Arc<Mutex<HashMap<i32, Arc<AtomicU8>>>>);
+
+impl LeasedJobs {
+    fn new(jobs: &[Job]) -> Self {
+        let jobs = jobs
+            .iter()
+            .map(|job| (job.job_id, Arc::new(AtomicU8::new(0))))
+            .collect();
+        Self(Arc::new(Mutex::new(jobs)))
+    }
+
+    /// Returns the progress of the job, or `None` if the job is not leased to the prover anymore.
+    fn progress(&self, job_id: i32) -> Option<Arc<AtomicU8>> {
+        self.0.lock().unwrap().get(&job_id).cloned()
+    }
+
+    fn remove(&self, job_id: i32) {
+        self.0.lock().unwrap().remove(&job_id);
+    }
+
+    fn heartbeats(&self) -> Vec<JobHeartbeat> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(job_id, progress)| JobHeartbeat {
+                job_id: *job_id,
+                progress: Some(progress.load(Ordering::SeqCst)),
+            })
+            .collect()
+    }
 }
 
 async fn compute_proof_no_blocking<PROVER>(
     prover: PROVER,
     data: JobRequestData,
+    progress: Arc<AtomicU8>,
 ) -> anyhow::Result<(PROVER, JobResultData)>
 where
     PROVER: ProverImpl + Send + Sync + 'static,
 {
     let (result_sender, result_receiver) = oneshot::channel();
     std::thread::spawn(move || {
-        let prover_with_proof = prover.create_proof(data).map(|proof| (prover, proof));
+        let prover_with_proof = prover
+            .create_proof_with_progress(data, &progress)
+            .map(|proof| (prover, proof));
         result_sender.send(prover_with_proof).unwrap_or_default();
     });
     result_receiver.await?
@@ -113,8 +164,7 @@ where
 /// notifies about it in the logs, but does not quit.
 async fn heartbeat_future_handle<CLIENT>(
     client: CLIENT,
-    prover_name: &str,
-    job_id: i32,
+    leased_jobs: LeasedJobs,
     heartbeat_interval: Duration,
 ) where
     CLIENT: 'static + Sync + Send + ApiClient,
@@ -128,14 +178,83 @@ async fn heartbeat_future_handle<CLIENT>(
         };
         tokio::time::sleep(timeout_value).await;
 
-        vlog::debug!("Starting sending heartbeats for job with ID: {}", job_id);
+        let heartbeats = leased_jobs.heartbeats();
+        if heartbeats.is_empty() {
+            continue;
+        }
+        vlog::debug!("Sending heartbeats for {} jobs", heartbeats.len());
+
+        match client.heartbeat(heartbeats).await {
+            Ok(response) => {
+                for job_id in response.lost_jobs {
+                    vlog::warn!("Job with ID {} is not leased to the prover anymore", job_id);
+                    leased_jobs.remove(job_id);
+                }
+            }
+            Err(e) => vlog::warn!("Failed to send heartbeat: {}", e),
+        }
+    }
+}
+
+/// Proves the jobs of the batch one by one and publishes their proofs.
+///
+/// Jobs whose leases were lost before the prover started them are skipped, since they
+/// are handed out to the other provers. The proof of the job that is already in progress
+/// is still published.
+async fn prove_jobs<PROVER, CLIENT>(
+    mut prover: PROVER,
+    client: CLIENT,
+    jobs: Vec<Job>,
+    leased_jobs: LeasedJobs,
+) -> PROVER
+where
+    CLIENT: 'static + Sync + Send + ApiClient,
+    PROVER: ProverImpl + Send + Sync + 'static,
+{
+    for job in jobs {
+        let Job {
+            job_id,
+            first_block,
+            last_block,
+            data,
+        } = job;
+        let progress = if let Some(progress) = leased_jobs.progress(job_id) {
+            progress
+        } else {
+            vlog::warn!("Skipping job id: {}, it is not leased anymore", job_id);
+            continue;
+        };
+
+        vlog::info!(
+            "starting to compute proof for blocks: [{}, {}]",
+            first_block,
+            last_block
+        );
+        let (ret_prover, proof) = compute_proof_no_blocking(prover, data, progress)
+            .await
+            .expect("Failed to compute proof");
+        prover = ret_prover;
 
         client
-            .working_on(job_id, prover_name)
+            .publish(ProverOutputRequest {
+                job_id,
+                first_block,
+                last_block,
+                data: proof,
+            })
             .await
-            .map_err(|e| vlog::warn!("Failed to send heartbeat: {}", e))
+            .map_err(|e| vlog::warn!("Failed to publish proof: {}", e))
             .unwrap_or_default();
+        leased_jobs.remove(job_id);
+
+        vlog::info!(
+            "finished and published proof for blocks: [{}, {}]",
+            first_block,
+            last_block
+        );
     }
+
+    prover
 }
 
 pub async fn prover_work_cycle<PROVER, CLIENT>(
@@ -157,81 +276,45 @@ pub async fn prover_work_cycle<PROVER, CLIENT>(
             break;
         }
 
-        let aux_data = prover.get_request_aux_data();
-        let prover_input_response = match client
-            .get_job(ProverInputRequest {
-                prover_name: prover_name.to_string(),
-                aux_data,
+        let jobs = match client
+            .get_jobs(JobsRequest {
+                max_jobs: prover_options.prover.jobs_per_request,
+                capabilities: prover.capabilities(),
             })
             .await
         {
-            Ok(job) => job,
+            Ok(jobs) => jobs,
             Err(e) => {
-                vlog::warn!("Failed to get job for prover: {}", e);
+                vlog::warn!("Failed to get jobs for prover: {}", e);
                 continue;
             }
         };
-
-        let ProverInputResponse {
-            job_id,
-            data: job_data,
-            first_block,
-            last_block,
-        } = prover_input_response;
-        let job_data = if let Some(job_data) = job_data {
-            job_data
-        } else {
+        if jobs.is_empty() {
             continue;
-        };
+        }
 
         vlog::info!(
-            "got job id: {}, blocks: [{}, {}]",
-            job_id,
-            first_block,
-            last_block
+            "prover {} got jobs with ids: {:?}",
+            prover_name,
+            jobs.iter().map(|job| job.job_id).collect::<Vec<_>>()
         );
 
+        let leased_jobs = LeasedJobs::new(&jobs);
         let heartbeat_future_handle = heartbeat_future_handle(
             client.clone(),
-            prover_name,
-            job_id,
+            leased_jobs.clone(),
             prover_options.prover.heartbeat_interval(),
         )
         .fuse();
-        let compute_proof_future = compute_proof_no_blocking(prover, job_data).fuse();
+        let prove_jobs_future = prove_jobs(prover, client.clone(), jobs, leased_jobs).fuse();
 
-        pin_mut!(heartbeat_future_handle, compute_proof_future);
+        pin_mut!(heartbeat_future_handle, prove_jobs_future);
 
-        vlog::info!(
-            "starting to compute proof for blocks: [{}, {}]",
-            first_block,
-            last_block
-        );
-
-        let (ret_prover, proof) = futures::select! {
-            comp_proof = compute_proof_future => {
-                comp_proof.expect("Failed to compute proof")
-            },
+        prover = futures::select! {
+            ret_prover = prove_jobs_future => ret_prover,
             _ = heartbeat_future_handle => unreachable!(),
         };
-        prover = ret_prover;
 
-        client
-            .publish(ProverOutputRequest {
-                job_id,
-                first_block,
-                last_block,
-                data: proof,
-            })
-            .await
-            .map_err(|e| vlog::warn!("Failed to publish proof: {}", e))
-            .unwrap_or_default();
-
-        vlog::info!(
-            "finished and published proof for blocks: [{}, {}]",
-            first_block,
-            last_block
-        );
         if prover_options.prover.die_after_proof {
             return;
         }
@@ -1,11 +1,14 @@
 // Built-in deps
-use std::sync::Mutex;
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    Mutex,
+};
 // Workspace deps
 use zksync_config::ChainConfig;
 use zksync_crypto::proof::{AggregatedProof, PrecomputedSampleProofs, SingleProof};
 use zksync_crypto::Engine;
 use zksync_prover_utils::aggregated_proofs::{gen_aggregate_proof, prepare_proof_data};
-use zksync_prover_utils::api::{JobRequestData, JobResultData};
+use zksync_prover_utils::api::{v2::ProverCapabilities, JobRequestData, JobResultData};
 use zksync_prover_utils::{PlonkVerificationKey, SetupForStepByStepProver};
 use zksync_utils::parse_env;
 // Local deps
@@ -55,6 +58,7 @@ impl PlonkStepByStepProver {
         &self,
         witness: zksync_circuit::circuit::ZkSyncCircuit<'_, Engine>,
         block_size: usize,
+        progress: &AtomicU8,
     ) -> anyhow::Result<SingleProof> {
         let valid_cached_precomp = {
             self.prepared_computations
@@ -73,6 +77,7 @@ impl PlonkStepByStepProver {
             )?;
             PreparedComputations { block_size, setup }
         };
+        progress.store(50, Ordering::SeqCst);
 
         let vk = PlonkVerificationKey::read_verification_key_for_main_circuit(block_size)?;
         let verified_proof = precomp
@@ -87,6 +92,7 @@ impl PlonkStepByStepProver {
     fn create_aggregated_block_proof(
         &self,
         proofs: Vec<(SingleProof, usize)>,
+        progress: &AtomicU8,
     ) -> anyhow::Result<AggregatedProof> {
         let start = Instant::now();
         // drop setup cache
@@ -126,6 +132,7 @@ impl PlonkStepByStepProver {
 
         let (vks, proof_data) = prepare_proof_data(&self.config.all_block_sizes, padded_proofs);
         metrics::histogram!("prover", start.elapsed(), "stage" => "prepare_proof", "type" => "aggregated_proof");
+        progress.store(30, Ordering::SeqCst);
         gen_aggregate_proof(
             vks,
             proof_data,
@@ -138,7 +145,24 @@ impl PlonkStepByStepProver {
 impl ProverImpl for PlonkStepByStepProver {
     type Config = PlonkStepByStepProverConfig;
 
+    fn capabilities(&self) -> ProverCapabilities {
+        ProverCapabilities {
+            max_block_chunks: self.config.block_sizes.iter().max().copied(),
+            aggregated_proofs: !self.config.aggregated_proof_sizes_with_setup_pow.is_empty(),
+        }
+    }
+
     fn create_proof(&self, data: JobRequestData) -> Result<JobResultData, anyhow::Error> {
+        self.create_proof_with_progress(data, &AtomicU8::new(0))
+    }
+
+    /// Progress is estimated by the stages of the proof: preparing the data and the setup
+    /// takes roughly as long as generating the proof itself.
+    fn create_proof_with_progress(
+        &self,
+        data: JobRequestData,
+        progress: &AtomicU8,
+    ) -> Result<JobResultData, anyhow::Error> {
         let proof = match data {
             JobRequestData::AggregatedBlockProof(proofs_to_aggregate) => {
                 let block_sizes = proofs_to_aggregate
@@ -146,7 +170,7 @@ impl ProverImpl for PlonkStepByStepProver {
                     .map(|(_, s)| *s)
                     .collect::<Vec<_>>();
 
-                let aggregate_proof = self.create_aggregated_block_proof(proofs_to_aggregate).map_err(|e| {
+                let aggregate_proof = self.create_aggregated_block_proof(proofs_to_aggregate, progress).map_err(|e| {
                     anyhow::format_err!("Failed to aggregate block proofs, num proofs: {}, block sizes: {:?}, err {}", block_sizes.len(), &block_sizes, e)
                 })?;
 
@@ -156,8 +180,9 @@ impl ProverImpl for PlonkStepByStepProver {
                 let start = Instant::now();
                 let zksync_circuit = zksync_circuit.into_circuit();
                 metrics::histogram!("prover", start.elapsed(), "stage" => "prepare_proof", "type" => "single_proof");
+                progress.store(10, Ordering::SeqCst);
                 let proof = self
-                    .create_single_block_proof(zksync_circuit, block_size)
+                    .create_single_block_proof(zksync_circuit, block_size, progress)
                     .map_err(|e| {
                         anyhow::format_err!(
                             "Failed to create single block proof, block size: {}, err: {}",
@@ -169,6 +194,7 @@ impl ProverImpl for PlonkStepByStepProver {
                 JobResultData::BlockProof(proof)
             }
         };
+        progress.store(100, Ordering::SeqCst);
 
         Ok(proof)
     }
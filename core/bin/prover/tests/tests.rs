@@ -23,7 +23,8 @@ use zksync_prover::plonk_step_by_step_prover::{
 };
 use zksync_prover::{ProverImpl, ShutdownRequest};
 use zksync_prover_utils::api::{
-    JobRequestData, ProverInputRequest, ProverInputResponse, ProverOutputRequest,
+    v2::{HeartbeatResponse, Job, JobHeartbeat, JobsRequest},
+    JobRequestData, ProverOutputRequest,
 };
 use zksync_types::{
     block::smallest_block_size_for_chunks, operations::DepositOp, Account, AccountId, Address,
@@ -57,14 +58,33 @@ impl Default for MockProverConfigs {
                 cycle_wait: 500,
                 request_timeout: 1,
                 die_after_proof: false,
+                jobs_per_request: 1,
             },
             core: zksync_config::configs::prover::Core {
                 gone_timeout: 2,
                 idle_provers: 1,
+                max_job_attempts: 5,
             },
             witness_generator: zksync_config::configs::prover::WitnessGenerator {
                 prepare_data_interval: 5000,
                 witness_generators: 2,
+                block_lease_timeout: 300000,
+            },
+            witness_store: zksync_config::configs::prover::WitnessStore {
+                backend: zksync_config::configs::prover::WitnessStoreBackend::Postgres,
+                compression_level: 3,
+                path: String::new(),
+                s3_endpoint: String::new(),
+                s3_region: String::new(),
+                s3_bucket: String::new(),
+                s3_prefix: String::new(),
+                s3_access_key: String::new(),
+                s3_secret_key: String::new(),
+            },
+            scaler: zksync_config::configs::prover::Scaler {
+                target_proof_latency: 600,
+                aggregation_deadline: 1800,
+                proving_time_window: 86400,
             },
         };
 
@@ -213,10 +233,7 @@ async fn test_receiving_heartbeats() {
         _ = prover_work_cycle => panic!("prover work ended too quickly"),
         _ = timeout => {
             shutdown_request.set();
-            assert_eq!(
-                client.working_on.lock().await.get(&0).cloned(),
-                Some("Test".to_string())
-            );
+            assert!(client.heartbeats.lock().await.contains_key(&0));
         },
     };
 }
@@ -257,38 +274,72 @@ async fn test_publishing_proof() {
     };
 }
 
+#[tokio::test]
+async fn test_publishing_batch() {
+    let MockProverConfigs {
+        plonk_config: _,
+        dummy_config,
+        mut prover_options,
+        shutdown_request,
+        prover_name,
+    } = MockProverConfigs::default();
+    prover_options.prover.jobs_per_request = 2;
+    prover_options.prover.die_after_proof = true;
+
+    let prover = DummyProver::create_from_config(dummy_config);
+    let client = MockApiClient::default();
+
+    // Prover dies once the first batch is proven.
+    zksync_prover::prover_work_cycle(
+        prover,
+        client.clone(),
+        shutdown_request,
+        prover_options,
+        &prover_name,
+    )
+    .await;
+
+    let published = client.published_prof.lock().await;
+    assert_eq!(published.len(), 2);
+    assert!(published.contains_key(&0));
+    assert!(published.contains_key(&1));
+}
+
 #[derive(Debug, Clone, Default)]
 struct MockApiClient {
     /// All published proofs are saved by `job_id`.
     published_prof: Arc<Mutex<HashMap<i32, ProverOutputRequest>>>,
-    /// Received heartbeats from `self.working_on()`.
-    working_on: Arc<Mutex<HashMap<i32, String>>>,
+    /// Last progress received in the heartbeats from `self.heartbeat()`.
+    heartbeats: Arc<Mutex<HashMap<i32, Option<u8>>>>,
     /// `gob_id` of the last work that has not yet been submitted.
     last_job_id: Arc<Mutex<i32>>,
 }
 
 #[async_trait::async_trait]
 impl zksync_prover::ApiClient for MockApiClient {
-    async fn get_job(&self, _: ProverInputRequest) -> anyhow::Result<ProverInputResponse> {
-        let last_job_id = *self.last_job_id.lock().await;
-        *self.last_job_id.lock().await += 1;
-        let response = ProverInputResponse {
-            job_id: last_job_id,
-            first_block: BlockNumber(1),
-            last_block: BlockNumber(1),
-            data: Some(test_data_for_prover()),
-        };
+    async fn get_jobs(&self, req: JobsRequest) -> anyhow::Result<Vec<Job>> {
+        let mut last_job_id = self.last_job_id.lock().await;
+        let mut jobs = Vec::new();
+        for _ in 0..req.max_jobs {
+            jobs.push(Job {
+                job_id: *last_job_id,
+                first_block: BlockNumber(1),
+                last_block: BlockNumber(1),
+                data: test_data_for_prover(),
+            });
+            *last_job_id += 1;
+        }
 
-        Ok(response)
+        Ok(jobs)
     }
 
-    async fn working_on(&self, job_id: i32, prover_name: &str) -> anyhow::Result<()> {
-        self.working_on
-            .lock()
-            .await
-            .insert(job_id, prover_name.to_string());
+    async fn heartbeat(&self, jobs: Vec<JobHeartbeat>) -> anyhow::Result<HeartbeatResponse> {
+        let mut heartbeats = self.heartbeats.lock().await;
+        for job in jobs {
+            heartbeats.insert(job.job_id, job.progress);
+        }
 
-        Ok(())
+        Ok(HeartbeatResponse::default())
     }
 
     async fn publish(&self, data: ProverOutputRequest) -> anyhow::Result<()> {
@@ -297,7 +348,7 @@ impl zksync_prover::ApiClient for MockApiClient {
         Ok(())
     }
 
-    async fn prover_stopped(&self, _: String) -> anyhow::Result<()> {
+    async fn prover_stopped(&self) -> anyhow::Result<()> {
         Ok(())
     }
 }
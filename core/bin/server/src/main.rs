@@ -6,7 +6,9 @@ use structopt::StructOpt;
 
 use serde::{Deserialize, Serialize};
 
+use zksync_api::api_server::rate_limiter::RateLimiter;
 use zksync_api::fee_ticker::{run_updaters, FeeTicker, TickerInfo};
+use zksync_blob_store::ContentStore;
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
 use zksync_eth_client::RootstockGateway;
 use zksync_forced_exit_requests::run_forced_exit_requests_actors;
@@ -18,12 +20,13 @@ use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig, TokenConfi
 use zksync_config::{
     configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
     ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    ForcedExitRequestsConfig, GatewayWatcherConfig, ProverConfig, TickerConfig, ZkSyncConfig,
+    EventListenerConfig, ForcedExitRequestsConfig, GatewayWatcherConfig, NotifierConfig,
+    ProverConfig, TickerConfig, ZkSyncConfig,
 };
 use zksync_core::rejected_tx_cleaner::run_rejected_tx_cleaner;
 use zksync_mempool::run_mempool_tx_handler;
 use zksync_prometheus_exporter::{run_operation_counter, run_prometheus_exporter};
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, StorageProcessor};
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
@@ -155,14 +158,41 @@ async fn run_server(components: &ComponentsToRun) {
     let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
 
-    let mut tasks = vec![];
+    let mut tasks = vec![hold_server_lock().await];
+
+    // Rate limits are shared by all the API servers.
+    let rate_limiter = RateLimiter::new(&CommonApiConfig::from_env());
+
+    // Fees are used by both the zkSync and the web3 APIs.
+    let chain_config = ChainConfig::from_env();
+    let fee_ticker_config = TickerConfig::from_env();
+    let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
+
+    let ticker = FeeTicker::new_with_default_validator(
+        ticker_info,
+        fee_ticker_config,
+        chain_config.max_blocks_to_aggregate(),
+        read_only_connection_pool.clone(),
+    );
 
     if components.0.contains(&Component::Web3Api) {
         // Run web3 api
+        let web3_config = Web3Config::from_env();
+        let token_config = TokenConfig::from_env();
         tasks.push(zksync_api::api_server::web3::start_rpc_server(
             connection_pool.clone(),
-            &Web3Config::from_env(),
-            &TokenConfig::from_env(),
+            ticker.clone(),
+            &web3_config,
+            &token_config,
+            rate_limiter.clone(),
+        ));
+        tasks.push(zksync_api::api_server::web3::start_ws_server(
+            connection_pool.clone(),
+            ticker.clone(),
+            &web3_config,
+            &token_config,
+            EventListenerConfig::from_env().channel_name,
+            rate_limiter.clone(),
         ));
     }
 
@@ -201,16 +231,6 @@ async fn run_server(components: &ComponentsToRun) {
         let contracts_config = ContractsConfig::from_env();
         let common_config = CommonApiConfig::from_env();
         let token_config = TokenConfig::from_env();
-        let chain_config = ChainConfig::from_env();
-        let fee_ticker_config = TickerConfig::from_env();
-        let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
-
-        let ticker = FeeTicker::new_with_default_validator(
-            ticker_info,
-            fee_ticker_config,
-            chain_config.max_blocks_to_aggregate(),
-            read_only_connection_pool.clone(),
-        );
 
         if components.0.contains(&Component::RpcWebSocketApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
@@ -219,6 +239,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.mempool.clone(),
             ));
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
@@ -230,6 +251,7 @@ async fn run_server(components: &ComponentsToRun) {
                 chain_config.state_keeper.miniblock_iteration_interval(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                rate_limiter.clone(),
             ));
         }
 
@@ -240,6 +262,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.mempool.clone(),
             ));
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
@@ -250,6 +273,7 @@ async fn run_server(components: &ComponentsToRun) {
                 &token_config,
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                rate_limiter.clone(),
             ));
         }
 
@@ -260,6 +284,7 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes,
+                chain_config.mempool,
             ));
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
@@ -271,12 +296,17 @@ async fn run_server(components: &ComponentsToRun) {
                 sign_check_sender,
                 mempool_tx_request_sender,
                 private_config.url,
+                rate_limiter,
             ));
         }
     }
 
     if components.0.contains(&Component::EthSender) {
-        tasks.push(run_eth_sender(connection_pool.clone()))
+        tasks.push(run_eth_sender(connection_pool.clone()));
+
+        if ETHSenderConfig::from_env().balance_watchdog.enabled {
+            tasks.push(run_balance_watchdog(connection_pool.clone()));
+        }
     }
 
     if components.0.contains(&Component::Core) {
@@ -338,6 +368,28 @@ async fn run_server(components: &ComponentsToRun) {
     };
 }
 
+/// Takes the shared server lock and holds it while the server is running,
+/// so the maintenance tools can check that the server is stopped.
+async fn hold_server_lock() -> JoinHandle<()> {
+    let mut storage = StorageProcessor::establish_connection()
+        .await
+        .expect("Unable to connect to the database");
+    let locked = storage
+        .misc_schema()
+        .try_lock_server()
+        .await
+        .expect("Unable to take the server lock");
+    if !locked {
+        panic!("Database is locked for maintenance by `zksync-admin`, the server can't be started");
+    }
+
+    tokio::spawn(async move {
+        // The lock is released once the connection is closed.
+        let _storage = storage;
+        futures::future::pending::<()>().await;
+    })
+}
+
 pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
     vlog::info!("Starting the ForcedExitRequests actors");
     let config = ForcedExitRequestsConfig::from_env();
@@ -352,6 +404,7 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
         connection_pool.clone(),
         mempool_tx_request_receiver,
         chain_config.state_keeper.block_chunk_sizes,
+        chain_config.mempool,
     );
     let forced_exit_task = run_forced_exit_requests_actors(
         connection_pool,
@@ -368,8 +421,15 @@ pub fn run_witness_generator(connection_pool: ConnectionPool) -> JoinHandle<()>
     vlog::info!("Starting the Prover server actors");
     let prover_api_config = ProverApiConfig::from_env();
     let prover_config = ProverConfig::from_env();
-    let database = zksync_witness_generator::database::Database::new(connection_pool);
-    run_prover_server(database, prover_api_config, prover_config)
+    let blob_store = ContentStore::from_config(&prover_config.witness_store);
+    let database = zksync_witness_generator::database::Database::new(connection_pool)
+        .with_blob_store(blob_store);
+    run_prover_server(
+        database,
+        prover_api_config,
+        prover_config,
+        NotifierConfig::from_env(),
+    )
 }
 
 pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
@@ -383,7 +443,25 @@ pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
         contracts.contract_addr,
     );
 
-    zksync_eth_sender::run_eth_sender(connection_pool, eth_gateway, eth_sender_config)
+    zksync_eth_sender::run_eth_sender(
+        connection_pool,
+        eth_gateway,
+        eth_sender_config,
+        NotifierConfig::from_env(),
+    )
+}
+
+pub fn run_balance_watchdog(connection_pool: ConnectionPool) -> JoinHandle<()> {
+    vlog::info!("Starting the operator balance watchdog");
+    let eth_sender_config = ETHSenderConfig::from_env();
+    let eth_gateway = create_eth_gateway();
+
+    zksync_eth_sender::run_balance_watchdog(
+        connection_pool,
+        eth_gateway,
+        eth_sender_config,
+        NotifierConfig::from_env(),
+    )
 }
 
 pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
@@ -1,21 +1,71 @@
+use std::path::PathBuf;
+
 use anyhow::{bail, ensure, format_err};
 use ethabi::Token;
-use std::str::FromStr;
 use structopt::StructOpt;
 use tokio::time::Duration;
 use web3::{
     contract::Options,
     types::{TransactionReceipt, U256, U64},
 };
-use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_config::NotifierConfig;
 use zksync_eth_client::RootstockGateway;
+use zksync_notifier::{Notification, Notifier};
 use zksync_storage::StorageProcessor;
-use zksync_types::{aggregated_operations::stored_block_info, block::Block, BlockNumber, H256};
+use zksync_types::{aggregated_operations::stored_block_info, block::Block, BlockNumber};
+
+use self::plan::RevertPlan;
+use crate::utils::{eth_gateway, lock_maintenance, Context};
+
+mod plan;
+
+#[derive(Debug, StructOpt)]
+enum Target {
+    /// Reverts blocks on contract and in storage
+    All,
+    /// Reverts blocks on contract
+    Contract,
+    /// Reverts blocks in storage
+    Storage,
+}
+
+impl Target {
+    fn reverts_contract(&self) -> bool {
+        matches!(self, Self::All | Self::Contract)
+    }
+
+    fn reverts_storage(&self) -> bool {
+        matches!(self, Self::All | Self::Storage)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Opt {
+    /// Last correct block, tool reverts blocks with numbers greater than this field.
+    #[structopt(long)]
+    last_correct_block: u32,
+    #[structopt(subcommand)]
+    target: Target,
+    /// Private key of operator which will call the contract function,
+    /// the one from the server configuration is used by default.
+    #[structopt(long = "key")]
+    operator_private_key: Option<String>,
+    /// Only shows what is going to be reverted. Storage revert is performed
+    /// in the transaction which is rolled back, and the contract is not called.
+    #[structopt(long)]
+    dry_run: bool,
+    /// Path to write the machine-readable (JSON) revert plan to.
+    #[structopt(long)]
+    report: Option<PathBuf>,
+}
 
 // TODO: don't use anyhow (ZKS-588)
+/// Reverts the blocks in storage. In the dry run mode all the changes are rolled back,
+/// which checks that the revert can be applied.
 async fn revert_blocks_in_storage(
     storage: &mut StorageProcessor<'_>,
     last_block: BlockNumber,
+    dry_run: bool,
 ) -> anyhow::Result<()> {
     let mut transaction = storage.start_transaction().await?;
 
@@ -24,101 +74,106 @@ async fn revert_blocks_in_storage(
         .mempool_schema()
         .return_executed_txs_to_mempool(last_block)
         .await?;
-    println!("`mempool_txs`, `executed_transactions` tables are updated");
+    eprintln!("`mempool_txs`, `executed_transactions` tables are updated");
     transaction
         .chain()
         .state_schema()
         .clear_current_nonce_table(last_block)
         .await?;
-    println!("`committed_nonce` table is updated");
+    eprintln!("`committed_nonce` table is updated");
     transaction
         .chain()
         .block_schema()
         .remove_blocks(last_block)
         .await?;
-    println!("`block` table is cleaned");
+    eprintln!("`block` table is cleaned");
     transaction
         .chain()
         .block_schema()
         .remove_pending_block()
         .await?;
-    println!("`pending_block` table is cleaned");
+    eprintln!("`pending_block` table is cleaned");
     transaction
         .chain()
         .tree_cache_schema_bincode()
         .remove_new_account_tree_cache(last_block)
         .await?;
-    println!("`account_tree_cache` table is cleaned");
+    eprintln!("`account_tree_cache` table is cleaned");
 
     transaction
         .chain()
         .state_schema()
         .remove_account_balance_updates(last_block)
         .await?;
-    println!("`account_balance_updates` table is cleaned");
+    eprintln!("`account_balance_updates` table is cleaned");
     transaction
         .chain()
         .state_schema()
         .remove_account_creates(last_block)
         .await?;
-    println!("`account_creates` table is cleaned");
+    eprintln!("`account_creates` table is cleaned");
     transaction
         .chain()
         .state_schema()
         .remove_account_pubkey_updates(last_block)
         .await?;
-    println!("`account_pubkey_updates` table is cleaned");
+    eprintln!("`account_pubkey_updates` table is cleaned");
 
     transaction
         .chain()
         .state_schema()
         .remove_mint_nft_updates(last_block)
         .await?;
-    println!("`mint_nft_updates` table is cleaned");
+    eprintln!("`mint_nft_updates` table is cleaned");
 
     transaction
         .chain()
         .operations_schema()
         .remove_eth_unprocessed_aggregated_ops()
         .await?;
-    println!("`eth_unprocessed_aggregated_ops` table is cleaned");
+    eprintln!("`eth_unprocessed_aggregated_ops` table is cleaned");
     transaction
         .chain()
         .operations_schema()
         .remove_aggregate_operations_and_bindings(last_block)
         .await?;
-    println!("`aggregate_operations`, `eth_aggregated_ops_binding`, `eth_tx_hashes`, `eth_operations` tables are cleaned");
+    eprintln!("`aggregate_operations`, `eth_aggregated_ops_binding`, `eth_tx_hashes`, `eth_operations` tables are cleaned");
 
     transaction
         .prover_schema()
         .remove_witnesses(last_block)
         .await?;
-    println!("`block_witness` table is cleaned");
+    eprintln!("`block_witness` table is cleaned");
     transaction
         .prover_schema()
         .remove_proofs(last_block)
         .await?;
-    println!("`proofs` table is cleaned");
+    eprintln!("`proofs` table is cleaned");
     transaction
         .prover_schema()
         .remove_aggregated_proofs(last_block)
         .await?;
-    println!("`aggregated_proofs` table is cleaned");
+    eprintln!("`aggregated_proofs` table is cleaned");
     transaction
         .prover_schema()
         .remove_prover_jobs(last_block)
         .await?;
-    println!("`prover_job_queue` table is cleaned");
+    eprintln!("`prover_job_queue` table is cleaned");
 
     transaction
         .rootstock_schema()
         .update_eth_parameters(last_block)
         .await?;
-    println!("`eth_parameters` table is updated");
+    eprintln!("`eth_parameters` table is updated");
 
+    if dry_run {
+        // Dropped transaction is rolled back.
+        eprintln!("Dry run: changes in storage are rolled back");
+        return Ok(());
+    }
     transaction.commit().await?;
 
-    println!("Blocks were reverted in storage");
+    eprintln!("Blocks were reverted in storage");
     Ok(())
 }
 
@@ -173,7 +228,7 @@ async fn revert_blocks_on_contract(
         anyhow::bail!("Tx to contract failed {:?}", reason);
     }
 
-    println!("Blocks were reverted on contract");
+    eprintln!("Blocks were reverted on contract");
     Ok(())
 }
 
@@ -198,53 +253,13 @@ async fn get_blocks(
     Ok(blocks)
 }
 
-#[derive(Debug, StructOpt)]
-enum Command {
-    /// Reverts blocks on contract and in storage
-    All,
-    /// Reverts blocks on contract
-    Contract,
-    /// Reverts blocks in storage
-    Storage,
-}
-
-#[derive(Debug, StructOpt)]
-#[structopt(name = "Rollup block revert tool", author = "Matter Labs")]
-#[structopt(about = "Tool to revert blocks in Rollup network on contract and/or in storage")]
-struct Opt {
-    /// Last correct block, tool reverts blocks with numbers greater than this field.
-    #[structopt(long)]
-    last_correct_block: u32,
-    #[structopt(subcommand)]
-    command: Command,
-    /// Private key of operator which will call the contract function.
-    #[structopt(long = "key", env = "ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")]
-    operator_private_key: String,
-}
-
 // TODO: don't use anyhow (ZKS-588)
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let opt = Opt::from_args();
-
-    let key_without_prefix = opt
-        .operator_private_key
-        .strip_prefix("0x")
-        .unwrap_or(opt.operator_private_key.as_str());
-
-    let contracts = ContractsConfig::from_env();
-    let eth_client_config = ETHClientConfig::from_env();
-    let mut eth_sender_config = ETHSenderConfig::from_env();
-
-    eth_sender_config.sender.operator_private_key =
-        H256::from_str(key_without_prefix).expect("Cannot deserialize private key");
-
+pub async fn run(context: &Context, opt: Opt) -> anyhow::Result<()> {
+    let (client, _) = eth_gateway(opt.operator_private_key.as_deref())?;
     let mut storage = StorageProcessor::establish_connection().await?;
-    let client = RootstockGateway::from_config(
-        &eth_client_config,
-        &eth_sender_config,
-        contracts.contract_addr,
-    );
+    if !opt.dry_run {
+        lock_maintenance(&mut storage).await?;
+    }
 
     let last_commited_block = storage
         .chain()
@@ -257,7 +272,7 @@ async fn main() -> anyhow::Result<()> {
         .get_last_verified_confirmed_block()
         .await?;
 
-    println!(
+    eprintln!(
         "Last committed block {} verified {}",
         &last_commited_block, &last_verified_block
     );
@@ -269,24 +284,65 @@ async fn main() -> anyhow::Result<()> {
     let blocks_to_revert = *last_commited_block - opt.last_correct_block;
     let last_block = BlockNumber(opt.last_correct_block);
 
-    match opt.command {
-        Command::All => {
-            println!("Start reverting blocks in database and in contract");
+    let plan = RevertPlan::new(
+        &mut storage,
+        last_block,
+        last_commited_block,
+        last_verified_block,
+        opt.target.reverts_contract(),
+        opt.target.reverts_storage(),
+    )
+    .await?;
+    context.print(&plan)?;
+    if let Some(path) = &opt.report {
+        plan.write_report(path)?;
+        eprintln!("Revert plan is written to {}", path.display());
+    }
+
+    if opt.dry_run {
+        if opt.target.reverts_storage() {
+            revert_blocks_in_storage(&mut storage, last_block, true).await?;
+        }
+        eprintln!("Dry run is finished, nothing was reverted");
+        return Ok(());
+    }
+    let prompt = format!(
+        "Type the last correct block number ({}) to confirm the revert",
+        last_block
+    );
+    if !context.confirm(&prompt, &last_block.to_string())? {
+        return Ok(());
+    }
+
+    match opt.target {
+        Target::All => {
+            eprintln!("Start reverting blocks in database and in contract");
             let blocks = get_blocks(last_commited_block, blocks_to_revert, &mut storage).await?;
-            println!("Last block for revert {}", &last_block);
+            eprintln!("Last block for revert {}", &last_block);
             revert_blocks_on_contract(&mut storage, &client, &blocks).await?;
-            revert_blocks_in_storage(&mut storage, last_block).await?;
+            revert_blocks_in_storage(&mut storage, last_block, false).await?;
         }
-        Command::Contract => {
-            println!("Start reverting blocks in contract");
+        Target::Contract => {
+            eprintln!("Start reverting blocks in contract");
             let blocks = get_blocks(last_commited_block, blocks_to_revert, &mut storage).await?;
             revert_blocks_on_contract(&mut storage, &client, &blocks).await?;
         }
-        Command::Storage => {
-            println!("Start reverting blocks in database");
-            revert_blocks_in_storage(&mut storage, last_block).await?;
+        Target::Storage => {
+            eprintln!("Start reverting blocks in database");
+            revert_blocks_in_storage(&mut storage, last_block, false).await?;
         }
     }
 
+    let notification = Notification::BlockRevert {
+        last_correct_block: last_block,
+        reverted_blocks: blocks_to_revert,
+    };
+    if let Err(err) = Notifier::from_config(&NotifierConfig::from_env())
+        .send(notification)
+        .await
+    {
+        eprintln!("Unable to send the block revert alert: {}", err);
+    }
+
     Ok(())
 }
@@ -0,0 +1,294 @@
+//! Revert plan, which describes everything that is going to be reverted,
+//! so the revert can be reviewed before it is actually performed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use num::BigUint;
+use serde::Serialize;
+use zksync_storage::StorageProcessor;
+use zksync_types::{
+    block::ExecutedOperations, AccountId, AccountUpdate, Address, BlockNumber, TokenId, H256,
+};
+
+/// State of the running server components that is not kept in the database.
+/// It's not reverted by the tool, so the components have to be restarted after the revert.
+const IN_MEMORY_ARTIFACTS: &[&str] = &[
+    "state keeper: pending block and the account tree built from the reverted blocks",
+    "mempool: transactions returned to the queue are loaded only on restart",
+    "eth_sender: queue of the removed Rootstock operations",
+    "witness generator: account tree cache and the jobs for the reverted blocks",
+];
+
+/// Priority operation which returns to the priority queue.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertedPriorityOp {
+    pub serial_id: u64,
+    pub block_number: BlockNumber,
+    pub op_type: String,
+    pub eth_hash: H256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub token_id: TokenId,
+    pub current_balance: String,
+    pub balance_after_revert: String,
+}
+
+/// Account affected by the reverted blocks.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChange {
+    pub account_id: AccountId,
+    pub address: Option<Address>,
+    /// Account was created in the reverted blocks and won't exist after the revert.
+    pub created: bool,
+    pub balances: Vec<BalanceChange>,
+}
+
+/// Amount of rows removed or updated in the table.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableRows {
+    pub table: &'static str,
+    pub rows: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertPlan {
+    pub last_correct_block: BlockNumber,
+    pub last_committed_block: BlockNumber,
+    pub last_verified_block: BlockNumber,
+    /// Blocks reverted by the `revertBlocks` contract call, empty if the contract is not touched.
+    pub contract_blocks: Vec<BlockNumber>,
+    /// Database changes, empty if the storage is not touched.
+    pub storage_rows: Vec<TableRows>,
+    pub priority_ops: Vec<RevertedPriorityOp>,
+    /// Amount of the successful transactions returned to the mempool.
+    pub returned_txs: usize,
+    /// Amount of the failed transactions which are dropped.
+    pub dropped_txs: usize,
+    pub accounts: Vec<AccountChange>,
+    pub in_memory_artifacts: Vec<&'static str>,
+}
+
+impl RevertPlan {
+    pub async fn new(
+        storage: &mut StorageProcessor<'_>,
+        last_correct_block: BlockNumber,
+        last_committed_block: BlockNumber,
+        last_verified_block: BlockNumber,
+        revert_contract: bool,
+        revert_storage: bool,
+    ) -> anyhow::Result<Self> {
+        let mut plan = Self {
+            last_correct_block,
+            last_committed_block,
+            last_verified_block,
+            contract_blocks: Vec::new(),
+            storage_rows: Vec::new(),
+            priority_ops: Vec::new(),
+            returned_txs: 0,
+            dropped_txs: 0,
+            accounts: Vec::new(),
+            in_memory_artifacts: IN_MEMORY_ARTIFACTS.to_vec(),
+        };
+
+        if revert_contract {
+            plan.contract_blocks = (*last_correct_block + 1..=*last_committed_block)
+                .map(BlockNumber)
+                .collect();
+        }
+        if revert_storage {
+            plan.storage_rows = storage
+                .chain()
+                .block_schema()
+                .count_rows_to_revert(last_correct_block)
+                .await?
+                .into_iter()
+                .map(|(table, rows)| TableRows { table, rows })
+                .collect();
+            plan.load_operations(storage).await?;
+            plan.load_accounts(storage).await?;
+        }
+
+        Ok(plan)
+    }
+
+    /// Loads the operations returned to the mempool by the revert, including the ones
+    /// of the incomplete and pending blocks.
+    async fn load_operations(&mut self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let blocks = storage
+            .chain()
+            .mempool_schema()
+            .get_executed_ops_to_revert(self.last_correct_block)
+            .await?;
+
+        for (block_number, operations) in blocks {
+            for operation in operations {
+                match operation {
+                    ExecutedOperations::Tx(tx) if tx.success => self.returned_txs += 1,
+                    ExecutedOperations::Tx(_) => self.dropped_txs += 1,
+                    ExecutedOperations::PriorityOp(op) => {
+                        self.priority_ops.push(RevertedPriorityOp {
+                            serial_id: op.priority_op.serial_id,
+                            block_number,
+                            op_type: op.priority_op.data.variance_name(),
+                            eth_hash: op.priority_op.eth_hash,
+                        })
+                    }
+                }
+            }
+        }
+        self.priority_ops.sort_by_key(|op| op.serial_id);
+
+        Ok(())
+    }
+
+    /// Collects the balance changes made in the reverted blocks.
+    async fn load_accounts(&mut self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let (_, updates) = storage
+            .chain()
+            .state_schema()
+            .load_state_diff(self.last_correct_block, Some(BlockNumber(u32::MAX)))
+            .await?
+            .unwrap_or_default();
+
+        let mut accounts: BTreeMap<AccountId, (Option<Address>, bool, BTreeMap<TokenId, _>)> =
+            BTreeMap::new();
+        for (account_id, update) in updates {
+            let (address, created, balances) =
+                accounts
+                    .entry(account_id)
+                    .or_insert((None, false, BTreeMap::new()));
+            match update {
+                AccountUpdate::Create { address: new, .. } => {
+                    *address = Some(new);
+                    *created = true;
+                }
+                AccountUpdate::UpdateBalance {
+                    balance_update: (token_id, old_balance, new_balance),
+                    ..
+                } => {
+                    balances
+                        .entry(token_id)
+                        .or_insert((old_balance, BigUint::default()))
+                        .1 = new_balance;
+                }
+                _ => {}
+            }
+        }
+
+        for (account_id, (address, created, balances)) in accounts {
+            let address = match address {
+                Some(address) => Some(address),
+                None => {
+                    storage
+                        .chain()
+                        .account_schema()
+                        .account_address_by_id(account_id)
+                        .await?
+                }
+            };
+            let balances = balances
+                .into_iter()
+                .map(|(token_id, (old_balance, new_balance))| BalanceChange {
+                    token_id,
+                    current_balance: new_balance.to_string(),
+                    balance_after_revert: old_balance.to_string(),
+                })
+                .collect();
+
+            self.accounts.push(AccountChange {
+                account_id,
+                address,
+                created,
+                balances,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Writes the machine-readable plan as JSON.
+    pub fn write_report(&self, path: &Path) -> anyhow::Result<()> {
+        let report = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, report)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for RevertPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Revert plan: blocks after {} (last committed {}, last verified {})",
+            self.last_correct_block, self.last_committed_block, self.last_verified_block
+        )?;
+        if !self.contract_blocks.is_empty() {
+            writeln!(
+                f,
+                "Contract: `revertBlocks` for {} blocks",
+                self.contract_blocks.len()
+            )?;
+        }
+        if self.storage_rows.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f, "Storage rows to remove or update:")?;
+        for TableRows { table, rows } in self.storage_rows.iter().filter(|t| t.rows > 0) {
+            writeln!(f, "  `{}`: {}", table, rows)?;
+        }
+        writeln!(
+            f,
+            "Transactions returned to the mempool: {}, failed transactions dropped: {}",
+            self.returned_txs, self.dropped_txs
+        )?;
+        writeln!(
+            f,
+            "Priority operations returned to the queue: {}",
+            self.priority_ops.len()
+        )?;
+        for op in &self.priority_ops {
+            writeln!(
+                f,
+                "  #{} {} (block {}, tx {:?})",
+                op.serial_id, op.op_type, op.block_number, op.eth_hash
+            )?;
+        }
+        writeln!(f, "Affected accounts: {}", self.accounts.len())?;
+        for account in &self.accounts {
+            let address = account
+                .address
+                .map(|address| format!("{:?}", address))
+                .unwrap_or_else(|| "unknown address".to_string());
+            let created = if account.created { ", removed" } else { "" };
+            writeln!(
+                f,
+                "  account {} ({}{})",
+                account.account_id, address, created
+            )?;
+            for balance in &account.balances {
+                writeln!(
+                    f,
+                    "    token {}: {} -> {}",
+                    balance.token_id, balance.current_balance, balance.balance_after_revert
+                )?;
+            }
+        }
+        writeln!(
+            f,
+            "In-memory state to be reset by restarting the components:"
+        )?;
+        for artifact in &self.in_memory_artifacts {
+            writeln!(f, "  {}", artifact)?;
+        }
+        Ok(())
+    }
+}
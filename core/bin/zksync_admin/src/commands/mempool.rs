@@ -0,0 +1,80 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::format_err;
+use serde::Serialize;
+use zksync_storage::StorageProcessor;
+use zksync_types::{tx::TxHash, AccountId, Address};
+
+use crate::utils::{lock_maintenance, Context};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PurgedTxs {
+    account_id: AccountId,
+    tx_hashes: Vec<TxHash>,
+}
+
+impl fmt::Display for PurgedTxs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Removed {} transactions of account {}:",
+            self.tx_hashes.len(),
+            self.account_id
+        )?;
+        for tx_hash in &self.tx_hashes {
+            writeln!(f, "  {}", tx_hash.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves the account given either by its ID or by its address.
+async fn resolve_account(
+    storage: &mut StorageProcessor<'_>,
+    account: &str,
+) -> anyhow::Result<AccountId> {
+    let address = match account.strip_prefix("0x") {
+        Some(address) => Address::from_str(address)?,
+        None => return Ok(account.parse()?),
+    };
+    storage
+        .chain()
+        .account_schema()
+        .account_id_by_address(address)
+        .await?
+        .ok_or_else(|| format_err!("Account {:?} doesn't exist", address))
+}
+
+/// Removes the queued transactions of the account. Server keeps the mempool in memory,
+/// so it has to be stopped for the removal to take effect.
+pub async fn purge(context: &Context, account: &str) -> anyhow::Result<()> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    lock_maintenance(&mut storage).await?;
+    let account_id = resolve_account(&mut storage, account).await?;
+
+    let mut transaction = storage.start_transaction().await?;
+    let tx_hashes = transaction
+        .chain()
+        .mempool_schema()
+        .purge_account_txs(account_id)
+        .await?;
+    eprintln!(
+        "{} transactions of account {} are going to be removed",
+        tx_hashes.len(),
+        account_id
+    );
+    let prompt = format!(
+        "Type the account ID ({}) to confirm the removal",
+        account_id
+    );
+    if !context.confirm(&prompt, &account_id.to_string())? {
+        return Ok(());
+    }
+    transaction.commit().await?;
+
+    context.print(&PurgedTxs {
+        account_id,
+        tx_hashes,
+    })
+}
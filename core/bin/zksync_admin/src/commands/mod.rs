@@ -0,0 +1,72 @@
+use structopt::StructOpt;
+
+use crate::utils::Context;
+
+mod add_seq_no;
+mod block_revert;
+mod mempool;
+mod prover;
+mod pub_data;
+mod remove_proofs;
+mod resend_eth_op;
+mod status;
+mod tree_cache;
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Shows the last blocks at each stage and the sizes of the queues.
+    Status,
+    /// Reverts blocks on contract and/or in storage.
+    BlockRevert(block_revert::Opt),
+    /// Removes proofs (and the operations using them) for the blocks that are not proven on contract.
+    RemoveProofs {
+        /// Last correct block, proofs are removed for blocks with numbers greater than this field.
+        #[structopt(long)]
+        last_correct_block: u32,
+    },
+    /// Fills the sequence numbers of the executed transactions, following the new transactions
+    /// until stopped.
+    AddSeqNo,
+    /// Migrates the account tree caches to the binary format.
+    UpdateTreeCache {
+        /// Maximum amount of blocks to convert.
+        #[structopt(long)]
+        max_blocks: usize,
+    },
+    /// Parses the hex-encoded public data of the block.
+    ParsePubData {
+        /// Public data in hex, without the `0x` prefix.
+        pub_data: String,
+    },
+    /// Returns the prover job that is in progress or poisoned back to the queue.
+    RequeueJob {
+        /// ID of the prover job.
+        job_id: i32,
+    },
+    /// Removes the queued transactions of the account from the mempool.
+    PurgeMempool {
+        /// Account ID or address.
+        #[structopt(long)]
+        account: String,
+    },
+    /// Re-sends the stuck Rootstock operation with the increased gas price.
+    ResendEthOp(resend_eth_op::Opt),
+}
+
+impl Command {
+    pub async fn run(self, context: &Context) -> anyhow::Result<()> {
+        match self {
+            Self::Status => status::run(context).await,
+            Self::BlockRevert(opt) => block_revert::run(context, opt).await,
+            Self::RemoveProofs { last_correct_block } => {
+                remove_proofs::run(context, last_correct_block).await
+            }
+            Self::AddSeqNo => add_seq_no::run().await,
+            Self::UpdateTreeCache { max_blocks } => tree_cache::run(context, max_blocks).await,
+            Self::ParsePubData { pub_data } => pub_data::run(context, &pub_data),
+            Self::RequeueJob { job_id } => prover::requeue_job(context, job_id).await,
+            Self::PurgeMempool { account } => mempool::purge(context, &account).await,
+            Self::ResendEthOp(opt) => resend_eth_op::run(context, opt).await,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+
+use anyhow::ensure;
+use serde::Serialize;
+use zksync_storage::StorageProcessor;
+
+use crate::utils::Context;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RequeuedJob {
+    job_id: i32,
+}
+
+impl fmt::Display for RequeuedJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Prover job {} is returned to the queue", self.job_id)
+    }
+}
+
+/// Returns the job to the queue. Witness generator reads the queue from the database,
+/// so unlike the other commands it can be used while the server is running.
+pub async fn requeue_job(context: &Context, job_id: i32) -> anyhow::Result<()> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    ensure!(
+        storage.prover_schema().requeue_job(job_id).await?,
+        "Prover job {} is neither in progress nor poisoned",
+        job_id
+    );
+
+    context.print(&RequeuedJob { job_id })
+}
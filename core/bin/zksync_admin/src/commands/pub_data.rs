@@ -0,0 +1,42 @@
+use std::fmt;
+
+use anyhow::ensure;
+use serde::Serialize;
+use zksync_types::ZkSyncOp;
+
+use crate::utils::Context;
+
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+struct ParsedPubData(Vec<ZkSyncOp>);
+
+impl fmt::Display for ParsedPubData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for op in &self.0 {
+            writeln!(f, "{:#?}", op)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits the public data of the block into the operations.
+pub fn run(context: &Context, pub_data: &str) -> anyhow::Result<()> {
+    let data = hex::decode(pub_data.strip_prefix("0x").unwrap_or(pub_data))?;
+
+    let mut ops = Vec::new();
+    let mut unparsed_data = data.as_slice();
+    while !unparsed_data.is_empty() {
+        let op_type = unparsed_data[0];
+        let op_data_len = ZkSyncOp::public_data_length(op_type)?;
+        ensure!(
+            unparsed_data.len() >= op_data_len,
+            "Not enough bytes in the pubdata for current op"
+        );
+        let (current_op, unparsed) = unparsed_data.split_at(op_data_len);
+        let op = ZkSyncOp::from_public_data(current_op)?;
+        ops.push(op);
+        unparsed_data = unparsed;
+    }
+
+    context.print(&ParsedPubData(ops))
+}
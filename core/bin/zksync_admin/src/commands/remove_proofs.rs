@@ -0,0 +1,113 @@
+use std::fmt;
+
+use anyhow::ensure;
+use serde::Serialize;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+
+use crate::utils::{lock_maintenance, Context};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RemovedProofs {
+    last_correct_block: BlockNumber,
+    last_committed_block: BlockNumber,
+    last_proven_block: BlockNumber,
+}
+
+impl fmt::Display for RemovedProofs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Proofs were deleted for blocks after {} (last committed {}, last proven {})",
+            self.last_correct_block, self.last_committed_block, self.last_proven_block
+        )
+    }
+}
+
+async fn remove_operations(
+    storage: &mut StorageProcessor<'_>,
+    last_block: BlockNumber,
+) -> anyhow::Result<()> {
+    let mut transaction = storage.start_transaction().await?;
+    transaction
+        .prover_schema()
+        .remove_witnesses(last_block)
+        .await?;
+    eprintln!("`witness` table is cleaned");
+
+    transaction
+        .chain()
+        .operations_schema()
+        .remove_eth_unprocessed_aggregated_ops()
+        .await?;
+    eprintln!("`eth_unprocessed_aggregated_ops` table is cleaned");
+    transaction
+        .chain()
+        .operations_schema()
+        .remove_aggregate_operations(last_block)
+        .await?;
+    eprintln!("`aggregate_operations`, `eth_aggregated_ops_binding`, `eth_tx_hashes`, `eth_operations` tables are cleaned");
+
+    transaction
+        .prover_schema()
+        .remove_proofs(last_block)
+        .await?;
+    eprintln!("`proofs` table is cleaned");
+    transaction
+        .prover_schema()
+        .remove_aggregated_proofs(last_block)
+        .await?;
+    eprintln!("`aggregated_proofs` table is cleaned");
+    transaction
+        .prover_schema()
+        .remove_prover_jobs(last_block)
+        .await?;
+    eprintln!("`prover_job_queue` table is cleaned");
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub async fn run(context: &Context, last_correct_block: u32) -> anyhow::Result<()> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    lock_maintenance(&mut storage).await?;
+
+    let last_committed_block = storage
+        .chain()
+        .block_schema()
+        .get_last_committed_confirmed_block()
+        .await?;
+    let last_proven_block = storage
+        .chain()
+        .block_schema()
+        .get_last_proven_confirmed_block()
+        .await?;
+
+    eprintln!(
+        "Last committed block {} proven {}",
+        &last_committed_block, &last_proven_block
+    );
+    ensure!(
+        *last_proven_block <= last_correct_block,
+        "Some proofs has already been published to rootstock"
+    );
+
+    let last_block = BlockNumber(last_correct_block);
+    let prompt = format!(
+        "Type the last correct block number ({}) to confirm the removal of proofs",
+        last_block
+    );
+    if !context.confirm(&prompt, &last_block.to_string())? {
+        return Ok(());
+    }
+
+    eprintln!("Start remove proofs");
+    remove_operations(&mut storage, last_block).await?;
+
+    context.print(&RemovedProofs {
+        last_correct_block: last_block,
+        last_committed_block,
+        last_proven_block,
+    })
+}
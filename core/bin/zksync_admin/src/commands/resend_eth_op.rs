@@ -0,0 +1,149 @@
+use std::{cmp, fmt, str::FromStr};
+
+use anyhow::{ensure, format_err};
+use num::BigUint;
+use serde::Serialize;
+use structopt::StructOpt;
+use web3::{contract::Options, types::U256};
+use zksync_storage::StorageProcessor;
+use zksync_types::H256;
+
+use crate::utils::{eth_gateway, lock_maintenance, Context};
+
+/// Minimal increase of the gas price (in percent) for the node to accept the replacement transaction.
+const GAS_PRICE_INCREASE_PERCENT: u64 = 15;
+
+#[derive(Debug, StructOpt)]
+pub struct Opt {
+    /// ID of the Rootstock operation.
+    eth_op_id: i64,
+    /// Gas price (in wei) for the new transaction. By default, the network gas price is used,
+    /// but not less than the previous gas price increased by 15%.
+    #[structopt(long)]
+    gas_price: Option<u64>,
+    /// Private key of the operator, the one from the server configuration is used by default.
+    #[structopt(long = "key")]
+    operator_private_key: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResentEthOp {
+    eth_op_id: i64,
+    nonce: U256,
+    old_gas_price: U256,
+    new_gas_price: U256,
+    deadline_block: u64,
+    tx_hash: H256,
+}
+
+impl fmt::Display for ResentEthOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Rootstock operation {} is re-sent: tx hash {:?}, nonce {}, gas price {} -> {}, deadline block {}",
+            self.eth_op_id,
+            self.tx_hash,
+            self.nonce,
+            self.old_gas_price,
+            self.new_gas_price,
+            self.deadline_block
+        )
+    }
+}
+
+fn increased(value: U256) -> U256 {
+    value * (100 + GAS_PRICE_INCREASE_PERCENT) / 100
+}
+
+fn u256_to_big_uint(value: U256) -> BigUint {
+    BigUint::from_str(&value.to_string()).unwrap()
+}
+
+/// Replaces the last transaction of the unconfirmed operation with the one paying the higher gas price.
+/// Server keeps the sent operations in memory, so it has to be stopped; the new transaction
+/// is tracked once the server is started again.
+pub async fn run(context: &Context, opt: Opt) -> anyhow::Result<()> {
+    let (client, eth_sender_config) = eth_gateway(opt.operator_private_key.as_deref())?;
+    let mut storage = StorageProcessor::establish_connection().await?;
+    lock_maintenance(&mut storage).await?;
+
+    let op = storage
+        .rootstock_schema()
+        .load_unconfirmed_operations()
+        .await?
+        .into_iter()
+        .find(|op| op.id == opt.eth_op_id)
+        .ok_or_else(|| format_err!("No unconfirmed Rootstock operation {}", opt.eth_op_id))?;
+
+    let old_gas_price = op.last_used_gas_price;
+    let new_gas_price = match opt.gas_price {
+        Some(gas_price) => U256::from(gas_price),
+        None => cmp::max(client.get_gas_price().await?, increased(old_gas_price)),
+    };
+    ensure!(
+        new_gas_price > old_gas_price,
+        "Gas price must be greater than the previous one ({})",
+        old_gas_price
+    );
+    // Dynamic fee (EIP-1559) transaction is replaced with the one of the same type.
+    let new_priority_fee = op
+        .last_used_priority_fee
+        .map(|fee| cmp::min(increased(fee), new_gas_price));
+
+    eprintln!(
+        "Operation {} (nonce {}, last tx {:?}) is going to be re-sent with the gas price {} instead of {}",
+        op.id,
+        op.nonce,
+        op.used_tx_hashes.last(),
+        new_gas_price,
+        old_gas_price
+    );
+    let prompt = format!("Type the operation ID ({}) to confirm", op.id);
+    if !context.confirm(&prompt, &op.id.to_string())? {
+        return Ok(());
+    }
+
+    let nonce = op.nonce;
+    let gas_limit = op.gas_limit();
+    let options = Options::with(move |opt| {
+        opt.gas_price = Some(new_gas_price);
+        opt.max_fee_per_gas = new_priority_fee.map(|_| new_gas_price);
+        opt.max_priority_fee_per_gas = new_priority_fee;
+        opt.nonce = Some(nonce);
+        opt.gas = Some(gas_limit);
+    });
+    let signed_tx = client
+        .sign_prepared_tx(op.encoded_tx_data.clone(), options)
+        .await?;
+    let deadline_block =
+        client.block_number().await?.as_u64() + eth_sender_config.sender.expected_wait_time_block;
+
+    // The transaction is stored before it's sent, so it's tracked by the server even if sending fails.
+    let mut transaction = storage.start_transaction().await?;
+    transaction
+        .rootstock_schema()
+        .update_eth_tx(
+            op.id,
+            deadline_block as i64,
+            u256_to_big_uint(new_gas_price),
+            new_priority_fee.map(u256_to_big_uint),
+        )
+        .await?;
+    transaction
+        .rootstock_schema()
+        .add_hash_entry(op.id, &signed_tx.hash)
+        .await?;
+    transaction.commit().await?;
+
+    client.send_raw_tx(signed_tx.raw_tx).await?;
+
+    context.print(&ResentEthOp {
+        eth_op_id: op.id,
+        nonce,
+        old_gas_price,
+        new_gas_price,
+        deadline_block,
+        tx_hash: signed_tx.hash,
+    })
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use serde::Serialize;
+use zksync_storage::StorageProcessor;
+use zksync_types::BlockNumber;
+
+use crate::utils::Context;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChainStatus {
+    last_saved_block: BlockNumber,
+    last_committed_block: BlockNumber,
+    last_proven_block: BlockNumber,
+    last_verified_block: BlockNumber,
+    mempool_size: u32,
+    pending_prover_jobs: u32,
+    poisoned_prover_jobs: usize,
+    /// Rootstock operations which are sent but not confirmed yet.
+    unconfirmed_eth_ops: usize,
+}
+
+impl fmt::Display for ChainStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Last saved block: {}", self.last_saved_block)?;
+        writeln!(f, "Last committed block: {}", self.last_committed_block)?;
+        writeln!(f, "Last proven block: {}", self.last_proven_block)?;
+        writeln!(f, "Last verified block: {}", self.last_verified_block)?;
+        writeln!(f, "Mempool size: {}", self.mempool_size)?;
+        writeln!(
+            f,
+            "Prover jobs: {} pending, {} poisoned",
+            self.pending_prover_jobs, self.poisoned_prover_jobs
+        )?;
+        writeln!(
+            f,
+            "Unconfirmed Rootstock operations: {}",
+            self.unconfirmed_eth_ops
+        )
+    }
+}
+
+pub async fn run(context: &Context) -> anyhow::Result<()> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    let mut block_schema = storage.chain().block_schema();
+
+    let status = ChainStatus {
+        last_saved_block: block_schema.get_last_saved_block().await?,
+        last_committed_block: block_schema.get_last_committed_confirmed_block().await?,
+        last_proven_block: block_schema.get_last_proven_confirmed_block().await?,
+        last_verified_block: block_schema.get_last_verified_confirmed_block().await?,
+        mempool_size: storage.chain().mempool_schema().get_mempool_size().await?,
+        pending_prover_jobs: storage.prover_schema().pending_jobs_count().await?,
+        poisoned_prover_jobs: storage.prover_schema().get_poisoned_jobs().await?.len(),
+        unconfirmed_eth_ops: storage
+            .rootstock_schema()
+            .load_unconfirmed_operations()
+            .await?
+            .len(),
+    };
+
+    context.print(&status)
+}
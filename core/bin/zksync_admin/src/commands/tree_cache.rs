@@ -1,25 +1,30 @@
-use structopt::StructOpt;
+use std::fmt;
+
+use serde::Serialize;
 use zksync_config::DBConfig;
 use zksync_crypto::merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256;
 use zksync_storage::StorageProcessor;
 use zksync_types::BlockNumber;
 
-#[derive(Debug, StructOpt)]
-#[structopt(
-    name = "tree_cache_updater",
-    about = "Tool to migrate server tree caches to the binary format."
-)]
-struct Opt {
-    /// Maximum amount of blocks to convert.
-    #[structopt(long)]
-    max_blocks: usize,
+use crate::utils::{lock_maintenance, Context};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConvertedCaches {
+    /// Blocks for which the cache was found and converted.
+    blocks: Vec<BlockNumber>,
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let opt = Opt::from_args();
+impl fmt::Display for ConvertedCaches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Converted caches: {}", self.blocks.len())
+    }
+}
 
+/// Migrates the account tree caches to the binary format.
+pub async fn run(context: &Context, max_blocks: usize) -> anyhow::Result<()> {
     let mut storage = StorageProcessor::establish_connection().await?;
+    lock_maintenance(&mut storage).await?;
     let mut transaction = storage.start_transaction().await?;
 
     let max_block = transaction
@@ -28,27 +33,20 @@ async fn main() -> anyhow::Result<()> {
         .get_last_saved_block()
         .await?;
 
-    let min_block = std::cmp::max(max_block.0.saturating_sub(opt.max_blocks as u32), 1); // We can't go below the 1st block.
+    let min_block = std::cmp::max(max_block.0.saturating_sub(max_blocks as u32), 1); // We can't go below the 1st block.
 
-    println!(
-        "I'm going to convert caches for blocks from {} to {}",
+    eprintln!(
+        "Caches for blocks from {} to {} are going to be converted",
         min_block, max_block.0
     );
-    println!("Database URL is {}", DBConfig::from_env().url);
-    println!("Proceed? [y/n]");
-
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-
-    if input.trim().to_lowercase() == "y" {
-        println!("OK. Starting!");
-    } else {
-        println!("Quitting");
+    eprintln!("Database URL is {}", DBConfig::from_env().url);
+    if !context.confirm("Proceed? [y/n]", "y")? {
         return Ok(());
     }
 
     // Go through the suggested blocks range. For each block in this range, if the cache exists, we will load it, convert to the bincode cache,
     // and store to the binary schema.
+    let mut converted = ConvertedCaches { blocks: Vec::new() };
     for block in min_block..(max_block.0) {
         if let Some(cache) = transaction
             .chain()
@@ -63,13 +61,12 @@ async fn main() -> anyhow::Result<()> {
                 .tree_cache_schema_bincode()
                 .store_account_tree_cache(BlockNumber(block), binary_cache)
                 .await?;
+            converted.blocks.push(BlockNumber(block));
         }
-        println!("Block {} processed", block);
+        eprintln!("Block {} processed", block);
     }
 
     transaction.commit().await?;
 
-    println!("Done");
-
-    Ok(())
+    context.print(&converted)
 }
@@ -0,0 +1,39 @@
+//! Operator tool for the zkSync server maintenance.
+//!
+//! Results of the commands are printed to stdout, either for the operator or as JSON (`--json`),
+//! while the progress and the confirmation prompts go to stderr.
+//! Commands that change the server state take the maintenance lock first, so they can't be run
+//! while the server is running.
+
+use structopt::StructOpt;
+
+use self::{commands::Command, utils::Context};
+
+mod commands;
+mod utils;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "zksync-admin", author = "Matter Labs")]
+#[structopt(about = "Tool for the zkSync server maintenance")]
+struct Opt {
+    /// Prints the result as JSON.
+    #[structopt(long, global = true)]
+    json: bool,
+    /// Skips the confirmations.
+    #[structopt(long, global = true)]
+    yes: bool,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+// TODO: don't use anyhow (ZKS-588)
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    let context = Context {
+        json: opt.json,
+        yes: opt.yes,
+    };
+
+    opt.command.run(&context).await
+}
@@ -0,0 +1,82 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use anyhow::ensure;
+use serde::Serialize;
+use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_eth_client::RootstockGateway;
+use zksync_storage::StorageProcessor;
+use zksync_types::H256;
+
+/// Options shared by all the commands.
+#[derive(Debug, Clone, Copy)]
+pub struct Context {
+    /// Results are printed as JSON.
+    pub json: bool,
+    /// Confirmations are skipped.
+    pub yes: bool,
+}
+
+impl Context {
+    /// Prints the result of the command to stdout.
+    pub fn print<R: Serialize + fmt::Display>(&self, report: &R) -> anyhow::Result<()> {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(report)?);
+        } else {
+            print!("{}", report);
+        }
+        Ok(())
+    }
+
+    /// Asks the operator to confirm the action by typing the `expected` answer.
+    pub fn confirm(&self, prompt: &str, expected: &str) -> anyhow::Result<bool> {
+        if self.yes {
+            return Ok(true);
+        }
+        eprint!("{}: ", prompt);
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        let confirmed = answer.trim() == expected;
+        if !confirmed {
+            eprintln!("Cancelled");
+        }
+        Ok(confirmed)
+    }
+}
+
+/// Takes the maintenance lock, which is held until the connection is closed.
+/// Fails if the server is running, since it holds the shared lock while running.
+pub async fn lock_maintenance(storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    ensure!(
+        storage.misc_schema().try_lock_maintenance().await?,
+        "Server (or another maintenance command) is running, stop it first"
+    );
+    Ok(())
+}
+
+/// Creates the Rootstock gateway from the server configuration.
+/// The operator private key from the configuration can be overridden.
+pub fn eth_gateway(
+    operator_private_key: Option<&str>,
+) -> anyhow::Result<(RootstockGateway, ETHSenderConfig)> {
+    let contracts = ContractsConfig::from_env();
+    let eth_client_config = ETHClientConfig::from_env();
+    let mut eth_sender_config = ETHSenderConfig::from_env();
+    if let Some(key) = operator_private_key {
+        let key_without_prefix = key.strip_prefix("0x").unwrap_or(key);
+        eth_sender_config.sender.operator_private_key = H256::from_str(key_without_prefix)
+            .map_err(|_| anyhow::format_err!("Cannot deserialize private key"))?;
+    }
+
+    let gateway = RootstockGateway::from_config(
+        &eth_client_config,
+        &eth_sender_config,
+        contracts.contract_addr,
+    );
+    Ok((gateway, eth_sender_config))
+}
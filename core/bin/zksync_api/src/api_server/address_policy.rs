@@ -0,0 +1,176 @@
+//! Address allow- and deny-lists applied to the submitted transactions.
+//!
+//! The lists are stored in the database and managed through the private API of the core,
+//! every API server reloads them periodically, so the changes are applied without restarts.
+
+// Built-in uses
+use std::{
+    collections::HashSet,
+    iter,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// External uses
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    address_lists::{AddressListEntry, AddressListType},
+    Address,
+};
+
+// Local uses
+use crate::api_server::tx_sender::SubmitError;
+
+/// How long the loaded lists are used before they are reloaded from the database.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error, Clone, Copy, PartialEq)]
+pub enum AddressPolicyViolation {
+    #[error("address {0:?} is not allowed to submit transactions")]
+    NotAllowed(Address),
+    #[error("address {0:?} is denied")]
+    Denied(Address),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct AddressLists {
+    allowed: HashSet<Address>,
+    denied: HashSet<Address>,
+}
+
+impl AddressLists {
+    pub fn new(entries: impl IntoIterator<Item = AddressListEntry>) -> Self {
+        let mut lists = Self::default();
+        for entry in entries {
+            match entry.list {
+                AddressListType::Allow => lists.allowed.insert(entry.address),
+                AddressListType::Deny => lists.denied.insert(entry.address),
+            };
+        }
+        lists
+    }
+
+    /// Checks the transaction with the given initiator and other participants
+    /// (recipients, order owners, etc.).
+    ///
+    /// The empty allow-list allows everyone, otherwise only the listed addresses can
+    /// initiate transactions. The denied addresses can't take part in transactions at all.
+    pub fn check(
+        &self,
+        initiator: Address,
+        participants: &[Address],
+    ) -> Result<(), AddressPolicyViolation> {
+        if !self.allowed.is_empty() && !self.allowed.contains(&initiator) {
+            return Err(AddressPolicyViolation::NotAllowed(initiator));
+        }
+
+        for address in iter::once(&initiator).chain(participants) {
+            if self.denied.contains(address) {
+                return Err(AddressPolicyViolation::Denied(*address));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Address lists cached by the API server.
+#[derive(Debug, Clone)]
+pub struct AddressPolicy {
+    pool: ConnectionPool,
+    lists: Arc<RwLock<(AddressLists, Option<Instant>)>>,
+}
+
+impl AddressPolicy {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self {
+            pool,
+            lists: Default::default(),
+        }
+    }
+
+    pub async fn check(
+        &self,
+        initiator: Address,
+        participants: &[Address],
+    ) -> Result<(), SubmitError> {
+        self.reload_if_outdated().await?;
+
+        let lists = self.lists.read().await;
+        lists.0.check(initiator, participants)?;
+        Ok(())
+    }
+
+    async fn reload_if_outdated(&self) -> Result<(), SubmitError> {
+        if let Some(loaded_at) = self.lists.read().await.1 {
+            if loaded_at.elapsed() < RELOAD_INTERVAL {
+                return Ok(());
+            }
+        }
+
+        let entries = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .address_lists_schema()
+            .load_entries()
+            .await
+            .map_err(SubmitError::internal)?;
+
+        *self.lists.write().await = (AddressLists::new(entries), Some(Instant::now()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn entry(address: Address, list: AddressListType) -> AddressListEntry {
+        AddressListEntry {
+            address,
+            list,
+            reason: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Checks that the deny-list applies to every participant and the allow-list to the initiator only.
+    #[test]
+    fn address_lists() {
+        let (user, recipient, denied) = (Address::random(), Address::random(), Address::random());
+
+        let lists = AddressLists::new(vec![entry(denied, AddressListType::Deny)]);
+        assert_eq!(lists.check(user, &[recipient]), Ok(()));
+        assert_eq!(
+            lists.check(denied, &[recipient]),
+            Err(AddressPolicyViolation::Denied(denied))
+        );
+        assert_eq!(
+            lists.check(user, &[recipient, denied]),
+            Err(AddressPolicyViolation::Denied(denied))
+        );
+
+        let lists = AddressLists::new(vec![
+            entry(user, AddressListType::Allow),
+            entry(denied, AddressListType::Allow),
+            entry(denied, AddressListType::Deny),
+        ]);
+        assert_eq!(lists.check(user, &[recipient]), Ok(()));
+        assert_eq!(
+            lists.check(recipient, &[user]),
+            Err(AddressPolicyViolation::NotAllowed(recipient))
+        );
+        // Denial takes precedence over the allowance.
+        assert_eq!(
+            lists.check(denied, &[]),
+            Err(AddressPolicyViolation::Denied(denied))
+        );
+    }
+}
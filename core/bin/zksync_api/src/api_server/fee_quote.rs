@@ -0,0 +1,187 @@
+//! Signed fee quotes allowing users to pay the fee they were told about,
+//! even if the token price or the gas price change before the transaction is submitted.
+//!
+//! Quote ID is a JSON Web Token signed with the secret shared by all the API servers,
+//! so the quotes don't have to be stored until they are used. Used quotes are recorded
+//! in the database, so every quote can be used for a single transaction only.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use chrono::{DateTime, TimeZone, Utc};
+use jsonwebtoken::{
+    decode, encode, errors::ErrorKind, DecodingKey, EncodingKey, Header, Validation,
+};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_api_types::v02::fee::FeeQuote;
+use zksync_types::{Address, TokenId, TxFeeTypes};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+// Local uses
+use super::tx_sender::SubmitError;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeQuoteClaims {
+    tx_type: TxFeeTypes,
+    token: TokenId,
+    address: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    fee: BigUint,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+/// Fee guaranteed by the valid quote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotedFee {
+    pub fee: BigUint,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct FeeQuoteSigner {
+    secret: String,
+    validity: Duration,
+}
+
+impl FeeQuoteSigner {
+    pub fn new(secret: String, validity: Duration) -> Self {
+        Self { secret, validity }
+    }
+
+    /// Creates the quote guaranteeing the fee for the transaction with the given parameters.
+    pub fn issue(
+        &self,
+        tx_type: TxFeeTypes,
+        token: TokenId,
+        address: Address,
+        fee: BigUint,
+    ) -> Result<FeeQuote, SubmitError> {
+        let exp = Utc::now().timestamp() as usize + self.validity.as_secs() as usize;
+        let claims = FeeQuoteClaims {
+            tx_type,
+            token,
+            address,
+            fee,
+            exp,
+        };
+        let quote_id = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.secret.as_ref()),
+        )
+        .map_err(SubmitError::internal)?;
+
+        Ok(FeeQuote {
+            quote_id,
+            expires_at: Utc.timestamp(exp as i64, 0),
+        })
+    }
+
+    /// Returns the fee guaranteed by the quote for the transaction with the given parameters.
+    ///
+    /// Returns `None` if the quote has already expired. Quotes with an invalid signature
+    /// or issued for another transaction are rejected.
+    pub fn quoted_fee(
+        &self,
+        quote_id: &str,
+        tx_type: TxFeeTypes,
+        token: TokenId,
+        address: Address,
+    ) -> Result<Option<QuotedFee>, SubmitError> {
+        let validation = Validation {
+            leeway: 0,
+            ..Validation::default()
+        };
+        let claims = match decode::<FeeQuoteClaims>(
+            quote_id,
+            &DecodingKey::from_secret(self.secret.as_ref()),
+            &validation,
+        ) {
+            Ok(token_data) => token_data.claims,
+            Err(err) if matches!(err.kind(), ErrorKind::ExpiredSignature) => return Ok(None),
+            Err(err) => return Err(SubmitError::InvalidFeeQuote(err.to_string())),
+        };
+
+        if claims.tx_type != tx_type || claims.token != token || claims.address != address {
+            return Err(SubmitError::InvalidFeeQuote(
+                "quote was issued for another transaction".to_string(),
+            ));
+        }
+        Ok(Some(QuotedFee {
+            fee: claims.fee,
+            expires_at: Utc.timestamp(claims.exp as i64, 0),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the quote is accepted only for the transaction it was issued for.
+    #[test]
+    fn quoted_fee() {
+        let signer = FeeQuoteSigner::new("secret".into(), Duration::from_secs(60));
+        let address = Address::random();
+        let fee = BigUint::from(1_000_000u32);
+
+        let quote = signer
+            .issue(TxFeeTypes::Transfer, TokenId(1), address, fee.clone())
+            .unwrap();
+        assert!(quote.expires_at > Utc::now());
+        assert_eq!(
+            signer
+                .quoted_fee(&quote.quote_id, TxFeeTypes::Transfer, TokenId(1), address)
+                .unwrap(),
+            Some(QuotedFee {
+                fee,
+                expires_at: quote.expires_at
+            })
+        );
+
+        assert!(signer
+            .quoted_fee(&quote.quote_id, TxFeeTypes::Withdraw, TokenId(1), address)
+            .is_err());
+        assert!(signer
+            .quoted_fee(&quote.quote_id, TxFeeTypes::Transfer, TokenId(2), address)
+            .is_err());
+        assert!(signer
+            .quoted_fee(
+                &quote.quote_id,
+                TxFeeTypes::Transfer,
+                TokenId(1),
+                Address::random()
+            )
+            .is_err());
+
+        // Quotes signed with another secret are rejected.
+        let other_signer = FeeQuoteSigner::new("other".into(), Duration::from_secs(60));
+        assert!(other_signer
+            .quoted_fee(&quote.quote_id, TxFeeTypes::Transfer, TokenId(1), address)
+            .is_err());
+
+        // Expired quotes are ignored.
+        let expired_signer = FeeQuoteSigner::new("secret".into(), Duration::from_secs(0));
+        let quote = expired_signer
+            .issue(
+                TxFeeTypes::Transfer,
+                TokenId(1),
+                address,
+                BigUint::from(1u32),
+            )
+            .unwrap();
+        std::thread::sleep(Duration::from_secs(1));
+        assert_eq!(
+            expired_signer
+                .quoted_fee(&quote.quote_id, TxFeeTypes::Transfer, TokenId(1), address)
+                .unwrap(),
+            None
+        );
+    }
+}
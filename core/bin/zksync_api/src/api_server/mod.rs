@@ -4,9 +4,12 @@
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
 
+mod address_policy;
 mod event_notify;
+mod fee_quote;
 pub mod forced_exit_checker;
 mod helpers;
+pub mod rate_limiter;
 pub mod rest;
 pub mod rpc_server;
 pub mod rpc_subscriptions;
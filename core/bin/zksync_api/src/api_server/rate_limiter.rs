@@ -0,0 +1,472 @@
+//! Token-bucket rate limiter shared by the API servers.
+//!
+//! Requests are limited per caller IP and method, transaction submissions are additionally
+//! limited per sender account. The limiter is created once and shared by the JSON-RPC, web3
+//! and REST API servers, so the quotas apply to all of them together.
+//!
+//! The `CF-Connecting-IP` header identifies the caller only if the API is configured to be behind
+//! a proxy setting it, otherwise anyone could get a fresh quota by sending a random header.
+//! The REST API falls back to the peer address. The JSON-RPC servers don't expose the peer address,
+//! so the callers without a trusted IP share a single quota per method. The calls made through
+//! the web3 WebSocket server are limited per session.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// External uses
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse},
+    http::StatusCode,
+    HttpResponse, ResponseError,
+};
+use futures::future::{self, Either};
+use jsonrpc_core::{
+    middleware::{NoopCallFuture, NoopFuture},
+    Call, Metadata, Middleware, Output,
+};
+use jsonrpc_pubsub::{PubSubMetadata, Session};
+use thiserror::Error;
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_types::Address;
+
+// Local uses
+use super::rest::v02::{error::Error as ApiV02Error, response::ApiResult, SharedData};
+use super::rpc_server::ip_insert_middleware::CLOUDFLARE_CONNECTING_IP_HEADER;
+
+/// Name of the quota used for the per-account limit of the submitted transactions.
+const ACCOUNT_QUOTA_NAME: &str = "account_txs";
+/// Caller key of the requests without a known IP.
+const UNKNOWN_CALLER: &str = "unknown";
+/// Interval of dropping the buckets which are full again, i.e. of the callers that stopped making requests.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("Rate limit exceeded for {0}")]
+pub struct RateLimitExceeded(pub String);
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, capacity: f64, now: Instant) -> Self {
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            updated_at: now,
+        }
+    }
+
+    /// Refills the bucket and takes a token from it if there is one.
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens + elapsed * self.rate >= self.capacity
+    }
+}
+
+#[derive(Debug)]
+struct Buckets {
+    buckets: HashMap<(String, String), TokenBucket>,
+    evicted_at: Instant,
+}
+
+impl Buckets {
+    /// Drops the buckets which are full again, at most once per `EVICTION_INTERVAL`.
+    /// The full scan of the map isn't done on every request, so it doesn't slow them down.
+    fn evict(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.evicted_at) >= EVICTION_INTERVAL {
+            self.buckets.retain(|_, bucket| !bucket.is_full(now));
+            self.evicted_at = now;
+        }
+    }
+}
+
+impl Default for Buckets {
+    fn default() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            evicted_at: Instant::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    default_quota: u32,
+    method_quotas: Arc<HashMap<String, u32>>,
+    account_quota: u32,
+    burst: u32,
+    behind_proxy: bool,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &CommonApiConfig) -> Self {
+        Self {
+            default_quota: config.rate_limit_requests_per_sec,
+            method_quotas: Arc::new(config.rate_limit_method_quotas()),
+            account_quota: config.rate_limit_account_txs_per_sec,
+            burst: config.rate_limit_burst,
+            behind_proxy: config.rate_limit_behind_proxy,
+            buckets: Default::default(),
+        }
+    }
+
+    /// Creates the limiter which lets all the requests through.
+    pub fn disabled() -> Self {
+        Self {
+            default_quota: 0,
+            method_quotas: Default::default(),
+            account_quota: 0,
+            burst: 0,
+            behind_proxy: false,
+            buckets: Default::default(),
+        }
+    }
+
+    /// Whether the `CF-Connecting-IP` header can be trusted to identify the caller.
+    pub fn behind_proxy(&self) -> bool {
+        self.behind_proxy
+    }
+
+    /// Checks the request to the API method made from the given IP.
+    pub fn check_ip(&self, method: &str, ip: &str) -> Result<(), RateLimitExceeded> {
+        let quota = self
+            .method_quotas
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_quota);
+        self.check(quota, method, ip)
+    }
+
+    /// Checks the transaction submitted by the given account.
+    pub fn check_account(&self, address: Address) -> Result<(), RateLimitExceeded> {
+        self.check(
+            self.account_quota,
+            ACCOUNT_QUOTA_NAME,
+            &format!("{:?}", address),
+        )
+    }
+
+    fn check(&self, quota: u32, method: &str, caller: &str) -> Result<(), RateLimitExceeded> {
+        if quota == 0 {
+            return Ok(());
+        }
+
+        let rate = quota as f64;
+        // Burst can't be less than the rate, otherwise the rate itself is never reached.
+        let capacity = self.burst.max(quota) as f64;
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.evict(now);
+
+        let allowed = buckets
+            .buckets
+            .entry((method.to_owned(), caller.to_owned()))
+            .or_insert_with(|| TokenBucket::new(rate, capacity, now))
+            .try_take(now);
+        drop(buckets);
+
+        if allowed {
+            Ok(())
+        } else {
+            metrics::increment_counter!(
+                "api.rate_limiter.rejected_requests",
+                "method" => method.to_owned()
+            );
+            Err(RateLimitExceeded(method.to_owned()))
+        }
+    }
+}
+
+/// Metadata of the JSON-RPC call, contains the IP of the caller.
+#[derive(Debug, Clone, Default)]
+pub struct CallerMetadata {
+    pub ip: Option<String>,
+}
+
+impl Metadata for CallerMetadata {}
+
+impl CallerMetadata {
+    /// Extracts the IP of the caller from the `CF-Connecting-IP` header set by the proxy.
+    /// The header is ignored if the API is not behind a proxy, since anyone can set it then.
+    pub fn from_request(request: &hyper::Request<hyper::Body>, behind_proxy: bool) -> Self {
+        let ip = request
+            .headers()
+            .get(CLOUDFLARE_CONNECTING_IP_HEADER)
+            .filter(|_| behind_proxy)
+            .and_then(|ip| ip.to_str().ok())
+            .map(|ip| ip.to_owned());
+        Self { ip }
+    }
+}
+
+/// Metadata of the call made through the WebSocket server. The server doesn't expose the caller IP,
+/// so the calls are limited per session.
+#[derive(Clone)]
+pub struct SessionMetadata {
+    pub session: Arc<Session>,
+    pub caller: String,
+}
+
+impl SessionMetadata {
+    pub fn new(session: Arc<Session>, session_id: impl fmt::Display) -> Self {
+        Self {
+            session,
+            caller: format!("ws_session_{}", session_id),
+        }
+    }
+}
+
+impl Metadata for SessionMetadata {}
+
+impl PubSubMetadata for SessionMetadata {
+    fn session(&self) -> Option<Arc<Session>> {
+        Some(self.session.clone())
+    }
+}
+
+/// JSON-RPC metadata identifying the caller the quotas are applied to.
+pub trait RateLimitedCaller: Metadata {
+    fn caller(&self) -> &str;
+}
+
+impl RateLimitedCaller for CallerMetadata {
+    fn caller(&self) -> &str {
+        self.ip.as_deref().unwrap_or(UNKNOWN_CALLER)
+    }
+}
+
+impl RateLimitedCaller for SessionMetadata {
+    fn caller(&self) -> &str {
+        &self.caller
+    }
+}
+
+/// JSON-RPC middleware rejecting the calls which exceed the rate limit.
+#[derive(Debug, Clone)]
+pub struct RateLimitMiddleware(pub RateLimiter);
+
+impl<M: RateLimitedCaller> Middleware<M> for RateLimitMiddleware {
+    type Future = NoopFuture;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: M, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, M) -> X + Send + Sync,
+        X: std::future::Future<Output = Option<Output>> + Send + 'static,
+    {
+        if let Call::MethodCall(method_call) = &call {
+            if let Err(err) = self.0.check_ip(&method_call.method, meta.caller()) {
+                let output =
+                    Output::from(Err(err.into()), method_call.id.clone(), method_call.jsonrpc);
+                return Either::Left(future::ready(Some(output)));
+            }
+        }
+        Either::Right(next(call, meta))
+    }
+}
+
+/// Error returned by the REST API to the rate limited requests, contains the serialized response.
+#[derive(Debug)]
+struct RestRateLimitError(String);
+
+impl fmt::Display for RestRateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl ResponseError for RestRateLimitError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .content_type("application/json")
+            .body(self.0.clone())
+    }
+}
+
+/// Returns the IP of the REST API caller. Behind a proxy it's taken from the `CF-Connecting-IP` header
+/// or the forwarding headers, otherwise it's the peer address.
+fn rest_caller_ip(req: &ServiceRequest, behind_proxy: bool) -> Option<String> {
+    if !behind_proxy {
+        return req.peer_addr().map(|addr| addr.ip().to_string());
+    }
+
+    let header_ip = req
+        .headers()
+        .get(CLOUDFLARE_CONNECTING_IP_HEADER)
+        .and_then(|ip| ip.to_str().ok());
+    match header_ip {
+        Some(ip) => Some(ip.to_owned()),
+        None => req
+            .connection_info()
+            .realip_remote_addr()
+            .map(|ip| ip.to_owned()),
+    }
+}
+
+/// Checks the request to the REST API v0.2, the method is the resource of the request
+/// (e.g. `transactions` for `/api/v0.2/transactions/{tx_hash}`). Requests to other APIs are not limited.
+///
+/// The rejected requests get the `429 Too Many Requests` status and the usual API error in the body.
+pub(crate) fn check_rest_request<S, B>(
+    limiter: &RateLimiter,
+    data: &SharedData,
+    req: ServiceRequest,
+    service: &S,
+) -> Either<S::Future, future::Ready<Result<ServiceResponse<B>, actix_web::Error>>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+{
+    let resource = req
+        .path()
+        .strip_prefix("/api/v0.2/")
+        .and_then(|path| path.split('/').next());
+
+    if let Some(resource) = resource {
+        let ip = rest_caller_ip(&req, limiter.behind_proxy());
+        let ip = ip.as_deref().unwrap_or(UNKNOWN_CALLER);
+        if let Err(err) = limiter.check_ip(resource, ip) {
+            let body = ApiResult::<()>::Error(ApiV02Error::from(err)).to_json(req.request(), data);
+            return Either::Right(future::ready(Err(RestRateLimitError(body).into())));
+        }
+    }
+    Either::Left(service.call(req))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the bucket lets the burst through and then refills with the configured rate.
+    #[test]
+    fn token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(1.0, 3.0, start);
+
+        for _ in 0..3 {
+            assert!(bucket.try_take(start));
+        }
+        assert!(!bucket.try_take(start));
+        assert!(!bucket.try_take(start + Duration::from_millis(500)));
+        assert!(bucket.try_take(start + Duration::from_secs(1)));
+        assert!(!bucket.is_full(start + Duration::from_secs(1)));
+        // The bucket never holds more tokens than its capacity.
+        assert!(bucket.is_full(start + Duration::from_secs(100)));
+        for _ in 0..3 {
+            assert!(bucket.try_take(start + Duration::from_secs(100)));
+        }
+        assert!(!bucket.try_take(start + Duration::from_secs(100)));
+    }
+
+    /// Checks that only the buckets which are full again are dropped, and only once per interval.
+    #[test]
+    fn buckets_eviction() {
+        let start = Instant::now();
+        let mut buckets = Buckets {
+            buckets: HashMap::new(),
+            evicted_at: start,
+        };
+        let mut empty_bucket = TokenBucket::new(0.001, 1.0, start);
+        assert!(empty_bucket.try_take(start));
+        buckets
+            .buckets
+            .insert(("tx_submit".to_owned(), "1.1.1.1".to_owned()), empty_bucket);
+        buckets.buckets.insert(
+            ("tx_submit".to_owned(), "2.2.2.2".to_owned()),
+            TokenBucket::new(1.0, 1.0, start),
+        );
+
+        buckets.evict(start + Duration::from_secs(1));
+        assert_eq!(buckets.buckets.len(), 2);
+
+        buckets.evict(start + EVICTION_INTERVAL);
+        assert_eq!(buckets.buckets.len(), 1);
+        assert!(buckets
+            .buckets
+            .contains_key(&("tx_submit".to_owned(), "1.1.1.1".to_owned())));
+    }
+
+    /// Checks that the `CF-Connecting-IP` header is trusted only behind a proxy.
+    #[test]
+    fn caller_metadata() {
+        let request = hyper::Request::builder()
+            .header(CLOUDFLARE_CONNECTING_IP_HEADER, "1.1.1.1")
+            .body(hyper::Body::empty())
+            .unwrap();
+
+        assert_eq!(
+            CallerMetadata::from_request(&request, true).ip,
+            Some("1.1.1.1".to_owned())
+        );
+        assert_eq!(CallerMetadata::from_request(&request, false).ip, None);
+    }
+
+    /// Checks that the quotas are applied per method and caller.
+    #[test]
+    fn rate_limiter() {
+        let limiter = RateLimiter {
+            default_quota: 0,
+            method_quotas: Arc::new(HashMap::from([("tx_submit".to_owned(), 1)])),
+            account_quota: 1,
+            burst: 2,
+            behind_proxy: false,
+            buckets: Default::default(),
+        };
+
+        for _ in 0..10 {
+            assert_eq!(limiter.check_ip("account_info", "1.1.1.1"), Ok(()));
+        }
+        for _ in 0..2 {
+            assert_eq!(limiter.check_ip("tx_submit", "1.1.1.1"), Ok(()));
+        }
+        assert_eq!(
+            limiter.check_ip("tx_submit", "1.1.1.1"),
+            Err(RateLimitExceeded("tx_submit".to_owned()))
+        );
+        assert_eq!(limiter.check_ip("tx_submit", "2.2.2.2"), Ok(()));
+
+        let (account, other_account) = (Address::random(), Address::random());
+        for _ in 0..2 {
+            assert_eq!(limiter.check_account(account), Ok(()));
+        }
+        assert!(limiter.check_account(account).is_err());
+        assert_eq!(limiter.check_account(other_account), Ok(()));
+
+        let limiter = RateLimiter::disabled();
+        for _ in 0..10 {
+            assert_eq!(limiter.check_ip("tx_submit", "1.1.1.1"), Ok(()));
+            assert_eq!(limiter.check_account(account), Ok(()));
+        }
+    }
+}
@@ -86,6 +86,8 @@ pub enum SumbitErrorCode {
     IncorrectTx = 104,
     TxAdd = 105,
     InappropriateFeeToken = 106,
+    AddressPolicyViolation = 107,
+    RateLimitExceeded = 108,
 
     Internal = 110,
     CommunicationCoreServer = 111,
@@ -101,10 +103,13 @@ impl SumbitErrorCode {
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
             SubmitError::TxAdd(_) => Self::TxAdd,
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::AddressPolicy(_) => Self::AddressPolicyViolation,
+            SubmitError::RateLimit(_) => Self::RateLimitExceeded,
             SubmitError::MempoolCommunication(_) => Self::CommunicationCoreServer,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
             SubmitError::Toggle2FA(_) => Self::Other,
+            SubmitError::InvalidFeeQuote(_) => Self::Other,
             SubmitError::PriceError(_) => Self::Other,
         }
     }
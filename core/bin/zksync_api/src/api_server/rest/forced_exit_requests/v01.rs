@@ -24,7 +24,7 @@ use zksync_storage::ConnectionPool;
 use zksync_types::{
     forced_exit_requests::{
         ForcedExitEligibilityResponse, ForcedExitRequest, ForcedExitRequestId,
-        SaveForcedExitRequestQuery,
+        ForcedExitRequestInfo, SaveForcedExitRequestQuery,
     },
     Address, TokenLike,
 };
@@ -168,7 +168,7 @@ pub async fn submit_request(
 pub async fn get_request_by_id(
     data: web::Data<ApiForcedExitRequestsData>,
     request_id: web::Path<ForcedExitRequestId>,
-) -> JsonResult<ForcedExitRequest> {
+) -> JsonResult<ForcedExitRequestInfo> {
     let start = Instant::now();
 
     let mut storage = data
@@ -185,11 +185,26 @@ pub async fn get_request_by_id(
         .await
         .map_err(ApiError::internal)?;
 
+    let request = match fe_request_from_db {
+        Some(fe_request) => fe_request,
+        None => return Err(ApiError::not_found("Request with such id does not exist")),
+    };
+
+    let exits = fe_requests_schema
+        .get_token_exits(request.id)
+        .await
+        .map_err(ApiError::internal)?;
+    let refund = fe_requests_schema
+        .get_refund(request.id)
+        .await
+        .map_err(ApiError::internal)?;
+
     metrics::histogram!("api", start.elapsed(), "type" => "v01", "endpoint_name" => "get_forced_exit_request_by_id");
-    match fe_request_from_db {
-        Some(fe_request) => Ok(Json(fe_request)),
-        None => Err(ApiError::not_found("Request with such id does not exist")),
-    }
+    Ok(Json(ForcedExitRequestInfo {
+        request,
+        exits,
+        refund,
+    }))
 }
 
 // Checks if the account is eligible for forced_exit in terms of
@@ -453,6 +468,12 @@ mod tests {
         assert_eq!(submit_result.tokens, tokens);
         assert_eq!(submit_result.target, target);
 
+        // The request is not processed yet, so it has neither exits nor refund
+        let request_info = client.get_forced_exit_request(submit_result.id).await?;
+        assert_eq!(request_info.request, submit_result);
+        assert!(request_info.exits.is_empty());
+        assert_eq!(request_info.refund, None);
+
         server.stop().await;
         Ok(())
     }
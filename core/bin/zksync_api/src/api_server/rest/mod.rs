@@ -11,7 +11,10 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use self::v01::api_decl::ApiV01;
 use crate::signature_checker::VerifySignatureRequest;
 
-use super::tx_sender::TxSender;
+use super::{
+    rate_limiter::{check_rest_request, RateLimiter},
+    tx_sender::TxSender,
+};
 
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use crate::fee_ticker::FeeTicker;
@@ -34,6 +37,7 @@ async fn start_server(
     bind_to: SocketAddr,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     use_https: bool,
+    rate_limiter: RateLimiter,
 ) {
     let server = HttpServer::new(move || {
         let api_v01 = api_v01.clone();
@@ -57,10 +61,14 @@ async fn start_server(
                 &api_v01.config.api.common,
                 &api_v01.config.api.token_config,
                 mempool_tx_sender.clone(),
+                rate_limiter.clone(),
             );
             v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
         };
+        let rate_limiter = rate_limiter.clone();
+        let api_v02_data = v02::SharedData::new(&api_v01.config);
         App::new()
+            .wrap_fn(move |req, srv| check_rest_request(&rate_limiter, &api_v02_data, req, srv))
             .wrap(
                 Cors::default()
                     .send_wildcard()
@@ -127,6 +135,7 @@ pub fn start_server_thread_detached(
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     core_address: String,
+    rate_limiter: RateLimiter,
 ) -> JoinHandle<()> {
     let (handler, panic_sender) = spawn_panic_handler();
 
@@ -164,6 +173,7 @@ pub fn start_server_thread_detached(
                     listen_addr,
                     mempool_tx_sender.clone(),
                     config.api.rest.use_https,
+                    rate_limiter,
                 )
                 .await;
             });
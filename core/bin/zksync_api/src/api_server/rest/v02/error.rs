@@ -11,7 +11,10 @@ use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 
 // Local uses
-use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
+use crate::{
+    api_server::{rate_limiter::RateLimitExceeded, tx_sender::SubmitError},
+    fee_ticker::PriceError,
+};
 
 #[derive(Serialize_repr, Debug, Deserialize_repr, Clone, PartialEq)]
 #[repr(u16)]
@@ -27,6 +30,7 @@ pub enum ErrorCode {
     PaginationLimitTooBig = 206,
     QueryDeserializationError = 207,
     InvalidNFTTokenId = 208,
+    BatchNotFound = 209,
     StorageError = 300,
     TokenNotFound = 500,
     ExternalApiError = 501,
@@ -39,6 +43,9 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    InvalidFeeQuote = 609,
+    AddressPolicyViolation = 610,
+    RateLimitExceeded = 611,
     Other = 60_000,
 }
 
@@ -103,6 +110,8 @@ pub enum InvalidDataError {
     PaginationLimitTooBig,
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error("Batch is not found")]
+    BatchNotFound,
 }
 
 impl ApiError for InvalidDataError {
@@ -120,6 +129,7 @@ impl ApiError for InvalidDataError {
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
             Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::BatchNotFound => ErrorCode::BatchNotFound,
         }
     }
 }
@@ -184,6 +194,16 @@ impl ApiError for anyhow::Error {
     }
 }
 
+impl ApiError for RateLimitExceeded {
+    fn error_type(&self) -> String {
+        String::from("rateLimitError")
+    }
+
+    fn code(&self) -> ErrorCode {
+        ErrorCode::RateLimitExceeded
+    }
+}
+
 impl ApiError for SubmitError {
     fn error_type(&self) -> String {
         String::from("submitError")
@@ -197,6 +217,9 @@ impl ApiError for SubmitError {
             Self::IncorrectTx(_) => ErrorCode::IncorrectTx,
             Self::TxAdd(_) => ErrorCode::TxAddError,
             Self::InappropriateFeeToken => ErrorCode::InappropriateFeeToken,
+            Self::InvalidFeeQuote(_) => ErrorCode::InvalidFeeQuote,
+            Self::AddressPolicy(_) => ErrorCode::AddressPolicyViolation,
+            Self::RateLimit(_) => ErrorCode::RateLimitExceeded,
             Self::MempoolCommunication(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
@@ -11,6 +11,7 @@ use actix_web::{
 
 // Workspace uses
 use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_types::TxFeeTypes;
 
 // Local uses
 use super::{error::Error, response::ApiResult};
@@ -45,17 +46,29 @@ async fn get_tx_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
+    let tx_type: TxFeeTypes = body.tx_type.into();
     // TODO implement subsidies for v02 api ZKS-888
-    let res = data
+    let fee = api_try!(data
         .tx_sender
         .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
+        .get_fee_from_ticker_in_wei(tx_type, body.token_like.clone(), body.address)
         .await
-        .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+        .map_err(Error::from))
+    .normal_fee;
+    let quote = api_try!(data
+        .tx_sender
+        .fee_quote(
+            tx_type,
+            body.token_like,
+            body.address,
+            fee.total_fee.clone()
+        )
+        .await
+        .map_err(Error::from));
+    let mut api_fee = ApiFee::from(fee);
+    api_fee.quote = quote;
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_tx_fee");
-    res
+    ApiResult::Ok(api_fee)
 }
 
 async fn get_batch_fee(
@@ -101,6 +114,7 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api_server::rate_limiter::RateLimiter;
     use crate::api_server::rest::v02::{
         test_utils::{
             deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
@@ -172,6 +186,7 @@ mod tests {
                     &cfg.config.api.common,
                     &cfg.config.api.token_config,
                     mempool_tx_request_sender.clone(),
+                    RateLimiter::disabled(),
                 ))
             },
             Some(shared_data),
@@ -197,6 +212,7 @@ mod tests {
         assert_eq!(api_fee.gas_fee, BigUint::from(1u32));
         assert_eq!(api_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_fee.total_fee, BigUint::from(2u32));
+        assert!(api_fee.quote.is_some());
 
         let tx = TxInBatchFeeRequest {
             tx_type: ApiTxFeeTypes::Withdraw,
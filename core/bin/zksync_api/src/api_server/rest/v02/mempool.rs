@@ -0,0 +1,276 @@
+//! Mempool part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::{
+    mempool::MempoolStats,
+    pagination::{
+        parse_query, MempoolBatchesRequest, MempoolPriorityOpsRequest, MempoolTxsRequest,
+        Paginated, PaginationQuery,
+    },
+    transaction::{ApiTxBatch, Transaction, TxHashSerializeWrapper},
+};
+use zksync_storage::ConnectionPool;
+use zksync_types::{tx::TxHash, SerialId};
+
+// Local uses
+use super::{error::Error, paginate_trait::Paginate, response::ApiResult};
+use crate::api_try;
+
+/// Shared data between `api/v0.2/mempool` endpoints.
+#[derive(Debug, Clone)]
+struct ApiMempoolData {
+    pool: ConnectionPool,
+}
+
+impl ApiMempoolData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+
+    async fn txs(
+        &self,
+        query: PaginationQuery<MempoolTxsRequest>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate_checked(&query).await
+    }
+
+    async fn batches(
+        &self,
+        query: PaginationQuery<MempoolBatchesRequest>,
+    ) -> Result<Paginated<ApiTxBatch, TxHashSerializeWrapper>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate_checked(&query).await
+    }
+
+    async fn priority_ops(
+        &self,
+        query: PaginationQuery<MempoolPriorityOpsRequest>,
+    ) -> Result<Paginated<Transaction, SerialId>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage.paginate_checked(&query).await
+    }
+
+    async fn stats(&self) -> Result<MempoolStats, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .mempool_schema()
+            .get_mempool_stats()
+            .await
+            .map_err(Error::storage)
+    }
+}
+
+// Server implementation
+
+async fn mempool_txs(
+    data: web::Data<ApiMempoolData>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query::<TxHash>(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: MempoolTxsRequest {
+            tx_hash: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let res = data.txs(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "mempool_txs");
+    res
+}
+
+async fn mempool_batches(
+    data: web::Data<ApiMempoolData>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<ApiTxBatch, TxHashSerializeWrapper>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query::<TxHash>(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: MempoolBatchesRequest {
+            batch_hash: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let res = data.batches(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "mempool_batches");
+    res
+}
+
+async fn mempool_priority_ops(
+    data: web::Data<ApiMempoolData>,
+    web::Query(query): web::Query<PaginationQuery<String>>,
+) -> ApiResult<Paginated<Transaction, SerialId>> {
+    let start = Instant::now();
+    let query = api_try!(parse_query::<SerialId>(query).map_err(Error::from));
+    let query = PaginationQuery {
+        from: MempoolPriorityOpsRequest {
+            serial_id: query.from,
+        },
+        limit: query.limit,
+        direction: query.direction,
+    };
+    let res = data.priority_ops(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "mempool_priority_ops");
+    res
+}
+
+async fn mempool_stats(data: web::Data<ApiMempoolData>) -> ApiResult<MempoolStats> {
+    let start = Instant::now();
+    let res = data.stats().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "mempool_stats");
+    res
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiMempoolData::new(pool);
+
+    web::scope("mempool")
+        .app_data(web::Data::new(data))
+        .route("txs", web::get().to(mempool_txs))
+        .route("batches", web::get().to(mempool_batches))
+        .route("priorityOps", web::get().to(mempool_priority_ops))
+        .route("stats", web::get().to(mempool_stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, dummy_deposit_op, TestServerConfig},
+        SharedData,
+    };
+    use chrono::Utc;
+    use std::str::FromStr;
+    use zksync_api_types::v02::{
+        pagination::{ApiEither, PaginationDirection},
+        transaction::{L1Transaction, TransactionData},
+        ApiVersion,
+    };
+    use zksync_types::{Address, SignedZkSyncTx, TokenId};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn mempool_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) =
+            cfg.start_server(move |cfg| api_scope(cfg.pool.clone()), Some(shared_data));
+
+        let (tx_hash, batch_hash, serial_id) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let mut mempool_txs =
+                TestServerConfig::gen_zk_txs(1_u64)
+                    .txs
+                    .into_iter()
+                    .map(|(tx, _)| SignedZkSyncTx {
+                        tx,
+                        eth_sign_data: None,
+                        created_at: Utc::now(),
+                    });
+
+            let tx = mempool_txs.next().unwrap();
+            storage.chain().mempool_schema().insert_tx(&tx).await?;
+
+            let batch: Vec<_> = mempool_txs.take(2).collect();
+            let batch_hash =
+                TxHash::batch_hash(&batch.iter().map(|tx| tx.hash()).collect::<Vec<_>>());
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_batch(&batch, Vec::new())
+                .await?;
+
+            let serial_id = storage
+                .chain()
+                .mempool_schema()
+                .get_max_priority_op_serial_id()
+                .await?
+                .map(|serial_id| serial_id + 1)
+                .unwrap_or_default();
+            let deposit = dummy_deposit_op(Address::default(), Default::default(), serial_id, 0);
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_priority_ops(&[deposit.priority_op], true)
+                .await?;
+
+            (tx.hash(), batch_hash, serial_id)
+        };
+
+        let query = PaginationQuery {
+            from: ApiEither::from_str("latest").unwrap(),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.mempool_txs(&query).await?;
+        let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
+        assert_eq!(txs.list.len(), 1);
+        assert_eq!(txs.list[0].tx_hash, tx_hash);
+        assert_eq!(txs.pagination.from, tx_hash);
+
+        let response = client.mempool_batches(&query).await?;
+        let batches: Paginated<ApiTxBatch, TxHash> = deserialize_response_result(response)?;
+        assert_eq!(batches.list.len(), 1);
+        assert_eq!(batches.list[0].batch_hash, batch_hash);
+        assert_eq!(batches.list[0].transaction_hashes.len(), 2);
+
+        let query = PaginationQuery {
+            from: ApiEither::from(serial_id),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.mempool_priority_ops(&query).await?;
+        let ops: Paginated<Transaction, SerialId> = deserialize_response_result(response)?;
+        assert_eq!(ops.list.len(), 1);
+        match &ops.list[0].op {
+            TransactionData::L1(L1Transaction::Deposit(deposit)) => {
+                assert_eq!(deposit.id, serial_id);
+            }
+            _ => panic!("mempool_priority_ops should return deposit"),
+        }
+
+        let expected_stats = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage.chain().mempool_schema().get_mempool_stats().await?
+        };
+        let response = client.mempool_stats().await?;
+        let stats: MempoolStats = deserialize_response_result(response)?;
+        assert_eq!(stats.queued_txs, expected_stats.queued_txs);
+        assert_eq!(stats.queued_batches, expected_stats.queued_batches);
+        assert_eq!(stats.priority_ops, expected_stats.priority_ops);
+        assert_eq!(stats.count_by_type, expected_stats.count_by_type);
+        assert_eq!(stats.count_by_token, expected_stats.count_by_token);
+        assert!(stats.count_by_token[&TokenId(0)] >= 3);
+        assert!(stats.oldest_pending_age.is_some());
+
+        // Pagination from the unknown transaction is an error.
+        let query = PaginationQuery {
+            from: ApiEither::from(TxHash::default()),
+            limit: 1,
+            direction: PaginationDirection::Older,
+        };
+        let response = client.mempool_txs(&query).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
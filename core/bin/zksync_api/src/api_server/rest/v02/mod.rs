@@ -17,9 +17,10 @@ mod block;
 mod config;
 pub mod error;
 mod fee;
+mod mempool;
 mod paginate_impl;
 mod paginate_trait;
-mod response;
+pub(crate) mod response;
 mod status;
 #[cfg(test)]
 pub mod test_utils;
@@ -32,15 +33,21 @@ pub struct SharedData {
     pub api_version: ApiVersion,
 }
 
+impl SharedData {
+    pub(crate) fn new(zk_config: &ZkSyncConfig) -> Self {
+        Self {
+            net: zk_config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        }
+    }
+}
+
 pub(crate) fn api_scope(
     tx_sender: TxSender,
     zk_config: &ZkSyncConfig,
     network_status: SharedNetworkStatus,
 ) -> Scope {
-    let data = SharedData {
-        net: zk_config.chain.eth.network,
-        api_version: ApiVersion::V02,
-    };
+    let data = SharedData::new(zk_config);
     web::scope("/api/v0.2")
         .app_data(web::Data::new(data))
         .service(account::api_scope(
@@ -54,6 +61,7 @@ pub(crate) fn api_scope(
         ))
         .service(config::api_scope(zk_config))
         .service(fee::api_scope(tx_sender.clone()))
+        .service(mempool::api_scope(tx_sender.pool.clone()))
         .service(status::api_scope(network_status))
         .service(token::api_scope(
             zk_config,
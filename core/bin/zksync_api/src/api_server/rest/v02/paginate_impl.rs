@@ -1,21 +1,23 @@
 // Built-in uses
 
 // External uses
+use chrono::{DateTime, Utc};
 
 // Workspace uses
 use zksync_api_types::{
     v02::{
         block::BlockInfo,
         pagination::{
-            AccountTxsRequest, ApiEither, BlockAndTxHash, Paginated, PaginationQuery,
+            AccountTxsRequest, ApiEither, BlockAndTxHash, MempoolBatchesRequest,
+            MempoolPriorityOpsRequest, MempoolTxsRequest, Paginated, PaginationQuery,
             PendingOpsRequest,
         },
-        transaction::{Transaction, TxHashSerializeWrapper},
+        transaction::{ApiTxBatch, Transaction, TxHashSerializeWrapper},
     },
     Either,
 };
 use zksync_storage::StorageProcessor;
-use zksync_types::{BlockNumber, SerialId, Token, TokenId};
+use zksync_types::{BlockNumber, PriorityOp, SerialId, Token, TokenId};
 
 // Local uses
 use super::{
@@ -308,26 +310,219 @@ impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
         let count = result.len() as u32;
         let txs = result
             .into_iter()
-            .map(|op| {
-                let tx_hash = op.tx_hash();
-                let tx = L1Transaction::from_pending_op(
-                    op.data.clone(),
-                    op.eth_hash,
-                    op.serial_id,
-                    tx_hash,
-                );
-                Transaction {
-                    tx_hash,
-                    block_index: None,
-                    block_number: None,
-                    op: TransactionData::L1(tx),
-                    status: TxInBlockStatus::Queued,
-                    fail_reason: None,
-                    created_at: None,
-                    batch_id: None,
+            .map(|op| queued_priority_op(op, None))
+            .collect();
+
+        Ok(Paginated::new(
+            txs,
+            serial_id,
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+/// Converts the priority operation awaiting for the execution into the API transaction.
+fn queued_priority_op(op: PriorityOp, created_at: Option<DateTime<Utc>>) -> Transaction {
+    let tx_hash = op.tx_hash();
+    let tx = L1Transaction::from_pending_op(op.data, op.eth_hash, op.serial_id, tx_hash);
+    Transaction {
+        tx_hash,
+        block_index: None,
+        block_number: None,
+        op: TransactionData::L1(tx),
+        status: TxInBlockStatus::Queued,
+        fail_reason: None,
+        created_at,
+        batch_id: None,
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<MempoolTxsRequest> for StorageProcessor<'_> {
+    type OutputObj = Transaction;
+    type OutputId = TxHashSerializeWrapper;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<MempoolTxsRequest>,
+    ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let tx_hash = match query.from.tx_hash.inner {
+            Either::Left(tx_hash) => tx_hash,
+            Either::Right(_) => {
+                if let Some(tx_hash) = transaction
+                    .chain()
+                    .mempool_schema()
+                    .get_last_queued_tx_hash()
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    tx_hash
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
                 }
-            })
+            }
+        };
+
+        let query = PaginationQuery {
+            from: tx_hash,
+            limit: query.limit,
+            direction: query.direction,
+        };
+
+        let txs = transaction
+            .chain()
+            .mempool_schema()
+            .load_queued_txs_page(&query)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TransactionNotFound))?;
+        let count = transaction
+            .chain()
+            .mempool_schema()
+            .get_queued_txs_count()
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            txs,
+            TxHashSerializeWrapper(tx_hash),
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<MempoolBatchesRequest> for StorageProcessor<'_> {
+    type OutputObj = ApiTxBatch;
+    type OutputId = TxHashSerializeWrapper;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<MempoolBatchesRequest>,
+    ) -> Result<Paginated<ApiTxBatch, TxHashSerializeWrapper>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let batch_hash = match query.from.batch_hash.inner {
+            Either::Left(batch_hash) => batch_hash,
+            Either::Right(_) => {
+                if let Some(batch_hash) = transaction
+                    .chain()
+                    .mempool_schema()
+                    .get_last_queued_batch_hash()
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    batch_hash
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let query = PaginationQuery {
+            from: batch_hash,
+            limit: query.limit,
+            direction: query.direction,
+        };
+
+        let batches = transaction
+            .chain()
+            .mempool_schema()
+            .load_queued_batches_page(&query)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::BatchNotFound))?;
+        let count = transaction
+            .chain()
+            .mempool_schema()
+            .get_queued_batches_count()
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
+
+        Ok(Paginated::new(
+            batches,
+            TxHashSerializeWrapper(batch_hash),
+            query.limit,
+            query.direction,
+            count,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl Paginate<MempoolPriorityOpsRequest> for StorageProcessor<'_> {
+    type OutputObj = Transaction;
+    type OutputId = SerialId;
+
+    async fn paginate(
+        &mut self,
+        query: &PaginationQuery<MempoolPriorityOpsRequest>,
+    ) -> Result<Paginated<Transaction, SerialId>, Error> {
+        let mut transaction = self.start_transaction().await.map_err(Error::storage)?;
+
+        let serial_id = match query.from.serial_id.inner {
+            Either::Left(serial_id) => serial_id,
+            // Right means the latest serial id
+            Either::Right(_) => {
+                if let Some(serial_id) = transaction
+                    .chain()
+                    .mempool_schema()
+                    .get_max_priority_op_serial_id()
+                    .await
+                    .map_err(Error::storage)?
+                {
+                    serial_id
+                } else {
+                    return Ok(Paginated::new(
+                        Vec::new(),
+                        Default::default(),
+                        query.limit,
+                        query.direction,
+                        0,
+                    ));
+                }
+            }
+        };
+
+        let txs = transaction
+            .chain()
+            .mempool_schema()
+            .get_priority_ops_page(serial_id, query.limit, query.direction)
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|(op, created_at)| queued_priority_op(op, Some(created_at)))
             .collect();
+        let count = transaction
+            .chain()
+            .mempool_schema()
+            .get_priority_ops_count()
+            .await
+            .map_err(Error::storage)?;
+
+        transaction.commit().await.map_err(Error::storage)?;
 
         Ok(Paginated::new(
             txs,
@@ -23,11 +23,9 @@ pub enum ApiResult<R: Serialize> {
     Error(Error),
 }
 
-impl<R: Serialize> Responder for ApiResult<R> {
-    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
-        let data = req
-            .app_data::<Data<SharedData>>()
-            .expect("Wrong app data type");
+impl<R: Serialize> ApiResult<R> {
+    /// Serializes the result into the API response for the given request.
+    pub(crate) fn to_json(self, req: &HttpRequest, data: &SharedData) -> String {
         let mut args = HashMap::new();
         for (name, value) in req.match_info().iter() {
             args.insert(name.to_string(), value.to_string());
@@ -60,7 +58,16 @@ impl<R: Serialize> Responder for ApiResult<R> {
             },
         };
 
-        let body = serde_json::to_string(&response).expect("Should be correct serializable");
+        serde_json::to_string(&response).expect("Should be correct serializable")
+    }
+}
+
+impl<R: Serialize> Responder for ApiResult<R> {
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse {
+        let data = req
+            .app_data::<Data<SharedData>>()
+            .expect("Wrong app data type");
+        let body = self.to_json(req, data);
 
         HttpResponse::Ok()
             .content_type("application/json")
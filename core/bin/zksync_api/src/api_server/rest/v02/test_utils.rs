@@ -3,6 +3,7 @@
 // Built-in uses
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 
 // External uses
 use actix_web::{web, App, Scope};
@@ -499,6 +500,7 @@ impl TestServerConfig {
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -536,12 +538,12 @@ impl TestServerConfig {
 
                 // Get job id.
                 let stored_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue("test_prover", Duration::from_secs(120))
                     .await?
                     .unwrap()
                     .job_id;
                 let stored_aggregated_job_id = ProverSchema(&mut storage)
-                    .get_idle_prover_job_from_job_queue()
+                    .get_idle_prover_job_from_job_queue("test_prover", Duration::from_secs(120))
                     .await?
                     .unwrap()
                     .job_id;
@@ -585,6 +587,7 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -626,6 +629,7 @@ impl TestServerConfig {
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -14,14 +14,15 @@ use num::{rational::Ratio, BigUint, FromPrimitive};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    pagination::{parse_query, ApiEither, Paginated, PaginationQuery},
-    token::{ApiNFT, ApiToken, TokenPrice},
+    pagination::{parse_query, ApiEither, Paginated, PaginationQuery, MAX_LIMIT},
+    token::{ApiNFT, ApiToken, HistoricalPrice, PriceHistoryQuery, TokenPrice, TokenPriceHistory},
 };
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{tx::TxHash, AccountId, Token, TokenId, TokenLike};
+use zksync_utils::ratio_to_big_decimal;
 
 // Local uses
 use super::{
@@ -31,7 +32,7 @@ use super::{
 };
 use crate::{
     api_try,
-    fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType},
+    fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType, TOKEN_PRICE_PRECISION},
 };
 
 /// Shared data between `api/v0.2/tokens` endpoints.
@@ -158,6 +159,37 @@ impl ApiTokenData {
             .await
             .map_err(Error::storage)
     }
+
+    async fn token_price_history(
+        &self,
+        token_like: TokenLike,
+        query: PriceHistoryQuery,
+    ) -> Result<TokenPriceHistory, Error> {
+        let limit = query.limit.unwrap_or(MAX_LIMIT);
+        if limit > MAX_LIMIT {
+            return Err(Error::from(InvalidDataError::PaginationLimitTooBig));
+        }
+        let token = self.token(token_like).await?;
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let prices = storage
+            .tokens_schema()
+            .get_ticker_price_history(token.id, query.from, query.to, limit)
+            .await
+            .map_err(Error::storage)?
+            .into_iter()
+            .map(|price| HistoricalPrice {
+                usd_price: ratio_to_big_decimal(&price.usd_price, TOKEN_PRICE_PRECISION),
+                last_updated: price.last_updated,
+            })
+            .collect();
+        Ok(TokenPriceHistory {
+            token_id: token.id,
+            token_symbol: token.symbol,
+            prices,
+        })
+    }
+
     // TODO: take `currency` as enum. (ZKS-628)
     async fn token_price_in(
         &self,
@@ -235,6 +267,18 @@ async fn token_price(
     })
 }
 
+async fn token_price_history(
+    data: web::Data<ApiTokenData>,
+    token_like_string: web::Path<String>,
+    web::Query(query): web::Query<PriceHistoryQuery>,
+) -> ApiResult<TokenPriceHistory> {
+    let start = Instant::now();
+    let token_like = TokenLike::parse(&token_like_string);
+    let res = data.token_price_history(token_like, query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_price_history");
+    res
+}
+
 async fn get_nft(
     data: web::Data<ApiTokenData>,
     id: web::Path<TokenId>,
@@ -304,6 +348,10 @@ pub fn api_scope(
             "{token_like}/priceIn/{currency}",
             web::get().to(token_price),
         )
+        .route(
+            "{token_like}/priceHistory",
+            web::get().to(token_price_history),
+        )
         .route("nft/{id}", web::get().to(get_nft))
         .route("nft/{id}/owner", web::get().to(get_nft_owner))
         .route(
@@ -456,6 +504,57 @@ mod tests {
         let response = client.token_price(&token_like, "333").await?;
         assert!(response.error.is_some());
 
+        let price_history = {
+            let mut storage = cfg.pool.access_storage().await?;
+            for price in [10_u32, 11] {
+                storage
+                    .tokens_schema()
+                    .update_historical_ticker_price(
+                        token.id,
+                        zksync_types::TokenPrice {
+                            usd_price: Ratio::from_integer(BigUint::from(price)),
+                            last_updated: chrono::Utc::now(),
+                        },
+                    )
+                    .await?;
+            }
+            storage
+                .tokens_schema()
+                .get_ticker_price_history(token.id, None, None, MAX_LIMIT)
+                .await?
+        };
+        let query = PriceHistoryQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let response = client.token_price_history(&token_like, &query).await?;
+        let history: TokenPriceHistory = deserialize_response_result(response)?;
+        assert_eq!(history.token_id, token.id);
+        assert_eq!(history.prices.len(), 2);
+        assert_eq!(history.prices[0].usd_price, BigDecimal::from(11));
+        assert_eq!(history.prices[1].usd_price, BigDecimal::from(10));
+        assert_eq!(
+            history.prices[0].last_updated,
+            price_history[0].last_updated
+        );
+
+        let query = PriceHistoryQuery {
+            to: Some(price_history[1].last_updated),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let response = client.token_price_history(&token_like, &query).await?;
+        let history: TokenPriceHistory = deserialize_response_result(response)?;
+        assert_eq!(history.prices.len(), 1);
+        assert_eq!(history.prices[0].usd_price, BigDecimal::from(10));
+
+        let query = PriceHistoryQuery {
+            limit: Some(MAX_LIMIT + 1),
+            ..Default::default()
+        };
+        let response = client.token_price_history(&token_like, &query).await?;
+        assert!(response.error.is_some());
+
         let nft_id = TokenId(65542);
         let response = client.nft_by_id(nft_id).await?;
         let nft: ApiNFT = deserialize_response_result(response)?;
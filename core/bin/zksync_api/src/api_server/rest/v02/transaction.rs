@@ -167,7 +167,7 @@ async fn submit_tx(
     let start = Instant::now();
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature, None)
+        .submit_tx(body.tx, body.signature, body.fee_quote, None)
         .await;
 
     if let Err(err) = &tx_hash {
@@ -251,7 +251,10 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api_server::fee_quote::FeeQuoteSigner;
+    use crate::api_server::rate_limiter::RateLimiter;
     use crate::api_server::rest::v02::{
+        error::{Error as ApiError, ErrorCode},
         test_utils::{
             deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
             TestTransactions,
@@ -259,19 +262,22 @@ mod tests {
         SharedData,
     };
     use crate::fee_ticker::validator::cache::TokenInMemoryCache;
+    use crate::signature_checker::{VerifiedTx, VerifySignatureRequest};
     use chrono::Utc;
     use futures::{channel::mpsc, StreamExt};
     use num::{rational::Ratio, BigUint};
     use std::collections::HashMap;
     use std::str::FromStr;
+    use std::time::Duration;
     use tokio::task::JoinHandle;
     use zksync_api_types::v02::{
         transaction::{L2Receipt, TxHashSerializeWrapper},
-        ApiVersion,
+        ApiVersion, Response,
     };
     use zksync_mempool::MempoolTransactionRequest;
     use zksync_types::{
         tokens::{Token, TokenMarketVolume},
+        tx::error::TxAddError,
         tx::{
             EthBatchSignData, EthBatchSignatures, PackedEthSignature, TxEthSignature,
             TxEthSignatureVariant,
@@ -353,6 +359,7 @@ mod tests {
                     &cfg.config.api.common,
                     &cfg.config.api.token_config,
                     sender.clone(),
+                    RateLimiter::disabled(),
                 ))
             },
             Some(shared_data),
@@ -375,6 +382,7 @@ mod tests {
                     TxWithSignature {
                         tx,
                         signature: TxEthSignatureVariant::Single(None),
+                        fee_quote: None,
                     },
                     tx_hash,
                 )
@@ -502,4 +510,202 @@ mod tests {
         task.abort();
         Ok(())
     }
+
+    /// Checks that the fee quote of the transaction rejected by the mempool can be used again.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn fee_quote_of_rejected_tx() -> anyhow::Result<()> {
+        // The mempool rejects the first transaction only.
+        let (sender, mut receiver) = mpsc::channel(100);
+        let task = tokio::spawn(async move {
+            let mut reject = true;
+            while let Some(request) = receiver.next().await {
+                if let MempoolTransactionRequest::NewTx(_, resp) = request {
+                    let result = if reject {
+                        Err(TxAddError::NonceMismatch)
+                    } else {
+                        Ok(())
+                    };
+                    resp.send(result).unwrap_or_default();
+                    reject = false;
+                }
+            }
+        });
+
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+
+        let mut tokens = HashMap::new();
+        tokens.insert(
+            TokenLike::Id(TokenId(0)),
+            Token::new(TokenId(0), Default::default(), "RBTC", 18, TokenKind::ERC20),
+        );
+        let mut market = HashMap::new();
+        market.insert(
+            TokenId(0),
+            TokenMarketVolume {
+                market_volume: Ratio::from_integer(BigUint::from(400u32)),
+                last_updated: Utc::now(),
+            },
+        );
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_market(market);
+        let prices = vec![(TokenLike::Id(TokenId(0)), 10500_u64.into())];
+        let signer = FeeQuoteSigner::new("secret".into(), Duration::from_secs(60));
+
+        let (client, server) = cfg.start_server(
+            {
+                let signer = signer.clone();
+                move |cfg: &TestServerConfig| {
+                    let mut tx_sender = TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, Some(cache.clone())),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                        RateLimiter::disabled(),
+                    );
+                    tx_sender.fee_quotes = Some(signer.clone());
+                    api_scope(tx_sender)
+                }
+            },
+            Some(shared_data),
+        );
+
+        let tx = TestServerConfig::gen_zk_txs(100_u64).txs[0].0.clone();
+        let (tx_type, token, address, fee) = tx.get_fee_info().unwrap();
+        let token = match token {
+            TokenLike::Id(token) => token,
+            _ => unreachable!("token is set by id"),
+        };
+        let fee_quote = signer.issue(tx_type, token, address, fee)?.quote_id;
+
+        let submit = || {
+            client.submit_tx_with_fee_quote(
+                tx.clone(),
+                TxEthSignatureVariant::Single(None),
+                fee_quote.clone(),
+            )
+        };
+        let error_code = |response: Response| {
+            let error: ApiError = serde_json::from_value(response.error.unwrap()).unwrap();
+            error.code
+        };
+
+        let response = submit().await?;
+        assert_eq!(error_code(response), ErrorCode::TxAddError);
+
+        let response = submit().await?;
+        let tx_hash: TxHash = deserialize_response_result(response)?;
+        assert_eq!(tx.hash(), tx_hash);
+
+        let response = submit().await?;
+        assert_eq!(error_code(response), ErrorCode::InvalidFeeQuote);
+
+        server.stop().await;
+        task.abort();
+        Ok(())
+    }
+
+    /// Checks that the account quota is charged only for the properly signed transactions.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn account_rate_limit_of_unsigned_tx() -> anyhow::Result<()> {
+        let (sender, task) = submit_txs_loopback();
+
+        // The signature checker rejects the first transaction only.
+        let (sign_verifier, mut sign_requests) = mpsc::channel::<VerifySignatureRequest>(10);
+        let sign_task = tokio::spawn(async move {
+            let mut reject = true;
+            while let Some(request) = sign_requests.next().await {
+                let result = if reject {
+                    Err(TxAddError::IncorrectEthSignature)
+                } else {
+                    Ok(VerifiedTx::unverified(request.data.get_tx_variant()))
+                };
+                request.response.send(result).unwrap_or_default();
+                reject = false;
+            }
+        });
+
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+
+        let mut tokens = HashMap::new();
+        tokens.insert(
+            TokenLike::Id(TokenId(0)),
+            Token::new(TokenId(0), Default::default(), "RBTC", 18, TokenKind::ERC20),
+        );
+        let mut market = HashMap::new();
+        market.insert(
+            TokenId(0),
+            TokenMarketVolume {
+                market_volume: Ratio::from_integer(BigUint::from(400u32)),
+                last_updated: Utc::now(),
+            },
+        );
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_market(market);
+        let prices = vec![(TokenLike::Id(TokenId(0)), 10500_u64.into())];
+
+        let mut api_config = cfg.config.api.common.clone();
+        api_config.rate_limit_account_txs_per_sec = 1;
+        api_config.rate_limit_burst = 1;
+
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(TxSender::new(
+                    cfg.pool.clone(),
+                    sign_verifier.clone(),
+                    dummy_fee_ticker(&prices, Some(cache.clone())),
+                    &cfg.config.api.common,
+                    &cfg.config.api.token_config,
+                    sender.clone(),
+                    RateLimiter::new(&api_config),
+                ))
+            },
+            Some(shared_data),
+        );
+
+        let tx = TestServerConfig::gen_zk_txs(100_u64).txs[0].0.clone();
+        let submit = || client.submit_tx(tx.clone(), TxEthSignatureVariant::Single(None));
+        let error_code = |response: Response| {
+            let error: ApiError = serde_json::from_value(response.error.unwrap()).unwrap();
+            error.code
+        };
+
+        let response = submit().await?;
+        assert_eq!(error_code(response), ErrorCode::TxAddError);
+
+        let response = submit().await?;
+        let tx_hash: TxHash = deserialize_response_result(response)?;
+        assert_eq!(tx.hash(), tx_hash);
+
+        let response = submit().await?;
+        assert_eq!(error_code(response), ErrorCode::RateLimitExceeded);
+
+        server.stop().await;
+        task.abort();
+        sign_task.abort();
+        Ok(())
+    }
 }
@@ -3,7 +3,7 @@ use jsonrpc_core::ErrorCode;
 use zksync_types::tx::error::TxAddError;
 // Workspace uses
 // Local uses
-use crate::api_server::tx_sender::SubmitError;
+use crate::api_server::{rate_limiter::RateLimitExceeded, tx_sender::SubmitError};
 
 #[derive(Debug, Clone, Copy)]
 pub enum RpcErrorCodes {
@@ -11,6 +11,9 @@ pub enum RpcErrorCodes {
     IncorrectTx = 103,
     FeeTooLow = 104,
     InappropriateFeeToken = 105,
+    InvalidFeeQuote = 106,
+    AddressPolicyViolation = 107,
+    RateLimitExceeded = 108,
 
     MissingEthSignature = 200,
     EIP1271SignatureVerificationFail = 201,
@@ -41,6 +44,11 @@ impl From<TxAddError> for RpcErrorCodes {
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
             TxAddError::EthSignaturesLimitExceeded => Self::Other,
+            TxAddError::AccountTxsLimitReached => Self::OperationsLimitReached,
+            TxAddError::MempoolIsFull => Self::OperationsLimitReached,
+            TxAddError::ReplacementFeeTooLow => Self::FeeTooLow,
+            TxAddError::ReplacementFeeUnknown => Self::InappropriateFeeToken,
+            TxAddError::NonceUsedInPendingBlock => Self::NonceMismatch,
         }
     }
 }
@@ -51,6 +59,16 @@ impl From<RpcErrorCodes> for ErrorCode {
     }
 }
 
+impl From<RateLimitExceeded> for jsonrpc_core::Error {
+    fn from(inner: RateLimitExceeded) -> Self {
+        Self {
+            code: RpcErrorCodes::RateLimitExceeded.into(),
+            message: inner.to_string(),
+            data: None,
+        }
+    }
+}
+
 impl From<SubmitError> for jsonrpc_core::Error {
     fn from(inner: SubmitError) -> Self {
         match inner {
@@ -87,6 +105,17 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::InvalidFeeQuote(_) => Self {
+                code: RpcErrorCodes::InvalidFeeQuote.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::AddressPolicy(_) => Self {
+                code: RpcErrorCodes::AddressPolicyViolation.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::RateLimit(err) => err.into(),
             SubmitError::MempoolCommunication(reason) => Self {
                 code: RpcErrorCodes::Other.into(),
                 message: "Error communicating core server".to_string(),
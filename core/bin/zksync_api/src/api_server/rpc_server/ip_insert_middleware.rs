@@ -8,7 +8,7 @@ use jsonrpc_http_server::{RequestMiddleware, RequestMiddlewareAction};
 
 use super::types::RequestMetadata;
 
-const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
+pub(crate) const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
 const METADATA_PARAM_NAME: &str = "extracted_request_metadata";
 
 /// Unfortunately, the JSON-RPC library does not natively support retrieving any information about the HTTP request,
@@ -47,7 +47,7 @@ fn get_call_with_ip_if_needed(
 ) -> jsonrpc_core::MethodCall {
     // Methods, which should have the information about the ip appended to them
     let methods_with_ip: HashMap<&'static str, MethodWithIpDescription> = HashMap::from_iter([
-        ("tx_submit", MethodWithIpDescription::new(1, 4)),
+        ("tx_submit", MethodWithIpDescription::new(1, 5)),
         ("submit_txs_batch", MethodWithIpDescription::new(1, 3)),
         ("get_tx_fee", MethodWithIpDescription::new(3, 4)),
         (
@@ -213,6 +213,7 @@ mod tests {
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Null,
+            Value::Null,
             json!({ "ip": IP }),
         ]);
         test_call_ip_insertion(
@@ -229,12 +230,14 @@ mod tests {
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("fee_quote".to_owned()),
             Value::String("override_ip".to_owned()),
         ]);
         let expected_result_params = Params::Array(vec![
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("fee_quote".to_owned()),
             json!({ "ip": IP }),
         ]);
         test_call_ip_insertion(
@@ -248,12 +251,14 @@ mod tests {
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("fee_quote".to_owned()),
             Value::String("override_ip".to_owned()),
         ]);
         let expected_result_params = Params::Array(vec![
             Value::String("serialized_transfer".to_owned()),
             Value::String("some_signature".to_owned()),
             Value::Bool(true),
+            Value::String("fee_quote".to_owned()),
         ]);
         test_call_ip_insertion(
             "tx_submit".to_string(),
@@ -320,6 +325,7 @@ mod tests {
                 Value::String("param2".to_owned()),
                 Value::String("param4".to_owned()),
                 Value::String("param5".to_owned()),
+                Value::String("param6".to_owned()),
             ]),
             Some(IP.to_owned()),
         );
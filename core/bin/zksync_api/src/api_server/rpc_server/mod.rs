@@ -3,7 +3,7 @@ use std::time::Instant;
 
 // External uses
 use futures::channel::mpsc;
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
 use tokio::task::JoinHandle;
 
@@ -23,14 +23,17 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use crate::{signature_checker::VerifySignatureRequest, utils::shared_lru_cache::AsyncLruCache};
 
 pub mod error;
-mod ip_insert_middleware;
+pub(crate) mod ip_insert_middleware;
 mod rpc_impl;
 mod rpc_trait;
 pub mod types;
 
 pub use self::rpc_trait::Rpc;
 use self::types::*;
-use super::tx_sender::TxSender;
+use super::{
+    rate_limiter::{CallerMetadata, RateLimitMiddleware, RateLimiter},
+    tx_sender::TxSender,
+};
 use crate::fee_ticker::FeeTicker;
 use ip_insert_middleware::IpInsertMiddleWare;
 use zksync_mempool::MempoolTransactionRequest;
@@ -47,6 +50,7 @@ pub struct RpcApp {
 }
 
 impl RpcApp {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
@@ -55,6 +59,7 @@ impl RpcApp {
         token_config: &TokenConfig,
         confirmations_for_eth_event: u64,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        rate_limiter: RateLimiter,
     ) -> Self {
         let api_requests_caches_size = config.caches_size;
 
@@ -65,6 +70,7 @@ impl RpcApp {
             config,
             token_config,
             mempool_tx_sender,
+            rate_limiter,
         );
 
         RpcApp {
@@ -266,6 +272,7 @@ pub fn start_rpc_server(
     token_config: &TokenConfig,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    rate_limiter: RateLimiter,
 ) -> JoinHandle<()> {
     let addr = config.http_bind_addr();
     let rpc_app = RpcApp::new(
@@ -276,15 +283,20 @@ pub fn start_rpc_server(
         token_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        rate_limiter.clone(),
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_sender);
-        let mut io = IoHandler::new();
+        let behind_proxy = rate_limiter.behind_proxy();
+        let meta_extractor = move |request: &hyper::Request<hyper::Body>| {
+            CallerMetadata::from_request(request, behind_proxy)
+        };
+        let mut io = MetaIoHandler::with_middleware(RateLimitMiddleware(rate_limiter));
         rpc_app.extend(&mut io);
 
-        let server = ServerBuilder::new(io)
+        let server = ServerBuilder::with_meta_extractor(io, meta_extractor)
             .threads(super::THREADS_PER_SERVER)
             .request_middleware(IpInsertMiddleWare {})
             .start_http(&addr)
@@ -15,7 +15,7 @@ use zksync_api_types::{
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_types::{
     tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
-    AccountId, Address, Fee, Token, TokenId, TokenLike, TotalFee, TxFeeTypes, ZkSyncTx,
+    AccountId, Address, Token, TokenId, TokenLike, TotalFee, TxFeeTypes, ZkSyncTx,
 };
 // Local uses
 use crate::{
@@ -142,6 +142,7 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash> {
         let start = Instant::now();
@@ -152,6 +153,7 @@ impl RpcApp {
                 *tx,
                 *signature,
                 fast_processing,
+                fee_quote,
                 extracted_request_metadata,
             )
             .await;
@@ -278,7 +280,7 @@ impl RpcApp {
         address: Address,
         token: TokenLike,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<Fee> {
+    ) -> Result<TxFeeResp> {
         let start = Instant::now();
         let token_allowed = self
             .tx_sender
@@ -290,10 +292,11 @@ impl RpcApp {
             return Err(SubmitError::InappropriateFeeToken.into());
         }
 
+        let tx_type = tx_type.into();
         let result = self
             .tx_sender
             .ticker
-            .get_fee_from_ticker_in_wei(tx_type.into(), token.clone(), address)
+            .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
             .await
             .map_err(SubmitError::Internal)?;
 
@@ -307,14 +310,19 @@ impl RpcApp {
             )
             .await?;
 
-        let fee = if should_subsidize_cpk {
-            result.subsidized_fee
+        // Subsidized fees are not quoted, since the subsidy must be checked upon the submission.
+        let (fee, quote) = if should_subsidize_cpk {
+            (result.subsidized_fee, None)
         } else {
-            result.normal_fee
+            let quote = self
+                .tx_sender
+                .fee_quote(tx_type, token, address, result.normal_fee.total_fee.clone())
+                .await?;
+            (result.normal_fee, quote)
         };
 
         metrics::histogram!("api", start.elapsed(), "type" => "rpc", "endpoint_name" => "get_tx_fee");
-        Ok(fee)
+        Ok(TxFeeResp { fee, quote })
     }
 
     pub async fn _impl_get_txs_batch_fee_in_wei(
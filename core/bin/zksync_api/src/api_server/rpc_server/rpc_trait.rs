@@ -16,7 +16,7 @@ use zksync_api_types::{
 use zksync_crypto::params::ROLLUP_VERSION;
 use zksync_types::{
     tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash},
-    AccountId, Address, Fee, Token, TokenId, TokenLike, TotalFee, ZkSyncTx,
+    AccountId, Address, Token, TokenId, TokenLike, TotalFee, ZkSyncTx,
 };
 
 // Local uses
@@ -48,6 +48,7 @@ pub trait Rpc {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> BoxFutureResult<TxHash>;
 
@@ -67,14 +68,14 @@ pub trait Rpc {
     fn tokens(&self) -> BoxFutureResult<HashMap<String, Token>>;
 
     // _address argument is left for the backward compatibility.
-    #[rpc(name = "get_tx_fee", returns = "Fee")]
+    #[rpc(name = "get_tx_fee", returns = "TxFeeResp")]
     fn get_tx_fee(
         &self,
         tx_type: ApiTxFeeTypes,
         _address: Address,
         token_like: TokenLike,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> BoxFutureResult<Fee>;
+    ) -> BoxFutureResult<TxFeeResp>;
 
     // _addresses argument is left for the backward compatibility.
     #[rpc(name = "get_txs_batch_fee_in_wei", returns = "TotalFee")]
@@ -131,9 +132,10 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
         meta: Option<RequestMetadata>,
     ) -> BoxFutureResult<TxHash> {
-        spawn!(self._impl_tx_submit(tx, signature, fast_processing, meta))
+        spawn!(self._impl_tx_submit(tx, signature, fast_processing, fee_quote, meta))
     }
 
     // Important: the last parameter should have name `meta` and be of type `RequestMetadata`
@@ -161,7 +163,7 @@ impl Rpc for RpcApp {
         address: Address,
         token_like: TokenLike,
         meta: Option<RequestMetadata>,
-    ) -> BoxFutureResult<Fee> {
+    ) -> BoxFutureResult<TxFeeResp> {
         spawn!(self._impl_get_tx_fee(tx_type, address, token_like, meta))
     }
 
@@ -7,12 +7,13 @@ use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_api_types::v02::{
     account::{DepositingAccountBalances, EthAccountType},
+    fee::FeeQuote,
     token::NFT,
 };
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{Account, AccountId, Address, Nonce, PubKeyHash, TokenId};
+use zksync_types::{Account, AccountId, Address, Fee, Nonce, PubKeyHash, TokenId};
 use zksync_utils::BigUintSerdeWrapper;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -126,6 +127,16 @@ pub struct ContractAddressResp {
     pub gov_contract: String,
 }
 
+/// Transaction fee along with the quote guaranteeing it, if the fee quotes are enabled.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxFeeResp {
+    #[serde(flatten)]
+    pub fee: Fee,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<FeeQuote>,
+}
+
 /// The metadata of the JSON-RPC call retrieved from the HTTP request of the call
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestMetadata {
@@ -20,6 +20,7 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use crate::fee_ticker::FeeTicker;
 use crate::{
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
+    api_server::rate_limiter::RateLimiter,
     api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
     signature_checker::VerifySignatureRequest,
 };
@@ -188,6 +189,7 @@ pub fn start_ws_server(
     miniblock_iteration_interval: Duration,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    rate_limiter: RateLimiter,
 ) -> JoinHandle<()> {
     let addr = config.ws_bind_addr();
 
@@ -209,6 +211,7 @@ pub fn start_ws_server(
         token_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        rate_limiter,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
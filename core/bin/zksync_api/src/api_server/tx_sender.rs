@@ -22,7 +22,10 @@ use thiserror::Error;
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper},
+    v02::{
+        fee::FeeQuote,
+        transaction::{SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper},
+    },
     TxWithSignature,
 };
 use zksync_storage::misc::records::Subsidy;
@@ -41,7 +44,12 @@ use zksync_utils::{
 
 // Local uses
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    api_server::{
+        address_policy::{AddressPolicy, AddressPolicyViolation},
+        fee_quote::{FeeQuoteSigner, QuotedFee},
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+        rate_limiter::{RateLimitExceeded, RateLimiter},
+    },
     fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
     signature_checker::{
         BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
@@ -69,6 +77,10 @@ pub struct TxSender {
     pub tokens: TokenDBCache,
 
     pub forced_exit_checker: ForcedExitChecker,
+    /// Allow- and deny-lists of the addresses.
+    pub address_policy: AddressPolicy,
+    /// Limits the number of transactions submitted by a single account.
+    pub rate_limiter: RateLimiter,
     pub blocks: BlockDetailsCache,
     /// List of account IDs that do not have to pay fees for operations.
     pub fee_free_accounts: HashSet<AccountId>,
@@ -80,6 +92,9 @@ pub struct TxSender {
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
+
+    /// Signer of the fee quotes, `None` if the fee quotes are disabled.
+    pub fee_quotes: Option<FeeQuoteSigner>,
 }
 
 #[derive(Debug, Error)]
@@ -96,6 +111,12 @@ pub enum SubmitError {
     TxAdd(#[from] TxAddError),
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Invalid fee quote: {0}.")]
+    InvalidFeeQuote(String),
+    #[error("Transaction is rejected: {0}.")]
+    AddressPolicy(#[from] AddressPolicyViolation),
+    #[error("{0}.")]
+    RateLimit(#[from] RateLimitExceeded),
     // Not all TxAddErrors would apply to Toggle2FA, but
     // it is helpful to re-use IncorrectEthSignature and DbError
     #[error("Failed to toggle 2FA: {0}.")]
@@ -149,6 +170,7 @@ impl TxSender {
         config: &CommonApiConfig,
         token_config: &TokenConfig,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        rate_limiter: RateLimiter,
     ) -> Self {
         let max_number_of_transactions_per_batch =
             config.max_number_of_transactions_per_batch as usize;
@@ -156,6 +178,8 @@ impl TxSender {
 
         Self {
             mempool_tx_sender,
+            address_policy: AddressPolicy::new(connection_pool.clone()),
+            rate_limiter,
             pool: connection_pool,
             sign_verify_requests: sign_verify_request_sender,
             ticker,
@@ -172,6 +196,10 @@ impl TxSender {
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
+            fee_quotes: config
+                .fee_quote_secret
+                .clone()
+                .map(|secret| FeeQuoteSigner::new(secret, config.fee_quote_validity())),
         }
     }
 
@@ -361,6 +389,7 @@ impl TxSender {
         mut tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash, SubmitError> {
         let fast_processing = fast_processing.unwrap_or(false);
@@ -380,13 +409,15 @@ impl TxSender {
         }
 
         let result = self
-            .submit_tx(tx, signature, extracted_request_metadata)
+            .submit_tx(tx, signature, fee_quote, extracted_request_metadata)
             .await;
 
         if let Err(err) = &result {
             let err_label = match err {
                 SubmitError::IncorrectTx(err) => err.clone(),
                 SubmitError::TxAdd(err) => err.to_string(),
+                SubmitError::AddressPolicy(_) => "address_policy".to_string(),
+                SubmitError::RateLimit(_) => "rate_limit".to_string(),
                 _ => "other".to_string(),
             };
             let labels = vec![("stage", "api".to_string()), ("error", err_label)];
@@ -481,10 +512,92 @@ impl TxSender {
         Ok(())
     }
 
+    /// Creates the quote guaranteeing the fee for the transaction with the given parameters.
+    /// Returns `None` if the fee quotes are disabled.
+    pub async fn fee_quote(
+        &self,
+        tx_type: TxFeeTypes,
+        token: TokenLike,
+        address: Address,
+        total_fee: BigUint,
+    ) -> Result<Option<FeeQuote>, SubmitError> {
+        let signer = match &self.fee_quotes {
+            Some(signer) => signer,
+            None => return Ok(None),
+        };
+        let token = self.token_info_from_id(token).await?;
+        signer
+            .issue(tx_type, token.id, address, total_fee)
+            .map(Some)
+    }
+
+    /// Returns the fee guaranteed by the quote for the transaction with the given parameters,
+    /// or `None` if the quote has expired or the fee quotes are disabled.
+    async fn quoted_fee(
+        &self,
+        fee_quote: &str,
+        tx_type: TxFeeTypes,
+        token: TokenLike,
+        address: Address,
+    ) -> Result<Option<QuotedFee>, SubmitError> {
+        let signer = match &self.fee_quotes {
+            Some(signer) => signer,
+            None => return Ok(None),
+        };
+        let token = self.token_info_from_id(token).await?;
+        signer.quoted_fee(fee_quote, tx_type, token.id, address)
+    }
+
+    /// Marks the fee quote as used, so it can't be used for another transaction.
+    async fn use_fee_quote(
+        &self,
+        fee_quote: &str,
+        quoted_fee: &QuotedFee,
+    ) -> Result<(), SubmitError> {
+        let is_unused = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .misc_schema()
+            .mark_fee_quote_used(fee_quote, quoted_fee.expires_at)
+            .await
+            .map_err(SubmitError::internal)?;
+        if !is_unused {
+            return Err(SubmitError::InvalidFeeQuote(
+                "quote has already been used".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Makes the fee quote available again after the transaction it was used for was rejected.
+    async fn release_fee_quote(&self, fee_quote: &str) -> Result<(), SubmitError> {
+        self.pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .misc_schema()
+            .release_fee_quote(fee_quote)
+            .await
+            .map_err(SubmitError::internal)
+    }
+
+    /// Submits the transaction to the mempool.
+    ///
+    /// If there is a queued transaction with the same account and nonce, it is
+    /// replaced as long as the new transaction pays a high enough fee.
+    ///
+    /// If the valid fee quote is provided, the transaction is accepted as long as it pays
+    /// the quoted fee, regardless of the fee currently required by the server.
+    /// Every quote can be used for a single transaction only. The quote is taken
+    /// once the transaction signatures are verified and is released if the
+    /// mempool rejects the transaction.
     pub async fn submit_tx(
         &self,
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
+        fee_quote: Option<String>,
         extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<TxHash, SubmitError> {
         let labels = vec![
@@ -503,6 +616,9 @@ impl TxSender {
             self.check_forced_exit(forced_exit).await?;
         }
 
+        self.check_initiator(&tx, extracted_request_metadata.as_ref())
+            .await?;
+
         // Resolve the token.
         let token = self.token_info_from_id(tx.token_id()).await?;
         let msg_to_sign = tx
@@ -523,6 +639,7 @@ impl TxSender {
         let sign_verify_channel = self.sign_verify_requests.clone();
 
         let mut fee_data_for_subsidy: Option<ResponseFee> = None;
+        let mut used_fee_quote: Option<(&str, QuotedFee)> = None;
 
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
             let should_enforce_fee = !matches!(tx_type, TxFeeTypes::ChangePubKey { .. })
@@ -534,33 +651,48 @@ impl TxSender {
                 return Err(SubmitError::InappropriateFeeToken);
             }
 
-            let required_fee_data = self
-                .ticker
-                .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
-                .await?;
-
-            let required_fee_data = if self
-                .should_subsidize_cpk(
-                    &required_fee_data.normal_fee.total_fee,
-                    &required_fee_data.subsidized_fee.total_fee,
-                    &required_fee_data.subsidy_size_usd,
-                    extracted_request_metadata,
-                )
-                .await?
-            {
-                fee_data_for_subsidy = Some(required_fee_data.clone());
-                required_fee_data.subsidized_fee
-            } else {
-                required_fee_data.normal_fee
+            let quoted_fee = match &fee_quote {
+                Some(fee_quote) => self
+                    .quoted_fee(fee_quote, tx_type, token.clone(), address)
+                    .await?
+                    .map(|quoted_fee| (fee_quote.as_str(), quoted_fee)),
+                None => None,
             };
+            if let Some((fee_quote, quoted_fee)) = quoted_fee {
+                if provided_fee < quoted_fee.fee && should_enforce_fee {
+                    return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                }
+                used_fee_quote = Some((fee_quote, quoted_fee));
+            } else {
+                let required_fee_data = self
+                    .ticker
+                    .get_fee_from_ticker_in_wei(tx_type, token.clone(), address)
+                    .await?;
 
-            // Converting `BitUint` to `BigInt` is safe.
-            let required_fee: BigDecimal = required_fee_data.total_fee.to_bigint().unwrap().into();
-            let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
-            // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
-            let scaled_provided_fee = scale_user_fee_up(provided_fee);
-            if required_fee >= scaled_provided_fee && should_enforce_fee {
-                return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                let required_fee_data = if self
+                    .should_subsidize_cpk(
+                        &required_fee_data.normal_fee.total_fee,
+                        &required_fee_data.subsidized_fee.total_fee,
+                        &required_fee_data.subsidy_size_usd,
+                        extracted_request_metadata,
+                    )
+                    .await?
+                {
+                    fee_data_for_subsidy = Some(required_fee_data.clone());
+                    required_fee_data.subsidized_fee
+                } else {
+                    required_fee_data.normal_fee
+                };
+
+                // Converting `BitUint` to `BigInt` is safe.
+                let required_fee: BigDecimal =
+                    required_fee_data.total_fee.to_bigint().unwrap().into();
+                let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
+                // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
+                let scaled_provided_fee = scale_user_fee_up(provided_fee);
+                if required_fee >= scaled_provided_fee && should_enforce_fee {
+                    return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                }
             }
         }
 
@@ -592,15 +724,22 @@ impl TxSender {
                 .await?;
         }
 
-        let (sender, receiver) = oneshot::channel();
-        let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
-        let mut mempool_sender = self.mempool_tx_sender.clone();
-        mempool_sender
-            .send(item)
-            .await
-            .map_err(SubmitError::internal)?;
+        // The account quota is charged only for the properly signed transaction, so nobody
+        // can exhaust the quota of someone else's account.
+        self.rate_limiter.check_account(tx_sender)?;
 
-        receiver.await.map_err(SubmitError::internal)??;
+        // The quote is taken only by the properly signed transaction, so it can't be
+        // burnt by anyone who has seen it.
+        if let Some((fee_quote, quoted_fee)) = &used_fee_quote {
+            self.use_fee_quote(fee_quote, quoted_fee).await?;
+        }
+
+        if let Err(err) = self.add_tx_to_mempool(verified_tx).await {
+            if let Some((fee_quote, _)) = used_fee_quote {
+                self.release_fee_quote(fee_quote).await?;
+            }
+            return Err(err);
+        }
 
         // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
         if let Some(fee_data_for_subsidy) = fee_data_for_subsidy {
@@ -630,6 +769,20 @@ impl TxSender {
         Ok(tx.hash())
     }
 
+    /// Sends the verified transaction to the mempool and waits until it is accepted.
+    async fn add_tx_to_mempool(&self, tx: SignedZkSyncTx) -> Result<(), SubmitError> {
+        let (sender, receiver) = oneshot::channel();
+        let item = MempoolTransactionRequest::NewTx(Box::new(tx), sender);
+        let mut mempool_sender = self.mempool_tx_sender.clone();
+        mempool_sender
+            .send(item)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        receiver.await.map_err(SubmitError::internal)??;
+        Ok(())
+    }
+
     pub async fn submit_txs_batch(
         &self,
         txs: Vec<TxWithSignature>,
@@ -667,6 +820,11 @@ impl TxSender {
             return Err(SubmitError::AccountCloseDisabled);
         }
 
+        for tx in &txs {
+            self.check_initiator(&tx.tx, extracted_request_metadata.as_ref())
+                .await?;
+        }
+
         // Checking fees data
         let mut provided_total_usd_fee = BigDecimal::from(0);
         let mut transaction_types = vec![];
@@ -859,6 +1017,7 @@ impl TxSender {
         } else {
             None
         };
+        let initiators = tx_senders.clone();
         let (verified_batch, sign_data) = verify_txs_batch_signature(
             txs,
             tx_senders,
@@ -875,6 +1034,12 @@ impl TxSender {
         }
         verified_txs.extend(verified_batch.into_iter());
 
+        // Same as for the single transaction, the account quotas are charged only for the
+        // properly signed batch.
+        for initiator in initiators {
+            self.rate_limiter.check_account(initiator)?;
+        }
+
         let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
 
         let (sender, receiver) = oneshot::channel();
@@ -951,6 +1116,67 @@ impl TxSender {
             .await
     }
 
+    /// Checks that the addresses taking part in the transaction pass the allow- and deny-lists.
+    /// The rate limit of the initiator is checked only once the transaction signatures are verified.
+    async fn check_initiator(
+        &self,
+        tx: &ZkSyncTx,
+        request_metadata: Option<&RequestMetadata>,
+    ) -> Result<(), SubmitError> {
+        let initiator = self
+            .get_tx_sender(tx)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+
+        self.check_address_policy(tx, initiator, request_metadata)
+            .await
+    }
+
+    /// Checks the addresses taking part in the transaction against the allow- and deny-lists.
+    /// Rejected submissions are logged for the audit.
+    async fn check_address_policy(
+        &self,
+        tx: &ZkSyncTx,
+        initiator: Address,
+        request_metadata: Option<&RequestMetadata>,
+    ) -> Result<(), SubmitError> {
+        let participants = self
+            .get_tx_participants(tx)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
+
+        let result = self.address_policy.check(initiator, &participants).await;
+        if let Err(SubmitError::AddressPolicy(violation)) = &result {
+            vlog::warn!(
+                "Address policy rejected tx {} initiated by {:?} (ip: {}): {}",
+                tx.hash().to_string(),
+                initiator,
+                request_metadata.map_or("unknown", |meta| meta.ip.as_str()),
+                violation
+            );
+            metrics::increment_counter!("api.address_policy.rejected_txs");
+        }
+        result
+    }
+
+    /// Returns the addresses taking part in the transaction except for its initiator.
+    async fn get_tx_participants(&self, tx: &ZkSyncTx) -> Result<Vec<Address>, anyhow::Error> {
+        Ok(match tx {
+            ZkSyncTx::Transfer(tx) => vec![tx.to],
+            ZkSyncTx::Withdraw(tx) => vec![tx.to],
+            ZkSyncTx::ForcedExit(tx) => vec![tx.target],
+            ZkSyncTx::MintNFT(tx) => vec![tx.recipient],
+            ZkSyncTx::WithdrawNFT(tx) => vec![tx.to],
+            ZkSyncTx::Swap(tx) => vec![
+                self.get_address_by_id(tx.orders.0.account_id).await?,
+                tx.orders.0.recipient_address,
+                self.get_address_by_id(tx.orders.1.account_id).await?,
+                tx.orders.1.recipient_address,
+            ],
+            ZkSyncTx::ChangePubKey(_) | ZkSyncTx::Close(_) => Vec::new(),
+        })
+    }
+
     /// Returns a message that user has to sign to send the transaction.
     /// If the transaction doesn't need a message signature, returns `None`.
     /// If any error is encountered during the message generation, returns `jsonrpc_core::Error`.
@@ -11,7 +11,7 @@ use tiny_keccak::keccak256;
 // Workspace uses
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{TokenId, TokenKind, NFT};
+use zksync_types::{BlockNumber, TokenId, TokenKind, NFT};
 
 // Local uses
 use super::{
@@ -22,6 +22,9 @@ use super::{
 
 type Selector = [u8; 4];
 
+/// Selector of the ERC20 `transfer(address,uint256)` function.
+const ERC20_TRANSFER_SELECTOR: Selector = [0xa9, 0x05, 0x9c, 0xbb];
+
 #[derive(Debug, Clone)]
 pub struct CallsHelper {
     erc20: HashMap<Selector, Function>,
@@ -93,11 +96,14 @@ impl CallsHelper {
         }
     }
 
+    /// Executes the call as of the given block. Only the token balances depend on the block,
+    /// the rest of the methods return the current values.
     pub async fn execute(
         &self,
         storage: &mut StorageProcessor<'_>,
         to: H160,
         data: Vec<u8>,
+        block: BlockNumber,
     ) -> Result<Vec<u8>> {
         let mut transaction = storage
             .start_transaction()
@@ -112,7 +118,7 @@ impl CallsHelper {
                 .await
                 .map_err(|_| Error::internal_error())?;
             match token {
-                Some(token) if matches!(token.kind, TokenKind::ERC20) => &self.erc20,
+                Some(token) if !matches!(token.kind, TokenKind::NFT) => &self.erc20,
                 _ => return Ok(Vec::new()),
             }
         };
@@ -271,16 +277,17 @@ impl CallsHelper {
                 .map_err(|_| Error::internal_error())?
                 .ok_or_else(Error::internal_error)?;
             match function.name.as_str() {
+                // Tokens which didn't pass the ERC20 check may have no metadata,
+                // only the balances and allowances are known for them.
+                "name" | "symbol" | "decimals" | "totalSupply"
+                    if !matches!(token.kind, TokenKind::ERC20) =>
+                {
+                    return Ok(Vec::new());
+                }
                 "name" | "symbol" => encode(&[AbiToken::String(token.symbol)]),
                 "decimals" => encode(&[AbiToken::Uint(U256::from(token.decimals))]),
                 "totalSupply" | "allowance" => encode(&[AbiToken::Uint(U256::max_value())]),
                 "balanceOf" => {
-                    let block = transaction
-                        .chain()
-                        .block_schema()
-                        .get_last_verified_confirmed_block()
-                        .await
-                        .map_err(|_| Error::internal_error())?;
                     let address = params[0]
                         .clone()
                         .into_address()
@@ -303,6 +310,29 @@ impl CallsHelper {
         Ok(result)
     }
 
+    /// Returns the token and the recipient of the transfer equivalent to the call:
+    /// the ERC20 `transfer` of the token called or the transfer of the native token
+    /// to the called address otherwise.
+    pub async fn transfer_params(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        to: H160,
+        data: &[u8],
+    ) -> Result<(TokenId, H160)> {
+        if data.len() >= 36 && data[0..4] == ERC20_TRANSFER_SELECTOR {
+            let token = self
+                .tokens
+                .get_token(storage, to)
+                .await
+                .map_err(|_| Error::internal_error())?;
+            if let Some(token) = token.filter(|token| !matches!(token.kind, TokenKind::NFT)) {
+                // The recipient is the first argument padded to 32 bytes.
+                return Ok((token.id, H160::from_slice(&data[16..36])));
+            }
+        }
+        Ok((TokenId(0), to))
+    }
+
     async fn get_nft(
         &self,
         storage: &mut StorageProcessor<'_>,
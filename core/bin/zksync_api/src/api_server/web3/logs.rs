@@ -14,7 +14,7 @@ use zksync_types::{Nonce, Token, TokenId, TokenKind, ZkSyncOp, NFT};
 // Local uses
 use super::{
     converter::{log, u256_from_biguint},
-    types::{Bytes, CommonLogData, Event, Log, H160, H256, U256},
+    types::{Bytes, CommonLogData, Event, Log, ValueOrArray, H160, H256, U256},
     NFT_FACTORY_ADDRESS, ZKSYNC_PROXY_ADDRESS,
 };
 
@@ -632,3 +632,55 @@ impl LogsHelper {
         bytes.into()
     }
 }
+
+/// Addresses and topics of the logs requested by `eth_getLogs` or `eth_subscribe`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    addresses: Vec<H160>,
+    topics: Vec<H256>,
+    /// Set if there is non-null topic at the non-first position,
+    /// such filter matches nothing since all our logs contain exactly one topic.
+    matches_nothing: bool,
+}
+
+impl LogFilter {
+    pub fn new(
+        address: Option<ValueOrArray<H160>>,
+        topics: Option<Vec<Option<ValueOrArray<H256>>>>,
+    ) -> Self {
+        let mut topics = topics.unwrap_or_default();
+        let matches_nothing = topics
+            .iter()
+            .enumerate()
+            .any(|(i, topic)| i > 0 && topic.is_some());
+        let topics = if topics.is_empty() {
+            Vec::new()
+        } else {
+            topics.remove(0).unwrap_or_default().0
+        };
+
+        Self {
+            addresses: address.map(|a| a.0).unwrap_or_default(),
+            topics,
+            matches_nothing,
+        }
+    }
+
+    /// Returns `false` if no log can match the filter.
+    pub fn can_match(&self) -> bool {
+        !self.matches_nothing
+    }
+
+    pub fn matches(&self, log: &Log) -> bool {
+        if self.matches_nothing {
+            return false;
+        }
+        if !self.topics.is_empty() && !self.topics.contains(&log.topics[0]) {
+            return false;
+        }
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false;
+        }
+        true
+    }
+}
@@ -1,14 +1,24 @@
 // Built-in uses
+use std::sync::Arc;
 // External uses
 
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
+use jsonrpc_pubsub::{PubSubHandler, Session};
+use jsonrpc_ws_server::RequestContext;
 // Workspace uses
 
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 // Local uses
-use self::{calls::CallsHelper, logs::LogsHelper, rpc_trait::Web3Rpc};
+use self::{
+    calls::CallsHelper,
+    logs::LogsHelper,
+    pubsub::{Web3PubSub, Web3PubSubApp},
+    rpc_trait::Web3Rpc,
+};
+use super::rate_limiter::{CallerMetadata, RateLimitMiddleware, RateLimiter, SessionMetadata};
+use crate::fee_ticker::FeeTicker;
 
 use tokio::task::JoinHandle;
 use zksync_config::configs::api::{TokenConfig, Web3Config};
@@ -16,6 +26,7 @@ use zksync_config::configs::api::{TokenConfig, Web3Config};
 mod calls;
 mod converter;
 mod logs;
+mod pubsub;
 mod rpc_impl;
 mod rpc_trait;
 #[cfg(test)]
@@ -30,6 +41,7 @@ pub struct Web3RpcApp {
     connection_pool: ConnectionPool,
     logs_helper: LogsHelper,
     calls_helper: CallsHelper,
+    ticker: FeeTicker,
     max_block_range: u32,
     chain_id: u32,
 }
@@ -37,6 +49,7 @@ pub struct Web3RpcApp {
 impl Web3RpcApp {
     pub fn new(
         connection_pool: ConnectionPool,
+        ticker: FeeTicker,
         config: &Web3Config,
         token_config: &TokenConfig,
     ) -> Self {
@@ -44,6 +57,7 @@ impl Web3RpcApp {
             connection_pool,
             logs_helper: LogsHelper::new(token_config.invalidate_token_cache_period()),
             calls_helper: CallsHelper::new(token_config.invalidate_token_cache_period()),
+            ticker,
             max_block_range: config.max_block_range,
             chain_id: config.chain_id,
         }
@@ -63,21 +77,27 @@ impl Web3RpcApp {
 
 pub fn start_rpc_server(
     connection_pool: ConnectionPool,
+    ticker: FeeTicker,
     web3_config: &Web3Config,
     token_config: &TokenConfig,
+    rate_limiter: RateLimiter,
 ) -> JoinHandle<()> {
     let addr = web3_config.bind_addr();
 
-    let rpc_app = Web3RpcApp::new(connection_pool, web3_config, token_config);
+    let rpc_app = Web3RpcApp::new(connection_pool, ticker, web3_config, token_config);
     let (handler, panic_sender) = spawn_panic_handler();
 
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_sender);
 
-        let mut io = IoHandler::new();
+        let behind_proxy = rate_limiter.behind_proxy();
+        let meta_extractor = move |request: &hyper::Request<hyper::Body>| {
+            CallerMetadata::from_request(request, behind_proxy)
+        };
+        let mut io = MetaIoHandler::with_middleware(RateLimitMiddleware(rate_limiter));
         rpc_app.extend(&mut io);
 
-        let server = ServerBuilder::new(io)
+        let server = ServerBuilder::with_meta_extractor(io, meta_extractor)
             .threads(super::THREADS_PER_SERVER)
             .start_http(&addr)
             .unwrap();
@@ -85,3 +105,45 @@ pub fn start_rpc_server(
     });
     handler
 }
+
+/// Starts the WebSocket server which serves the web3 methods and `eth_subscribe` subscriptions.
+/// Subscribers are notified using the storage notifications sent to `event_channel_name`.
+pub fn start_ws_server(
+    connection_pool: ConnectionPool,
+    ticker: FeeTicker,
+    web3_config: &Web3Config,
+    token_config: &TokenConfig,
+    event_channel_name: String,
+    rate_limiter: RateLimiter,
+) -> JoinHandle<()> {
+    let addr = web3_config.ws_bind_addr();
+
+    let rpc_app = Web3RpcApp::new(connection_pool, ticker, web3_config, token_config);
+    let pubsub_app = Web3PubSubApp::default();
+    pubsub_app.start_notifier(rpc_app.clone(), event_channel_name);
+    let (handler, panic_sender) = spawn_panic_handler();
+
+    std::thread::spawn(move || {
+        let _panic_sentinel = ThreadPanicNotify(panic_sender);
+
+        let mut io = PubSubHandler::new(MetaIoHandler::with_middleware(RateLimitMiddleware(
+            rate_limiter,
+        )));
+        rpc_app.extend(&mut io);
+        io.extend_with(pubsub_app.to_delegate());
+
+        let server = jsonrpc_ws_server::ServerBuilder::with_meta_extractor(
+            io,
+            |context: &RequestContext| {
+                let session = Arc::new(Session::new(context.sender()));
+                SessionMetadata::new(session, context.session_id)
+            },
+        )
+        .max_connections(1000)
+        .start(&addr)
+        .expect("Unable to start web3 ws server");
+
+        server.wait().expect("web3 ws server start");
+    });
+    handler
+}
@@ -0,0 +1,277 @@
+//! `eth_subscribe` support of the web3 WebSocket server.
+//!
+//! Subscribers are notified when a block is finalized, i.e. when it becomes
+//! the block returned by `eth_blockNumber`. New blocks are detected through the
+//! storage notifications about new events, the same ones the event listener uses.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+// External uses
+use jsonrpc_core::{Error, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{
+    typed::{Sink, Subscriber},
+    SubscriptionId,
+};
+// Workspace uses
+use zksync_storage::listener::StorageListener;
+use zksync_types::{
+    event::{block::BlockStatus, EventData, EventId, ZkSyncEvent},
+    BlockNumber,
+};
+// Local uses
+use super::{
+    super::rate_limiter::SessionMetadata,
+    logs::LogFilter,
+    types::{Filter, Log, PubSubResult, SubscriptionKind},
+    Web3RpcApp,
+};
+
+/// Delay before reconnecting to the database if the storage listener fails.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[rpc]
+pub trait Web3PubSub {
+    type Metadata;
+
+    #[pubsub(subscription = "eth_subscription", subscribe, name = "eth_subscribe")]
+    fn subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<PubSubResult>,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    );
+
+    #[pubsub(
+        subscription = "eth_subscription",
+        unsubscribe,
+        name = "eth_unsubscribe"
+    )]
+    fn unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
+}
+
+#[derive(Debug)]
+enum SubscriptionParams {
+    NewHeads,
+    Logs(LogFilter),
+}
+
+#[derive(Debug)]
+struct ActiveSubscription {
+    sink: Sink<PubSubResult>,
+    params: SubscriptionParams,
+}
+
+type Subscriptions = Arc<Mutex<HashMap<SubscriptionId, ActiveSubscription>>>;
+
+#[derive(Debug, Clone, Default)]
+pub struct Web3PubSubApp {
+    subscriptions: Subscriptions,
+}
+
+impl Web3PubSub for Web3PubSubApp {
+    type Metadata = SessionMetadata;
+
+    fn subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<PubSubResult>,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    ) {
+        let params = match (kind, filter) {
+            (SubscriptionKind::NewHeads, None) => SubscriptionParams::NewHeads,
+            (SubscriptionKind::NewHeads, Some(_)) => {
+                let error = Error::invalid_params("`newHeads` subscription has no parameters");
+                subscriber.reject(error).unwrap_or_default();
+                return;
+            }
+            (SubscriptionKind::Logs, filter) => {
+                let filter = filter.unwrap_or_default();
+                SubscriptionParams::Logs(LogFilter::new(filter.address, filter.topics))
+            }
+        };
+
+        let id = SubscriptionId::String(format!("0x{:016x}", zksync_crypto::rand::random::<u64>()));
+        if let Ok(sink) = subscriber.assign_id(id.clone()) {
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .insert(id.clone(), ActiveSubscription { sink, params });
+
+            // Subscriptions of the closed connections are never unsubscribed explicitly.
+            let subscriptions = self.subscriptions.clone();
+            meta.session.on_drop(move || {
+                subscriptions.lock().unwrap().remove(&id);
+            });
+        }
+    }
+
+    fn unsubscribe(&self, _meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+        Ok(self.subscriptions.lock().unwrap().remove(&id).is_some())
+    }
+}
+
+impl Web3PubSubApp {
+    /// Starts the task notifying the subscribers about the finalized blocks.
+    pub fn start_notifier(
+        &self,
+        rpc_app: Web3RpcApp,
+        event_channel_name: String,
+    ) -> tokio::task::JoinHandle<()> {
+        let notifier = Web3Notifier {
+            rpc_app,
+            subscriptions: self.subscriptions.clone(),
+            last_event_id: None,
+        };
+        tokio::spawn(notifier.run(event_channel_name))
+    }
+}
+
+struct Web3Notifier {
+    rpc_app: Web3RpcApp,
+    subscriptions: Subscriptions,
+    /// The id of the last processed event, `None` until the notifier is connected.
+    last_event_id: Option<EventId>,
+}
+
+impl Web3Notifier {
+    async fn run(mut self, event_channel_name: String) {
+        loop {
+            if let Err(err) = self.listen(&event_channel_name).await {
+                vlog::warn!(
+                    "Web3 subscriptions notifier failed: {}, reconnecting in {:?}",
+                    err,
+                    RECONNECT_INTERVAL
+                );
+            }
+            tokio::time::sleep(RECONNECT_INTERVAL).await;
+        }
+    }
+
+    async fn listen(&mut self, event_channel_name: &str) -> anyhow::Result<()> {
+        let mut listener = StorageListener::connect().await?;
+        listener.listen(event_channel_name).await?;
+
+        if self.last_event_id.is_none() {
+            // Blocks finalized before the start are not broadcasted.
+            let last_event_id = self
+                .rpc_app
+                .connection_pool
+                .access_storage()
+                .await?
+                .event_schema()
+                .get_last_event_id()
+                .await?;
+            self.last_event_id = Some(last_event_id.unwrap_or(EventId(0)));
+        } else {
+            // Process the events stored while the listener was disconnected.
+            self.process_new_events().await?;
+        }
+
+        loop {
+            let notification = listener.recv().await?;
+            // Events are fetched in packs, the notification about
+            // an already processed event can be skipped.
+            let event_id = EventId(notification.payload().parse::<u64>()?);
+            if matches!(self.last_event_id, Some(last_event_id) if last_event_id >= event_id) {
+                continue;
+            }
+            self.process_new_events().await?;
+        }
+    }
+
+    async fn process_new_events(&mut self) -> anyhow::Result<()> {
+        let last_event_id = self.last_event_id.unwrap_or(EventId(0));
+        let events = self
+            .rpc_app
+            .connection_pool
+            .access_storage()
+            .await?
+            .event_schema()
+            .fetch_new_events(last_event_id)
+            .await?;
+
+        for event in events {
+            let event = ZkSyncEvent::try_from(event)?;
+            if let EventData::Block(block_event) = &event.data {
+                if block_event.status == BlockStatus::Finalized {
+                    self.notify_block(block_event.block_details.block_number)
+                        .await?;
+                }
+            }
+            self.last_event_id = Some(event.id);
+        }
+        Ok(())
+    }
+
+    /// Sends the header and the logs of the finalized block to the subscribers.
+    async fn notify_block(&mut self, block_number: BlockNumber) -> anyhow::Result<()> {
+        let (has_heads, has_logs) = {
+            let subscriptions = self.subscriptions.lock().unwrap();
+            let has_heads = subscriptions
+                .values()
+                .any(|sub| matches!(sub.params, SubscriptionParams::NewHeads));
+            let has_logs = subscriptions
+                .values()
+                .any(|sub| matches!(sub.params, SubscriptionParams::Logs(_)));
+            (has_heads, has_logs)
+        };
+        if !has_heads && !has_logs {
+            return Ok(());
+        }
+
+        let mut storage = self.rpc_app.connection_pool.access_storage().await?;
+        let header = if has_heads {
+            Some(Web3RpcApp::block_by_number(&mut storage, block_number, false).await?)
+        } else {
+            None
+        };
+        let mut logs: Vec<Log> = Vec::new();
+        if has_logs {
+            let receipts = storage
+                .chain()
+                .operations_ext_schema()
+                .web3_receipts(block_number, block_number)
+                .await?;
+            for receipt in receipts {
+                logs.extend(
+                    self.rpc_app
+                        .logs_from_receipt(&mut storage, receipt)
+                        .await?,
+                );
+            }
+        }
+        drop(storage);
+
+        self.subscriptions.lock().unwrap().retain(|id, sub| {
+            let result = match (&sub.params, &header) {
+                (SubscriptionParams::NewHeads, Some(header)) => {
+                    sub.sink.notify(Ok(PubSubResult::Header(header.clone())))
+                }
+                (SubscriptionParams::Logs(filter), _) => logs
+                    .iter()
+                    .filter(|log| filter.matches(log))
+                    .try_for_each(|log| sub.sink.notify(Ok(PubSubResult::Log(log.clone())))),
+                _ => Ok(()),
+            };
+            // The subscription is dropped if the client is gone.
+            if let Err(err) = result {
+                vlog::debug!("Removing web3 subscription {:?}: {}", id, err);
+                return false;
+            }
+            true
+        });
+        Ok(())
+    }
+}
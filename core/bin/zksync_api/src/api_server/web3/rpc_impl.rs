@@ -9,10 +9,11 @@ use zksync_storage::{
     chain::{block::records::StorageBlock, operations_ext::records::Web3TxReceipt},
     StorageProcessor,
 };
-use zksync_types::{ExecutedOperations, TokenId, ZkSyncOp};
+use zksync_types::{ExecutedOperations, TokenId, TokenLike, TxFeeTypes, ZkSyncOp};
 // Local uses
 use super::{
     converter::{resolve_block_number, transaction_from_tx_data, u256_from_biguint},
+    logs::LogFilter,
     types::{
         BlockInfo, BlockNumber, Bytes, CallRequest, CommonLogData, Filter, Log, Transaction,
         TransactionReceipt, TxData, H160, H2048, H256, U256, U64,
@@ -252,24 +253,10 @@ impl Web3RpcApp {
             )));
         }
 
-        let topics = if let Some(mut topics) = filter.topics {
-            // If there is non-null topic at the non-first position then return empty vec,
-            // since all our logs contain exactly one topic.
-            let has_not_first = topics
-                .iter()
-                .enumerate()
-                .any(|(i, topic)| i > 0 && topic.is_some());
-            if has_not_first {
-                return Ok(Vec::new());
-            } else if topics.is_empty() {
-                Vec::new()
-            } else {
-                topics.remove(0).unwrap_or_default().0
-            }
-        } else {
-            Vec::new()
-        };
-        let addresses = filter.address.map(|a| a.0).unwrap_or_default();
+        let log_filter = LogFilter::new(filter.address, filter.topics);
+        if !log_filter.can_match() {
+            return Ok(Vec::new());
+        }
         let mut result = Vec::new();
 
         let receipts = transaction
@@ -280,15 +267,7 @@ impl Web3RpcApp {
             .map_err(|_| Error::internal_error())?;
         for receipt in receipts {
             let logs = self.logs_from_receipt(&mut transaction, receipt).await?;
-            let filtered = logs.into_iter().filter(|log| {
-                if !topics.is_empty() && !topics.contains(&log.topics[0]) {
-                    return false;
-                }
-                if !addresses.is_empty() && !addresses.contains(&log.address) {
-                    return false;
-                }
-                true
-            });
+            let filtered = logs.into_iter().filter(|log| log_filter.matches(log));
             result.extend(filtered);
         }
 
@@ -301,19 +280,87 @@ impl Web3RpcApp {
         Ok(result)
     }
 
-    pub async fn _impl_call(self, req: CallRequest, _block: Option<BlockNumber>) -> Result<Bytes> {
+    pub async fn _impl_call(self, req: CallRequest, block: Option<BlockNumber>) -> Result<Bytes> {
         let start = Instant::now();
         let mut storage = self.access_storage().await?;
+        let block_number = resolve_block_number(&mut storage, block)
+            .await?
+            .ok_or_else(|| Error::invalid_params("Block with such number doesn't exist yet"))?;
 
         let result = self
             .calls_helper
-            .execute(&mut storage, req.to, req.data.unwrap_or_default().0)
+            .execute(
+                &mut storage,
+                req.to,
+                req.data.unwrap_or_default().0,
+                block_number,
+            )
             .await;
 
         metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "call");
         result.map(Bytes)
     }
 
+    pub async fn _impl_get_transaction_count(
+        self,
+        address: H160,
+        block: Option<BlockNumber>,
+    ) -> Result<U256> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|_| Error::internal_error())?;
+        let block_number = resolve_block_number(&mut transaction, block)
+            .await?
+            .ok_or_else(|| Error::invalid_params("Block with such number doesn't exist yet"))?;
+        let nonce = transaction
+            .chain()
+            .account_schema()
+            .get_account_nonce_for_block(address, block_number)
+            .await
+            .map_err(|_| Error::internal_error())?;
+        transaction
+            .commit()
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "get_transaction_count");
+        Ok(U256::from(*nonce))
+    }
+
+    /// zkSync has no gas, the estimation is the fee of the equivalent `Transfer`
+    /// in the smallest units of the transferred token.
+    pub async fn _impl_estimate_gas(self, req: CallRequest) -> Result<U256> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let (token_id, recipient) = self
+            .calls_helper
+            .transfer_params(&mut storage, req.to, &req.data.unwrap_or_default().0)
+            .await?;
+        drop(storage);
+
+        let token_allowed = self
+            .ticker
+            .token_allowed_for_fees(TokenLike::Id(token_id))
+            .await
+            .map_err(|_| Error::internal_error())?;
+        if !token_allowed {
+            return Err(Error::invalid_params(
+                "Chosen token is not suitable for paying fees",
+            ));
+        }
+        let fee = self
+            .ticker
+            .get_fee_from_ticker_in_wei(TxFeeTypes::Transfer, TokenLike::Id(token_id), recipient)
+            .await
+            .map_err(|_| Error::internal_error())?;
+
+        metrics::histogram!("api", start.elapsed(), "type" => "web3", "endpoint_name" => "estimate_gas");
+        Ok(u256_from_biguint(fee.normal_fee.total_fee))
+    }
+
     pub(crate) async fn logs_from_receipt(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -94,6 +94,19 @@ pub trait Web3Rpc {
 
     #[rpc(name = "eth_call", returns = "Bytes")]
     fn call(&self, req: CallRequest, _block: Option<BlockNumber>) -> BoxFutureResult<Bytes>;
+
+    #[rpc(name = "eth_chainId", returns = "U64")]
+    fn chain_id(&self) -> Result<U64>;
+
+    #[rpc(name = "eth_getTransactionCount", returns = "U256")]
+    fn get_transaction_count(
+        &self,
+        address: H160,
+        block: Option<BlockNumber>,
+    ) -> BoxFutureResult<U256>;
+
+    #[rpc(name = "eth_estimateGas", returns = "U256")]
+    fn estimate_gas(&self, req: CallRequest, _block: Option<BlockNumber>) -> BoxFutureResult<U256>;
 }
 
 impl Web3Rpc for Web3RpcApp {
@@ -183,4 +196,20 @@ impl Web3Rpc for Web3RpcApp {
     fn call(&self, req: CallRequest, block: Option<BlockNumber>) -> BoxFutureResult<Bytes> {
         spawn! { self._impl_call(req, block) }
     }
+
+    fn chain_id(&self) -> Result<U64> {
+        Ok(U64::from(self.chain_id))
+    }
+
+    fn get_transaction_count(
+        &self,
+        address: H160,
+        block: Option<BlockNumber>,
+    ) -> BoxFutureResult<U256> {
+        spawn!(self._impl_get_transaction_count(address, block))
+    }
+
+    fn estimate_gas(&self, req: CallRequest, _block: Option<BlockNumber>) -> BoxFutureResult<U256> {
+        spawn!(self._impl_estimate_gas(req))
+    }
 }
@@ -1,11 +1,13 @@
 // Built-in uses
+use std::collections::HashMap;
 use std::str::FromStr;
 // External uses
+use chrono::Utc;
 use ethabi::{ParamType, Token};
 use futures::future::{join, join5, Future};
 use jsonrpc_core::{Error, ErrorCode, IoHandler, Params};
 use jsonrpc_core_client::{RawClient, RpcError, RpcResult};
-use num::BigUint;
+use num::{rational::Ratio, BigUint};
 use serde_json::{Map, Value};
 // Workspace uses
 
@@ -13,24 +15,54 @@ use zksync_storage::{chain::operations_ext::records::Web3TxReceipt, ConnectionPo
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
     tx::ChangePubKeyType, AccountId, AccountUpdate, BlockNumber, ChangePubKeyOp, CloseOp, Deposit,
-    DepositOp, ForcedExitOp, FullExit, FullExitOp, MintNFTOp, Nonce, SwapOp, TokenId, TransferOp,
-    WithdrawNFTOp, WithdrawOp, ZkSyncOp, NFT,
+    DepositOp, ForcedExitOp, FullExit, FullExitOp, MintNFTOp, Nonce, SwapOp, TokenId, TokenKind,
+    TokenLike, TokenMarketVolume, TransferOp, TxFeeTypes, WithdrawNFTOp, WithdrawOp, ZkSyncOp, NFT,
 };
 // Local uses
 use super::{
     calls::CallsHelper,
     converter::{transaction_from_tx_data, u256_from_biguint},
-    types::{BlockInfo, Event, Log, Transaction, TransactionReceipt, H160, H256, U256, U64},
+    logs::LogFilter,
+    types::{
+        BlockInfo, Event, Log, Transaction, TransactionReceipt, ValueOrArray, H160, H256, U256, U64,
+    },
     Web3RpcApp, NFT_FACTORY_ADDRESS, ZKSYNC_PROXY_ADDRESS,
 };
-use crate::api_server::rest::v02::test_utils::TestServerConfig;
+use crate::api_server::rest::v02::test_utils::{dummy_fee_ticker, TestServerConfig};
+use crate::fee_ticker::{validator::cache::TokenInMemoryCache, FeeTicker};
 use zksync_config::configs::api::{TokenConfig, Web3Config};
 
+/// Creates the fee ticker which allows to pay fees only in the native token.
+fn fee_ticker() -> FeeTicker {
+    let mut tokens = HashMap::new();
+    tokens.insert(
+        TokenLike::Id(TokenId(0)),
+        zksync_types::Token::new(TokenId(0), Default::default(), "RBTC", 18, TokenKind::ERC20),
+    );
+    let mut market = HashMap::new();
+    market.insert(
+        TokenId(0),
+        TokenMarketVolume {
+            market_volume: Ratio::from_integer(BigUint::from(400u32)),
+            last_updated: Utc::now(),
+        },
+    );
+    let cache = TokenInMemoryCache::new()
+        .with_tokens(tokens)
+        .with_market(market);
+    dummy_fee_ticker(&[(TokenLike::Id(TokenId(0)), 10_u64.into())], Some(cache))
+}
+
 async fn local_client() -> anyhow::Result<(RawClient, impl Future<Output = RpcResult<()>>)> {
     let cfg = TestServerConfig::default();
     cfg.fill_database().await?;
 
-    let rpc_app = Web3RpcApp::new(cfg.pool, &cfg.config.api.web3, &cfg.config.api.token_config);
+    let rpc_app = Web3RpcApp::new(
+        cfg.pool,
+        fee_ticker(),
+        &cfg.config.api.web3,
+        &cfg.config.api.token_config,
+    );
     let mut io = IoHandler::new();
     rpc_app.extend(&mut io);
 
@@ -101,6 +133,13 @@ async fn static_methods() -> anyhow::Result<()> {
         get_uncle_count_by_block_number.unwrap().as_str().unwrap(),
         "0x0"
     );
+
+    let fut = {
+        let (client, server) = local_client().await?;
+        join(client.call_method("eth_chainId", Params::None), server)
+    };
+    let chain_id = fut.await.0.unwrap();
+    assert_eq!(chain_id.as_str().unwrap(), "0x21");
     Ok(())
 }
 
@@ -225,6 +264,69 @@ async fn get_balance() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Tests `eth_getTransactionCount` method
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn get_transaction_count() -> anyhow::Result<()> {
+    let pool = ConnectionPool::new(Some(1));
+    let address = H160::from_str("09d1ef5f45cfa30225edff40cebf657b4226b27b").unwrap();
+    // Checks that nonce of the account is zero after block with number 0.
+    let fut = {
+        let (client, server) = local_client().await?;
+        join(
+            client.call_method(
+                "eth_getTransactionCount",
+                Params::Array(vec![
+                    Value::String(format!("{:#?}", address)),
+                    Value::String("earliest".to_string()),
+                ]),
+            ),
+            server,
+        )
+    };
+    let earliest_nonce = fut.await.0.unwrap();
+    assert_eq!(earliest_nonce.as_str().unwrap(), "0x0");
+
+    // Checks that nonce of the account equals expected nonce after the last committed block.
+    let fut = {
+        let (client, server) = local_client().await?;
+        join(
+            client.call_method(
+                "eth_getTransactionCount",
+                Params::Array(vec![
+                    Value::String(format!("{:#?}", address)),
+                    Value::String("committed".to_string()),
+                ]),
+            ),
+            server,
+        )
+    };
+    let committed_nonce = fut.await.0.unwrap();
+    let expected_nonce = {
+        let mut storage = pool.access_storage().await?;
+        let last_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await?;
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .get_account_nonce_for_block(address, last_block)
+            .await?;
+        U256::from(*nonce)
+    };
+    assert_eq!(
+        serde_json::from_value::<U256>(committed_nonce).unwrap(),
+        expected_nonce
+    );
+
+    Ok(())
+}
+
 /// Tests `eth_getBlockTransactionCountByHash` and `eth_getBlockTransactionCountByNumber` methods
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(
@@ -465,7 +567,12 @@ async fn get_block() -> anyhow::Result<()> {
 async fn create_logs() -> anyhow::Result<()> {
     let cfg = TestServerConfig::default();
     cfg.fill_database().await?;
-    let rpc_app = Web3RpcApp::new(cfg.pool, &cfg.config.api.web3, &cfg.config.api.token_config);
+    let rpc_app = Web3RpcApp::new(
+        cfg.pool,
+        fee_ticker(),
+        &cfg.config.api.web3,
+        &cfg.config.api.token_config,
+    );
 
     let from_account_id = AccountId(3);
     let from_account = ZkSyncAccount::rand_with_seed([1, 2, 3, 4]);
@@ -855,6 +962,7 @@ async fn get_transaction_receipt() -> anyhow::Result<()> {
             .unwrap();
         let rpc_app = Web3RpcApp::new(
             pool.clone(),
+            fee_ticker(),
             &Web3Config::from_env(),
             &TokenConfig::from_env(),
         );
@@ -878,6 +986,7 @@ async fn get_logs() -> anyhow::Result<()> {
     let pool = ConnectionPool::new(Some(1));
     let rpc_app = Web3RpcApp::new(
         pool.clone(),
+        fee_ticker(),
         &Web3Config::from_env(),
         &TokenConfig::from_env(),
     );
@@ -908,10 +1017,17 @@ async fn get_logs() -> anyhow::Result<()> {
             let config = Web3Config {
                 port: 0,
                 url: "".to_string(),
+                ws_port: 0,
+                ws_url: "".to_string(),
                 max_block_range: 3,
                 chain_id: 9,
             };
-            let rpc_app = Web3RpcApp::new(pool.clone(), &config, &TokenConfig::from_env());
+            let rpc_app = Web3RpcApp::new(
+                pool.clone(),
+                fee_ticker(),
+                &config,
+                &TokenConfig::from_env(),
+            );
             let mut io = IoHandler::new();
             rpc_app.extend(&mut io);
 
@@ -1200,6 +1316,38 @@ async fn erc20_calls() -> anyhow::Result<()> {
     .unwrap();
     assert_eq!(outputs[0].clone().into_uint().unwrap(), balance);
 
+    // Test `balanceOf` function at the historical block.
+    let fut = {
+        let (client, server) = local_client().await?;
+        let mut req = Map::new();
+        req.insert(
+            "to".to_string(),
+            Value::String(format!("{:#?}", token.address)),
+        );
+        let address = ethabi::encode(&[Token::Address(address)]);
+        let mut data = "0x70a08231".to_string();
+        data.push_str(hex::encode(address).as_str());
+        req.insert("data".to_string(), Value::String(data));
+        join(
+            client.call_method(
+                "eth_call",
+                Params::Array(vec![
+                    Value::Object(req),
+                    Value::String("earliest".to_string()),
+                ]),
+            ),
+            server,
+        )
+    };
+    let resp_data = fut.await.0.unwrap();
+    let resp_data = serde_json::from_value::<String>(resp_data).unwrap();
+    let outputs = ethabi::decode(
+        &[ParamType::Uint(256)],
+        &hex::decode(resp_data.strip_prefix("0x").unwrap()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(outputs[0].clone().into_uint().unwrap(), U256::zero());
+
     // Test `allowance` function.
     let fut = {
         let (client, server) = local_client().await?;
@@ -1231,6 +1379,77 @@ async fn erc20_calls() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Tests `eth_estimateGas` method
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+async fn estimate_gas() -> anyhow::Result<()> {
+    let cfg = TestServerConfig::default();
+    cfg.fill_database().await?;
+    let pool = ConnectionPool::new(Some(1));
+
+    // Checks that the plain call is estimated as the transfer of the native token.
+    let recipient = H160::random();
+    let fut = {
+        let (client, server) = local_client().await?;
+        let mut req = Map::new();
+        req.insert("to".to_string(), Value::String(format!("{:#?}", recipient)));
+        join(
+            client.call_method("eth_estimateGas", Params::Array(vec![Value::Object(req)])),
+            server,
+        )
+    };
+    let estimation = fut.await.0.unwrap();
+    let expected_fee = fee_ticker()
+        .get_fee_from_ticker_in_wei(TxFeeTypes::Transfer, TokenId(0).into(), recipient)
+        .await?
+        .normal_fee
+        .total_fee;
+    assert_eq!(
+        serde_json::from_value::<U256>(estimation).unwrap(),
+        u256_from_biguint(expected_fee)
+    );
+
+    // Checks that the ERC20 transfer is estimated in the called token,
+    // which is not suitable for paying fees.
+    let token = {
+        let mut storage = pool.access_storage().await?;
+        storage
+            .tokens_schema()
+            .get_token(TokenId(1).into())
+            .await?
+            .unwrap()
+    };
+    let fut = {
+        let (client, server) = local_client().await?;
+        let mut req = Map::new();
+        req.insert(
+            "to".to_string(),
+            Value::String(format!("{:#?}", token.address)),
+        );
+        let params = ethabi::encode(&[Token::Address(recipient), Token::Uint(U256::one())]);
+        let mut data = "0xa9059cbb".to_string();
+        data.push_str(hex::encode(params).as_str());
+        req.insert("data".to_string(), Value::String(data));
+        join(
+            client.call_method("eth_estimateGas", Params::Array(vec![Value::Object(req)])),
+            server,
+        )
+    };
+    let error = fut.await.0.unwrap_err();
+    assert!(matches!(
+        error,
+        RpcError::JsonRpcError(Error {
+            code: ErrorCode::InvalidParams,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
 /// Tests `eth_call` method for erc721 contracts
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(
@@ -1515,3 +1734,34 @@ fn ipfs_cid() {
     let ipfs_cid = CallsHelper::ipfs_cid(content_hash.as_bytes());
     assert_eq!(ipfs_cid, "QmQbSVaG7DUjQ9ktPtMnSXReJ29XHezBghcxJeZDsGG7wB")
 }
+
+#[test]
+#[cfg_attr(
+    not(feature = "api_test"),
+    ignore = "Use `zk test rust-api` command to perform this test"
+)]
+/// Tests that the log filter used by `eth_getLogs` and `eth_subscribe` matches addresses and topics.
+fn log_filter() {
+    let address = H160::from_str(ZKSYNC_PROXY_ADDRESS).unwrap();
+    let topic = H256::from_low_u64_be(1);
+    let log = Log {
+        address,
+        topics: vec![topic],
+        ..Default::default()
+    };
+
+    assert!(LogFilter::new(None, None).matches(&log));
+    let filter = LogFilter::new(
+        Some(ValueOrArray(vec![address])),
+        Some(vec![Some(ValueOrArray(vec![topic, H256::zero()]))]),
+    );
+    assert!(filter.matches(&log));
+    let filter = LogFilter::new(Some(ValueOrArray(vec![H160::zero()])), None);
+    assert!(!filter.matches(&log));
+    let filter = LogFilter::new(None, Some(vec![Some(ValueOrArray(vec![H256::zero()]))]));
+    assert!(!filter.matches(&log));
+    // All our logs contain exactly one topic.
+    let filter = LogFilter::new(None, Some(vec![None, Some(ValueOrArray(vec![topic]))]));
+    assert!(!filter.can_match());
+    assert!(!filter.matches(&log));
+}
@@ -248,6 +248,22 @@ pub struct Filter {
     pub topics: Option<Vec<Option<ValueOrArray<H256>>>>,
 }
 
+/// Kind of the `eth_subscribe` subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+    NewHeads,
+    Logs,
+}
+
+/// Notification sent to the `eth_subscribe` subscribers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PubSubResult {
+    Header(BlockInfo),
+    Log(Log),
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub enum Event {
     ZkSyncTransfer,
@@ -41,8 +41,9 @@ pub use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::FeeTokenValidator;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, http_json::HttpJsonAPI,
+        median::MedianAggregatorAPI, price_file::PriceFileAPI, FeeTickerAPI, PriceHistoryPolicy,
+        TickerApi, TokenPriceAPI, CONNECTION_TIMEOUT,
     },
     validator::{watcher::CoinGeckoTokenWatcher, MarketUpdater},
 };
@@ -205,6 +206,8 @@ const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
 // Make no more than (Number of tokens) queries per 5 minutes to database is a good result
 // for updating names for tokens.
 const TOKEN_INVALIDATE_CACHE: Duration = Duration::from_secs(5 * 60);
+/// Precision of the token prices returned by the ticker.
+pub(crate) const TOKEN_PRICE_PRECISION: usize = 100;
 
 #[must_use]
 pub fn run_updaters(db_pool: ConnectionPool, config: &ZkSyncConfig) -> Vec<JoinHandle<()>> {
@@ -224,30 +227,72 @@ pub fn run_updaters(db_pool: ConnectionPool, config: &ZkSyncConfig) -> Vec<JoinH
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.ticker.price_source();
-    let price_updater = match price_source {
-        TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
-
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
-            tokio::spawn(ticker_api.keep_price_updated())
-        }
-
-        TokenPriceSource::CoinGecko => tokio::spawn(async move {
-            let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
-                    .await
-                    .expect("failed to init CoinGecko client");
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
+    let ticker_config = config.ticker.clone();
+    let price_updater = tokio::spawn(async move {
+        let token_price_api = create_token_price_api(&ticker_config, client).await;
+        let ticker_api = TickerApi::new(
+            db_pool,
+            token_price_api,
+            PriceHistoryPolicy::from_config(&ticker_config),
+        );
 
-            ticker_api.keep_price_updated().await;
-        }),
-    };
+        ticker_api.keep_price_updated().await;
+    });
     tasks.push(price_updater);
     tasks
 }
 
+/// Creates the token price API for the configured price source.
+async fn create_token_price_api(
+    config: &zksync_config::TickerConfig,
+    client: reqwest::Client,
+) -> Box<dyn TokenPriceAPI + Send + Sync> {
+    match config.token_price_source {
+        TokenPriceSource::Median => {
+            let mut sources = Vec::with_capacity(config.median_sources.len());
+            for &source in &config.median_sources {
+                assert_ne!(
+                    source,
+                    TokenPriceSource::Median,
+                    "Median price source can't be aggregated by itself"
+                );
+                sources.push(create_single_token_price_api(config, source, client.clone()).await);
+            }
+            Box::new(MedianAggregatorAPI::new(
+                sources,
+                config.median_max_deviation_percent,
+            ))
+        }
+        source => create_single_token_price_api(config, source, client).await,
+    }
+}
+
+async fn create_single_token_price_api(
+    config: &zksync_config::TickerConfig,
+    source: TokenPriceSource,
+    client: reqwest::Client,
+) -> Box<dyn TokenPriceAPI + Send + Sync> {
+    let base_url = config.source_url(source);
+    match source {
+        TokenPriceSource::CoinMarketCap => Box::new(CoinMarketCapAPI::new(
+            client,
+            base_url.parse().expect("Correct CoinMarketCap url"),
+        )),
+        TokenPriceSource::CoinGecko => Box::new(
+            CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
+                .await
+                .expect("failed to init CoinGecko client"),
+        ),
+        TokenPriceSource::File => Box::new(PriceFileAPI::new(base_url, config.price_file_signer)),
+        TokenPriceSource::Http => Box::new(HttpJsonAPI::new(
+            client,
+            base_url,
+            config.http_price_selector.clone(),
+        )),
+        TokenPriceSource::Median => unreachable!("Median price source is not a single source"),
+    }
+}
+
 impl FeeTicker {
     pub fn new(
         info: Box<dyn FeeTickerInfo>,
@@ -316,11 +361,10 @@ impl FeeTicker {
             TokenPriceRequestType::USDForOneToken => BigUint::from(1u32),
         };
 
-        let res = self
-            .info
-            .get_last_token_price(token)
-            .await
-            .map(|price| ratio_to_big_decimal(&(price.usd_price / factor), 100));
+        let res =
+            self.info.get_last_token_price(token).await.map(|price| {
+                ratio_to_big_decimal(&(price.usd_price / factor), TOKEN_PRICE_PRECISION)
+            });
         metrics::histogram!("ticker.get_token_price", start.elapsed());
         res
     }
@@ -0,0 +1,138 @@
+// External deps
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+// Workspace deps
+use super::{parse_price, TokenPriceAPI, REQUEST_TIMEOUT};
+use crate::fee_ticker::PriceError;
+use zksync_types::{Token, TokenPrice};
+
+/// Token price source querying a generic HTTP endpoint that returns JSON.
+///
+/// `{symbol}` and `{address}` placeholders in both the URL and the selector are replaced
+/// with the symbol and the address of the requested token.
+#[derive(Debug, Clone)]
+pub struct HttpJsonAPI {
+    client: reqwest::Client,
+    url: String,
+    selector: String,
+}
+
+impl HttpJsonAPI {
+    pub fn new(client: reqwest::Client, url: String, selector: String) -> Self {
+        Self {
+            client,
+            url,
+            selector,
+        }
+    }
+}
+
+fn substitute_placeholders(template: &str, token: &Token) -> String {
+    template
+        .replace("{symbol}", &token.symbol)
+        .replace("{address}", &format!("{:?}", token.address))
+}
+
+/// Returns the value located by the JSONPath-style selector, e.g. `$.data[0].price`.
+///
+/// Only the subset of JSONPath is supported: dot-separated object keys, array indices
+/// and quoted keys in brackets (`$['usd-price']`).
+fn select<'a>(value: &'a Value, selector: &str) -> Option<&'a Value> {
+    let selector = selector.strip_prefix('$').unwrap_or(selector);
+
+    let mut current = value;
+    for part in selector.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut indices) = match part.find('[') {
+            Some(position) => part.split_at(position),
+            None => (part, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        while let Some(rest) = indices.strip_prefix('[') {
+            let end = rest.find(']')?;
+            let index = &rest[..end];
+            current = if let Some(key) = index
+                .strip_prefix('\'')
+                .and_then(|index| index.strip_suffix('\''))
+                .or_else(|| {
+                    index
+                        .strip_prefix('"')
+                        .and_then(|index| index.strip_suffix('"'))
+                }) {
+                current.get(key)?
+            } else {
+                current.get(index.parse::<usize>().ok()?)?
+            };
+            indices = &rest[end + 1..];
+        }
+        if !indices.is_empty() {
+            return None;
+        }
+    }
+    Some(current)
+}
+
+#[async_trait]
+impl TokenPriceAPI for HttpJsonAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let url = substitute_placeholders(&self.url, token);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| PriceError::api_error(format!("Price API request failed: {}", err)))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(PriceError::token_not_found(format!(
+                "Token '{}, {:?}' is not found by the price API",
+                token.symbol, token.address
+            )));
+        }
+        let response = response
+            .json::<Value>()
+            .await
+            .map_err(PriceError::api_error)?;
+
+        let selector = substitute_placeholders(&self.selector, token);
+        let price = select(&response, &selector)
+            .filter(|price| !price.is_null())
+            .ok_or_else(|| {
+                PriceError::token_not_found(format!(
+                    "Price API response has no value for the selector '{}'",
+                    selector
+                ))
+            })?;
+
+        Ok(TokenPrice {
+            usd_price: parse_price(price)?,
+            last_updated: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_path_selector() {
+        let value = json!({
+            "data": [
+                { "price": "1.5", "usd-price": 2 },
+            ],
+            "RBTC": { "usd": 42000 }
+        });
+
+        assert_eq!(select(&value, "$.data[0].price"), Some(&json!("1.5")));
+        assert_eq!(select(&value, "data[0]['usd-price']"), Some(&json!(2)));
+        assert_eq!(select(&value, "$.RBTC[\"usd\"]"), Some(&json!(42000)));
+        assert_eq!(select(&value, "$"), Some(&value));
+        assert_eq!(select(&value, "$.data[1].price"), None);
+        assert_eq!(select(&value, "$.data[0]price"), None);
+        assert_eq!(select(&value, "$.unknown"), None);
+    }
+}
@@ -0,0 +1,164 @@
+// External deps
+use async_trait::async_trait;
+use futures::future::join_all;
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_types::{Token, TokenPrice};
+
+/// Token price source combining the prices reported by several other sources.
+///
+/// The resulting price is the median of the prices that don't deviate from the median
+/// of all the reported prices by more than `max_deviation_percent`. Failed sources are skipped,
+/// so the price is available as long as at least one source is available.
+pub struct MedianAggregatorAPI {
+    sources: Vec<Box<dyn TokenPriceAPI + Send + Sync>>,
+    max_deviation_percent: u32,
+}
+
+impl MedianAggregatorAPI {
+    pub fn new(
+        sources: Vec<Box<dyn TokenPriceAPI + Send + Sync>>,
+        max_deviation_percent: u32,
+    ) -> Self {
+        Self {
+            sources,
+            max_deviation_percent,
+        }
+    }
+
+    fn is_outlier(&self, price: &Ratio<BigUint>, median: &Ratio<BigUint>) -> bool {
+        let deviation = if price > median {
+            price - median
+        } else {
+            median - price
+        };
+        deviation * BigUint::from(100u32)
+            > median.clone() * BigUint::from(self.max_deviation_percent)
+    }
+}
+
+/// Returns the median of the prices. Panics if `prices` is empty.
+fn median(mut prices: Vec<Ratio<BigUint>>) -> Ratio<BigUint> {
+    prices.sort();
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        prices.swap_remove(middle)
+    } else {
+        (&prices[middle - 1] + &prices[middle]) / BigUint::from(2u32)
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for MedianAggregatorAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let results = join_all(self.sources.iter().map(|source| source.get_price(token))).await;
+
+        let mut prices = Vec::with_capacity(results.len());
+        let mut error = None;
+        for result in results {
+            match result {
+                Ok(price) => prices.push(price),
+                Err(err) => {
+                    vlog::warn!(
+                        "Price source failed to provide price for token {}: {}",
+                        token.symbol,
+                        err
+                    );
+                    // API errors are more important than the token being unknown to some source.
+                    if !matches!(error, Some(PriceError::ApiError(_))) {
+                        error = Some(err);
+                    }
+                }
+            }
+        }
+        if prices.is_empty() {
+            return Err(
+                error.unwrap_or_else(|| PriceError::api_error("No token price sources configured"))
+            );
+        }
+
+        let median_price = median(prices.iter().map(|price| price.usd_price.clone()).collect());
+        let prices: Vec<_> = prices
+            .into_iter()
+            .filter(|price| !self.is_outlier(&price.usd_price, &median_price))
+            .collect();
+        if prices.is_empty() {
+            return Err(PriceError::api_error(format!(
+                "Token price sources disagree on the price of {}",
+                token.symbol
+            )));
+        }
+
+        let last_updated = prices
+            .iter()
+            .map(|price| price.last_updated)
+            .max()
+            .expect("At least one price is present");
+        let usd_price = median(prices.into_iter().map(|price| price.usd_price).collect());
+        Ok(TokenPrice {
+            usd_price,
+            last_updated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use zksync_types::{Address, TokenId, TokenKind};
+
+    struct FixedPrice(Result<u32, ()>);
+
+    #[async_trait]
+    impl TokenPriceAPI for FixedPrice {
+        async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+            match self.0 {
+                Ok(price) => Ok(TokenPrice {
+                    usd_price: Ratio::from_integer(BigUint::from(price)),
+                    last_updated: Utc::now(),
+                }),
+                Err(_) => Err(PriceError::api_error("Source is down")),
+            }
+        }
+    }
+
+    fn aggregator(prices: &[Result<u32, ()>]) -> MedianAggregatorAPI {
+        let sources = prices
+            .iter()
+            .map(|price| Box::new(FixedPrice(*price)) as Box<dyn TokenPriceAPI + Send + Sync>)
+            .collect();
+        MedianAggregatorAPI::new(sources, 10)
+    }
+
+    async fn price(prices: &[Result<u32, ()>]) -> Result<Ratio<BigUint>, PriceError> {
+        let token = Token::new(TokenId(0), Address::default(), "RBTC", 18, TokenKind::ERC20);
+        aggregator(prices)
+            .get_price(&token)
+            .await
+            .map(|price| price.usd_price)
+    }
+
+    fn ratio(numer: u32, denom: u32) -> Ratio<BigUint> {
+        Ratio::new(BigUint::from(numer), BigUint::from(denom))
+    }
+
+    /// Checks that outliers and failed sources are ignored.
+    #[tokio::test]
+    async fn median_of_sources() {
+        assert_eq!(
+            price(&[Ok(100), Ok(104), Ok(500)]).await.unwrap(),
+            ratio(102, 1)
+        );
+        assert_eq!(
+            price(&[Ok(100), Err(()), Ok(105)]).await.unwrap(),
+            ratio(205, 2)
+        );
+        assert_eq!(price(&[Err(()), Ok(100)]).await.unwrap(), ratio(100, 1));
+        assert!(price(&[Err(()), Err(())]).await.is_err());
+        // Both prices deviate too much from their median.
+        assert!(price(&[Ok(100), Ok(200)]).await.is_err());
+    }
+}
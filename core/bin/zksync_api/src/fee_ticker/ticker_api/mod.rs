@@ -2,16 +2,23 @@ use super::PriceError;
 
 use anyhow::format_err;
 use async_trait::async_trait;
+use bigdecimal::BigDecimal;
 use chrono::Utc;
-use num::rational::Ratio;
+use num::{rational::Ratio, BigUint};
 
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
+use zksync_config::TickerConfig;
 use zksync_storage::ConnectionPool;
 use zksync_types::{Token, TokenId, TokenPrice};
+use zksync_utils::big_decimal_to_ratio;
 
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod http_json;
+pub mod median;
+pub mod price_file;
 
 const UPDATE_PRICE_INTERVAL_SECS: u64 = 10 * 60;
 /// The limit of time we are willing to wait for response.
@@ -24,24 +31,75 @@ pub trait TokenPriceAPI {
     async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError>;
 }
 
+#[async_trait]
+impl TokenPriceAPI for Box<dyn TokenPriceAPI + Send + Sync> {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        self.as_ref().get_price(token).await
+    }
+}
+
+/// Parses the USD price given either as a JSON number or as a decimal string.
+pub(super) fn parse_price(value: &serde_json::Value) -> Result<Ratio<BigUint>, PriceError> {
+    let price = match value {
+        serde_json::Value::Number(number) => number.to_string(),
+        serde_json::Value::String(string) => string.clone(),
+        _ => {
+            return Err(PriceError::api_error(format!(
+                "Price should be a number or a string, got {}",
+                value
+            )))
+        }
+    };
+    let price = BigDecimal::from_str(&price)
+        .map_err(|err| PriceError::api_error(format!("Invalid price {}: {}", price, err)))?;
+    big_decimal_to_ratio(&price).map_err(PriceError::api_error)
+}
+
 /// Api responsible for querying for TokenPrices
 #[async_trait]
 pub trait FeeTickerAPI {
     async fn keep_price_updated(self);
 }
 
+/// Rules of reducing the size of the stored token price history.
+#[derive(Debug, Clone)]
+pub(super) struct PriceHistoryPolicy {
+    /// Prices older than this are downsampled.
+    pub downsample_after: Duration,
+    /// Only one price per this number of seconds is kept for the downsampled prices.
+    pub downsample_interval_secs: u64,
+    /// Prices older than this are removed.
+    pub retention: Option<Duration>,
+}
+
+impl PriceHistoryPolicy {
+    pub fn from_config(config: &TickerConfig) -> Self {
+        Self {
+            downsample_after: config.price_history_downsample_after(),
+            downsample_interval_secs: config.price_history_downsample_interval_secs,
+            retention: config.price_history_retention(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct TickerApi<T: TokenPriceAPI> {
     db_pool: ConnectionPool,
 
     token_price_api: T,
+    history_policy: PriceHistoryPolicy,
 }
 
 impl<T: TokenPriceAPI> TickerApi<T> {
-    pub fn new(db_pool: ConnectionPool, token_price_api: T) -> Self {
+    pub fn new(
+        db_pool: ConnectionPool,
+        token_price_api: T,
+        history_policy: PriceHistoryPolicy,
+    ) -> Self {
         Self {
             db_pool,
             token_price_api,
+            history_policy,
         }
     }
 
@@ -96,6 +154,36 @@ impl<T: TokenPriceAPI> TickerApi<T> {
         metrics::histogram!("ticker.update_price", start.elapsed());
         Ok(())
     }
+
+    /// Downsamples and removes the old prices from the token price history.
+    async fn prune_price_history(&self) -> Result<(), anyhow::Error> {
+        let now = Utc::now();
+        let age_to_time = |age: Duration| {
+            chrono::Duration::from_std(age)
+                .ok()
+                .and_then(|age| now.checked_sub_signed(age))
+                .unwrap_or(chrono::MIN_DATETIME)
+        };
+        let downsample_before = age_to_time(self.history_policy.downsample_after);
+        let remove_before = self.history_policy.retention.map(age_to_time);
+
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        let removed = storage
+            .tokens_schema()
+            .prune_ticker_price_history(
+                downsample_before,
+                self.history_policy.downsample_interval_secs,
+                remove_before,
+            )
+            .await
+            .map_err(|e| format_err!("Can't prune ticker price history: {}", e))?;
+        vlog::debug!("Removed {} prices from the ticker price history", removed);
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -115,6 +203,9 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
             } else {
                 vlog::warn!("Can't get info from the database; waiting for the next iteration");
             };
+            if let Err(e) = self.prune_price_history().await {
+                vlog::error!("Can't prune the token price history. Error: {}", e);
+            }
             tokio::time::sleep(Duration::from_secs(UPDATE_PRICE_INTERVAL_SECS)).await;
         }
     }
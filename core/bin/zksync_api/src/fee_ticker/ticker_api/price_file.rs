@@ -0,0 +1,237 @@
+// Built-in deps
+use std::collections::HashMap;
+use std::path::PathBuf;
+// External deps
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use super::{parse_price, TokenPriceAPI};
+use crate::fee_ticker::PriceError;
+use zksync_types::{tx::PackedEthSignature, Address, Token, TokenPrice};
+
+/// Token price source reading the prices from a local JSON or CSV file.
+///
+/// JSON file is an object mapping the token symbol or address to its USD price, e.g.
+/// `{"RBTC": "42000.5", "0x6b175474e89094c44da98b954eedeac495271d0f": 1}`.
+/// CSV file contains `token,price` lines with the same meaning, lines starting with `#` are ignored.
+///
+/// If the signer is set, the file must be accompanied with the `<path>.sig` file containing
+/// the hex-encoded Ethereum signature of the file content made by the signer.
+/// The file is read on every request, so the prices can be updated without the server restart.
+#[derive(Debug, Clone)]
+pub struct PriceFileAPI {
+    path: PathBuf,
+    signer: Option<Address>,
+}
+
+impl PriceFileAPI {
+    pub fn new(path: impl Into<PathBuf>, signer: Option<Address>) -> Self {
+        Self {
+            path: path.into(),
+            signer,
+        }
+    }
+
+    fn signature_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".sig");
+        path.into()
+    }
+
+    fn is_csv(&self) -> bool {
+        self.path
+            .extension()
+            .map(|extension| extension.eq_ignore_ascii_case("csv"))
+            .unwrap_or(false)
+    }
+
+    async fn check_signature(&self, content: &[u8], signer: Address) -> Result<(), PriceError> {
+        let signature = tokio::fs::read_to_string(self.signature_path())
+            .await
+            .map_err(|err| {
+                PriceError::api_error(format!("Can't read the price file signature: {}", err))
+            })?;
+        let signature = signature.trim();
+        let signature = hex::decode(signature.strip_prefix("0x").unwrap_or(signature))
+            .map_err(|err| PriceError::api_error(format!("Invalid signature encoding: {}", err)))?;
+        let recovered = PackedEthSignature::deserialize_packed(&signature)
+            .and_then(|signature| signature.signature_recover_signer(content))
+            .map_err(|err| {
+                PriceError::api_error(format!("Invalid price file signature: {}", err))
+            })?;
+
+        if recovered != signer {
+            return Err(PriceError::api_error(format!(
+                "Price file is signed by {:?}, expected {:?}",
+                recovered, signer
+            )));
+        }
+        Ok(())
+    }
+
+    /// Loads the prices from the file along with the time of its last modification.
+    async fn load_prices(
+        &self,
+    ) -> Result<(HashMap<String, Ratio<BigUint>>, DateTime<Utc>), PriceError> {
+        let content = tokio::fs::read(&self.path).await.map_err(|err| {
+            PriceError::api_error(format!(
+                "Can't read the price file {}: {}",
+                self.path.display(),
+                err
+            ))
+        })?;
+        if let Some(signer) = self.signer {
+            self.check_signature(&content, signer).await?;
+        }
+        let last_updated = tokio::fs::metadata(&self.path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .map(DateTime::<Utc>::from)
+            .map_err(PriceError::api_error)?;
+
+        let content = String::from_utf8(content).map_err(PriceError::api_error)?;
+        let prices = if self.is_csv() {
+            parse_csv(&content)?
+        } else {
+            parse_json(&content)?
+        };
+        Ok((prices, last_updated))
+    }
+}
+
+fn parse_json(content: &str) -> Result<HashMap<String, Ratio<BigUint>>, PriceError> {
+    let values: HashMap<String, serde_json::Value> =
+        serde_json::from_str(content).map_err(PriceError::api_error)?;
+    values
+        .into_iter()
+        .map(|(token, price)| Ok((token.to_lowercase(), parse_price(&price)?)))
+        .collect()
+}
+
+fn parse_csv(content: &str) -> Result<HashMap<String, Ratio<BigUint>>, PriceError> {
+    let mut prices = HashMap::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut columns = line.split(',').map(str::trim);
+        let (token, price) = match (columns.next(), columns.next()) {
+            (Some(token), Some(price)) => (token, price),
+            _ => {
+                return Err(PriceError::api_error(format!(
+                    "Invalid price file line: {}",
+                    line
+                )))
+            }
+        };
+        // Skip the optional header.
+        if token.eq_ignore_ascii_case("token") && price.eq_ignore_ascii_case("price") {
+            continue;
+        }
+        prices.insert(
+            token.to_lowercase(),
+            parse_price(&serde_json::Value::String(price.to_string()))?,
+        );
+    }
+    Ok(prices)
+}
+
+#[async_trait]
+impl TokenPriceAPI for PriceFileAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let (prices, last_updated) = self.load_prices().await?;
+
+        let address = format!("{:?}", token.address);
+        let usd_price = prices
+            .get(&address)
+            .or_else(|| prices.get(&token.symbol.to_lowercase()))
+            .cloned()
+            .ok_or_else(|| {
+                PriceError::token_not_found(format!(
+                    "Token '{}, {:?}' is not listed in the price file",
+                    token.symbol, token.address
+                ))
+            })?;
+
+        Ok(TokenPrice {
+            usd_price,
+            last_updated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{TokenId, TokenKind, H256};
+
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}_{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn token(symbol: &str, address: Address) -> Token {
+        Token::new(TokenId(1), address, symbol, 18, TokenKind::ERC20)
+    }
+
+    /// Checks that prices are loaded from both JSON and CSV files by symbol or address.
+    #[tokio::test]
+    async fn load_prices() {
+        let address = Address::random();
+        let json = format!(r#"{{"RBTC": "1.5", "{:?}": 2}}"#, address);
+        let csv = format!("token,price\n# comment\nRBTC, 1.5\n{:?},2\n", address);
+
+        for path in [
+            temp_file("prices.json", json.as_bytes()),
+            temp_file("prices.csv", csv.as_bytes()),
+        ] {
+            let api = PriceFileAPI::new(&path, None);
+            let price = api
+                .get_price(&token("RBTC", Address::random()))
+                .await
+                .unwrap();
+            assert_eq!(
+                price.usd_price,
+                Ratio::new(BigUint::from(3u32), BigUint::from(2u32))
+            );
+            let price = api.get_price(&token("DAI", address)).await.unwrap();
+            assert_eq!(price.usd_price, Ratio::from_integer(BigUint::from(2u32)));
+            assert!(matches!(
+                api.get_price(&token("DAI", Address::random())).await,
+                Err(PriceError::TokenNotFound(_))
+            ));
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    /// Checks that the price file signed by the wrong account is rejected.
+    #[tokio::test]
+    async fn check_signature() {
+        let private_key = H256::random();
+        let signer = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+        let content = br#"{"RBTC": "1.5"}"#;
+        let path = temp_file("signed_prices.json", content);
+
+        let signature = PackedEthSignature::sign(&private_key, content).unwrap();
+        std::fs::write(
+            format!("{}.sig", path.display()),
+            format!("0x{}", hex::encode(signature.serialize_packed())),
+        )
+        .unwrap();
+
+        let rbtc = token("RBTC", Address::default());
+        assert!(PriceFileAPI::new(&path, Some(signer))
+            .get_price(&rbtc)
+            .await
+            .is_ok());
+        assert!(PriceFileAPI::new(&path, Some(Address::random()))
+            .get_price(&rbtc)
+            .await
+            .is_err());
+
+        std::fs::remove_file(format!("{}.sig", path.display())).unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -3,6 +3,7 @@ use crate::state_keeper::ZkSyncStateInitParams;
 use crate::{
     committer::run_committer,
     eth_watch::start_eth_watch,
+    priority_queue_watchdog::run_priority_queue_watchdog,
     state_keeper::{start_root_hash_calculator, start_state_keeper, ZkSyncStateKeeper},
     token_handler::run_token_handler,
 };
@@ -18,6 +19,7 @@ const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
 pub mod committer;
 pub mod eth_watch;
+pub mod priority_queue_watchdog;
 pub mod register_factory_handler;
 pub mod rejected_tx_cleaner;
 pub mod state_keeper;
@@ -108,6 +110,7 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.mempool.clone(),
     );
 
     // Run health check api for core
@@ -172,6 +175,7 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_block_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.mempool.clone(),
     );
 
     // Start token handler.
@@ -179,6 +183,7 @@ pub async fn run_core(
         connection_pool.clone(),
         eth_gateway.clone(),
         &config.token_handler,
+        &config.notifier,
         eth_watch_req_sender.clone(),
     );
 
@@ -189,6 +194,10 @@ pub async fn run_core(
         config.token_handler.clone(),
     );
 
+    // Start priority queue watchdog.
+    let priority_queue_watchdog_task =
+        run_priority_queue_watchdog(connection_pool.clone(), &config.notifier);
+
     let tx_event_emitter_task = tx_event_emitter::run_tx_event_emitter_task(
         connection_pool.clone(),
         processed_tx_events_receiver,
@@ -202,6 +211,7 @@ pub async fn run_core(
         token_handler_task,
         register_factory_task,
         tx_event_emitter_task,
+        priority_queue_watchdog_task,
         mempool_block_handler_task,
         mempool_tx_handler_task,
         private_api_task,
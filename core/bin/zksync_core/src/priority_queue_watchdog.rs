@@ -0,0 +1,76 @@
+//! The watchdog alerts the operator when the priority operations are piling up in the queue
+//! without being processed, e.g. because the block proposer is stuck.
+//!
+//! The alert is raised once the oldest confirmed operation waits longer than the configured timeout,
+//! and is repeated only after the queue is processed.
+
+// Built-in uses
+use std::time::Duration;
+
+// External uses
+use chrono::Utc;
+use tokio::{task::JoinHandle, time};
+
+// Workspace deps
+use zksync_config::NotifierConfig;
+use zksync_notifier::{Notification, Notifier};
+use zksync_storage::ConnectionPool;
+
+/// Interval of checking the priority queue.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Returns the alert if the oldest operation in the queue waits longer than `backlog_timeout`.
+async fn check_backlog(
+    db_pool: &ConnectionPool,
+    backlog_timeout: Duration,
+) -> anyhow::Result<Option<Notification>> {
+    let (unprocessed_ops, oldest_created_at) = db_pool
+        .access_storage()
+        .await?
+        .chain()
+        .mempool_schema()
+        .get_priority_ops_backlog()
+        .await?;
+    let oldest_op_age = oldest_created_at
+        .and_then(|created_at| (Utc::now() - created_at).to_std().ok())
+        .unwrap_or_default();
+    if unprocessed_ops == 0 || oldest_op_age < backlog_timeout {
+        return Ok(None);
+    }
+
+    Ok(Some(Notification::PriorityQueueBacklog {
+        unprocessed_ops: unprocessed_ops as u64,
+        oldest_op_age,
+    }))
+}
+
+#[must_use]
+pub fn run_priority_queue_watchdog(
+    db_pool: ConnectionPool,
+    notifier_config: &NotifierConfig,
+) -> JoinHandle<()> {
+    let notifier = Notifier::from_config(notifier_config);
+    let backlog_timeout = notifier_config.priority_queue_backlog_timeout();
+    let mut timer = time::interval(CHECK_INTERVAL);
+
+    tokio::spawn(async move {
+        let mut alert_sent = false;
+        loop {
+            timer.tick().await;
+
+            match check_backlog(&db_pool, backlog_timeout).await {
+                Ok(Some(notification)) => {
+                    if !alert_sent {
+                        vlog::warn!("{}", notification);
+                        if let Err(e) = notifier.send(notification).await {
+                            vlog::error!("Unable to send the priority queue alert: {}", e);
+                        }
+                        alert_sent = true;
+                    }
+                }
+                Ok(None) => alert_sent = false,
+                Err(e) => vlog::error!("Can't check the priority queue backlog {:?}", e),
+            }
+        }
+    })
+}
@@ -12,6 +12,7 @@ use std::time::{Duration, Instant};
 
 use actix_web::{web, App, HttpResponse, HttpServer};
 use futures::{channel::mpsc, StreamExt};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use zksync_api_types::CoreStatus;
@@ -19,6 +20,7 @@ use zksync_api_types::CoreStatus;
 use zksync_config::configs::api::PrivateApiConfig;
 use zksync_eth_client::RootstockGateway;
 use zksync_storage::ConnectionPool;
+use zksync_types::{address_lists::AddressListType, Address};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 const STATUS_INVALIDATION_PERIOD: Duration = Duration::from_secs(60);
@@ -63,6 +65,83 @@ async fn status(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddressListUpdate {
+    list: AddressListType,
+    addresses: Vec<Address>,
+    #[serde(default)]
+    reason: String,
+}
+
+/// Returns all the entries of the address allow- and deny-lists.
+#[actix_web::get("/address_lists")]
+async fn address_lists(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let entries = storage
+        .address_lists_schema()
+        .load_entries()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+/// Adds the addresses to the list. API servers pick the changes up without restarts.
+#[actix_web::post("/address_lists")]
+async fn add_to_address_list(
+    data: web::Data<AppState>,
+    update: web::Json<AddressListUpdate>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    storage
+        .address_lists_schema()
+        .add_entries(update.list, &update.addresses, &update.reason)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    vlog::info!(
+        "Added {} addresses to the {} address list: {}",
+        update.addresses.len(),
+        update.list.to_string(),
+        update.reason
+    );
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Removes the addresses from the list and returns the number of the removed entries.
+#[actix_web::delete("/address_lists")]
+async fn remove_from_address_list(
+    data: web::Data<AppState>,
+    update: web::Json<AddressListUpdate>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .connection_pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let removed = storage
+        .address_lists_schema()
+        .remove_entries(update.list, &update.addresses)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    vlog::info!(
+        "Removed {} addresses from the {} address list",
+        removed,
+        update.list.to_string()
+    );
+    Ok(HttpResponse::Ok().json(removed))
+}
+
 pub fn start_private_core_api(
     connection_pool: ConnectionPool,
     read_only_connection_pool: ConnectionPool,
@@ -94,6 +173,9 @@ pub fn start_private_core_api(
                         .app_data(web::Data::new(app_state))
                         .app_data(web::JsonConfig::default().limit(2usize.pow(32)))
                         .service(status)
+                        .service(address_lists)
+                        .service(add_to_address_list)
+                        .service(remove_from_address_list)
                 })
                 .bind(config.bind_addr())
                 .expect("failed to bind")
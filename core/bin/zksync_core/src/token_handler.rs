@@ -14,8 +14,8 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 // Workspace uses
-use zksync_config::TokenHandlerConfig;
-use zksync_notifier::Notifier;
+use zksync_config::{NotifierConfig, TokenHandlerConfig};
+use zksync_notifier::{MatterMostNotifier, Notifier};
 use zksync_storage::{tokens::StoreTokenError, ConnectionPool, StorageProcessor};
 use zksync_types::{
     tokens::{NewTokenEvent, Token, TokenInfo},
@@ -34,7 +34,7 @@ struct TokenHandler {
     eth_client: RootstockGateway,
     token_list: HashMap<Address, TokenInfo>,
     last_eth_block: Option<u64>,
-    notifier: Option<Notifier>,
+    notifier: Notifier,
 }
 
 impl TokenHandler {
@@ -43,6 +43,7 @@ impl TokenHandler {
         eth_watcher_req: mpsc::Sender<EthWatchRequest>,
         eth_client: RootstockGateway,
         config: TokenHandlerConfig,
+        notifier_config: NotifierConfig,
     ) -> Self {
         let poll_interval = config.poll_interval();
         let token_list = config
@@ -51,8 +52,11 @@ impl TokenHandler {
             .map(|token| (token.address, token))
             .collect::<HashMap<Address, TokenInfo>>();
 
-        let webhook_url = reqwest::Url::parse(&config.webhook_url).ok();
-        let notifier = webhook_url.map(Notifier::with_mattermost);
+        // Token list notifications may be sent to a dedicated channel in addition to the common ones.
+        let mut notifier = Notifier::from_config(&notifier_config);
+        if let Ok(webhook_url) = reqwest::Url::parse(&config.webhook_url) {
+            notifier = notifier.with_backend(MatterMostNotifier::new(webhook_url));
+        }
 
         Self {
             connection_pool,
@@ -223,15 +227,13 @@ impl TokenHandler {
                 .expect("failed to add tokens to the database");
 
             // Send a notification that the token has been successfully added to the database.
-            if let Some(notifier) = &self.notifier {
-                for token in new_tokens {
-                    notifier
-                        .send_new_token_notify(token)
-                        .await
-                        .unwrap_or_else(|e| {
-                            vlog::error!("Failed to send a token insertion notification: {}", e);
-                        });
-                }
+            for token in new_tokens {
+                self.notifier
+                    .send_new_token_notify(token)
+                    .await
+                    .unwrap_or_else(|e| {
+                        vlog::error!("Failed to send a token insertion notification: {}", e);
+                    });
             }
         }
     }
@@ -242,12 +244,19 @@ pub fn run_token_handler(
     db_pool: ConnectionPool,
     eth_client: RootstockGateway,
     config: &TokenHandlerConfig,
+    notifier_config: &NotifierConfig,
     eth_watcher_req: mpsc::Sender<EthWatchRequest>,
 ) -> JoinHandle<()> {
     let config = config.clone();
+    let notifier_config = notifier_config.clone();
     tokio::spawn(async move {
-        let mut token_handler =
-            TokenHandler::new(db_pool, eth_watcher_req, eth_client, config.clone());
+        let mut token_handler = TokenHandler::new(
+            db_pool,
+            eth_watcher_req,
+            eth_client,
+            config.clone(),
+            notifier_config,
+        );
 
         token_handler.run().await
     })
@@ -0,0 +1,223 @@
+//! Operator balance watchdog.
+//!
+//! Periodically estimates how many blocks the operator account can still commit,
+//! verify and execute with its current balance, and raises an alert once this runway
+//! becomes too short, so the account can be topped up before `ETHSender` gets stuck.
+
+// External uses
+use tokio::time;
+use web3::types::{Address, U256};
+// Workspace uses
+use zksync_config::configs::eth_sender::BalanceWatchdog as BalanceWatchdogConfig;
+use zksync_eth_client::RootstockGateway;
+use zksync_notifier::{MatterMostNotifier, Notification, Notifier, Severity};
+// Local uses
+use crate::database::DatabaseInterface;
+
+/// Severity of the operator balance state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AlertLevel {
+    /// The balance is enough for a long time.
+    Ok,
+    /// The balance should be topped up soon.
+    Warning,
+    /// The balance is about to run out.
+    Critical,
+}
+
+impl AlertLevel {
+    /// Chooses the alert level for the estimated runway.
+    /// Unknown runway (e.g. there are no blocks yet) is not considered dangerous.
+    pub fn from_runway(runway_blocks: Option<U256>, config: &BalanceWatchdogConfig) -> Self {
+        match runway_blocks {
+            Some(blocks) if blocks < config.critical_runway_blocks.into() => Self::Critical,
+            Some(blocks) if blocks < config.warning_runway_blocks.into() => Self::Warning,
+            _ => Self::Ok,
+        }
+    }
+
+    fn severity(self) -> Severity {
+        match self {
+            Self::Ok => Severity::Info,
+            Self::Warning => Severity::Warning,
+            Self::Critical => Severity::Critical,
+        }
+    }
+}
+
+/// Estimates the amount of blocks the operator can pay for with the given balance.
+///
+/// Returns `None` if the cost of a block cannot be estimated.
+pub(super) fn estimate_runway(
+    balance: U256,
+    block_gas_limit: Option<U256>,
+    gas_price: U256,
+) -> Option<U256> {
+    let block_cost = block_gas_limit?.checked_mul(gas_price)?;
+    if block_cost.is_zero() {
+        return None;
+    }
+    Some(balance / block_cost)
+}
+
+/// `BalanceWatchdog` keeps track of the operator account balance.
+///
+/// The gas spent per block is estimated from the gas limits of the latest blocks
+/// (calculated by the `GasCounter` when the block is sealed), and the cost of gas is
+/// taken from the Rootstock node. The operator is notified only when the alert level
+/// changes, so the alerts are not repeated on every check.
+pub(super) struct BalanceWatchdog<DB: DatabaseInterface> {
+    db: DB,
+    rootstock: RootstockGateway,
+    notifier: Notifier,
+    operator: Address,
+    config: BalanceWatchdogConfig,
+    alert_level: AlertLevel,
+}
+
+impl<DB: DatabaseInterface> BalanceWatchdog<DB> {
+    pub fn new(
+        db: DB,
+        rootstock: RootstockGateway,
+        operator: Address,
+        config: BalanceWatchdogConfig,
+        mut notifier: Notifier,
+    ) -> Self {
+        if let Ok(webhook_url) = reqwest::Url::parse(&config.webhook_url) {
+            notifier = notifier.with_backend(MatterMostNotifier::new(webhook_url));
+        }
+
+        Self {
+            db,
+            rootstock,
+            notifier,
+            operator,
+            config,
+            alert_level: AlertLevel::Ok,
+        }
+    }
+
+    pub async fn run(mut self) {
+        let mut timer = time::interval(self.config.check_interval());
+
+        loop {
+            timer.tick().await;
+
+            if let Err(err) = self.check_balance().await {
+                vlog::error!("Unable to check the operator balance: {}", err);
+            }
+        }
+    }
+
+    async fn check_balance(&mut self) -> anyhow::Result<()> {
+        let balance = self.rootstock.sender_rbtc_balance().await?;
+        let gas_price = self.rootstock.get_gas_price().await?;
+        let block_gas_limit = {
+            let mut connection = self.db.acquire_connection().await?;
+            self.db
+                .load_average_block_gas_limit(&mut connection, self.config.blocks_sample)
+                .await?
+        };
+        let runway_blocks = estimate_runway(balance, block_gas_limit, gas_price);
+
+        metrics::gauge!("eth_sender.operator_balance", balance.low_u128() as f64);
+        if let Some(runway_blocks) = runway_blocks {
+            metrics::gauge!(
+                "eth_sender.balance_runway_blocks",
+                runway_blocks.low_u64() as f64
+            );
+        }
+
+        let alert_level = AlertLevel::from_runway(runway_blocks, &self.config);
+        if alert_level != self.alert_level {
+            self.report(alert_level, balance, runway_blocks).await;
+            self.alert_level = alert_level;
+        }
+
+        Ok(())
+    }
+
+    async fn report(&self, alert_level: AlertLevel, balance: U256, runway_blocks: Option<U256>) {
+        let notification = Notification::OperatorBalance {
+            severity: alert_level.severity(),
+            operator: self.operator,
+            balance,
+            runway_blocks,
+        };
+        match alert_level {
+            AlertLevel::Ok => vlog::info!("{}", notification),
+            AlertLevel::Warning => vlog::warn!("{}", notification),
+            AlertLevel::Critical => vlog::error!("{}", notification),
+        }
+
+        if let Err(err) = self.notifier.send(notification).await {
+            vlog::error!("Unable to send the operator balance alert: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog_config() -> BalanceWatchdogConfig {
+        BalanceWatchdogConfig {
+            enabled: true,
+            check_interval: 60,
+            blocks_sample: 10,
+            warning_runway_blocks: 1000,
+            critical_runway_blocks: 100,
+            webhook_url: String::new(),
+        }
+    }
+
+    /// Checks that the runway is calculated from the block cost and can't be estimated without it.
+    #[test]
+    fn runway_estimation() {
+        let balance = U256::from(1_000_000_000u64);
+
+        assert_eq!(
+            estimate_runway(balance, Some(1000.into()), 1000.into()),
+            Some(1000.into())
+        );
+        // Partially paid block is not taken into account.
+        assert_eq!(
+            estimate_runway(balance, Some(3000.into()), 1000.into()),
+            Some(333.into())
+        );
+        assert_eq!(estimate_runway(balance, None, 1000.into()), None);
+        assert_eq!(estimate_runway(balance, Some(1000.into()), 0.into()), None);
+        assert_eq!(
+            estimate_runway(balance, Some(U256::max_value()), 2.into()),
+            None
+        );
+    }
+
+    /// Checks that the alert level is chosen according to the configured thresholds.
+    #[test]
+    fn alert_levels() {
+        let config = watchdog_config();
+
+        assert_eq!(AlertLevel::from_runway(None, &config), AlertLevel::Ok);
+        assert_eq!(
+            AlertLevel::from_runway(Some(1000.into()), &config),
+            AlertLevel::Ok
+        );
+        assert_eq!(
+            AlertLevel::from_runway(Some(999.into()), &config),
+            AlertLevel::Warning
+        );
+        assert_eq!(
+            AlertLevel::from_runway(Some(100.into()), &config),
+            AlertLevel::Warning
+        );
+        assert_eq!(
+            AlertLevel::from_runway(Some(99.into()), &config),
+            AlertLevel::Critical
+        );
+        assert_eq!(
+            AlertLevel::from_runway(Some(0.into()), &config),
+            AlertLevel::Critical
+        );
+    }
+}
@@ -55,6 +55,7 @@ pub(super) trait DatabaseInterface {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse>;
 
@@ -73,6 +74,7 @@ pub(super) trait DatabaseInterface {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()>;
 
     /// Marks an operation as completed in the database.
@@ -105,6 +107,14 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         op: &ETHOperation,
     ) -> anyhow::Result<bool>;
+
+    /// Loads the average gas limit (commit and verify combined) of the latest `blocks_count` blocks.
+    /// Returns `None` if there are no blocks yet.
+    async fn load_average_block_gas_limit(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        blocks_count: u32,
+    ) -> anyhow::Result<Option<U256>>;
 }
 
 /// The actual database wrapper.
@@ -184,6 +194,7 @@ impl DatabaseInterface for Database {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         raw_tx: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let result = connection
@@ -193,6 +204,7 @@ impl DatabaseInterface for Database {
                 op,
                 deadline_block,
                 BigUint::from_str(&used_gas_price.to_string()).unwrap(),
+                used_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
                 raw_tx,
             )
             .await?;
@@ -218,6 +230,7 @@ impl DatabaseInterface for Database {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         Ok(connection
             .rootstock_schema()
@@ -225,6 +238,7 @@ impl DatabaseInterface for Database {
                 eth_op_id,
                 new_deadline_block,
                 BigUint::from_str(&new_gas_value.to_string()).unwrap(),
+                new_priority_fee.map(|fee| BigUint::from_str(&fee.to_string()).unwrap()),
             )
             .await?)
     }
@@ -343,6 +357,19 @@ impl DatabaseInterface for Database {
             .await?;
         Ok(())
     }
+
+    async fn load_average_block_gas_limit(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        blocks_count: u32,
+    ) -> anyhow::Result<Option<U256>> {
+        let gas_limit = connection
+            .chain()
+            .block_schema()
+            .get_average_block_gas_limit(blocks_count)
+            .await?;
+        Ok(gas_limit.map(U256::from))
+    }
 }
 
 impl Database {
@@ -11,6 +11,15 @@ mod parameters;
 #[cfg(test)]
 mod tests;
 
+/// Fee parameters of the dynamic fee (EIP-1559) transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct DynamicGasPrice {
+    /// Maximum total price per gas the sender is willing to pay.
+    pub max_fee_per_gas: U256,
+    /// Price per gas paid to the block producer on top of the base fee.
+    pub max_priority_fee_per_gas: U256,
+}
+
 /// Gas adjuster is an entity capable of scaling the gas price for
 /// all the Rootstock transactions.
 ///
@@ -20,10 +29,16 @@ mod tests;
 /// transactions only), which guarantees that we will increase the
 /// gas price for transactions that were not mined by the network
 /// within a reasonable time.
+///
+/// For the dynamic fee (EIP-1559) transactions the same limits are applied
+/// to the max fee per gas, which is calculated from the base fee tracked
+/// over the recent block headers.
 #[derive(Debug)]
 pub(super) struct GasAdjuster<DB: DatabaseInterface> {
     /// Collected statistics about recently used gas prices.
     statistics: GasStatistics,
+    /// Base fees observed in the recent block headers.
+    base_fees: VecDeque<U256>,
     /// Timestamp of the last maximum gas price update.
     last_price_renewal: Instant,
     /// Timestamp of the last sample added to the `statistics`.
@@ -43,6 +58,7 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             .expect("Can't load the gas price limit");
         Self {
             statistics: GasStatistics::new(gas_price_limit),
+            base_fees: VecDeque::new(),
             last_price_renewal: Instant::now(),
             last_sample_added: Instant::now(),
 
@@ -95,6 +111,75 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
         Ok(price)
     }
 
+    /// Calculates the fee parameters for the dynamic fee (EIP-1559) transaction.
+    ///
+    /// Priority fee is the one suggested by the network, bounded by the configured policy.
+    /// Max fee is the highest base fee of the recent blocks scaled by the configured multiplier
+    /// plus the priority fee. For the replacement of the stuck tx both values are increased
+    /// by at least 15%, since the replacement must bump both of them to be accepted.
+    pub async fn get_dynamic_gas_price(
+        &mut self,
+        rootstock: &RootstockGateway,
+        old_tx_price: Option<DynamicGasPrice>,
+    ) -> anyhow::Result<DynamicGasPrice> {
+        let policy = parameters::dynamic_fee_policy();
+
+        let latest_base_fee = rootstock
+            .get_base_fee()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Network does not support dynamic fee transactions"))?;
+        self.add_base_fee_sample(latest_base_fee, policy.base_fee_samples);
+        let base_fee = self.get_base_fee_estimate();
+
+        // Legacy gas price suggested by the network includes the suggested priority fee.
+        let network_price = rootstock.get_gas_price().await?;
+        let mut priority_fee = network_price
+            .saturating_sub(latest_base_fee)
+            .max(policy.min_priority_fee.into())
+            .min(policy.max_priority_fee.into());
+        if let Some(old_price) = old_tx_price {
+            // Stuck transaction, scale it up.
+            priority_fee = self.scale_up(old_price.max_priority_fee_per_gas, priority_fee);
+        }
+
+        let mut max_fee = scale_by_factor(base_fee, policy.base_fee_multiplier) + priority_fee;
+        if let Some(old_price) = old_tx_price {
+            max_fee = self.scale_up(old_price.max_fee_per_gas, max_fee);
+        }
+        // Now, cut the price if it's too big.
+        let max_fee = self.limit_max(max_fee);
+        let priority_fee = std::cmp::min(priority_fee, max_fee);
+
+        if max_fee == self.get_current_max_price() {
+            vlog::warn!(
+                "Maximum possible max fee per gas will be used: <{}>",
+                max_fee
+            );
+        }
+
+        // Report the expected effective price to be gathered by the statistics module.
+        self.statistics
+            .add_sample(std::cmp::min(latest_base_fee + priority_fee, max_fee));
+
+        Ok(DynamicGasPrice {
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: priority_fee,
+        })
+    }
+
+    fn add_base_fee_sample(&mut self, base_fee: U256, samples_amount: usize) {
+        self.base_fees.push_back(base_fee);
+        while self.base_fees.len() > samples_amount.max(1) {
+            self.base_fees.pop_front();
+        }
+    }
+
+    /// Returns the highest base fee within the recent blocks, so the transaction
+    /// doesn't become underpriced right after a short-term base fee drop.
+    fn get_base_fee_estimate(&self) -> U256 {
+        self.base_fees.iter().copied().max().unwrap_or_default()
+    }
+
     /// Performs an actualization routine for `GasAdjuster`:
     /// This method is intended to be invoked periodically, and it updates the
     /// current max gas price limit according to the configurable update interval.
@@ -111,6 +196,18 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
                     vlog::warn!("Cannot add the sample gas price: {}", err);
                 }
             }
+
+            // Track the base fee as well if the network supports dynamic fee transactions.
+            match rootstock.get_base_fee().await {
+                Ok(Some(base_fee)) => {
+                    let samples_amount = parameters::dynamic_fee_policy().base_fee_samples;
+                    self.add_base_fee_sample(base_fee, samples_amount);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    vlog::warn!("Cannot add the sample base fee: {}", err);
+                }
+            }
         }
 
         if self.last_price_renewal.elapsed() >= parameters::limit_update_interval() {
@@ -207,20 +304,9 @@ impl GasStatistics {
             return;
         }
 
-        // Since `U256` cannot be multiplied by `f64`, we replace this operation
-        // with two:
-        // Instead of `a` * `b`, we do `a` * `U256::from(b * 100)` / `U256::from(100)`.
-        //
-        // This approach assumes that the scale factor is not too precise, e.g. `1.5` or `5.0`,
-        // but not `3.14159265`.
-        let multiplier = (scale_factor * 100.0f64).round() as u64;
-        let multiplier = U256::from(multiplier);
-
-        let divider = U256::from(100);
-
         let average_price = self.current_sum / self.samples.len();
 
-        self.current_max_price = average_price * multiplier / divider;
+        self.current_max_price = scale_by_factor(average_price, scale_factor);
     }
 
     pub fn get_average_price(&self) -> Option<U256> {
@@ -235,3 +321,19 @@ impl GasStatistics {
         self.current_max_price
     }
 }
+
+/// Multiplies the value by the floating point factor.
+fn scale_by_factor(value: U256, factor: f64) -> U256 {
+    // Since `U256` cannot be multiplied by `f64`, we replace this operation
+    // with two:
+    // Instead of `a` * `b`, we do `a` * `U256::from(b * 100)` / `U256::from(100)`.
+    //
+    // This approach assumes that the scale factor is not too precise, e.g. `1.5` or `5.0`,
+    // but not `3.14159265`.
+    let multiplier = (factor * 100.0f64).round() as u64;
+    let multiplier = U256::from(multiplier);
+
+    let divider = U256::from(100);
+
+    value * multiplier / divider
+}
@@ -5,6 +5,8 @@
 //!   gas price suggested by `GasAdjuster`.
 //! - Maximum gas price scale: multiplier to be applied to the average gas price to
 //!   calculate the upper limit for gas price in `GasAdjuster`.
+//! - Dynamic fee policy: base fee tracking and priority fee bounds for the
+//!   dynamic fee (EIP-1559) transactions.
 //!
 //! The module uses a child module `parameters_impl` which contains two implementations
 //! for functions declared in module: one for the actual usage, and one for tests.
@@ -13,6 +15,8 @@
 
 // Built-in deps.
 use std::time::Duration;
+// Workspace deps
+use zksync_config::configs::eth_sender::DynamicFee;
 
 /// Obtains the interval for renewing the maximum gas price.
 ///
@@ -41,13 +45,22 @@ pub fn sample_adding_interval() -> Duration {
     parameters_impl::sample_adding_interval()
 }
 
+/// Obtains the policy for the dynamic fee (EIP-1559) transactions.
+///
+/// This value is not cached internally, as it may be changed for the already running
+/// server by an administrator. This may be required if existing settings aren't flexible
+/// enough to match the current network price.
+pub fn dynamic_fee_policy() -> DynamicFee {
+    parameters_impl::dynamic_fee_policy()
+}
+
 // Actual methods implementation for non-test purposes.
 #[cfg(not(test))]
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
     // Workspace deps
-    use zksync_config::configs::eth_sender::ETHSenderConfig;
+    use zksync_config::configs::eth_sender::{DynamicFee, ETHSenderConfig};
 
     /// Obtains the interval for renewing the maximum gas price.
     ///
@@ -78,6 +91,16 @@ mod parameters_impl {
         let config = ETHSenderConfig::from_env();
         config.gas_price_limit.sample_interval()
     }
+
+    /// Obtains the policy for the dynamic fee (EIP-1559) transactions.
+    ///
+    /// This value is not cached internally, as it may be changed for the already running
+    /// server by an administrator. This may be required if existing settings aren't flexible
+    /// enough to match the current network price.
+    pub fn dynamic_fee_policy() -> DynamicFee {
+        let config = ETHSenderConfig::from_env();
+        config.dynamic_fee
+    }
 }
 
 // Hard-coded implementation for tests.
@@ -85,6 +108,8 @@ mod parameters_impl {
 mod parameters_impl {
     // Built-in deps.
     use std::time::Duration;
+    // Workspace deps
+    use zksync_config::configs::eth_sender::DynamicFee;
 
     /// `limit_update_interval` version for tests not looking for an environment variable value
     /// but using a zero interval instead.
@@ -103,4 +128,17 @@ mod parameters_impl {
     pub fn sample_adding_interval() -> Duration {
         Duration::from_secs(0)
     }
+
+    /// `dynamic_fee_policy` version for tests not looking for an environment variable value
+    /// but using a fixed policy instead: 3 base fee samples, 2x base fee multiplier,
+    /// priority fee between 10 and 100 wei.
+    pub fn dynamic_fee_policy() -> DynamicFee {
+        DynamicFee {
+            enabled: true,
+            base_fee_samples: 3,
+            base_fee_multiplier: 2.0f64,
+            min_priority_fee: 10,
+            max_priority_fee: 100,
+        }
+    }
 }
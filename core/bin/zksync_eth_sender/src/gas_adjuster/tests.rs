@@ -3,7 +3,7 @@
 use zksync_basic_types::U256;
 // Local uses
 use crate::{
-    gas_adjuster::{parameters::limit_scale_factor, GasStatistics},
+    gas_adjuster::{parameters::limit_scale_factor, DynamicGasPrice, GasStatistics},
     tests::mock::{default_eth_sender, MockDatabase},
     DatabaseInterface, GasAdjuster,
 };
@@ -378,3 +378,112 @@ async fn gas_price_limit_preservation() {
         assert_eq!(new_limit, price_limit.into());
     }
 }
+
+/// Sets the base fee and the gas price suggested by the Rootstock client.
+async fn set_network_fees(rootstock: &mut RootstockGateway, base_fee: u64, gas_price: u64) {
+    let mock = rootstock.get_mut_mock().unwrap();
+    mock.set_base_fee(Some(base_fee.into())).await.unwrap();
+    mock.set_gas_price(gas_price.into()).await.unwrap();
+}
+
+/// Checks that dynamic fee cannot be calculated if the network does not report the base fee.
+#[tokio::test]
+async fn dynamic_fee_unsupported() {
+    let (rootstock, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    assert!(gas_adjuster
+        .get_dynamic_gas_price(&rootstock, None)
+        .await
+        .is_err());
+}
+
+/// Checks that priority fee is the network-suggested one bounded by the policy (10..=100 in tests),
+/// and max fee is the doubled base fee plus the priority fee.
+#[tokio::test]
+async fn dynamic_gas_price() {
+    let (mut rootstock, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    // Test vector of pairs (rootstock client price, expected priority fee) with the base fee of 100.
+    let test_vector = vec![
+        (130, 30),   // Network suggests 30 on top of the base fee.
+        (100, 10),   // No priority fee suggested, use the lower bound.
+        (1000, 100), // Suggested priority fee is too high, use the upper bound.
+    ];
+
+    for (eth_client_price, expected_priority_fee) in test_vector {
+        set_network_fees(&mut rootstock, 100, eth_client_price).await;
+
+        let price = gas_adjuster
+            .get_dynamic_gas_price(&rootstock, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            price,
+            DynamicGasPrice {
+                max_fee_per_gas: (200 + expected_priority_fee).into(),
+                max_priority_fee_per_gas: expected_priority_fee.into(),
+            }
+        );
+    }
+}
+
+/// Checks that the highest base fee of the recent blocks (3 in tests) is used for the max fee.
+#[tokio::test]
+async fn base_fee_tracking() {
+    let (mut rootstock, db) = eth_and_db_clients().await;
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+
+    // Test vector of pairs (base fee of the latest block, expected max fee), priority fee is always 30.
+    let test_vector = vec![(100, 230), (50, 230), (50, 230), (50, 130)];
+
+    for (base_fee, expected_max_fee) in test_vector {
+        set_network_fees(&mut rootstock, base_fee, base_fee + 30).await;
+
+        let price = gas_adjuster
+            .get_dynamic_gas_price(&rootstock, None)
+            .await
+            .unwrap();
+        assert_eq!(price.max_fee_per_gas, expected_max_fee.into());
+        assert_eq!(price.max_priority_fee_per_gas, 30.into());
+    }
+}
+
+/// Checks that for the stuck transaction both the max fee and the priority fee are
+/// increased by at least 15% (or set to the actual network values if they are higher).
+#[tokio::test]
+async fn dynamic_gas_price_replacement() {
+    let (mut rootstock, db) = eth_and_db_clients().await;
+
+    // Test vector of (base fee, rootstock client price, price of the last tx, expected price).
+    let test_vector = vec![
+        // Network fees are the same, increase both values by 15%.
+        ((100, 130), (230, 30), (264, 34)),
+        // Network fees have grown, use them.
+        ((200, 260), (230, 30), (460, 60)),
+        // Legacy transaction replacement: its gas price is used as both values.
+        ((100, 130), (200, 200), (430, 230)),
+    ];
+
+    for ((base_fee, eth_client_price), old_price, expected_price) in test_vector {
+        let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+        set_network_fees(&mut rootstock, base_fee, eth_client_price).await;
+
+        let old_price = DynamicGasPrice {
+            max_fee_per_gas: old_price.0.into(),
+            max_priority_fee_per_gas: old_price.1.into(),
+        };
+        let price = gas_adjuster
+            .get_dynamic_gas_price(&rootstock, Some(old_price))
+            .await
+            .unwrap();
+        assert_eq!(
+            price,
+            DynamicGasPrice {
+                max_fee_per_gas: expected_price.0.into(),
+                max_priority_fee_per_gas: expected_price.1.into(),
+            }
+        );
+    }
+}
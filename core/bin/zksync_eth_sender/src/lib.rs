@@ -14,22 +14,22 @@ use web3::{
     types::{TransactionReceipt, H256, U256},
 };
 // Workspace uses
-use zksync_config::ETHSenderConfig;
+use zksync_config::{ETHSenderConfig, NotifierConfig};
 use zksync_eth_client::{RootstockGateway, SignedCallResult};
+use zksync_notifier::{Notification, Notifier};
 use zksync_storage::ConnectionPool;
 use zksync_types::rootstock::ETHOperation;
 // Local uses
 use self::{
+    balance_watchdog::BalanceWatchdog,
     database::{Database, DatabaseInterface},
-    gas_adjuster::GasAdjuster,
+    gas_adjuster::{DynamicGasPrice, GasAdjuster},
     transactions::*,
     tx_queue::{TxData, TxQueue, TxQueueBuilder},
 };
-use zksync_types::{
-    aggregated_operations::{AggregatedActionType, AggregatedOperation},
-    gas_counter::GasCounter,
-};
+use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 
+mod balance_watchdog;
 mod database;
 mod gas_adjuster;
 mod transactions;
@@ -121,10 +121,17 @@ struct ETHSender<DB: DatabaseInterface> {
     gas_adjuster: GasAdjuster<DB>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
+    /// Operator alerts about the stuck transactions.
+    notifier: Notifier,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
-    pub async fn new(options: ETHSenderConfig, db: DB, rootstock: RootstockGateway) -> Self {
+    pub async fn new(
+        options: ETHSenderConfig,
+        db: DB,
+        rootstock: RootstockGateway,
+        notifier: Notifier,
+    ) -> Self {
         let mut connection = db
             .acquire_connection()
             .await
@@ -179,6 +186,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             tx_queue,
             gas_adjuster,
             options,
+            notifier,
         }
     }
 
@@ -338,10 +346,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     /// Stores the new operation in the database and sends the corresponding transaction.
     async fn initialize_operation(&mut self, tx: TxData, current_block: u64) -> anyhow::Result<()> {
         let deadline_block = self.get_deadline_block(current_block);
-        let gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.rootstock, None)
-            .await?;
+        let (gas_price, priority_fee) = self.get_gas_price(None).await?;
         //TODO RSK: let gas_price = U256::from("1000000000");
         let mut connection = self.db.acquire_connection().await?;
         let mut transaction = connection.start_transaction().await?;
@@ -358,6 +363,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     Some(tx.operation.clone()),
                     deadline_block as i64,
                     gas_price,
+                    priority_fee,
                     tx.raw.clone(),
                 )
                 .await?;
@@ -369,6 +375,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 nonce: assigned_data.nonce,
                 last_deadline_block: deadline_block,
                 last_used_gas_price: gas_price,
+                last_used_priority_fee: priority_fee,
                 used_tx_hashes: vec![], // No hash yet, will be added below.
                 encoded_tx_data: tx.raw,
                 confirmed: false,
@@ -414,9 +421,13 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     fn eth_tx_description(&self, tx: &SignedCallResult) -> String {
         // Gas price in gwei (wei / 10^9).
         let gas_price = tx.gas_price / (1_000_000_000);
+        let priority_fee = match tx.max_priority_fee_per_gas {
+            Some(fee) => format!("; priority fee: {} gwei", fee / (1_000_000_000)),
+            None => String::new(),
+        };
         format!(
-            "<hash: {:#x}; gas price: {} gwei; nonce: {}>",
-            tx.hash, gas_price, tx.nonce
+            "<hash: {:#x}; gas price: {} gwei{}; nonce: {}>",
+            tx.hash, gas_price, priority_fee, tx.nonce
         )
     }
 
@@ -545,6 +556,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 op.id,
                 deadline_block as i64,
                 new_tx.gas_price,
+                new_tx.max_priority_fee_per_gas,
             )
             .await?;
         self.db
@@ -559,6 +571,15 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         self.rootstock.send_raw_tx(new_tx.raw_tx).await?;
         transaction.commit().await?;
 
+        let notification = Notification::EthTxStuck {
+            eth_op_id: op.id,
+            tx_hash: new_tx.hash,
+            gas_price: new_tx.gas_price,
+        };
+        if let Err(err) = self.notifier.send(notification).await {
+            vlog::error!("Unable to send the stuck transaction alert: {}", err);
+        }
+
         metrics::histogram!("eth_sender.perform_commitment_step", start.elapsed());
         Ok(OperationCommitment::Pending)
     }
@@ -646,7 +667,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         let tx_options = {
             // We set the gas limit for commit / verify operations as pre-calculated estimation.
             // This estimation is a higher bound based on a pre-calculated cost of every operation in the block.
-            let gas_limit = Self::gas_limit_for_op(op);
+            let gas_limit = op.gas_limit();
 
             assert!(
                 gas_limit > 0.into(),
@@ -664,8 +685,8 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 nonce: Some(op.nonce),
                 gas_price: Some(op.last_used_gas_price),
                 gas: Some(gas_limit),
-                max_fee_per_gas: None,
-                max_priority_fee_per_gas: None,
+                max_fee_per_gas: op.last_used_priority_fee.map(|_| op.last_used_gas_price),
+                max_priority_fee_per_gas: op.last_used_priority_fee,
                 ..Default::default()
             }
         };
@@ -677,28 +698,6 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         Ok(signed_tx)
     }
 
-    /// Calculates the gas limit for transaction to be send, depending on the type of operation.
-    fn gas_limit_for_op(op: &ETHOperation) -> U256 {
-        let (_, op) = op
-            .op
-            .as_ref()
-            .expect("Operation not found - can't compute gas limit");
-        match op {
-            AggregatedOperation::CommitBlocks(commit) => {
-                GasCounter::commit_gas_limit_aggregated(&commit.blocks)
-            }
-            AggregatedOperation::ExecuteBlocks(execute) => {
-                GasCounter::execute_gas_limit_aggregated(&execute.blocks)
-            }
-            AggregatedOperation::PublishProofBlocksOnchain(_) => {
-                U256::from(GasCounter::BASE_PROOF_BLOCKS_TX_COST)
-            }
-            AggregatedOperation::CreateProofBlocks(_) => {
-                panic!("Can't compute gas limit for CreateProofBlocks")
-            }
-        }
-    }
-
     /// Creates a new transaction for the existing Rootstock operation.
     /// This method is used to create supplement transactions instead of the stuck one.
     async fn create_supplement_tx(
@@ -713,6 +712,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
         stuck_tx.last_deadline_block = deadline_block;
         stuck_tx.last_used_gas_price = signed_tx.gas_price;
+        stuck_tx.last_used_priority_fee = signed_tx.max_priority_fee_per_gas;
         stuck_tx.used_tx_hashes.push(signed_tx.hash);
 
         Ok(signed_tx)
@@ -726,12 +726,9 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     ) -> anyhow::Result<Options> {
         let old_tx_gas_price = stuck_tx.last_used_gas_price;
 
-        let new_gas_price = self
-            .gas_adjuster
-            .get_gas_price(&self.rootstock, Some(old_tx_gas_price))
-            .await?;
+        let (new_gas_price, new_priority_fee) = self.get_gas_price(Some(stuck_tx)).await?;
         let nonce = stuck_tx.nonce;
-        let gas_limit = Self::gas_limit_for_op(stuck_tx);
+        let gas_limit = stuck_tx.gas_limit();
 
         assert!(
             gas_limit > 0.into(),
@@ -740,21 +737,57 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         );
 
         vlog::info!(
-            "Replacing tx: hash: {:#x}, old_gas: {}, new_gas: {}, used nonce: {}, gas limit: {}",
+            "Replacing tx: hash: {:#x}, old_gas: {}, new_gas: {}, old_priority_fee: {:?}, new_priority_fee: {:?}, used nonce: {}, gas limit: {}",
             stuck_tx.used_tx_hashes.last().unwrap(),
             old_tx_gas_price,
             new_gas_price,
+            stuck_tx.last_used_priority_fee,
+            new_priority_fee,
             nonce,
             gas_limit,
         );
 
         Ok(Options::with(move |opt| {
             opt.gas_price = Some(new_gas_price);
+            opt.max_fee_per_gas = new_priority_fee.map(|_| new_gas_price);
+            opt.max_priority_fee_per_gas = new_priority_fee;
             opt.nonce = Some(nonce);
             opt.gas = Some(gas_limit);
         }))
     }
 
+    /// Returns the gas price and the priority fee for the new transaction.
+    /// If dynamic fee (EIP-1559) transactions are enabled, the gas price is the max fee per gas,
+    /// otherwise there is no priority fee.
+    ///
+    /// For the replacement of the stuck tx the previously sent transaction must be provided.
+    async fn get_gas_price(
+        &mut self,
+        old_tx: Option<&ETHOperation>,
+    ) -> anyhow::Result<(U256, Option<U256>)> {
+        if self.options.dynamic_fee.enabled {
+            // Legacy transaction pays its gas price both as the max fee and the priority fee.
+            let old_tx_price = old_tx.map(|op| DynamicGasPrice {
+                max_fee_per_gas: op.last_used_gas_price,
+                max_priority_fee_per_gas: op
+                    .last_used_priority_fee
+                    .unwrap_or(op.last_used_gas_price),
+            });
+            let price = self
+                .gas_adjuster
+                .get_dynamic_gas_price(&self.rootstock, old_tx_price)
+                .await?;
+            Ok((price.max_fee_per_gas, Some(price.max_priority_fee_per_gas)))
+        } else {
+            let old_tx_gas_price = old_tx.map(|op| op.last_used_gas_price);
+            let gas_price = self
+                .gas_adjuster
+                .get_gas_price(&self.rootstock, old_tx_gas_price)
+                .await?;
+            Ok((gas_price, None))
+        }
+    }
+
     /// Encodes the operation data to the Ethereum tx payload (not signs it!).
     fn operation_to_raw_tx(&self, op: &AggregatedOperation) -> Vec<u8> {
         match op {
@@ -806,12 +839,43 @@ pub fn run_eth_sender(
     pool: ConnectionPool,
     eth_gateway: RootstockGateway,
     options: ETHSenderConfig,
+    notifier_config: NotifierConfig,
 ) -> JoinHandle<()> {
     let db = Database::new(pool);
 
     tokio::spawn(async move {
-        let eth_sender = ETHSender::new(options, db, eth_gateway).await;
+        let eth_sender = ETHSender::new(
+            options,
+            db,
+            eth_gateway,
+            Notifier::from_config(&notifier_config),
+        )
+        .await;
 
         eth_sender.run().await
     })
 }
+
+/// Runs the operator balance watchdog, which alerts when the operator account
+/// is about to run out of funds to pay for the sent transactions.
+#[must_use]
+pub fn run_balance_watchdog(
+    pool: ConnectionPool,
+    eth_gateway: RootstockGateway,
+    options: ETHSenderConfig,
+    notifier_config: NotifierConfig,
+) -> JoinHandle<()> {
+    let db = Database::new(pool);
+
+    tokio::spawn(async move {
+        let watchdog = BalanceWatchdog::new(
+            db,
+            eth_gateway,
+            options.sender.operator_commit_eth_addr,
+            options.balance_watchdog,
+            Notifier::from_config(&notifier_config),
+        );
+
+        watchdog.run().await
+    })
+}
@@ -8,8 +8,11 @@ use tokio::sync::RwLock;
 use web3::contract::Options;
 use zksync_basic_types::{BlockNumber, H256, U256};
 // Workspace uses
-use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
+use zksync_config::configs::eth_sender::{
+    BalanceWatchdog, DynamicFee, ETHSenderConfig, GasLimit, Sender,
+};
 use zksync_eth_client::RootstockGateway;
+use zksync_notifier::Notifier;
 use zksync_storage::{rootstock::records::ETHParams, StorageProcessor};
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::rootstock::{ETHOperation, EthOpId, InsertedOperationResponse};
@@ -204,6 +207,7 @@ impl DatabaseInterface for MockDatabase {
         op: Option<(i64, AggregatedOperation)>,
         deadline_block: i64,
         used_gas_price: U256,
+        used_priority_fee: Option<U256>,
         encoded_tx_data: Vec<u8>,
     ) -> anyhow::Result<InsertedOperationResponse> {
         let mut eth_operations = self.eth_operations.write().await;
@@ -218,6 +222,7 @@ impl DatabaseInterface for MockDatabase {
             nonce: nonce.into(),
             last_deadline_block: deadline_block as u64,
             last_used_gas_price: used_gas_price,
+            last_used_priority_fee: used_priority_fee,
             used_tx_hashes: vec![],
             encoded_tx_data,
             confirmed: false,
@@ -261,6 +266,7 @@ impl DatabaseInterface for MockDatabase {
         eth_op_id: EthOpId,
         new_deadline_block: i64,
         new_gas_value: U256,
+        new_priority_fee: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let eth_op = eth_operations
@@ -270,6 +276,7 @@ impl DatabaseInterface for MockDatabase {
         if let Some(eth_op) = eth_op {
             eth_op.last_deadline_block = new_deadline_block as u64;
             eth_op.last_used_gas_price = new_gas_value;
+            eth_op.last_used_priority_fee = new_priority_fee;
         } else {
             panic!("Attempt to update tx that is not unconfirmed");
         }
@@ -355,6 +362,31 @@ impl DatabaseInterface for MockDatabase {
 
         Ok(confirmed)
     }
+
+    async fn load_average_block_gas_limit(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        blocks_count: u32,
+    ) -> anyhow::Result<Option<U256>> {
+        let aggregated_operations = self.aggregated_operations.read().await;
+        let mut blocks: Vec<_> = aggregated_operations
+            .iter()
+            .flat_map(|(_, op)| match op {
+                AggregatedOperation::CommitBlocks(commit) => commit.blocks.as_slice(),
+                _ => &[],
+            })
+            .collect();
+        blocks.sort_by_key(|block| std::cmp::Reverse(block.block_number));
+        blocks.truncate(blocks_count as usize);
+
+        if blocks.is_empty() {
+            return Ok(None);
+        }
+        let total_gas_limit = blocks.iter().fold(U256::zero(), |acc, block| {
+            acc + block.commit_gas_limit + block.verify_gas_limit
+        });
+        Ok(Some(total_gas_limit / blocks.len()))
+    }
 }
 
 /// Creates a default `ETHParams` for use by mock `ETHSender` .
@@ -384,6 +416,22 @@ pub(crate) async fn default_eth_sender() -> ETHSender<MockDatabase> {
     .await
 }
 
+/// Creates an `ETHSender` with mock Rootstock connection/database and no operations in DB
+/// which sends dynamic fee (EIP-1559) transactions. Base fee reported by the network is 100.
+pub(crate) async fn dynamic_fee_eth_sender() -> ETHSender<MockDatabase> {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.options.dynamic_fee.enabled = true;
+    eth_sender
+        .rootstock
+        .get_mut_mock()
+        .unwrap()
+        .set_base_fee(Some(100.into()))
+        .await
+        .unwrap();
+
+    eth_sender
+}
+
 /// Creates an `ETHSender` with mock Rootstock connection/database and no operations in DB
 /// which supports multiple transactions in flight.
 /// Returns the `ETHSender` itself along with communication channels to interact with it.
@@ -454,9 +502,24 @@ async fn build_eth_sender(
             update_interval: 15,
             scale_factor: 1.0f64,
         },
+        dynamic_fee: DynamicFee {
+            enabled: false,
+            base_fee_samples: 3,
+            base_fee_multiplier: 2.0f64,
+            min_priority_fee: 10,
+            max_priority_fee: 100,
+        },
+        balance_watchdog: BalanceWatchdog {
+            enabled: false,
+            check_interval: 60,
+            blocks_sample: 10,
+            warning_runway_blocks: 1000,
+            critical_runway_blocks: 100,
+            webhook_url: String::new(),
+        },
     };
 
-    ETHSender::new(options, db, rootstock).await
+    ETHSender::new(options, db, rootstock, Notifier::default()).await
 }
 
 /// Helper method for configurable creation of `ETHSender`.
@@ -505,6 +568,7 @@ pub(crate) async fn create_signed_tx(
         nonce: signed_tx.nonce,
         last_deadline_block: deadline_block,
         last_used_gas_price: signed_tx.gas_price,
+        last_used_priority_fee: signed_tx.max_priority_fee_per_gas,
         used_tx_hashes: vec![signed_tx.hash],
         encoded_tx_data: raw_tx,
         confirmed: false,
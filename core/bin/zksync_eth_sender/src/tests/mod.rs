@@ -1,7 +1,7 @@
 // Local uses
 use self::mock::{
     complete_withdrawals_eth_sender, concurrent_eth_sender, create_signed_tx,
-    default_eth_parameters, default_eth_sender, restored_eth_sender,
+    default_eth_parameters, default_eth_sender, dynamic_fee_eth_sender, restored_eth_sender,
 };
 use super::{transactions::TxCheckOutcome, ETHSender, TxCheckMode};
 use web3::types::U64;
@@ -410,6 +410,59 @@ async fn stuck_transaction() {
     eth_sender.db.assert_confirmed(&stuck_tx).await;
 }
 
+/// Checks that with dynamic fees enabled:
+/// - A transaction is sent with the max fee and the priority fee calculated from the base fee.
+/// - It is not processed after some blocks.
+/// - `ETHSender` creates a new transaction with both fees increased.
+#[tokio::test]
+async fn dynamic_fee_stuck_transaction() {
+    let mut eth_sender = dynamic_fee_eth_sender().await;
+
+    let aggregated_operation = test_data::commit_blocks_operation(0);
+    eth_sender
+        .db
+        .send_aggregated_operation(aggregated_operation)
+        .await
+        .unwrap();
+
+    eth_sender.load_new_operations().await.unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    // Network suggests no priority fee (the gas price is equal to the base fee),
+    // so the minimal one is used: max fee is 2 * 100 + 10.
+    let eth_op = eth_sender.ongoing_ops.front().unwrap();
+    assert_eq!(eth_op.last_used_gas_price, 210.into());
+    assert_eq!(eth_op.last_used_priority_fee, Some(10.into()));
+
+    // Skip some blocks and expect sender to send a new tx.
+    let block_number = U64::from(
+        eth_sender
+            .rootstock
+            .get_mock()
+            .unwrap()
+            .block_number()
+            .await
+            .unwrap()
+            .as_u64()
+            + EXPECTED_WAIT_TIME_BLOCKS,
+    );
+    eth_sender
+        .rootstock
+        .get_mut_mock()
+        .unwrap()
+        .set_block_number(block_number)
+        .await
+        .unwrap();
+    eth_sender.proceed_next_operations(0).await;
+
+    // Both fees are increased by 15%.
+    let eth_op = eth_sender.ongoing_ops.front().unwrap();
+    assert_eq!(eth_op.used_tx_hashes.len(), 2);
+    assert_eq!(eth_op.last_used_gas_price, 241.into());
+    assert_eq!(eth_op.last_used_priority_fee, Some(11.into()));
+    eth_sender.db.assert_stored(eth_op).await;
+}
+
 /// This test verifies that with multiple operations received all-together,
 /// their order is respected and no processing of the next operation is started until
 /// the previous one is committed.
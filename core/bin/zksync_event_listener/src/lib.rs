@@ -1,28 +1,37 @@
 //! The `zksync_event_listener` is a stand-alone server-application responsible for
 //! fetching new events that happen in the zkSync network from the database
-//! and streaming them to the connected WebSocket clients.
+//! and streaming them to the connected WebSocket clients and the
+//! registered webhooks.
 
 // Built-in uses
 // Workspace uses
 use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
 // External uses
 use actix::prelude::*;
 use actix_web::{web, App, Error, HttpRequest, HttpResponse, HttpServer};
 use actix_web_actors::ws;
 // Local uses
 use listener::EventListener;
-use messages::RegisterServerHandle;
+use messages::{RegisterServerHandle, RegisterWebhookDispatcher};
 use monitor::ServerMonitor;
 use subscriber::Subscriber;
+use webhooks::WebhookDispatcher;
 
 pub mod listener;
 pub mod messages;
 pub mod monitor;
 pub mod subscriber;
+pub mod webhooks;
+
+/// Size of the connection pool used by subscribers to replay the events.
+const REPLAY_DB_POOL_SIZE: u32 = 4;
 
 #[derive(Debug)]
 struct AppState {
     server_monitor: Addr<ServerMonitor>,
+    db_pool: ConnectionPool,
+    max_replay_events: u64,
 }
 
 async fn ws_index(
@@ -30,7 +39,12 @@ async fn ws_index(
     stream: web::Payload,
     data: web::Data<AppState>,
 ) -> Result<HttpResponse, Error> {
-    ws::start(Subscriber::new(data.server_monitor.clone()), &req, stream)
+    let subscriber = Subscriber::new(
+        data.server_monitor.clone(),
+        data.db_pool.clone(),
+        data.max_replay_events,
+    );
+    ws::start(subscriber, &req, stream)
 }
 
 pub async fn run_event_server(config: ZkSyncConfig) {
@@ -39,15 +53,29 @@ pub async fn run_event_server(config: ZkSyncConfig) {
         .await
         .unwrap()
         .start();
+    let webhook_dispatcher = WebhookDispatcher::new(&config).await.unwrap().start();
+    monitor
+        .send(RegisterWebhookDispatcher(webhook_dispatcher.clone()))
+        .await
+        .unwrap();
 
+    let db_pool = ConnectionPool::new(Some(REPLAY_DB_POOL_SIZE));
+    let webhook_api_token = config.event_listener.webhook_api_token.clone();
     let state = web::Data::new(AppState {
         server_monitor: monitor.clone(),
+        db_pool: db_pool.clone(),
+        max_replay_events: config.event_listener.max_replay_events,
     });
 
     let server = HttpServer::new(move || {
         App::new()
             .app_data(state.clone())
             .route("/", web::get().to(ws_index))
+            .service(webhooks::api::api_scope(
+                db_pool.clone(),
+                webhook_dispatcher.clone(),
+                webhook_api_token.clone(),
+            ))
     })
     .bind(config.event_listener.ws_bind_addr())
     .unwrap()
@@ -4,10 +4,13 @@ use std::{convert::TryFrom, sync::Arc};
 use actix::prelude::*;
 use actix_web::dev::Server;
 // Workspace uses
-use zksync_storage::listener::notification::StorageNotification;
+use zksync_storage::{
+    listener::notification::StorageNotification, webhooks::records::StoredWebhook,
+};
 use zksync_types::event::{EventId, ZkSyncEvent};
 // Local uses
 use crate::subscriber::Subscriber;
+use crate::webhooks::WebhookDispatcher;
 
 /// Message emitted by the `EventListener` actor, indicates
 /// that an internal error ocurred and the server should stop
@@ -23,6 +26,22 @@ pub struct Shutdown;
 #[rtype(result = "()")]
 pub struct RegisterServerHandle(pub Server);
 
+/// Passes the address of the [`WebhookDispatcher`] to the monitor,
+/// which forwards the new events to it.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct RegisterWebhookDispatcher(pub Addr<WebhookDispatcher>);
+
+/// Starts the delivery of the events to the newly registered webhook.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct RegisterWebhook(pub StoredWebhook);
+
+/// Stops the delivery of the events to the removed webhook.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct RemoveWebhook(pub i64);
+
 #[derive(Debug, Message)]
 #[rtype(result = "()")]
 pub struct RegisterSubscriber(pub Addr<Subscriber>);
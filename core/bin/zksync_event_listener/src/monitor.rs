@@ -7,12 +7,14 @@ use actix_web::dev::Server;
 // Local uses
 use crate::messages::*;
 use crate::subscriber::Subscriber;
+use crate::webhooks::WebhookDispatcher;
 
 /// The actor responsible for maintaining the set of connections.
 #[derive(Debug, Default)]
 pub struct ServerMonitor {
     addrs: HashSet<Addr<Subscriber>>,
     server_handle: Option<Server>,
+    webhook_dispatcher: Option<Addr<WebhookDispatcher>>,
 }
 
 impl ServerMonitor {
@@ -59,6 +61,9 @@ impl Handler<NewEvents> for ServerMonitor {
             vlog::info!("Server monitor received empty array of events");
             return;
         }
+        if let Some(dispatcher) = &self.webhook_dispatcher {
+            dispatcher.do_send(msg.clone());
+        }
         for addr in self.addrs.iter().cloned() {
             addr.send(msg.clone())
                 .into_actor(self)
@@ -85,6 +90,14 @@ impl Handler<RegisterServerHandle> for ServerMonitor {
     }
 }
 
+impl Handler<RegisterWebhookDispatcher> for ServerMonitor {
+    type Result = ();
+
+    fn handle(&mut self, msg: RegisterWebhookDispatcher, _ctx: &mut Self::Context) {
+        self.webhook_dispatcher.replace(msg.0);
+    }
+}
+
 impl Handler<Shutdown> for ServerMonitor {
     type Result = ();
 
@@ -95,6 +108,9 @@ impl Handler<Shutdown> for ServerMonitor {
         // and only then stop the server and the context.
         let server_handle = self.server_handle.take().unwrap();
         let addrs = self.addrs.clone();
+        if let Some(dispatcher) = self.webhook_dispatcher.take() {
+            dispatcher.do_send(Shutdown);
+        }
         async move {
             // Stop accepting new connections.
             server_handle.pause().await;
@@ -3,9 +3,9 @@ use std::collections::HashMap;
 use std::fmt;
 // Workspace uses
 use zksync_storage::event::{get_event_type, EventType};
-use zksync_types::event::ZkSyncEvent;
+use zksync_types::event::{EventId, ZkSyncEvent};
 // External uses
-use serde::de::{MapAccess, Visitor};
+use serde::de::{Error, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 // Local uses
 use self::{account::AccountFilter, block::BlockFilter, transaction::TransactionFilter};
@@ -34,23 +34,65 @@ impl EventFilter {
     }
 }
 
-#[derive(Debug)]
-pub struct SubscriberFilters(HashMap<EventType, EventFilter>);
+/// Filters of the events the subscriber is interested in.
+///
+/// Filters for the event types are combined with OR: the event must match the filter
+/// for its type, any event matches if there are no type filters at all. On top of that,
+/// `all` requires the event to match every nested filter, `any` requires it to match
+/// at least one of them and `not` requires it not to match the nested filter.
+#[derive(Debug, Default)]
+pub struct SubscriberFilters {
+    types: HashMap<EventType, EventFilter>,
+    all: Vec<SubscriberFilters>,
+    any: Vec<SubscriberFilters>,
+    not: Option<Box<SubscriberFilters>>,
+}
 
 impl SubscriberFilters {
     pub fn matches(&self, event: &ZkSyncEvent) -> bool {
         let event_type = get_event_type(event);
-        match self.0.get(&event_type) {
+        let type_matches = match self.types.get(&event_type) {
             Some(filter) => filter.matches(event),
-            None => self.0.is_empty(),
-        }
+            None => self.types.is_empty(),
+        };
+        type_matches
+            && self.all.iter().all(|filters| filters.matches(event))
+            && (self.any.is_empty() || self.any.iter().any(|filters| filters.matches(event)))
+            && !self
+                .not
+                .as_ref()
+                .map_or(false, |filters| filters.matches(event))
     }
 }
 
+/// The first message sent by the client: filters of the events it's
+/// interested in and an optional cursor to resume the stream from.
+#[derive(Debug, Deserialize)]
+pub struct SubscribeRequest {
+    /// Id of the last event received by the client. If set, the events
+    /// that happened after it are replayed before the new ones.
+    #[serde(default)]
+    pub from_event_id: Option<EventId>,
+    #[serde(flatten)]
+    pub filters: SubscriberFilters,
+}
+
+/// Keys of the JSON-serialized filters.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FilterKey {
+    Account,
+    Block,
+    Transaction,
+    All,
+    Any,
+    Not,
+}
+
 struct EventFiltersVisitor;
 
 impl<'de> Visitor<'de> for EventFiltersVisitor {
-    type Value = HashMap<EventType, EventFilter>;
+    type Value = SubscriberFilters;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("map")
@@ -60,21 +102,37 @@ impl<'de> Visitor<'de> for EventFiltersVisitor {
     where
         M: MapAccess<'de>,
     {
-        let mut map = HashMap::with_capacity(access.size_hint().unwrap_or(0));
+        let mut filters = SubscriberFilters::default();
 
         while let Some(key) = access.next_key()? {
-            let value = match key {
-                EventType::Account => EventFilter::Account(access.next_value::<AccountFilter>()?),
-                EventType::Block => EventFilter::Block(access.next_value::<BlockFilter>()?),
-                EventType::Transaction => {
-                    EventFilter::Transaction(access.next_value::<TransactionFilter>()?)
+            match key {
+                FilterKey::Account => {
+                    let filter = EventFilter::Account(access.next_value::<AccountFilter>()?);
+                    filters.types.insert(EventType::Account, filter);
                 }
-            };
-
-            map.insert(key, value);
+                FilterKey::Block => {
+                    let filter = EventFilter::Block(access.next_value::<BlockFilter>()?);
+                    filters.types.insert(EventType::Block, filter);
+                }
+                FilterKey::Transaction => {
+                    let tx_filter = access.next_value::<TransactionFilter>()?;
+                    tx_filter.validate().map_err(M::Error::custom)?;
+                    filters
+                        .types
+                        .insert(EventType::Transaction, EventFilter::Transaction(tx_filter));
+                }
+                FilterKey::All => filters.all = access.next_value()?,
+                FilterKey::Any => {
+                    filters.any = access.next_value()?;
+                    if filters.any.is_empty() {
+                        return Err(M::Error::custom("`any` must contain at least one filter"));
+                    }
+                }
+                FilterKey::Not => filters.not = Some(access.next_value()?),
+            }
         }
 
-        Ok(map)
+        Ok(filters)
     }
 }
 
@@ -83,6 +141,6 @@ impl<'de> Deserialize<'de> for SubscriberFilters {
     where
         D: Deserializer<'de>,
     {
-        Ok(Self(deserializer.deserialize_map(EventFiltersVisitor)?))
+        deserializer.deserialize_map(EventFiltersVisitor)
     }
 }
@@ -3,11 +3,11 @@
 // Workspace uses
 use zksync_storage::event::EventType;
 use zksync_types::{
-    event::{account::*, block::*, test_data::*, transaction::*},
+    event::{account::*, block::*, test_data::*, transaction::*, EventId},
     AccountId, TokenId,
 };
 // Local uses
-use super::{EventFilter, SubscriberFilters};
+use super::{EventFilter, SubscribeRequest, SubscriberFilters};
 
 fn deserialize_valid(input: &str) -> SubscriberFilters {
     serde_json::from_str(input)
@@ -128,15 +128,15 @@ fn test_filters_deserialize() {
     }"#;
     let filters: SubscriberFilters = deserialize_valid(INPUT);
     assert!(matches!(
-        filters.0.get(&EventType::Account).unwrap(),
+        filters.types.get(&EventType::Account).unwrap(),
         EventFilter::Account(_)
     ));
     assert!(matches!(
-        filters.0.get(&EventType::Block).unwrap(),
+        filters.types.get(&EventType::Block).unwrap(),
         EventFilter::Block(_)
     ));
     assert!(matches!(
-        filters.0.get(&EventType::Transaction).unwrap(),
+        filters.types.get(&EventType::Transaction).unwrap(),
         EventFilter::Transaction(_)
     ));
 }
@@ -201,3 +201,145 @@ fn test_subscriber_filters() {
     assert!(filters.matches(&block_event));
     assert!(filters.matches(&tx_event));
 }
+
+/// Checks that the subscribe request is parsed with and without the cursor.
+#[test]
+fn test_subscribe_request_deserialize() {
+    let request: SubscribeRequest = serde_json::from_str(
+        r#"{
+            "from_event_id": 15,
+            "block": {
+                "status": "committed"
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(request.from_event_id, Some(EventId(15)));
+    assert!(request
+        .filters
+        .matches(&get_block_event(BlockStatus::Committed)));
+    assert!(!request
+        .filters
+        .matches(&get_block_event(BlockStatus::Finalized)));
+
+    let request: SubscribeRequest = serde_json::from_str("{}").unwrap();
+    assert_eq!(request.from_event_id, None);
+    assert!(request
+        .filters
+        .matches(&get_block_event(BlockStatus::Finalized)));
+
+    let result = serde_json::from_str::<SubscribeRequest>(
+        r#"{
+            "from_event_id": 15,
+            "blocks": {}
+        }"#,
+    );
+    assert!(result.is_err());
+}
+
+/// Checks the `all`, `any` and `not` composition of the filters.
+#[test]
+fn test_filters_composition() {
+    let committed_block = get_block_event(BlockStatus::Committed);
+    let finalized_block = get_block_event(BlockStatus::Finalized);
+    let transfer = get_transaction_event(
+        TransactionType::Transfer,
+        AccountId(1),
+        TokenId(0),
+        TransactionStatus::Committed,
+    );
+    let rejected_transfer = get_transaction_event(
+        TransactionType::Transfer,
+        AccountId(1),
+        TokenId(0),
+        TransactionStatus::Rejected,
+    );
+
+    // Any transaction which is not rejected.
+    let input = r#"{
+        "transaction": {},
+        "not": {
+            "transaction": {
+                "status": "rejected"
+            }
+        }
+    }"#;
+    let filters = deserialize_valid(input);
+    assert!(filters.matches(&transfer));
+    assert!(!filters.matches(&rejected_transfer));
+    assert!(!filters.matches(&committed_block));
+
+    // Committed blocks or transfers of the account.
+    let input = r#"{
+        "any": [
+            {
+                "block": {
+                    "status": "committed"
+                }
+            },
+            {
+                "transaction": {
+                    "types": ["Transfer"],
+                    "accounts": [1]
+                }
+            }
+        ]
+    }"#;
+    let filters = deserialize_valid(input);
+    assert!(filters.matches(&committed_block));
+    assert!(!filters.matches(&finalized_block));
+    assert!(filters.matches(&transfer));
+    assert!(filters.matches(&rejected_transfer));
+
+    // All the nested filters must match.
+    let input = r#"{
+        "all": [
+            {
+                "transaction": {
+                    "accounts": [1]
+                }
+            },
+            {
+                "not": {
+                    "transaction": {
+                        "status": "committed"
+                    }
+                }
+            }
+        ]
+    }"#;
+    let filters = deserialize_valid(input);
+    assert!(!filters.matches(&transfer));
+    assert!(filters.matches(&rejected_transfer));
+    assert!(!filters.matches(&committed_block));
+
+    const INVALID: &[&str] = &[
+        // `any` can't be empty, otherwise nothing matches.
+        r#"{
+            "any": []
+        }"#,
+        // Nested filters are validated as well.
+        r#"{
+            "not": {
+                "blocks": {}
+            }
+        }"#,
+        r#"{
+            "all": [
+                {
+                    "transaction": {
+                        "min_amount": "100",
+                        "max_amount": "10"
+                    }
+                }
+            ]
+        }"#,
+        r#"{
+            "not": []
+        }"#,
+    ];
+    for (i, input) in INVALID.iter().enumerate() {
+        let result = serde_json::from_str::<SubscriberFilters>(input);
+        assert!(result.is_err(), "Input #{} is supposed to be invalid", i);
+    }
+}
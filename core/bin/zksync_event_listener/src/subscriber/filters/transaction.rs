@@ -3,22 +3,49 @@ use std::collections::HashSet;
 // External uses
 use serde::Deserialize;
 // Workspace uses
+use zksync_crypto::params::MIN_NFT_TOKEN_ID;
 use zksync_types::{
     event::{transaction::*, EventData, ZkSyncEvent},
-    AccountId, TokenId,
+    AccountId, Address, TokenId,
 };
+use zksync_utils::BigUintSerdeWrapper;
 // Local uses
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TransactionFilter {
     pub types: Option<HashSet<TransactionType>>,
     pub accounts: Option<HashSet<AccountId>>,
     pub tokens: Option<HashSet<TokenId>>,
     pub status: Option<TransactionStatus>,
+    /// Minimal amount of `Transfer` and `Withdraw` transactions, other
+    /// transactions don't match if this field is set.
+    pub min_amount: Option<BigUintSerdeWrapper>,
+    /// Maximal amount of `Transfer` and `Withdraw` transactions, other
+    /// transactions don't match if this field is set.
+    pub max_amount: Option<BigUintSerdeWrapper>,
+    /// Tokens the fee is paid in. Priority operations don't have a fee.
+    pub fee_tokens: Option<HashSet<TokenId>>,
+    /// If `true`, only NFT operations match, if `false`, only fungible ones.
+    pub nft: Option<bool>,
+    /// Addresses of the recipients of the transactions.
+    pub counterparties: Option<HashSet<Address>>,
 }
 
 impl TransactionFilter {
+    /// Checks that the filter can match at least some transactions.
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(min_amount), Some(max_amount)) = (&self.min_amount, &self.max_amount) {
+            if min_amount.0 > max_amount.0 {
+                return Err(format!(
+                    "min_amount {} is greater than max_amount {}",
+                    min_amount.0, max_amount.0
+                ));
+            }
+        }
+        Ok(())
+    }
+
     pub fn matches(&self, event: &ZkSyncEvent) -> bool {
         let tx_event = match &event.data {
             EventData::Transaction(tx_event) => tx_event,
@@ -47,15 +74,109 @@ impl TransactionFilter {
                 return false;
             }
         }
+        if self.min_amount.is_some() || self.max_amount.is_some() {
+            let amount = match tx_amount(tx_event) {
+                Some(amount) => amount,
+                None => return false,
+            };
+            if matches!(&self.min_amount, Some(min_amount) if amount.0 < min_amount.0) {
+                return false;
+            }
+            if matches!(&self.max_amount, Some(max_amount) if amount.0 > max_amount.0) {
+                return false;
+            }
+        }
+        if let Some(fee_tokens) = &self.fee_tokens {
+            match tx_fee_token(tx_event) {
+                Some(fee_token) if fee_tokens.contains(&fee_token) => {}
+                _ => return false,
+            }
+        }
+        if let Some(nft) = self.nft {
+            if is_nft_operation(tx_event) != nft {
+                return false;
+            }
+        }
+        if let Some(counterparties) = &self.counterparties {
+            match tx_counterparty(tx_event) {
+                Some(address) if counterparties.contains(&address) => {}
+                _ => return false,
+            }
+        }
         true
     }
 }
 
+/// Returns the amount of `Transfer` and `Withdraw` transactions.
+fn tx_amount(tx_event: &TransactionEvent) -> Option<BigUintSerdeWrapper> {
+    match tx_event.tx_type() {
+        TransactionType::Transfer | TransactionType::Withdraw => {
+            serde_json::from_value(tx_event.tx["amount"].clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Returns the token the fee is paid in, `None` for priority operations.
+fn tx_fee_token(tx_event: &TransactionEvent) -> Option<TokenId> {
+    let field = match tx_event.tx_type() {
+        TransactionType::Transfer | TransactionType::Withdraw | TransactionType::ForcedExit => {
+            "token"
+        }
+        TransactionType::WithdrawNFT
+        | TransactionType::MintNFT
+        | TransactionType::Swap
+        | TransactionType::ChangePubKey => "feeToken",
+        TransactionType::Deposit | TransactionType::FullExit => return None,
+    };
+    serde_json::from_value(tx_event.tx[field].clone()).ok()
+}
+
+fn is_nft_operation(tx_event: &TransactionEvent) -> bool {
+    match tx_event.tx_type() {
+        TransactionType::MintNFT | TransactionType::WithdrawNFT => true,
+        _ => *tx_event.token_id >= MIN_NFT_TOKEN_ID,
+    }
+}
+
+/// Returns the address receiving the funds, `None` for the transactions without one.
+fn tx_counterparty(tx_event: &TransactionEvent) -> Option<Address> {
+    let field = match tx_event.tx_type() {
+        TransactionType::Transfer | TransactionType::Withdraw | TransactionType::WithdrawNFT => {
+            "to"
+        }
+        TransactionType::ForcedExit => "target",
+        TransactionType::MintNFT => "recipient",
+        _ => return None,
+    };
+    serde_json::from_value(tx_event.tx[field].clone()).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num::BigUint;
+    use serde_json::json;
     use zksync_types::event::test_data::get_transaction_event;
 
+    /// Constructs committed transaction event with the given serialized transaction.
+    fn get_event_with_tx(
+        tx_type: TransactionType,
+        token_id: TokenId,
+        tx: serde_json::Value,
+    ) -> ZkSyncEvent {
+        let mut event = get_transaction_event(
+            tx_type,
+            AccountId(1),
+            token_id,
+            TransactionStatus::Committed,
+        );
+        if let EventData::Transaction(tx_event) = &mut event.data {
+            tx_event.tx = tx;
+        }
+        event
+    }
+
     #[test]
     fn test_transaction_filter() {
         // Match all events.
@@ -64,6 +185,7 @@ mod tests {
             accounts: None,
             tokens: None,
             status: None,
+            ..Default::default()
         };
 
         let event = get_transaction_event(
@@ -142,4 +264,73 @@ mod tests {
             assert!(tx_filter.matches(&event));
         }
     }
+
+    #[test]
+    fn test_transaction_filter_tx_fields() {
+        let recipient = Address::repeat_byte(0x11);
+        let transfer = get_event_with_tx(
+            TransactionType::Transfer,
+            TokenId(1),
+            json!({ "to": recipient, "token": 1, "amount": "500" }),
+        );
+        let mint_nft = get_event_with_tx(
+            TransactionType::MintNFT,
+            TokenId(2),
+            json!({ "recipient": recipient, "feeToken": 2 }),
+        );
+        let deposit = get_event_with_tx(TransactionType::Deposit, TokenId(1), json!({}));
+
+        // Amount range.
+        let mut tx_filter = TransactionFilter {
+            min_amount: Some(BigUint::from(100u32).into()),
+            max_amount: Some(BigUint::from(500u32).into()),
+            ..Default::default()
+        };
+        assert!(tx_filter.matches(&transfer));
+        // Transactions without amount don't match.
+        assert!(!tx_filter.matches(&mint_nft));
+        assert!(!tx_filter.matches(&deposit));
+        tx_filter.max_amount = Some(BigUint::from(499u32).into());
+        assert!(!tx_filter.matches(&transfer));
+        tx_filter.min_amount = Some(BigUint::from(501u32).into());
+        assert!(tx_filter.validate().is_err());
+
+        // Fee tokens.
+        let tx_filter = TransactionFilter {
+            fee_tokens: Some([TokenId(2)].iter().copied().collect()),
+            ..Default::default()
+        };
+        assert!(!tx_filter.matches(&transfer));
+        assert!(tx_filter.matches(&mint_nft));
+        assert!(!tx_filter.matches(&deposit));
+
+        // NFT vs fungible.
+        let mut tx_filter = TransactionFilter {
+            nft: Some(true),
+            ..Default::default()
+        };
+        let nft_transfer = get_event_with_tx(
+            TransactionType::Transfer,
+            TokenId(MIN_NFT_TOKEN_ID),
+            json!({ "to": recipient, "token": MIN_NFT_TOKEN_ID, "amount": "1" }),
+        );
+        assert!(tx_filter.matches(&mint_nft));
+        assert!(tx_filter.matches(&nft_transfer));
+        assert!(!tx_filter.matches(&transfer));
+        tx_filter.nft = Some(false);
+        assert!(!tx_filter.matches(&mint_nft));
+        assert!(!tx_filter.matches(&nft_transfer));
+        assert!(tx_filter.matches(&transfer));
+
+        // Counterparties.
+        let mut tx_filter = TransactionFilter {
+            counterparties: Some([recipient].iter().copied().collect()),
+            ..Default::default()
+        };
+        assert!(tx_filter.matches(&transfer));
+        assert!(tx_filter.matches(&mint_nft));
+        assert!(!tx_filter.matches(&deposit));
+        tx_filter.counterparties = Some([Address::repeat_byte(0x22)].iter().copied().collect());
+        assert!(!tx_filter.matches(&transfer));
+    }
 }
@@ -1,14 +1,24 @@
 // Built-in uses
+use std::{convert::TryFrom, sync::Arc};
 // External uses
 use actix::prelude::*;
 use actix_web_actors::ws;
+use serde::Serialize;
 // Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::event::{EventId, ZkSyncEvent};
 // Local uses
 use crate::messages::{NewEvents, RegisterSubscriber, RemoveSubscriber, Shutdown};
 use crate::monitor::ServerMonitor;
-use filters::SubscriberFilters;
+use filters::{SubscribeRequest, SubscriberFilters};
 
-mod filters;
+pub(crate) mod filters;
+
+/// Message sent to the client if its subscribe request is invalid.
+#[derive(Debug, Serialize)]
+struct SubscribeError {
+    error: String,
+}
 
 /// The WebSocket actor. Created for each connected client.
 #[derive(Debug)]
@@ -19,14 +29,106 @@ pub struct Subscriber {
     filters: Option<SubscriberFilters>,
     /// The address of the [`ServerMonitor`] for registering.
     monitor: Addr<ServerMonitor>,
+    /// Pool of connections to the database used for replaying the events.
+    db_pool: ConnectionPool,
+    /// Maximum number of events that can be replayed.
+    max_replay_events: u64,
+    /// New events received while the history is being replayed. They're
+    /// sent right after the history, `None` if there's no replay in progress.
+    pending_events: Option<Vec<Arc<Vec<ZkSyncEvent>>>>,
+    /// The id of the last event processed by the subscriber. Events with
+    /// lower ids are skipped, so the client never gets the same event twice.
+    last_event_id: Option<EventId>,
 }
 
 impl Subscriber {
-    pub fn new(monitor: Addr<ServerMonitor>) -> Self {
+    pub fn new(
+        monitor: Addr<ServerMonitor>,
+        db_pool: ConnectionPool,
+        max_replay_events: u64,
+    ) -> Self {
         Self {
             filters: None,
             monitor,
+            db_pool,
+            max_replay_events,
+            pending_events: None,
+            last_event_id: None,
+        }
+    }
+
+    /// Sends the events matching the subscriber's filters to the client.
+    fn send_events(&mut self, events: &[ZkSyncEvent], ctx: &mut <Self as Actor>::Context) {
+        let filters = match &self.filters {
+            Some(filters) => filters,
+            None => return,
+        };
+        for event in events {
+            if matches!(self.last_event_id, Some(id) if id >= event.id) {
+                continue;
+            }
+            self.last_event_id = Some(event.id);
+            if !filters.matches(event) {
+                continue;
+            }
+            let json = serde_json::to_string(&event).unwrap();
+            ctx.text(json);
+        }
+    }
+
+    /// Loads the events that happened after `from` and sends them to the client,
+    /// then sends the new events received in the meantime. If there are more
+    /// events than can be replayed, the connection is closed.
+    fn replay(&mut self, from: EventId, ctx: &mut <Self as Actor>::Context) {
+        self.pending_events = Some(Vec::new());
+        self.last_event_id = Some(from);
+
+        let pool = self.db_pool.clone();
+        // Load one extra event to find out whether the limit is exceeded.
+        let limit = self.max_replay_events + 1;
+        async move {
+            Ok(pool
+                .access_storage()
+                .await?
+                .event_schema()
+                .fetch_events_page(from, limit)
+                .await?
+                .into_iter()
+                .map(ZkSyncEvent::try_from)
+                .collect::<Result<_, _>>()?)
         }
+        .into_actor(self)
+        .map(|result: anyhow::Result<Vec<ZkSyncEvent>>, act, ctx| {
+            let events = match result {
+                Ok(events) if events.len() as u64 > act.max_replay_events => {
+                    let reason = Some(ws::CloseReason {
+                        code: ws::CloseCode::Policy,
+                        description: Some(format!(
+                            "from_event_id is too old, at most {} events can be replayed",
+                            act.max_replay_events
+                        )),
+                    });
+                    ctx.close(reason);
+                    return act.shutdown(ctx);
+                }
+                Ok(events) => events,
+                Err(err) => {
+                    vlog::error!("Couldn't load events to replay, reason: {}", err);
+                    let reason = Some(ws::CloseReason {
+                        code: ws::CloseCode::Error,
+                        description: Some("internal server error".to_string()),
+                    });
+                    ctx.close(reason);
+                    return act.shutdown(ctx);
+                }
+            };
+            act.send_events(&events, ctx);
+            // Switch to the new events, the ones that were already replayed are skipped.
+            for events in act.pending_events.take().unwrap_or_default() {
+                act.send_events(&events, ctx);
+            }
+        })
+        .spawn(ctx);
     }
 
     /// Remove the subscriber's address from the monitor's set and stop
@@ -89,19 +191,20 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Subscriber {
                 if self.filters.is_some() {
                     return;
                 }
-                match serde_json::from_str(&text) {
-                    Ok(filters) => {
-                        self.filters = Some(filters);
+                match serde_json::from_str::<SubscribeRequest>(&text) {
+                    Ok(request) => {
+                        self.filters = Some(request.filters);
+                        if let Some(from) = request.from_event_id {
+                            self.replay(from, ctx);
+                        }
                     }
                     Err(err) => {
-                        // The client provided invalid JSON, give
-                        // him the error message and close the connection.
-                        let reason = Some(ws::CloseReason {
-                            code: ws::CloseCode::Policy,
-                            description: Some(err.to_string()),
-                        });
-                        ctx.close(reason);
-                        self.shutdown(ctx);
+                        // The client provided invalid filters, send back the error
+                        // and keep the connection open, so it can try again.
+                        let error = SubscribeError {
+                            error: err.to_string(),
+                        };
+                        ctx.text(serde_json::to_string(&error).unwrap());
                     }
                 }
             }
@@ -132,16 +235,13 @@ impl Handler<NewEvents> for Subscriber {
     type Result = ();
 
     fn handle(&mut self, msg: NewEvents, ctx: &mut Self::Context) {
-        let filters = match &self.filters {
-            Some(filters) => filters,
-            None => return,
-        };
-        for event in msg.0.as_ref() {
-            if !filters.matches(event) {
-                continue;
-            }
-            let json = serde_json::to_string(&event).unwrap();
-            ctx.text(json);
+        if self.filters.is_none() {
+            return;
+        }
+        match &mut self.pending_events {
+            // The history is being replayed, send the events after it.
+            Some(pending_events) => pending_events.push(msg.0),
+            None => self.send_events(msg.0.as_ref(), ctx),
         }
     }
 }
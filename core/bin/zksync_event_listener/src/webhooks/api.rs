@@ -0,0 +1,228 @@
+//! REST API for managing the webhooks, every request must contain
+//! the `Authorization: Bearer <webhook_api_token>` header.
+
+// Built-in uses
+// External uses
+use actix::Addr;
+use actix_web::{
+    dev::{HttpServiceFactory, ServiceRequest},
+    error::{ErrorBadRequest, ErrorInternalServerError, ErrorNotFound},
+    web, HttpResponse,
+};
+use actix_web_httpauth::{
+    extractors::{
+        bearer::{BearerAuth, Config},
+        AuthenticationError,
+    },
+    middleware::HttpAuthentication,
+};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac, NewMac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+// Workspace uses
+use zksync_storage::{webhooks::records::StoredWebhook, ConnectionPool};
+use zksync_types::event::EventId;
+// Local uses
+use super::WebhookDispatcher;
+use crate::messages::{RegisterWebhook, RemoveWebhook};
+use crate::subscriber::filters::SubscriberFilters;
+
+#[derive(Debug)]
+struct ApiState {
+    db_pool: ConnectionPool,
+    dispatcher: Addr<WebhookDispatcher>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterWebhookRequest {
+    url: String,
+    /// Key used to sign the requests sent to the webhook.
+    secret: String,
+    /// Filters in the same format as the ones sent by the WebSocket clients.
+    #[serde(default = "empty_filters")]
+    filters: Value,
+    /// Id of the event after which the delivery starts. If not set,
+    /// only the events that happen after the registration are delivered.
+    #[serde(default)]
+    from_event_id: Option<EventId>,
+}
+
+fn empty_filters() -> Value {
+    Value::Object(Default::default())
+}
+
+/// Registered webhook, its secret is never returned by the API.
+#[derive(Debug, Serialize)]
+struct WebhookInfo {
+    id: i64,
+    url: String,
+    filters: Value,
+    last_delivered_event_id: EventId,
+    created_at: DateTime<Utc>,
+}
+
+impl From<StoredWebhook> for WebhookInfo {
+    fn from(webhook: StoredWebhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            filters: webhook.filters,
+            last_delivered_event_id: EventId(webhook.last_delivered_event_id as u64),
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+async fn register_webhook(
+    data: web::Data<ApiState>,
+    request: web::Json<RegisterWebhookRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    let url = reqwest::Url::parse(&request.url).map_err(ErrorBadRequest)?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(ErrorBadRequest("webhook URL must use http or https"));
+    }
+    if request.secret.is_empty() {
+        return Err(ErrorBadRequest("webhook secret must not be empty"));
+    }
+    serde_json::from_value::<SubscriberFilters>(request.filters.clone())
+        .map_err(ErrorBadRequest)?;
+
+    let mut storage = data
+        .db_pool
+        .access_storage()
+        .await
+        .map_err(ErrorInternalServerError)?;
+    let from_event_id = match request.from_event_id {
+        Some(event_id) => event_id,
+        None => storage
+            .event_schema()
+            .get_last_event_id()
+            .await
+            .map_err(ErrorInternalServerError)?
+            .unwrap_or(EventId(0)),
+    };
+    let webhook = storage
+        .webhooks_schema()
+        .add_webhook(
+            url.as_str(),
+            &request.secret,
+            &request.filters,
+            from_event_id,
+        )
+        .await
+        .map_err(ErrorInternalServerError)?;
+    drop(storage);
+
+    vlog::info!("Registered webhook #{} ({})", webhook.id, webhook.url);
+    data.dispatcher
+        .send(RegisterWebhook(webhook.clone()))
+        .await
+        .map_err(ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(WebhookInfo::from(webhook)))
+}
+
+async fn list_webhooks(data: web::Data<ApiState>) -> actix_web::Result<HttpResponse> {
+    let webhooks = data
+        .db_pool
+        .access_storage()
+        .await
+        .map_err(ErrorInternalServerError)?
+        .webhooks_schema()
+        .load_webhooks()
+        .await
+        .map_err(ErrorInternalServerError)?;
+
+    let webhooks: Vec<_> = webhooks.into_iter().map(WebhookInfo::from).collect();
+    Ok(HttpResponse::Ok().json(webhooks))
+}
+
+async fn remove_webhook(
+    data: web::Data<ApiState>,
+    id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let id = id.into_inner();
+    let removed = data
+        .db_pool
+        .access_storage()
+        .await
+        .map_err(ErrorInternalServerError)?
+        .webhooks_schema()
+        .remove_webhook(id)
+        .await
+        .map_err(ErrorInternalServerError)?;
+    if !removed {
+        return Err(ErrorNotFound(format!("webhook #{} is not found", id)));
+    }
+
+    vlog::info!("Removed webhook #{}", id);
+    data.dispatcher
+        .send(RemoveWebhook(id))
+        .await
+        .map_err(ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Checks that the request contains the API token. If the token
+/// is not configured, all the requests are rejected.
+async fn validate_token(
+    api_token: &str,
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    if api_token.is_empty() || !token_matches(api_token, credentials.token()) {
+        let config = req.app_data::<Config>().cloned().unwrap_or_default();
+        return Err(AuthenticationError::from(config).into());
+    }
+    Ok(req)
+}
+
+/// Compares the token with the API token in constant time. The HMAC digests of the tokens
+/// are compared rather than the tokens themselves, so the length of the API token doesn't leak either.
+fn token_matches(api_token: &str, token: &str) -> bool {
+    let mac = |data: &str| {
+        let mut mac = Hmac::<Sha256>::new_from_slice(api_token.as_bytes())
+            .expect("HMAC accepts keys of any size");
+        mac.update(data.as_bytes());
+        mac
+    };
+    let expected = mac(api_token).finalize().into_bytes();
+    mac(token).verify(&expected).is_ok()
+}
+
+pub fn api_scope(
+    db_pool: ConnectionPool,
+    dispatcher: Addr<WebhookDispatcher>,
+    api_token: String,
+) -> impl HttpServiceFactory {
+    let auth = HttpAuthentication::bearer(move |req, credentials| {
+        let api_token = api_token.clone();
+        async move { validate_token(&api_token, req, credentials).await }
+    });
+
+    web::scope("/webhooks")
+        .app_data(web::Data::new(ApiState {
+            db_pool,
+            dispatcher,
+        }))
+        .wrap(auth)
+        .route("", web::get().to(list_webhooks))
+        .route("", web::post().to(register_webhook))
+        .route("/{id}", web::delete().to(remove_webhook))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that only the exact API token is accepted.
+    #[test]
+    fn api_token_comparison() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secret", "secreT"));
+        assert!(!token_matches("secret", "secret2"));
+        assert!(!token_matches("secret", ""));
+    }
+}
@@ -0,0 +1,290 @@
+//! Delivery of the events to the registered HTTP endpoints.
+//!
+//! Every webhook has its own delivery task which loads the events after the
+//! webhook's cursor from the database, sends the ones matching its filters
+//! and only then moves the cursor forward. If the request fails, it's retried
+//! with an exponential backoff, so each event is delivered at least once.
+//! Receivers may get the same event twice and should deduplicate them by
+//! `event_id`.
+
+// Built-in uses
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
+// External uses
+use actix::prelude::*;
+use hmac::{Hmac, Mac, NewMac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::{sync::watch, task::JoinHandle};
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_storage::{webhooks::records::StoredWebhook, ConnectionPool};
+use zksync_types::event::{EventId, ZkSyncEvent};
+// Local uses
+use crate::messages::{NewEvents, RegisterWebhook, RemoveWebhook, Shutdown};
+use crate::subscriber::filters::SubscriberFilters;
+
+pub mod api;
+
+/// Header containing the hex-encoded HMAC-SHA256 of the request body
+/// computed with the webhook's secret.
+pub const SIGNATURE_HEADER: &str = "X-Zksync-Signature";
+
+/// Delivery tasks check the database for new events at least this often,
+/// even if they weren't notified about them.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Body of the request sent to the webhook.
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    webhook_id: i64,
+    events: Vec<&'a ZkSyncEvent>,
+}
+
+/// Parameters shared by all the delivery tasks.
+#[derive(Debug, Clone)]
+struct DeliverySettings {
+    db_pool: ConnectionPool,
+    client: reqwest::Client,
+    batch_size: u64,
+    min_retry_backoff: Duration,
+    max_retry_backoff: Duration,
+}
+
+/// The actor responsible for running the delivery tasks of the registered webhooks.
+/// It gets notified about new events by the [`ServerMonitor`](crate::monitor::ServerMonitor).
+#[derive(Debug)]
+pub struct WebhookDispatcher {
+    settings: DeliverySettings,
+    /// Webhooks loaded on the start, their tasks are spawned once the actor is started.
+    initial_webhooks: Vec<StoredWebhook>,
+    /// Id of the last event known to the dispatcher, delivery tasks wait for its changes.
+    last_event_id: watch::Sender<EventId>,
+    deliveries: HashMap<i64, JoinHandle<()>>,
+}
+
+impl WebhookDispatcher {
+    const DB_POOL_SIZE: u32 = 2;
+
+    pub async fn new(config: &ZkSyncConfig) -> anyhow::Result<Self> {
+        let db_pool = ConnectionPool::new(Some(Self::DB_POOL_SIZE));
+        let mut storage = db_pool.access_storage().await?;
+        let initial_webhooks = storage.webhooks_schema().load_webhooks().await?;
+        let last_event_id = storage
+            .event_schema()
+            .get_last_event_id()
+            .await?
+            .unwrap_or(EventId(0));
+        drop(storage);
+
+        let client = reqwest::Client::builder()
+            .timeout(config.event_listener.webhook_request_timeout())
+            .build()?;
+        let settings = DeliverySettings {
+            db_pool,
+            client,
+            batch_size: config.event_listener.webhook_batch_size,
+            min_retry_backoff: config.event_listener.webhook_min_retry_backoff(),
+            max_retry_backoff: config.event_listener.webhook_max_retry_backoff(),
+        };
+
+        Ok(Self {
+            settings,
+            initial_webhooks,
+            last_event_id: watch::channel(last_event_id).0,
+            deliveries: HashMap::new(),
+        })
+    }
+
+    fn start_delivery(&mut self, webhook: StoredWebhook) {
+        let filters = match serde_json::from_value::<SubscriberFilters>(webhook.filters.clone()) {
+            Ok(filters) => filters,
+            Err(err) => {
+                vlog::error!(
+                    "Couldn't parse the filters of the webhook #{}: {}, the events won't be delivered",
+                    webhook.id,
+                    err
+                );
+                return;
+            }
+        };
+        let id = webhook.id;
+        let task = tokio::spawn(deliver_events(
+            webhook,
+            filters,
+            self.settings.clone(),
+            self.last_event_id.subscribe(),
+        ));
+        if let Some(previous) = self.deliveries.insert(id, task) {
+            previous.abort();
+        }
+    }
+}
+
+impl Actor for WebhookDispatcher {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _ctx: &mut Self::Context) {
+        for webhook in std::mem::take(&mut self.initial_webhooks) {
+            self.start_delivery(webhook);
+        }
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        for (_, task) in self.deliveries.drain() {
+            task.abort();
+        }
+        vlog::warn!("WebhookDispatcher actor has stopped");
+    }
+}
+
+impl Handler<NewEvents> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: NewEvents, _ctx: &mut Self::Context) {
+        if let Some(event) = msg.0.last() {
+            // Fails only if there are no delivery tasks, nobody to notify then.
+            let _ = self.last_event_id.send(event.id);
+        }
+    }
+}
+
+impl Handler<RegisterWebhook> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: RegisterWebhook, _ctx: &mut Self::Context) {
+        self.start_delivery(msg.0);
+    }
+}
+
+impl Handler<RemoveWebhook> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: RemoveWebhook, _ctx: &mut Self::Context) {
+        if let Some(task) = self.deliveries.remove(&msg.0) {
+            task.abort();
+        }
+    }
+}
+
+impl Handler<Shutdown> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, _msg: Shutdown, ctx: &mut Self::Context) {
+        ctx.stop();
+    }
+}
+
+/// Delivers the events to the webhook until the task is aborted.
+async fn deliver_events(
+    webhook: StoredWebhook,
+    filters: SubscriberFilters,
+    settings: DeliverySettings,
+    mut last_event_id: watch::Receiver<EventId>,
+) {
+    let mut cursor = EventId(webhook.last_delivered_event_id as u64);
+    let mut retry_backoff = settings.min_retry_backoff;
+    loop {
+        match deliver_batch(&webhook, &filters, &settings, cursor).await {
+            Ok(Some(new_cursor)) => {
+                cursor = new_cursor;
+                retry_backoff = settings.min_retry_backoff;
+            }
+            Ok(None) => {
+                // Everything is delivered, wait for the new events.
+                if let Ok(Err(_)) =
+                    tokio::time::timeout(POLL_INTERVAL, last_event_id.changed()).await
+                {
+                    // The dispatcher is stopped.
+                    return;
+                }
+            }
+            Err(err) => {
+                vlog::warn!(
+                    "Couldn't deliver events to the webhook #{} ({}): {}, retrying in {:?}",
+                    webhook.id,
+                    webhook.url,
+                    err,
+                    retry_backoff
+                );
+                tokio::time::sleep(retry_backoff).await;
+                retry_backoff = (retry_backoff * 2).min(settings.max_retry_backoff);
+            }
+        }
+    }
+}
+
+/// Sends the events that happened after `cursor` to the webhook and stores the new cursor.
+/// Returns `None` if there are no new events.
+async fn deliver_batch(
+    webhook: &StoredWebhook,
+    filters: &SubscriberFilters,
+    settings: &DeliverySettings,
+    cursor: EventId,
+) -> anyhow::Result<Option<EventId>> {
+    let events: Vec<ZkSyncEvent> = settings
+        .db_pool
+        .access_storage()
+        .await?
+        .event_schema()
+        .fetch_events_page(cursor, settings.batch_size)
+        .await?
+        .into_iter()
+        .map(ZkSyncEvent::try_from)
+        .collect::<Result<_, _>>()?;
+    let new_cursor = match events.last() {
+        Some(event) => event.id,
+        None => return Ok(None),
+    };
+
+    let events: Vec<_> = events
+        .iter()
+        .filter(|event| filters.matches(event))
+        .collect();
+    // The cursor is moved past the filtered out events without sending a request.
+    if !events.is_empty() {
+        let body = serde_json::to_vec(&WebhookPayload {
+            webhook_id: webhook.id,
+            events,
+        })?;
+        settings
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign_payload(&webhook.secret, &body))
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?;
+    }
+
+    settings
+        .db_pool
+        .access_storage()
+        .await?
+        .webhooks_schema()
+        .update_delivery_cursor(webhook.id, new_cursor)
+        .await?;
+    Ok(Some(new_cursor))
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of the payload.
+fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the signature against the test vector from RFC 4231.
+    #[test]
+    fn payload_signature() {
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -3,13 +3,16 @@ use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
-use num::Zero;
+use num::{BigUint, Zero};
 
 use zksync_storage::{chain::operations_ext::records::TxReceiptResponse, ConnectionPool};
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
+    forced_exit_requests::{
+        ForcedExitRefund, ForcedExitRefundStatus, ForcedExitRequest, ForcedExitRequestId,
+        ForcedExitTokenExit, ForcedExitTokenStatus,
+    },
     tx::TxHash,
-    AccountId, Nonce,
+    AccountId, Address, Nonce, TokenId,
 };
 
 use zksync_api::api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker};
@@ -43,6 +46,49 @@ pub trait CoreInteractionWrapper {
         deleting_threshold: chrono::Duration,
     ) -> anyhow::Result<()>;
     async fn check_forced_exit_request(&self, request: &ForcedExitRequest) -> anyhow::Result<bool>;
+    /// Returns the tokens of the request in which the target has a non-zero balance.
+    async fn get_exitable_tokens(
+        &self,
+        request: &ForcedExitRequest,
+    ) -> anyhow::Result<Vec<TokenId>>;
+    async fn get_token_exits(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> anyhow::Result<Vec<ForcedExitTokenExit>>;
+    async fn set_token_exit_status(
+        &self,
+        id: ForcedExitRequestId,
+        token: TokenId,
+        status: ForcedExitTokenStatus,
+        tx_hash: Option<TxHash>,
+    ) -> anyhow::Result<()>;
+    async fn set_payer(&self, id: ForcedExitRequestId, payer: Address) -> anyhow::Result<()>;
+    async fn get_payer(&self, id: ForcedExitRequestId) -> anyhow::Result<Option<Address>>;
+    async fn store_refund(
+        &self,
+        id: ForcedExitRequestId,
+        recipient: Address,
+        amount: BigUint,
+    ) -> anyhow::Result<()>;
+    async fn get_unfinished_refunds(&self) -> anyhow::Result<Vec<ForcedExitRefund>>;
+    async fn set_refund_status(
+        &self,
+        id: ForcedExitRequestId,
+        status: ForcedExitRefundStatus,
+        tx_hash: Option<TxHash>,
+    ) -> anyhow::Result<()>;
+    /// Saves the refund transfer and marks the refund as sent, must be called before the transfer is sent.
+    async fn save_refund_tx(
+        &self,
+        id: ForcedExitRequestId,
+        tx: &SignedZkSyncTx,
+    ) -> anyhow::Result<()>;
+    async fn get_refund_tx(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> anyhow::Result<Option<SignedZkSyncTx>>;
+    /// Sends the saved refund transfer to the mempool.
+    async fn send_refund(&mut self, tx: SignedZkSyncTx) -> anyhow::Result<()>;
 }
 
 #[derive(Clone)]
@@ -203,4 +249,163 @@ impl CoreInteractionWrapper for MempoolCoreInteractionWrapper {
             Ok(false)
         }
     }
+
+    async fn get_exitable_tokens(
+        &self,
+        request: &ForcedExitRequest,
+    ) -> anyhow::Result<Vec<TokenId>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let target_state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(request.target)
+            .await?
+            .committed;
+
+        let tokens = match target_state {
+            Some((_, account)) => request
+                .tokens
+                .iter()
+                .copied()
+                .filter(|token| !account.get_balance(*token).is_zero())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(tokens)
+    }
+
+    async fn get_token_exits(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> anyhow::Result<Vec<ForcedExitTokenExit>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let exits = storage
+            .forced_exit_requests_schema()
+            .get_token_exits(id)
+            .await?;
+
+        Ok(exits)
+    }
+
+    async fn set_token_exit_status(
+        &self,
+        id: ForcedExitRequestId,
+        token: TokenId,
+        status: ForcedExitTokenStatus,
+        tx_hash: Option<TxHash>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_token_exit_status(id, token, status, tx_hash)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_payer(&self, id: ForcedExitRequestId, payer: Address) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_payer(id, payer)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_payer(&self, id: ForcedExitRequestId) -> anyhow::Result<Option<Address>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let payer = storage.forced_exit_requests_schema().get_payer(id).await?;
+
+        Ok(payer)
+    }
+
+    async fn store_refund(
+        &self,
+        id: ForcedExitRequestId,
+        recipient: Address,
+        amount: BigUint,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .store_refund(id, recipient, amount)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_unfinished_refunds(&self) -> anyhow::Result<Vec<ForcedExitRefund>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let refunds = storage
+            .forced_exit_requests_schema()
+            .get_unfinished_refunds()
+            .await?;
+
+        Ok(refunds)
+    }
+
+    async fn set_refund_status(
+        &self,
+        id: ForcedExitRequestId,
+        status: ForcedExitRefundStatus,
+        tx_hash: Option<TxHash>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .set_refund_status(id, status, tx_hash)
+            .await?;
+
+        vlog::info!(
+            "Refund for ForcedExit request with id {} is {}",
+            id,
+            status.to_string()
+        );
+
+        Ok(())
+    }
+
+    async fn save_refund_tx(
+        &self,
+        id: ForcedExitRequestId,
+        tx: &SignedZkSyncTx,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        storage
+            .forced_exit_requests_schema()
+            .save_refund_tx(id, tx)
+            .await?;
+
+        vlog::info!(
+            "Refund for ForcedExit request with id {} is sent in tx {}",
+            id,
+            tx.hash()
+        );
+
+        Ok(())
+    }
+
+    async fn get_refund_tx(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> anyhow::Result<Option<SignedZkSyncTx>> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let tx = storage
+            .forced_exit_requests_schema()
+            .get_refund_tx(id)
+            .await?;
+
+        Ok(tx)
+    }
+
+    async fn send_refund(&mut self, tx: SignedZkSyncTx) -> anyhow::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        let item = MempoolTransactionRequest::NewTx(Box::new(tx), sender);
+        self.mempool_tx_sender.send(item).await?;
+        receiver.await??;
+
+        Ok(())
+    }
 }
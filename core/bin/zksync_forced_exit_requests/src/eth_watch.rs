@@ -12,14 +12,14 @@ use tokio::time;
 use web3::{
     contract::Contract,
     transports::Http,
-    types::{BlockNumber, FilterBuilder, Log},
+    types::{BlockNumber, FilterBuilder, Log, TransactionId},
     Web3,
 };
 use zksync_config::ForcedExitRequestsConfig;
 use zksync_storage::ConnectionPool;
 
 use zksync_contracts::forced_exit_contract;
-use zksync_types::H160;
+use zksync_types::{H160, H256};
 
 use zksync_core::eth_watch::{get_web3_block_number, WatcherMode};
 use zksync_mempool::MempoolTransactionRequest;
@@ -60,6 +60,8 @@ pub trait EthClient {
         to: u64,
     ) -> anyhow::Result<Vec<FundsReceivedEvent>>;
     async fn block_number(&self) -> anyhow::Result<u64>;
+    /// Returns the sender of the transaction, i.e. the address that paid for the request.
+    async fn get_tx_sender(&self, tx_hash: H256) -> anyhow::Result<Address>;
 }
 
 pub struct EthHttpClient {
@@ -121,6 +123,18 @@ impl EthClient for EthHttpClient {
     async fn block_number(&self) -> anyhow::Result<u64> {
         get_web3_block_number(&self.web3).await
     }
+
+    async fn get_tx_sender(&self, tx_hash: H256) -> anyhow::Result<Address> {
+        let tx = self
+            .web3
+            .eth()
+            .transaction(TransactionId::Hash(tx_hash))
+            .await?
+            .ok_or_else(|| anyhow::format_err!("Transaction {:?} not found", tx_hash))?;
+
+        tx.from
+            .ok_or_else(|| anyhow::format_err!("Transaction {:?} has no sender", tx_hash))
+    }
 }
 
 struct ForcedExitContractWatcher<Sender, Client, Interactor>
@@ -324,9 +338,26 @@ where
             }
         };
 
-        for e in events {
+        // Payers are needed to refund the unused part of the payments,
+        // so they are fetched before processing any of the requests
+        let mut payers = Vec::with_capacity(events.len());
+        for e in events.iter() {
+            match self.eth_client.get_tx_sender(e.tx_hash).await {
+                Ok(payer) => payers.push(payer),
+                Err(error) => {
+                    self.handle_infura_error(error);
+                    return;
+                }
+            }
+        }
+
+        for (e, payer) in events.into_iter().zip(payers) {
             self.forced_exit_sender
-                .process_request(e.amount, lower_bound_block_time(e.block_number, last_block))
+                .process_request(
+                    e.amount,
+                    payer,
+                    lower_bound_block_time(e.block_number, last_block),
+                )
                 .await;
         }
 
@@ -511,9 +542,19 @@ mod test {
         async fn block_number(&self) -> anyhow::Result<u64> {
             Ok(self.current_block_number)
         }
+
+        async fn get_tx_sender(&self, tx_hash: H256) -> anyhow::Result<Address> {
+            Ok(test_tx_sender(tx_hash))
+        }
+    }
+
+    // The sender of the transaction is derived from its hash, so the tests can check it
+    fn test_tx_sender(tx_hash: H256) -> Address {
+        Address::from_slice(&tx_hash.as_bytes()[12..])
     }
+
     struct DummyForcedExitSender {
-        pub processed_requests: Mutex<Vec<(BigUint, DateTime<Utc>)>>,
+        pub processed_requests: Mutex<Vec<(BigUint, Address, DateTime<Utc>)>>,
     }
 
     impl DummyForcedExitSender {
@@ -526,12 +567,17 @@ mod test {
 
     #[async_trait::async_trait]
     impl ForcedExitSender for DummyForcedExitSender {
-        async fn process_request(&mut self, amount: BigUint, submission_time: DateTime<Utc>) {
+        async fn process_request(
+            &mut self,
+            amount: BigUint,
+            payer: Address,
+            submission_time: DateTime<Utc>,
+        ) {
             let mut write_lock = self
                 .processed_requests
                 .lock()
                 .expect("Failed to get write lock for processed_requests");
-            (*write_lock).push((amount, submission_time));
+            (*write_lock).push((amount, payer, submission_time));
         }
     }
 
@@ -688,16 +734,19 @@ mod test {
                 // Should be processed
                 amount: BigUint::from_str("1000000001").unwrap(),
                 block_number: TEST_FIRST_CURRENT_BLOCK - 2 * wait_confirmations,
+                tx_hash: H256::random(),
             },
             FundsReceivedEvent {
                 amount: BigUint::from_str("1000000002").unwrap(),
                 // Should be processed
                 block_number: TEST_FIRST_CURRENT_BLOCK - wait_confirmations - 1,
+                tx_hash: H256::random(),
             },
             FundsReceivedEvent {
                 amount: BigUint::from_str("1000000003").unwrap(),
                 // Should not be processed
                 block_number: TEST_FIRST_CURRENT_BLOCK - 1,
+                tx_hash: H256::random(),
             },
         ];
 
@@ -729,5 +778,14 @@ mod test {
             processed_requests[1].0,
             BigUint::from_str("1000000002").unwrap()
         );
+        // The payers are taken from the transactions that emitted the events
+        assert_eq!(
+            processed_requests[0].1,
+            test_tx_sender(watcher.eth_client.events[0].tx_hash)
+        );
+        assert_eq!(
+            processed_requests[1].1,
+            test_tx_sender(watcher.eth_client.events[1].tx_hash)
+        );
     }
 }
@@ -1,18 +1,24 @@
 use std::ops::AddAssign;
 
 use chrono::{DateTime, Utc};
-use num::BigUint;
+use num::{BigUint, Zero};
 use tokio::time;
 
 use zksync_config::ForcedExitRequestsConfig;
 
 use zksync_types::{
-    forced_exit_requests::ForcedExitRequest, tx::TimeRange, tx::TxHash, AccountId, Address, Nonce,
-    TokenId, ZkSyncTx,
+    forced_exit_requests::{
+        ForcedExitRefund, ForcedExitRefundStatus, ForcedExitRequest, ForcedExitTokenStatus,
+    },
+    helpers::closest_packable_token_amount,
+    tx::error::TxAddError,
+    tx::TimeRange,
+    tx::TxHash,
+    AccountId, Address, Nonce, TokenId, ZkSyncTx,
 };
 
-use zksync_types::ForcedExit;
 use zksync_types::SignedZkSyncTx;
+use zksync_types::{ForcedExit, Transfer};
 
 use crate::{core_interaction_wrapper::CoreInteractionWrapper, utils};
 
@@ -22,9 +28,17 @@ use crate::utils::read_signing_key;
 // We try to process a request 3 times before sending warnings in the console
 const PROCESSING_ATTEMPTS: u32 = 3;
 
+// The requests are paid in the base token, so the refunds are made in it as well
+const REFUND_TOKEN: TokenId = TokenId(0);
+
 #[async_trait::async_trait]
 pub trait ForcedExitSender {
-    async fn process_request(&mut self, amount: BigUint, submission_time: DateTime<Utc>);
+    async fn process_request(
+        &mut self,
+        amount: BigUint,
+        payer: Address,
+        submission_time: DateTime<Utc>,
+    );
 }
 
 pub struct MempoolForcedExitSender<T: CoreInteractionWrapper> {
@@ -36,14 +50,19 @@ pub struct MempoolForcedExitSender<T: CoreInteractionWrapper> {
 
 #[async_trait::async_trait]
 impl<T: CoreInteractionWrapper + Sync + Send> ForcedExitSender for MempoolForcedExitSender<T> {
-    async fn process_request(&mut self, amount: BigUint, submission_time: DateTime<Utc>) {
+    async fn process_request(
+        &mut self,
+        amount: BigUint,
+        payer: Address,
+        submission_time: DateTime<Utc>,
+    ) {
         let mut attempts: u32 = 0;
         // Typically this should not run any longer than 1 iteration
         // In case something bad happens we do not want the server crush because
         // of the forced_exit_requests component
         loop {
             let processing_attempt = self
-                .try_process_request(amount.clone(), submission_time)
+                .try_process_request(amount.clone(), payer, submission_time)
                 .await;
 
             if processing_attempt.is_ok() {
@@ -103,21 +122,53 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         }
     }
 
-    pub async fn build_transactions(
+    pub fn build_refund(
         &self,
-        // storage: &mut StorageProcessor<'_>,
-        fe_request: ForcedExitRequest,
-    ) -> anyhow::Result<Vec<SignedZkSyncTx>> {
-        let mut sender_nonce = self
+        nonce: Nonce,
+        recipient: Address,
+        amount: BigUint,
+    ) -> SignedZkSyncTx {
+        let tx = Transfer::new_signed(
+            self.forced_exit_sender_account_id,
+            self.config.sender_account_address,
+            recipient,
+            REFUND_TOKEN,
+            amount,
+            BigUint::from(0u32),
+            nonce,
+            TimeRange::default(),
+            &self.sender_private_key,
+        )
+        .expect("Failed to create signed Transfer transaction");
+
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(tx)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    async fn get_sender_nonce(&self) -> anyhow::Result<Nonce> {
+        let nonce = self
             .core_interaction_wrapper
             .get_nonce(self.forced_exit_sender_account_id)
             .await?
             .expect("Forced Exit sender account does not have nonce");
 
+        Ok(nonce)
+    }
+
+    pub async fn build_transactions(
+        &self,
+        target: Address,
+        tokens: &[TokenId],
+    ) -> anyhow::Result<Vec<SignedZkSyncTx>> {
+        let mut sender_nonce = self.get_sender_nonce().await?;
+
         let mut transactions: Vec<SignedZkSyncTx> = vec![];
 
-        for token in fe_request.tokens.into_iter() {
-            transactions.push(self.build_forced_exit(sender_nonce, fe_request.target, token));
+        for token in tokens.iter() {
+            transactions.push(self.build_forced_exit(sender_nonce, target, *token));
             sender_nonce.add_assign(1);
         }
 
@@ -163,6 +214,27 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
                     .await?;
             }
         }
+        self.finish_sent_token_exits(request, ForcedExitTokenStatus::Executed)
+            .await
+    }
+
+    async fn finish_sent_token_exits(
+        &self,
+        request: &ForcedExitRequest,
+        status: ForcedExitTokenStatus,
+    ) -> anyhow::Result<()> {
+        let exits = self
+            .core_interaction_wrapper
+            .get_token_exits(request.id)
+            .await?;
+
+        for exit in exits {
+            if exit.status == ForcedExitTokenStatus::Sent {
+                self.core_interaction_wrapper
+                    .set_token_exit_status(request.id, exit.token, status, None)
+                    .await?;
+            }
+        }
         Ok(())
     }
 
@@ -182,16 +254,41 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
                 vlog::error!(
                     "A previously sent forced exit transaction has failed. Canceling the tx."
                 );
+                self.finish_sent_token_exits(&request, ForcedExitTokenStatus::Failed)
+                    .await?;
                 self.core_interaction_wrapper
                     .set_fulfilled_by(request.id, None)
                     .await?;
+
+                // The payment for the failed tokens is returned the same way
+                // as when the failure is seen right after sending the transactions
+                match self.core_interaction_wrapper.get_payer(request.id).await? {
+                    Some(payer) => self.store_refund(&request, payer).await?,
+                    None => vlog::error!(
+                        "Payer of ForcedExit request with id {} is unknown, it has to be refunded manually",
+                        request.id
+                    ),
+                }
+                self.core_interaction_wrapper
+                    .set_fulfilled_at(request.id)
+                    .await?;
             }
         }
 
-        Ok(())
+        // The refunds recorded before the restart are sent now
+        self.process_refunds().await
     }
 
     pub async fn wait_until_comitted(&self, tx_hash: TxHash) -> anyhow::Result<()> {
+        if self.wait_for_receipt(tx_hash).await? {
+            Ok(())
+        } else {
+            Err(anyhow::Error::msg("ForcedExit transaction failed"))
+        }
+    }
+
+    // Returns whether the transaction was committed successfully
+    pub async fn wait_for_receipt(&self, tx_hash: TxHash) -> anyhow::Result<bool> {
         let timeout_millis: u64 = 120000;
         let poll_interval_millis: u64 = 200;
         let poll_interval = time::Duration::from_millis(poll_interval_millis);
@@ -209,11 +306,7 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             let receipt = self.core_interaction_wrapper.get_receipt(tx_hash).await?;
 
             if let Some(tx_receipt) = receipt {
-                if tx_receipt.success {
-                    return Ok(());
-                } else {
-                    return Err(anyhow::Error::msg("ForcedExit transaction failed"));
-                }
+                return Ok(tx_receipt.success);
             }
 
             timer.tick().await;
@@ -221,9 +314,151 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
         }
     }
 
+    // Sends the refunds that were not sent yet and waits until the sent ones are committed
+    pub async fn process_refunds(&mut self) -> anyhow::Result<()> {
+        let refunds = self
+            .core_interaction_wrapper
+            .get_unfinished_refunds()
+            .await?;
+
+        for refund in refunds.into_iter() {
+            self.process_refund(&refund).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn process_refund(&mut self, refund: &ForcedExitRefund) -> anyhow::Result<()> {
+        let saved_tx = self
+            .core_interaction_wrapper
+            .get_refund_tx(refund.request_id)
+            .await?;
+
+        let tx_hash = match saved_tx {
+            Some(tx) => {
+                let tx_hash = tx.hash();
+                if !self.resubmit_refund(refund, tx).await? {
+                    return self
+                        .core_interaction_wrapper
+                        .set_refund_status(refund.request_id, ForcedExitRefundStatus::Failed, None)
+                        .await;
+                }
+                tx_hash
+            }
+            None => {
+                let nonce = self.get_sender_nonce().await?;
+                let tx = self.build_refund(nonce, refund.recipient, refund.amount.clone());
+                let tx_hash = tx.hash();
+                // The transfer is saved before it's sent, so a retry or a restart
+                // resubmits the same transaction and the payment is never returned twice
+                self.core_interaction_wrapper
+                    .save_refund_tx(refund.request_id, &tx)
+                    .await?;
+                self.core_interaction_wrapper.send_refund(tx).await?;
+                tx_hash
+            }
+        };
+
+        let status = if self.wait_for_receipt(tx_hash).await? {
+            ForcedExitRefundStatus::Completed
+        } else {
+            // Most likely the sender account is out of funds, the refund
+            // has to be made by the operator
+            vlog::error!(
+                "Refund for ForcedExit request with id {} has failed",
+                refund.request_id
+            );
+            ForcedExitRefundStatus::Failed
+        };
+
+        self.core_interaction_wrapper
+            .set_refund_status(refund.request_id, status, None)
+            .await
+    }
+
+    /// Resubmits the saved refund transfer unless it's executed already.
+    /// Returns `false` if the transfer can never be executed.
+    async fn resubmit_refund(
+        &mut self,
+        refund: &ForcedExitRefund,
+        tx: SignedZkSyncTx,
+    ) -> anyhow::Result<bool> {
+        let tx_hash = tx.hash();
+        if self
+            .core_interaction_wrapper
+            .get_receipt(tx_hash)
+            .await?
+            .is_some()
+        {
+            return Ok(true);
+        }
+
+        let error = match self.core_interaction_wrapper.send_refund(tx).await {
+            Ok(()) => return Ok(true),
+            Err(error) => error,
+        };
+        // The transfer is either in the mempool already or its nonce was taken by another transaction
+        let nonce_taken = matches!(
+            error.downcast_ref::<TxAddError>(),
+            Some(TxAddError::NonceMismatch)
+        ) && self
+            .core_interaction_wrapper
+            .get_receipt(tx_hash)
+            .await?
+            .is_none();
+        if nonce_taken {
+            vlog::error!(
+                "Refund for ForcedExit request with id {} can't be executed: {}",
+                refund.request_id,
+                error
+            );
+        } else {
+            vlog::warn!(
+                "Refund for ForcedExit request with id {} is not resubmitted: {}",
+                refund.request_id,
+                error
+            );
+        }
+
+        Ok(!nonce_taken)
+    }
+
+    /// Stores the refund of the part of the payment for the tokens that were not exited.
+    async fn store_refund(
+        &self,
+        request: &ForcedExitRequest,
+        payer: Address,
+    ) -> anyhow::Result<()> {
+        let exits = self
+            .core_interaction_wrapper
+            .get_token_exits(request.id)
+            .await?;
+        let unused_tokens = exits
+            .iter()
+            .filter(|exit| exit.status.is_refundable())
+            .count();
+        if unused_tokens == 0 {
+            return Ok(());
+        }
+
+        // The user pays the same price for every token, so the part of the payment
+        // for the tokens that were not exited is returned
+        let refund = &request.price_in_wei * BigUint::from(unused_tokens)
+            / BigUint::from(request.tokens.len());
+        let refund = closest_packable_token_amount(&refund);
+        if !refund.is_zero() {
+            self.core_interaction_wrapper
+                .store_refund(request.id, payer, refund)
+                .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn try_process_request(
         &mut self,
         amount: BigUint,
+        payer: Address,
         submission_time: DateTime<Utc>,
     ) -> anyhow::Result<()> {
         let (id, amount) = utils::extract_id_from_amount(amount, self.config.digits_in_id as u32);
@@ -238,30 +473,66 @@ impl<T: CoreInteractionWrapper> MempoolForcedExitSender<T> {
             return Ok(());
         };
 
-        let txs = self.build_transactions(fe_request.clone()).await?;
+        // The payer is needed to return the payment if the transactions fail after a restart
+        self.core_interaction_wrapper.set_payer(id, payer).await?;
 
-        // Right before sending the transactions we must check if the request is possible at all
+        // Right before sending the transactions we must check which tokens can be exited at all
         let is_request_possible = self
             .core_interaction_wrapper
             .check_forced_exit_request(&fe_request)
             .await?;
-        if !is_request_possible {
-            // If not possible at all, return without sending any transactions
-            return Ok(());
+        let exitable_tokens = if is_request_possible {
+            self.core_interaction_wrapper
+                .get_exitable_tokens(&fe_request)
+                .await?
+        } else {
+            Vec::new()
+        };
+
+        for token in fe_request.tokens.iter() {
+            if !exitable_tokens.contains(token) {
+                self.core_interaction_wrapper
+                    .set_token_exit_status(id, *token, ForcedExitTokenStatus::Skipped, None)
+                    .await?;
+            }
         }
-        let hashes = self
-            .core_interaction_wrapper
-            .send_and_save_txs_batch(&fe_request, txs)
-            .await?;
 
-        // We wait only for the first transaction to complete since the transactions
-        // are sent in a batch
-        self.wait_until_comitted(hashes[0]).await?;
+        if !exitable_tokens.is_empty() {
+            let txs = self
+                .build_transactions(fe_request.target, &exitable_tokens)
+                .await?;
+            let hashes = self
+                .core_interaction_wrapper
+                .send_and_save_txs_batch(&fe_request, txs)
+                .await?;
+            for (token, hash) in exitable_tokens.iter().zip(hashes.iter()) {
+                self.core_interaction_wrapper
+                    .set_token_exit_status(id, *token, ForcedExitTokenStatus::Sent, Some(*hash))
+                    .await?;
+            }
+
+            // We wait only for the first transaction to complete since the transactions
+            // are sent in a batch
+            let status = if self.wait_for_receipt(hashes[0]).await? {
+                ForcedExitTokenStatus::Executed
+            } else {
+                ForcedExitTokenStatus::Failed
+            };
+            for token in exitable_tokens.iter() {
+                self.core_interaction_wrapper
+                    .set_token_exit_status(id, *token, status, None)
+                    .await?;
+            }
+        }
+
+        self.store_refund(&fe_request, payer).await?;
         self.core_interaction_wrapper.set_fulfilled_at(id).await?;
+        self.process_refunds().await?;
 
         Ok(())
     }
 }
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -319,7 +590,11 @@ mod test {
 
         // Not the right amount, because not enough zeroes
         forced_exit_sender
-            .process_request(BigUint::from_str("1000000012").unwrap(), Utc::now())
+            .process_request(
+                BigUint::from_str("1000000012").unwrap(),
+                Address::random(),
+                Utc::now(),
+            )
             .await;
         assert_eq!(
             forced_exit_sender
@@ -333,7 +608,11 @@ mod test {
 
         // Not the right amount, because id is not correct
         forced_exit_sender
-            .process_request(BigUint::from_str("10000000001").unwrap(), Utc::now())
+            .process_request(
+                BigUint::from_str("10000000001").unwrap(),
+                Address::random(),
+                Utc::now(),
+            )
             .await;
         assert_eq!(
             forced_exit_sender
@@ -349,6 +628,7 @@ mod test {
         forced_exit_sender
             .process_request(
                 BigUint::from_str("10000000001").unwrap(),
+                Address::random(),
                 Utc::now().add(day.mul(3)),
             )
             .await;
@@ -365,7 +645,11 @@ mod test {
 
         // The transaction is correct
         forced_exit_sender
-            .process_request(BigUint::from_str("10000000012").unwrap(), Utc::now())
+            .process_request(
+                BigUint::from_str("10000000012").unwrap(),
+                Address::random(),
+                Utc::now(),
+            )
             .await;
 
         assert_eq!(
@@ -378,4 +662,209 @@ mod test {
             1
         );
     }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_partial_refund() {
+        let day = chrono::Duration::days(1);
+
+        let forced_exit_requests = ForcedExitRequestsConfig {
+            digits_in_id: 10,
+            ..ForcedExitRequestsConfig::from_env()
+        };
+
+        let mut forced_exit_sender = get_test_forced_exit_sender(Some(forced_exit_requests));
+        // The target has no balance in the second token
+        forced_exit_sender.core_interaction_wrapper.exitable_tokens = Some(vec![TokenId(1)]);
+
+        add_request(
+            &forced_exit_sender.core_interaction_wrapper.requests,
+            ForcedExitRequest {
+                id: 12,
+                target: Address::random(),
+                tokens: vec![TokenId(1), TokenId(2)],
+                price_in_wei: BigUint::from_str("10000000000").unwrap(),
+                valid_until: Utc::now().add(day),
+                created_at: Utc::now(),
+                fulfilled_by: None,
+                fulfilled_at: None,
+            },
+        );
+
+        let payer = Address::random();
+        forced_exit_sender
+            .process_request(BigUint::from_str("10000000012").unwrap(), payer, Utc::now())
+            .await;
+
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+
+        // One ForcedExit and the refund transfer
+        let sent_txs = wrapper.sent_txs.lock().unwrap();
+        assert_eq!(sent_txs.len(), 2);
+        assert!(matches!(sent_txs[0].tx, ZkSyncTx::ForcedExit(_)));
+        match &sent_txs[1].tx {
+            ZkSyncTx::Transfer(transfer) => {
+                assert_eq!(transfer.to, payer);
+                assert_eq!(transfer.token, REFUND_TOKEN);
+                assert_eq!(transfer.amount, BigUint::from_str("5000000000").unwrap());
+            }
+            tx => panic!("Unexpected refund transaction: {:?}", tx),
+        }
+
+        let exits = wrapper.token_exits.lock().unwrap();
+        assert_eq!(exits.len(), 2);
+        let status_of = |token| {
+            exits
+                .iter()
+                .find(|(_, exit)| exit.token == token)
+                .map(|(_, exit)| exit.status)
+        };
+        assert_eq!(status_of(TokenId(1)), Some(ForcedExitTokenStatus::Executed));
+        assert_eq!(status_of(TokenId(2)), Some(ForcedExitTokenStatus::Skipped));
+
+        // Half of the payment is returned to the payer
+        let refunds = wrapper.refunds.lock().unwrap();
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds[0].request_id, 12);
+        assert_eq!(refunds[0].recipient, payer);
+        assert_eq!(refunds[0].amount, BigUint::from_str("5000000000").unwrap());
+        assert_eq!(refunds[0].status, ForcedExitRefundStatus::Completed);
+
+        let requests = wrapper.requests.lock().unwrap();
+        assert!(requests[0].fulfilled_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_full_refund() {
+        let day = chrono::Duration::days(1);
+
+        let forced_exit_requests = ForcedExitRequestsConfig {
+            digits_in_id: 10,
+            ..ForcedExitRequestsConfig::from_env()
+        };
+
+        let mut forced_exit_sender = get_test_forced_exit_sender(Some(forced_exit_requests));
+        // The target has withdrawn everything before the request was paid
+        forced_exit_sender.core_interaction_wrapper.exitable_tokens = Some(vec![]);
+
+        add_request(
+            &forced_exit_sender.core_interaction_wrapper.requests,
+            ForcedExitRequest {
+                id: 12,
+                target: Address::random(),
+                tokens: vec![TokenId(1)],
+                price_in_wei: BigUint::from_str("10000000000").unwrap(),
+                valid_until: Utc::now().add(day),
+                created_at: Utc::now(),
+                fulfilled_by: None,
+                fulfilled_at: None,
+            },
+        );
+
+        let payer = Address::random();
+        forced_exit_sender
+            .process_request(BigUint::from_str("10000000012").unwrap(), payer, Utc::now())
+            .await;
+
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+
+        // Only the refund is sent
+        let sent_txs = wrapper.sent_txs.lock().unwrap();
+        assert_eq!(sent_txs.len(), 1);
+        assert!(matches!(sent_txs[0].tx, ZkSyncTx::Transfer(_)));
+
+        let refunds = wrapper.refunds.lock().unwrap();
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds[0].amount, BigUint::from_str("10000000000").unwrap());
+        assert_eq!(refunds[0].status, ForcedExitRefundStatus::Completed);
+
+        // The request is processed, so the refund is not repeated if the event is seen again
+        drop(sent_txs);
+        drop(refunds);
+        forced_exit_sender
+            .process_request(BigUint::from_str("10000000012").unwrap(), payer, Utc::now())
+            .await;
+        assert_eq!(
+            forced_exit_sender
+                .core_interaction_wrapper
+                .sent_txs
+                .lock()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_refund_after_restart() {
+        let day = chrono::Duration::days(1);
+
+        let mut forced_exit_sender = get_test_forced_exit_sender(None);
+        // The batch sent before the restart has failed
+        if let Some(receipt) = &mut forced_exit_sender.core_interaction_wrapper.tx_receipt {
+            receipt.success = false;
+        }
+
+        let tx_hash = TxHash::default();
+        add_request(
+            &forced_exit_sender.core_interaction_wrapper.requests,
+            ForcedExitRequest {
+                id: 12,
+                target: Address::random(),
+                tokens: vec![TokenId(1), TokenId(2)],
+                price_in_wei: BigUint::from_str("10000000000").unwrap(),
+                valid_until: Utc::now().add(day),
+                created_at: Utc::now(),
+                fulfilled_by: Some(vec![tx_hash]),
+                fulfilled_at: None,
+            },
+        );
+        let payer = Address::random();
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+        wrapper.set_payer(12, payer).await.unwrap();
+        wrapper
+            .set_token_exit_status(12, TokenId(1), ForcedExitTokenStatus::Sent, Some(tx_hash))
+            .await
+            .unwrap();
+        wrapper
+            .set_token_exit_status(12, TokenId(2), ForcedExitTokenStatus::Skipped, None)
+            .await
+            .unwrap();
+
+        forced_exit_sender.await_unconfirmed().await.unwrap();
+
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+        // The whole payment is returned, since neither of the tokens was exited
+        let refunds = wrapper.refunds.lock().unwrap();
+        assert_eq!(refunds.len(), 1);
+        assert_eq!(refunds[0].recipient, payer);
+        assert_eq!(refunds[0].amount, BigUint::from_str("10000000000").unwrap());
+
+        let sent_txs = wrapper.sent_txs.lock().unwrap();
+        assert_eq!(sent_txs.len(), 1);
+        assert!(matches!(sent_txs[0].tx, ZkSyncTx::Transfer(_)));
+
+        let requests = wrapper.requests.lock().unwrap();
+        assert!(requests[0].fulfilled_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_forced_exit_sender_saved_refund_is_reused() {
+        let mut forced_exit_sender = get_test_forced_exit_sender(None);
+
+        let payer = Address::random();
+        let amount = BigUint::from_str("10000000000").unwrap();
+        let tx = forced_exit_sender.build_refund(Nonce(0), payer, amount.clone());
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+        wrapper.store_refund(12, payer, amount).await.unwrap();
+        wrapper.save_refund_tx(12, &tx).await.unwrap();
+
+        // The saved transfer is executed already, so nothing is sent again
+        forced_exit_sender.process_refunds().await.unwrap();
+
+        let wrapper = &forced_exit_sender.core_interaction_wrapper;
+        assert!(wrapper.sent_txs.lock().unwrap().is_empty());
+        let refunds = wrapper.refunds.lock().unwrap();
+        assert_eq!(refunds[0].status, ForcedExitRefundStatus::Completed);
+        assert_eq!(refunds[0].tx_hash, Some(tx.hash()));
+    }
 }
@@ -1,12 +1,16 @@
 use std::{ops::Sub, sync::Mutex};
 
 use chrono::Utc;
+use num::BigUint;
 use zksync_storage::chain::operations_ext::records::TxReceiptResponse;
 use zksync_types::Nonce;
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId},
+    forced_exit_requests::{
+        ForcedExitRefund, ForcedExitRefundStatus, ForcedExitRequest, ForcedExitRequestId,
+        ForcedExitTokenExit, ForcedExitTokenStatus,
+    },
     tx::TxHash,
-    AccountId, SignedZkSyncTx,
+    AccountId, Address, SignedZkSyncTx, TokenId,
 };
 
 use super::core_interaction_wrapper::CoreInteractionWrapper;
@@ -18,6 +22,12 @@ pub struct MockCoreInteractionWrapper {
     pub sent_txs: Mutex<Vec<SignedZkSyncTx>>,
     // It is easier when keeping track of the deleted txs
     pub deleted_requests: Mutex<Vec<ForcedExitRequest>>,
+    // Tokens the targets have balance in, `None` means all the tokens
+    pub exitable_tokens: Option<Vec<TokenId>>,
+    pub token_exits: Mutex<Vec<(ForcedExitRequestId, ForcedExitTokenExit)>>,
+    pub refunds: Mutex<Vec<ForcedExitRefund>>,
+    pub refund_txs: Mutex<Vec<(ForcedExitRequestId, SignedZkSyncTx)>>,
+    pub payers: Mutex<Vec<(ForcedExitRequestId, Address)>>,
 }
 
 impl Default for MockCoreInteractionWrapper {
@@ -36,6 +46,11 @@ impl Default for MockCoreInteractionWrapper {
             }),
             sent_txs: Mutex::new(vec![]),
             deleted_requests: Mutex::new(vec![]),
+            exitable_tokens: None,
+            token_exits: Mutex::new(vec![]),
+            refunds: Mutex::new(vec![]),
+            refund_txs: Mutex::new(vec![]),
+            payers: Mutex::new(vec![]),
         }
     }
 }
@@ -64,6 +79,10 @@ impl MockCoreInteractionWrapper {
             .lock()
             .expect("Failed to allocate deleted requests")
     }
+
+    fn lock_refunds(&self) -> std::sync::MutexGuard<'_, Vec<ForcedExitRefund>> {
+        self.refunds.lock().expect("Failed to get the write lock")
+    }
 }
 
 #[async_trait::async_trait]
@@ -168,6 +187,169 @@ impl CoreInteractionWrapper for MockCoreInteractionWrapper {
         // For tests it is better to just return true all the time
         Ok(true)
     }
+
+    async fn get_exitable_tokens(
+        &self,
+        request: &ForcedExitRequest,
+    ) -> anyhow::Result<Vec<TokenId>> {
+        let tokens = match &self.exitable_tokens {
+            Some(exitable) => request
+                .tokens
+                .iter()
+                .copied()
+                .filter(|token| exitable.contains(token))
+                .collect(),
+            None => request.tokens.clone(),
+        };
+
+        Ok(tokens)
+    }
+
+    async fn get_token_exits(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> anyhow::Result<Vec<ForcedExitTokenExit>> {
+        let exits = self.token_exits.lock().unwrap();
+
+        Ok(exits
+            .iter()
+            .filter(|(request_id, _)| *request_id == id)
+            .map(|(_, exit)| exit.clone())
+            .collect())
+    }
+
+    async fn set_token_exit_status(
+        &self,
+        id: ForcedExitRequestId,
+        token: TokenId,
+        status: ForcedExitTokenStatus,
+        tx_hash: Option<TxHash>,
+    ) -> anyhow::Result<()> {
+        let mut exits = self.token_exits.lock().unwrap();
+
+        match exits
+            .iter_mut()
+            .find(|(request_id, exit)| *request_id == id && exit.token == token)
+        {
+            Some((_, exit)) => {
+                exit.status = status;
+                exit.tx_hash = tx_hash.or(exit.tx_hash);
+            }
+            None => exits.push((
+                id,
+                ForcedExitTokenExit {
+                    token,
+                    status,
+                    tx_hash,
+                },
+            )),
+        }
+
+        Ok(())
+    }
+
+    async fn set_payer(&self, id: ForcedExitRequestId, payer: Address) -> anyhow::Result<()> {
+        let mut payers = self.payers.lock().unwrap();
+
+        payers.retain(|(request_id, _)| *request_id != id);
+        payers.push((id, payer));
+
+        Ok(())
+    }
+
+    async fn get_payer(&self, id: ForcedExitRequestId) -> anyhow::Result<Option<Address>> {
+        let payers = self.payers.lock().unwrap();
+
+        Ok(payers
+            .iter()
+            .find(|(request_id, _)| *request_id == id)
+            .map(|(_, payer)| *payer))
+    }
+
+    async fn store_refund(
+        &self,
+        id: ForcedExitRequestId,
+        recipient: Address,
+        amount: BigUint,
+    ) -> anyhow::Result<()> {
+        let mut refunds = self.lock_refunds();
+
+        if refunds.iter().all(|refund| refund.request_id != id) {
+            refunds.push(ForcedExitRefund {
+                request_id: id,
+                recipient,
+                amount,
+                status: ForcedExitRefundStatus::Pending,
+                tx_hash: None,
+                created_at: Utc::now(),
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn get_unfinished_refunds(&self) -> anyhow::Result<Vec<ForcedExitRefund>> {
+        let refunds = self.lock_refunds();
+
+        Ok(refunds
+            .iter()
+            .filter(|refund| {
+                matches!(
+                    refund.status,
+                    ForcedExitRefundStatus::Pending | ForcedExitRefundStatus::Sent
+                )
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn set_refund_status(
+        &self,
+        id: ForcedExitRequestId,
+        status: ForcedExitRefundStatus,
+        tx_hash: Option<TxHash>,
+    ) -> anyhow::Result<()> {
+        let mut refunds = self.lock_refunds();
+
+        let refund = refunds
+            .iter_mut()
+            .find(|refund| refund.request_id == id)
+            .ok_or_else(|| anyhow::Error::msg("Refund not found"))?;
+        refund.status = status;
+        refund.tx_hash = tx_hash.or(refund.tx_hash);
+
+        Ok(())
+    }
+
+    async fn save_refund_tx(
+        &self,
+        id: ForcedExitRequestId,
+        tx: &SignedZkSyncTx,
+    ) -> anyhow::Result<()> {
+        self.set_refund_status(id, ForcedExitRefundStatus::Sent, Some(tx.hash()))
+            .await?;
+        self.refund_txs.lock().unwrap().push((id, tx.clone()));
+
+        Ok(())
+    }
+
+    async fn get_refund_tx(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> anyhow::Result<Option<SignedZkSyncTx>> {
+        let refund_txs = self.refund_txs.lock().unwrap();
+
+        Ok(refund_txs
+            .iter()
+            .find(|(request_id, _)| *request_id == id)
+            .map(|(_, tx)| tx.clone()))
+    }
+
+    async fn send_refund(&mut self, tx: SignedZkSyncTx) -> anyhow::Result<()> {
+        self.lock_sent_txs().push(tx);
+
+        Ok(())
+    }
 }
 
 pub fn add_request(requests: &Mutex<Vec<ForcedExitRequest>>, new_request: ForcedExitRequest) {
@@ -0,0 +1,277 @@
+//! Prover server API v2.
+//!
+//! Provers are authenticated with the personal tokens from the `prover_tokens` table
+//! rather than the shared JWT secret, may take several jobs per request and report
+//! the progress of the jobs in the heartbeats. All the errors are returned as `ApiError`.
+
+// Built-in
+use std::fmt;
+// External
+use actix_web::dev::{HttpServiceFactory, ServiceRequest};
+use actix_web::{http::StatusCode, web, HttpMessage, HttpResponse, ResponseError};
+use actix_web_httpauth::extractors::bearer::BearerAuth;
+use actix_web_httpauth::middleware::HttpAuthentication;
+use sha2::{Digest, Sha256};
+// Workspace deps
+use zksync_prover_utils::api::{
+    v2::{
+        ApiError, ErrorCode, HeartbeatRequest, HeartbeatResponse, Job, JobsRequest, JobsResponse,
+    },
+    ProverOutputRequest,
+};
+use zksync_types::prover::ProverJobFilter;
+// Local deps
+use crate::{is_duplicate_proof, store_job_result, AppState, DatabaseInterface};
+
+/// Maximum amount of jobs handed out per request.
+const MAX_JOBS_PER_REQUEST: u32 = 16;
+
+/// Name of the prover that owns the token the request was authenticated with.
+#[derive(Debug, Clone)]
+struct AuthenticatedProver(String);
+
+/// Wrapper making `ApiError` an actix error.
+#[derive(Debug)]
+struct Error(ApiError);
+
+impl Error {
+    fn unauthorized(message: impl ToString) -> Self {
+        Self(ApiError::new(ErrorCode::Unauthorized, message))
+    }
+
+    fn invalid_request(message: impl ToString) -> Self {
+        Self(ApiError::new(ErrorCode::InvalidRequest, message))
+    }
+
+    fn job_not_leased(message: impl ToString) -> Self {
+        Self(ApiError::new(ErrorCode::JobNotLeased, message))
+    }
+
+    fn storage(err: anyhow::Error) -> Self {
+        vlog::warn!("Prover API v2 storage layer error: {}", err);
+        Self(ApiError::new(
+            ErrorCode::StorageError,
+            "storage layer error",
+        ))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for Error {
+    fn status_code(&self) -> StatusCode {
+        match self.0.code {
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::InvalidRequest => StatusCode::BAD_REQUEST,
+            ErrorCode::DuplicateProof | ErrorCode::JobNotLeased => StatusCode::CONFLICT,
+            ErrorCode::StorageError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(&self.0)
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Returns the hash the token is stored under in the `prover_tokens` table.
+pub(crate) fn prover_token_hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+async fn authenticate<DB: DatabaseInterface>(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> actix_web::Result<ServiceRequest> {
+    let data = req
+        .app_data::<web::Data<AppState<DB>>>()
+        .expect("failed get AppState upon receipt of the authentication token")
+        .clone();
+    let mut storage = data
+        .database
+        .acquire_connection()
+        .await
+        .map_err(Error::storage)?;
+    let prover_name = data
+        .database
+        .load_prover_by_token(&mut storage, &prover_token_hash(credentials.token()))
+        .await
+        .map_err(Error::storage)?
+        .ok_or_else(|| Error::unauthorized("unknown or revoked token"))?;
+
+    req.extensions_mut()
+        .insert(AuthenticatedProver(prover_name));
+    Ok(req)
+}
+
+async fn get_jobs<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    prover: web::ReqData<AuthenticatedProver>,
+    r: web::Json<JobsRequest>,
+) -> Result<HttpResponse> {
+    if r.max_jobs == 0 || r.max_jobs > MAX_JOBS_PER_REQUEST {
+        return Err(Error::invalid_request(format!(
+            "max_jobs must be in range [1, {}]",
+            MAX_JOBS_PER_REQUEST
+        )));
+    }
+    let filter = ProverJobFilter {
+        max_block_chunks: r.capabilities.max_block_chunks,
+        aggregated_proofs: r.capabilities.aggregated_proofs,
+    };
+
+    let mut storage = data
+        .database
+        .acquire_connection()
+        .await
+        .map_err(Error::storage)?;
+    let prover_jobs = data
+        .database
+        .load_idle_prover_jobs_from_job_queue(
+            &mut storage,
+            &prover.0,
+            data.job_lease,
+            r.max_jobs,
+            filter,
+        )
+        .await
+        .map_err(Error::storage)?;
+
+    let jobs = prover_jobs
+        .into_iter()
+        .map(|job| {
+            Ok(Job {
+                job_id: job.job_id,
+                first_block: job.first_block,
+                last_block: job.last_block,
+                data: serde_json::from_value(job.job_data)
+                    .map_err(|err| Error::storage(err.into()))?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !jobs.is_empty() {
+        vlog::info!("Prover '{}' took {} jobs", prover.0, jobs.len());
+    }
+
+    Ok(HttpResponse::Ok().json(JobsResponse { jobs }))
+}
+
+async fn heartbeat<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    prover: web::ReqData<AuthenticatedProver>,
+    r: web::Json<HeartbeatRequest>,
+) -> Result<HttpResponse> {
+    if let Some(job) = r.jobs.iter().find(|job| job.progress > Some(100)) {
+        return Err(Error::invalid_request(format!(
+            "progress of job {} is above 100%",
+            job.job_id
+        )));
+    }
+
+    let mut storage = data
+        .database
+        .acquire_connection()
+        .await
+        .map_err(Error::storage)?;
+    let mut lost_jobs = Vec::new();
+    for job in &r.jobs {
+        let lease_renewed = data
+            .database
+            .record_prover_is_working(&mut storage, job.job_id, &prover.0, data.job_lease)
+            .await
+            .map_err(Error::storage)?;
+        if !lease_renewed {
+            vlog::warn!(
+                "Prover '{}' is working on job {} which is not leased to it",
+                prover.0,
+                job.job_id
+            );
+            lost_jobs.push(job.job_id);
+            continue;
+        }
+
+        if let Some(progress) = job.progress {
+            data.database
+                .record_job_progress(&mut storage, job.job_id, &prover.0, progress)
+                .await
+                .map_err(Error::storage)?;
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(HeartbeatResponse { lost_jobs }))
+}
+
+async fn publish<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    prover: web::ReqData<AuthenticatedProver>,
+    r: web::Json<ProverOutputRequest>,
+) -> Result<HttpResponse> {
+    let mut storage = data
+        .database
+        .acquire_connection()
+        .await
+        .map_err(Error::storage)?;
+    let leased = data
+        .database
+        .is_job_leased_to(&mut storage, r.job_id, &prover.0)
+        .await
+        .map_err(Error::storage)?;
+    if !leased {
+        return Err(Error::job_not_leased(format!(
+            "job {} is not leased to prover '{}'",
+            r.job_id, prover.0
+        )));
+    }
+
+    store_job_result(&data.database, &mut storage, &r)
+        .await
+        .map_err(|err| {
+            if is_duplicate_proof(&err) {
+                Error(ApiError::new(
+                    ErrorCode::DuplicateProof,
+                    format!("proof for job {} is already stored", r.job_id),
+                ))
+            } else {
+                Error::storage(err)
+            }
+        })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn stopped<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    prover: web::ReqData<AuthenticatedProver>,
+) -> Result<HttpResponse> {
+    vlog::info!(
+        "Prover instance '{}' send a stopping notification",
+        prover.0
+    );
+
+    let mut storage = data
+        .database
+        .acquire_connection()
+        .await
+        .map_err(Error::storage)?;
+    data.database
+        .record_prover_stop(&mut storage, &prover.0)
+        .await
+        .map_err(Error::storage)?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Returns the scope with the v2 endpoints, the `AppState` is expected to be set for the app.
+pub(crate) fn api_scope<DB: DatabaseInterface>() -> impl HttpServiceFactory {
+    web::scope("/api/v2")
+        .wrap(HttpAuthentication::bearer(authenticate::<DB>))
+        .route("/jobs", web::post().to(get_jobs::<DB>))
+        .route("/heartbeat", web::post().to(heartbeat::<DB>))
+        .route("/publish", web::post().to(publish::<DB>))
+        .route("/stopped", web::post().to(stopped::<DB>))
+}
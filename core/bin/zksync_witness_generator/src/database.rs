@@ -5,13 +5,18 @@
 
 // Built-in
 use std::clone::Clone;
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use chrono::{DateTime, Utc};
 // Workspace uses
+use zksync_blob_store::{BlobRef, ContentStore};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobFilter, ProverJobType, ProverJobsStats},
     AccountMap, AccountUpdates, BlockNumber,
 };
 // Local uses
@@ -21,15 +26,56 @@ const NUMBER_OF_STORED_ACCOUNT_TREE_CACHE: u32 = 300;
 
 /// The actual database wrapper.
 /// This structure uses `StorageProcessor` to interact with an existing database.
+///
+/// If the blob store is configured, witnesses and prover job data are kept there,
+/// and the database holds only the references to them.
 #[derive(Debug, Clone)]
 pub struct Database {
     /// Connection to the database.
     db_pool: ConnectionPool,
+    /// Store for the witnesses and prover job data.
+    blob_store: Option<ContentStore>,
 }
 
 impl Database {
     pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+        Self {
+            db_pool,
+            blob_store: None,
+        }
+    }
+
+    pub fn with_blob_store(mut self, blob_store: Option<ContentStore>) -> Self {
+        self.blob_store = blob_store;
+        self
+    }
+
+    fn blob_store(&self) -> anyhow::Result<&ContentStore> {
+        self.blob_store
+            .as_ref()
+            .ok_or_else(|| format_err!("Data is kept in the blob store, but it is not configured"))
+    }
+
+    /// Replaces the prover job data with the reference to it if the blob store is used.
+    async fn offload_job_data(
+        &self,
+        job_data: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        match &self.blob_store {
+            Some(blob_store) => Ok(blob_store.put_json(&job_data).await?.to_json()),
+            None => Ok(job_data),
+        }
+    }
+
+    /// Loads the prover job data if it's kept in the blob store.
+    async fn resolve_job_data(
+        &self,
+        job_data: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        match BlobRef::from_json(&job_data) {
+            Some(blob_ref) => self.blob_store()?.get_json(&blob_ref).await,
+            None => Ok(job_data),
+        }
     }
 }
 
@@ -59,7 +105,21 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         block_number: BlockNumber,
     ) -> anyhow::Result<Option<serde_json::Value>> {
-        let witness = connection.prover_schema().get_witness(block_number).await?;
+        let record = connection
+            .prover_schema()
+            .get_witness_record(block_number)
+            .await?;
+        let witness = match record {
+            Some(record) => match (record.witness, record.blob_hash) {
+                (Some(witness), _) => Some(serde_json::from_str(&witness)?),
+                (None, Some(blob_hash)) => {
+                    let blob_ref = BlobRef { blob_hash };
+                    Some(self.blob_store()?.get_json(&blob_ref).await?)
+                }
+                (None, None) => None,
+            },
+            None => None,
+        };
 
         Ok(witness)
     }
@@ -73,6 +133,7 @@ impl DatabaseInterface for Database {
         job_priority: i32,
         job_type: ProverJobType,
     ) -> anyhow::Result<()> {
+        let job_data = self.offload_job_data(job_data).await?;
         connection
             .prover_schema()
             .add_prover_job_to_job_queue(first_block, last_block, job_data, job_priority, job_type)
@@ -109,8 +170,12 @@ impl DatabaseInterface for Database {
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
+        max_attempts: u32,
     ) -> anyhow::Result<()> {
-        connection.prover_schema().mark_stale_jobs_as_idle().await?;
+        connection
+            .prover_schema()
+            .mark_stale_jobs_as_idle(max_attempts)
+            .await?;
 
         Ok(())
     }
@@ -154,13 +219,40 @@ impl DatabaseInterface for Database {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease: Duration,
     ) -> anyhow::Result<Option<ProverJob>> {
-        let proof = connection
+        let job = connection
             .prover_schema()
-            .get_idle_prover_job_from_job_queue()
+            .get_idle_prover_job_from_job_queue(prover_name, lease)
             .await?;
 
-        Ok(proof)
+        match job {
+            Some(mut job) => {
+                job.job_data = self.resolve_job_data(job.job_data).await?;
+                Ok(Some(job))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn load_idle_prover_jobs_from_job_queue(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease: Duration,
+        limit: u32,
+        filter: ProverJobFilter,
+    ) -> anyhow::Result<Vec<ProverJob>> {
+        let mut jobs = connection
+            .prover_schema()
+            .get_idle_prover_jobs_from_job_queue(prover_name, lease, limit, filter)
+            .await?;
+
+        for job in &mut jobs {
+            job.job_data = self.resolve_job_data(job.job_data.take()).await?;
+        }
+        Ok(jobs)
     }
 
     async fn record_prover_is_working(
@@ -168,13 +260,56 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
-        connection
+        lease: Duration,
+    ) -> anyhow::Result<bool> {
+        let renewed = connection
             .prover_schema()
-            .record_prover_is_working(job_id, prover_name)
+            .record_prover_is_working(job_id, prover_name, lease)
             .await?;
 
-        Ok(())
+        Ok(renewed)
+    }
+
+    async fn is_job_leased_to(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+    ) -> anyhow::Result<bool> {
+        let leased = connection
+            .prover_schema()
+            .is_job_leased_to(job_id, prover_name)
+            .await?;
+
+        Ok(leased)
+    }
+
+    async fn record_job_progress(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+        progress: u8,
+    ) -> anyhow::Result<bool> {
+        let recorded = connection
+            .prover_schema()
+            .record_job_progress(job_id, prover_name, progress)
+            .await?;
+
+        Ok(recorded)
+    }
+
+    async fn load_prover_by_token(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        token_hash: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let prover_name = connection
+            .prover_schema()
+            .get_prover_by_token(token_hash)
+            .await?;
+
+        Ok(prover_name)
     }
 
     async fn store_proof(
@@ -276,10 +411,65 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         block: BlockNumber,
         witness: serde_json::Value,
+    ) -> anyhow::Result<()> {
+        match &self.blob_store {
+            Some(blob_store) => {
+                let blob_ref = blob_store.put_json(&witness).await?;
+                connection
+                    .prover_schema()
+                    .store_witness_blob(block, &blob_ref.blob_hash)
+                    .await?;
+            }
+            None => {
+                connection
+                    .prover_schema()
+                    .store_witness(block, witness)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn claim_block_for_witness(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        instance: &str,
+        from_block: BlockNumber,
+        lease: Duration,
+    ) -> anyhow::Result<Option<BlockNumber>> {
+        let block = connection
+            .prover_schema()
+            .claim_block_for_witness(instance, from_block, lease)
+            .await?;
+
+        Ok(block)
+    }
+
+    async fn renew_witness_block_lease(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+        instance: &str,
+        lease: Duration,
+    ) -> anyhow::Result<bool> {
+        let renewed = connection
+            .prover_schema()
+            .renew_witness_block_lease(block, instance, lease)
+            .await?;
+
+        Ok(renewed)
+    }
+
+    async fn release_witness_block(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+        instance: &str,
     ) -> anyhow::Result<()> {
         connection
             .prover_schema()
-            .store_witness(block, witness)
+            .release_witness_block(block, instance)
             .await?;
 
         Ok(())
@@ -293,4 +483,68 @@ impl DatabaseInterface for Database {
 
         Ok(count)
     }
+
+    async fn load_idle_jobs_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<(u32, Option<DateTime<Utc>>)> {
+        let stats = connection.prover_schema().idle_jobs_stats().await?;
+
+        Ok(stats)
+    }
+
+    async fn load_prover_jobs_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        proving_time_window: Duration,
+    ) -> anyhow::Result<Vec<ProverJobsStats>> {
+        let stats = connection
+            .prover_schema()
+            .prover_jobs_stats(proving_time_window)
+            .await?;
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_blob_store::FileBlobStore;
+
+    /// Checks that the job data is replaced with the reference to the blob and loaded back,
+    /// and that the data kept in the database is returned as is.
+    #[tokio::test]
+    async fn job_data_offload_roundtrip() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join(format!(
+            "zksync_witness_generator_blobs_{}",
+            std::process::id()
+        ));
+        let blob_store = ContentStore::new(FileBlobStore::new(&root), 3);
+        let database =
+            Database::new(ConnectionPool::new(Some(1))).with_blob_store(Some(blob_store));
+
+        let job_data = serde_json::json!({ "operations": vec![42u64; 100] });
+        let offloaded = database.offload_job_data(job_data.clone()).await?;
+        assert!(BlobRef::from_json(&offloaded).is_some());
+        assert_eq!(database.resolve_job_data(offloaded).await?, job_data);
+        assert_eq!(database.resolve_job_data(job_data.clone()).await?, job_data);
+
+        // Blobs can't be resolved if the blob store is not configured.
+        let database_without_blobs = Database::new(ConnectionPool::new(Some(1)));
+        let offloaded = database.offload_job_data(job_data.clone()).await?;
+        assert_eq!(
+            database_without_blobs
+                .offload_job_data(job_data.clone())
+                .await?,
+            job_data
+        );
+        assert!(database_without_blobs
+            .resolve_job_data(offloaded)
+            .await
+            .is_err());
+
+        tokio::fs::remove_dir_all(root).await?;
+        Ok(())
+    }
 }
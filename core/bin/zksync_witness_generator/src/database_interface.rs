@@ -6,13 +6,16 @@
 // Built-in
 use std::clone::Clone;
 use std::marker::{Send, Sync};
+use std::time::Duration;
+// External uses
+use chrono::{DateTime, Utc};
 // Workspace uses
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_storage::StorageProcessor;
 use zksync_types::aggregated_operations::{AggregatedActionType, AggregatedOperation};
 use zksync_types::{
     block::Block,
-    prover::{ProverJob, ProverJobType},
+    prover::{ProverJob, ProverJobFilter, ProverJobType, ProverJobsStats},
     AccountMap, AccountUpdates, BlockNumber,
 };
 
@@ -62,6 +65,7 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
     async fn mark_stale_jobs_as_idle(
         &self,
         connection: &mut StorageProcessor<'_>,
+        max_attempts: u32,
     ) -> anyhow::Result<()>;
 
     async fn load_last_verified_block(
@@ -83,14 +87,49 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
     async fn load_idle_prover_job_from_job_queue(
         &self,
         connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease: Duration,
     ) -> anyhow::Result<Option<ProverJob>>;
 
+    async fn load_idle_prover_jobs_from_job_queue(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease: Duration,
+        limit: u32,
+        filter: ProverJobFilter,
+    ) -> anyhow::Result<Vec<ProverJob>>;
+
     async fn record_prover_is_working(
         &self,
         connection: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()>;
+        lease: Duration,
+    ) -> anyhow::Result<bool>;
+
+    /// Returns `true` if the job is in progress and leased to the prover.
+    async fn is_job_leased_to(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+    ) -> anyhow::Result<bool>;
+
+    async fn record_job_progress(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+        progress: u8,
+    ) -> anyhow::Result<bool>;
+
+    /// Returns the name of the prover owning the active token with such hash.
+    async fn load_prover_by_token(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        token_hash: &str,
+    ) -> anyhow::Result<Option<String>>;
 
     async fn store_proof(
         &self,
@@ -142,8 +181,44 @@ pub trait DatabaseInterface: Send + Sync + Clone + 'static {
         witness: serde_json::Value,
     ) -> anyhow::Result<()>;
 
+    async fn claim_block_for_witness(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        instance: &str,
+        from_block: BlockNumber,
+        lease: Duration,
+    ) -> anyhow::Result<Option<BlockNumber>>;
+
+    async fn renew_witness_block_lease(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+        instance: &str,
+        lease: Duration,
+    ) -> anyhow::Result<bool>;
+
+    async fn release_witness_block(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+        instance: &str,
+    ) -> anyhow::Result<()>;
+
     async fn pending_jobs_count(
         &self,
         connection: &mut StorageProcessor<'_>,
     ) -> anyhow::Result<u32>;
+
+    /// Returns the amount of jobs waiting for a prover and the time since which they are waiting.
+    async fn load_idle_jobs_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<(u32, Option<DateTime<Utc>>)>;
+
+    /// Returns the statistics of the pending and recently proven jobs grouped by the job type and size.
+    async fn load_prover_jobs_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        proving_time_window: Duration,
+    ) -> anyhow::Result<Vec<ProverJobsStats>>;
 }
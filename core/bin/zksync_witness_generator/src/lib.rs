@@ -11,15 +11,17 @@ use actix_web_httpauth::extractors::{
 };
 use actix_web_httpauth::middleware::HttpAuthentication;
 
+use chrono::Utc;
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 // Workspace deps
-use zksync_config::ProverConfig;
+use zksync_config::{configs::prover::Scaler as ScalerConfig, NotifierConfig, ProverConfig};
+use zksync_notifier::{Notification, Notifier};
 // Local deps
 use self::database_interface::DatabaseInterface;
-use self::scaler::ScalerOracle;
+use self::scaler::{ScalerOracle, SizeReplicas};
 use tokio::task::JoinHandle;
 use zksync_circuit::serialization::ProverData;
 use zksync_config::configs::api::ProverApiConfig;
@@ -39,11 +41,15 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 #[cfg(test)]
 mod tests;
 
+mod api_v2;
 pub mod database;
 mod database_interface;
 mod scaler;
 mod witness_generator;
 
+/// Interval of checking whether the provers take the pending jobs.
+const PROVER_IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Serialize, Deserialize)]
 struct PayloadAuthToken {
     /// Subject (whom auth token refers to).
@@ -57,19 +63,29 @@ struct AppState<DB: DatabaseInterface> {
     secret_auth: String,
     database: DB,
     scaler_oracle: Arc<RwLock<ScalerOracle<DB>>>,
+    /// Period the job is leased to the prover for, renewed on every heartbeat.
+    job_lease: Duration,
 }
 
 impl<DB: DatabaseInterface> AppState<DB> {
-    pub fn new(secret_auth: String, database: DB, idle_provers: u32) -> Self {
+    pub fn new(
+        secret_auth: String,
+        database: DB,
+        idle_provers: u32,
+        scaler_opts: ScalerConfig,
+        job_lease: Duration,
+    ) -> Self {
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             database.clone(),
             idle_provers,
+            scaler_opts,
         )));
 
         Self {
             secret_auth,
             database,
             scaler_oracle,
+            job_lease,
         }
     }
 
@@ -129,7 +145,7 @@ async fn get_job<DB: DatabaseInterface>(
     let mut storage = data.access_storage().await?;
     let ret = data
         .database
-        .load_idle_prover_job_from_job_queue(&mut storage)
+        .load_idle_prover_job_from_job_queue(&mut storage, &r.prover_name, data.job_lease)
         .await
         .map_err(|e| {
             vlog::warn!("could not get next unverified commit operation: {}", e);
@@ -167,34 +183,43 @@ async fn working_on<DB: DatabaseInterface>(
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
-    data.database
-        .record_prover_is_working(&mut storage, r.job_id, &r.prover_name)
+    let lease_renewed = data
+        .database
+        .record_prover_is_working(&mut storage, r.job_id, &r.prover_name, data.job_lease)
         .await
         .map_err(|e| {
             vlog::warn!("failed to record prover work in progress request: {}", e);
             actix_web::error::ErrorInternalServerError("storage layer error")
         })?;
+    if !lease_renewed {
+        vlog::warn!(
+            "Prover '{}' is working on job {} which is not leased to it",
+            r.prover_name,
+            r.job_id
+        );
+        return Err(actix_web::error::ErrorConflict(
+            "job is not leased to the prover",
+        ));
+    }
 
     Ok(HttpResponse::Ok().finish())
 }
 
-async fn publish<DB: DatabaseInterface>(
-    data: web::Data<AppState<DB>>,
-    r: web::Json<ProverOutputRequest>,
-) -> actix_web::Result<HttpResponse> {
-    let mut storage = data
-        .access_storage()
-        .await
-        .map_err(actix_web::error::ErrorInternalServerError)?;
-    let storage_result = match &r.data {
+/// Stores the proof received from the prover.
+async fn store_job_result<DB: DatabaseInterface>(
+    database: &DB,
+    storage: &mut zksync_storage::StorageProcessor<'_>,
+    r: &ProverOutputRequest,
+) -> anyhow::Result<()> {
+    match &r.data {
         JobResultData::BlockProof(single_proof) => {
             vlog::info!(
                 "Received a proof for job: {}, single block: {}",
                 r.job_id,
                 r.first_block
             );
-            data.database
-                .store_proof(&mut storage, r.job_id, r.first_block, single_proof)
+            database
+                .store_proof(storage, r.job_id, r.first_block, single_proof)
                 .await
         }
         JobResultData::AggregatedBlockProof(aggregated_proof) => {
@@ -204,9 +229,9 @@ async fn publish<DB: DatabaseInterface>(
                 r.first_block,
                 r.last_block
             );
-            data.database
+            database
                 .store_aggregated_proof(
-                    &mut storage,
+                    storage,
                     r.job_id,
                     r.first_block,
                     r.last_block,
@@ -214,10 +239,25 @@ async fn publish<DB: DatabaseInterface>(
                 )
                 .await
         }
-    };
-    if let Err(e) = storage_result {
+    }
+}
+
+/// Checks whether the proof wasn't stored because there is a proof for the same blocks already.
+fn is_duplicate_proof(err: &anyhow::Error) -> bool {
+    err.to_string().contains("duplicate key")
+}
+
+async fn publish<DB: DatabaseInterface>(
+    data: web::Data<AppState<DB>>,
+    r: web::Json<ProverOutputRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if let Err(e) = store_job_result(&data.database, &mut storage, &r).await {
         vlog::error!("failed to store received proof: {}", e);
-        let message = if e.to_string().contains("duplicate key") {
+        let message = if is_duplicate_proof(&e) {
             "duplicate key"
         } else {
             "storage layer error"
@@ -266,6 +306,10 @@ pub struct RequiredReplicasOutput {
     /// Amount of the prover entities required for server
     /// to run optimally.
     needed_count: u32,
+    /// Amount of the provers required for the single proofs, by the block chunk size.
+    block_chunks_sizes: Vec<SizeReplicas>,
+    /// Amount of the provers required for the aggregated proofs, by the amount of blocks.
+    aggregation_sizes: Vec<SizeReplicas>,
 }
 
 async fn required_replicas<DB: DatabaseInterface>(
@@ -274,12 +318,16 @@ async fn required_replicas<DB: DatabaseInterface>(
 ) -> actix_web::Result<HttpResponse> {
     let mut oracle = data.scaler_oracle.write().await;
 
-    let needed_count = oracle
+    let required = oracle
         .provers_required()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
 
-    let response = RequiredReplicasOutput { needed_count };
+    let response = RequiredReplicasOutput {
+        needed_count: required.total,
+        block_chunks_sizes: required.block_chunks_sizes,
+        aggregation_sizes: required.aggregation_sizes,
+    };
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -287,6 +335,7 @@ async fn required_replicas<DB: DatabaseInterface>(
 async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     database: DB,
     prepare_data_interval: Duration,
+    max_job_attempts: u32,
 ) {
     // We use `prepare_data_interval` as timeout in this function to align creating prover jobs
     // with witness generator routine.
@@ -295,7 +344,7 @@ async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     loop {
         interval.tick().await;
 
-        update_prover_job_queue(database.clone())
+        update_prover_job_queue(database.clone(), max_job_attempts)
             .await
             .unwrap_or_else(|e| {
                 vlog::warn!("Failed to update prover job queue: {}", e);
@@ -303,7 +352,10 @@ async fn update_prover_job_queue_loop<DB: DatabaseInterface>(
     }
 }
 
-async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow::Result<()> {
+async fn update_prover_job_queue<DB: DatabaseInterface>(
+    database: DB,
+    max_job_attempts: u32,
+) -> anyhow::Result<()> {
     let mut connection = database.acquire_connection().await?;
     {
         let next_single_block_to_add = database
@@ -381,18 +433,77 @@ async fn update_prover_job_queue<DB: DatabaseInterface>(database: DB) -> anyhow:
                 .await?;
         }
     }
-    database.mark_stale_jobs_as_idle(&mut connection).await?;
+    database
+        .mark_stale_jobs_as_idle(&mut connection, max_job_attempts)
+        .await?;
 
     Ok(())
 }
 
+/// Alerts the operator once the jobs are waiting for the provers longer than `idle_timeout`.
+/// The alert is repeated only after the provers take the pending jobs.
+async fn prover_idle_watchdog_loop<DB: DatabaseInterface>(
+    database: DB,
+    notifier: Notifier,
+    idle_timeout: Duration,
+) {
+    let mut interval = tokio::time::interval(PROVER_IDLE_CHECK_INTERVAL);
+    let mut alert_sent = false;
+    loop {
+        interval.tick().await;
+
+        match check_provers_idle(&database, idle_timeout).await {
+            Ok(Some(notification)) => {
+                if !alert_sent {
+                    vlog::warn!("{}", notification);
+                    if let Err(e) = notifier.send(notification).await {
+                        vlog::error!("Unable to send the prover idle alert: {}", e);
+                    }
+                    alert_sent = true;
+                }
+            }
+            Ok(None) => alert_sent = false,
+            Err(e) => vlog::warn!("Failed to check whether the provers are idle: {}", e),
+        }
+    }
+}
+
+/// Returns the alert if there are jobs waiting for the provers longer than `idle_timeout`.
+async fn check_provers_idle<DB: DatabaseInterface>(
+    database: &DB,
+    idle_timeout: Duration,
+) -> anyhow::Result<Option<Notification>> {
+    let mut connection = database.acquire_connection().await?;
+    let (idle_jobs, waiting_since) = database.load_idle_jobs_stats(&mut connection).await?;
+    let idle_time = waiting_since
+        .and_then(|waiting_since| (Utc::now() - waiting_since).to_std().ok())
+        .unwrap_or_default();
+    if idle_jobs == 0 || idle_time < idle_timeout {
+        return Ok(None);
+    }
+
+    Ok(Some(Notification::ProverIdle {
+        idle_time,
+        pending_blocks: idle_jobs as u64,
+    }))
+}
+
+/// Returns the name prefix of the witness generators started by this process.
+/// Host name (which is the pod name in k8s) and process ID make it unique across the cluster.
+fn witness_generator_instance_prefix() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "witness_generator".to_string());
+    format!("{}_{}", host, std::process::id())
+}
+
 pub fn run_prover_server<DB: DatabaseInterface>(
     database: DB,
     prover_api_opts: ProverApiConfig,
     prover_opts: ProverConfig,
+    notifier_config: NotifierConfig,
 ) -> JoinHandle<()> {
     let witness_generator_opts = prover_opts.witness_generator;
     let core_opts = prover_opts.core;
+    let scaler_opts = prover_opts.scaler;
     let (handler, panic_sender) = spawn_panic_handler();
 
     thread::Builder::new()
@@ -405,44 +516,53 @@ pub fn run_prover_server<DB: DatabaseInterface>(
                 tokio::spawn(update_prover_job_queue_loop(
                     database.clone(),
                     witness_generator_opts.prepare_data_interval(),
+                    core_opts.max_job_attempts,
+                ));
+                tokio::spawn(prover_idle_watchdog_loop(
+                    database.clone(),
+                    Notifier::from_config(&notifier_config),
+                    notifier_config.prover_idle_timeout(),
                 ));
 
-                let last_verified_block = {
+                let from_block = {
                     let mut storage = database
                         .acquire_connection()
                         .await
                         .expect("Failed to access storage");
 
-                    *database
+                    database
                         .load_last_verified_block(&mut storage)
                         .await
                         .expect("Failed to get last verified block number")
-                        as usize
+                        + 1
                 };
 
                 // Start pool maintainer threads.
+                let instance_prefix = witness_generator_instance_prefix();
                 for offset in 0..witness_generator_opts.witness_generators {
-                    let start_block = (last_verified_block + offset + 1) as u32;
-                    let block_step = witness_generator_opts.witness_generators as u32;
-                    vlog::info!(
-                        "Starting witness generator ({},{})",
-                        start_block,
-                        block_step
-                    );
+                    let instance = format!("{}_{}", instance_prefix, offset);
+                    vlog::info!("Starting witness generator '{}'", instance);
                     let pool_maintainer = witness_generator::WitnessGenerator::new(
                         database.clone(),
                         witness_generator_opts.prepare_data_interval(),
-                        BlockNumber(start_block),
-                        BlockNumber(block_step),
+                        instance,
+                        from_block,
+                        witness_generator_opts.block_lease_timeout(),
                     );
                     pool_maintainer.start(panic_sender.clone());
                 }
                 // Start HTTP server.
                 let secret_auth = prover_api_opts.secret_auth.clone();
                 let idle_provers = core_opts.idle_provers;
+                let job_lease = core_opts.gone_timeout();
                 HttpServer::new(move || {
-                    let app_state =
-                        AppState::new(secret_auth.clone(), database.clone(), idle_provers);
+                    let app_state = AppState::new(
+                        secret_auth.clone(),
+                        database.clone(),
+                        idle_provers,
+                        scaler_opts.clone(),
+                        job_lease,
+                    );
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
                         let secret_auth = req
@@ -457,17 +577,23 @@ pub fn run_prover_server<DB: DatabaseInterface>(
 
                     // By calling `register_data` instead of `data` we're avoiding double
                     // `Arc` wrapping of the object.
+                    // API v2 has its own authentication, so it must be registered before
+                    // the v1 scope which matches all the paths.
                     App::new()
-                        .wrap(auth)
                         .app_data(web::Data::new(app_state))
-                        .route("/status", web::get().to(status))
-                        .route("/get_job", web::get().to(get_job::<DB>))
-                        .route("/working_on", web::post().to(working_on::<DB>))
-                        .route("/publish", web::post().to(publish::<DB>))
-                        .route("/stopped", web::post().to(stopped::<DB>))
-                        .route(
-                            "/api/internal/prover/replicas",
-                            web::post().to(required_replicas::<DB>),
+                        .service(api_v2::api_scope::<DB>())
+                        .service(
+                            web::scope("")
+                                .wrap(auth)
+                                .route("/status", web::get().to(status))
+                                .route("/get_job", web::get().to(get_job::<DB>))
+                                .route("/working_on", web::post().to(working_on::<DB>))
+                                .route("/publish", web::post().to(publish::<DB>))
+                                .route("/stopped", web::post().to(stopped::<DB>))
+                                .route(
+                                    "/api/internal/prover/replicas",
+                                    web::post().to(required_replicas::<DB>),
+                                ),
                         )
                 })
                 .bind(&prover_api_opts.bind_addr())
@@ -1,34 +1,247 @@
 //! Module with utilities for prover scaler service.
 
+// Built-in deps
+use std::time::Duration;
+// External deps
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 // Workspace deps
+use zksync_config::configs::prover::Scaler as ScalerConfig;
+use zksync_types::prover::{ProverJobType, ProverJobsStats};
+// Local deps
 use crate::database_interface::DatabaseInterface;
+
+/// Recommended amount of the provers for the jobs of a certain size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizeReplicas {
+    /// Block size in chunks for the single proofs, amount of blocks for the aggregated proofs.
+    pub size: usize,
+    pub needed_count: u32,
+}
+
+/// Amount of provers required for server to operate optimally.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequiredProvers {
+    /// Total amount of provers, never less than the amount of idle provers.
+    pub total: u32,
+    /// Provers for the single proofs by the block chunk size, smallest size first.
+    pub block_chunks_sizes: Vec<SizeReplicas>,
+    /// Provers for the aggregated proofs by the amount of blocks, smallest size first.
+    pub aggregation_sizes: Vec<SizeReplicas>,
+}
+
 /// Scaler oracle provides information for prover scaler
 /// service about required amount of provers for server
 /// to operate optimally.
 #[derive(Debug)]
 pub struct ScalerOracle<DB: DatabaseInterface> {
-    /// Database access to gather the information about amount of pending blocks.
+    /// Database access to gather the information about pending jobs and proving time.
     db: DB,
 
     /// Number of idle provers running for faster up-scaling.
     idle_provers: u32,
+
+    config: ScalerConfig,
 }
 
 impl<DB: DatabaseInterface> ScalerOracle<DB> {
-    pub fn new(db: DB, idle_provers: u32) -> Self {
-        Self { db, idle_provers }
+    pub fn new(db: DB, idle_provers: u32, config: ScalerConfig) -> Self {
+        Self {
+            db,
+            idle_provers,
+            config,
+        }
     }
 
-    /// Decides how many prover entities should be created depending on the amount of pending blocks.
-    pub async fn provers_required(&mut self) -> anyhow::Result<u32> {
-        // Currently the logic of this method is very simple:
-        // We require a prover for each pending block or IDLE_RROVERS amount if there are not so many
-        // pending jobs.
-
+    /// Decides how many prover entities of each kind should be created, so the pending jobs
+    /// are proven within the target latency (or before the aggregation deadline).
+    pub async fn provers_required(&mut self) -> anyhow::Result<RequiredProvers> {
         let mut storage = self.db.acquire_connection().await?;
-        let pending_jobs = self.db.pending_jobs_count(&mut storage).await?;
-        let provers_required = std::cmp::max(pending_jobs, self.idle_provers);
+        let stats = self
+            .db
+            .load_prover_jobs_stats(&mut storage, self.config.proving_time_window())
+            .await?;
+
+        Ok(self.recommend(&stats, Utc::now()))
+    }
+
+    fn recommend(&self, stats: &[ProverJobsStats], now: DateTime<Utc>) -> RequiredProvers {
+        let mut required = RequiredProvers::default();
+        for stats in stats {
+            let (target_latency, replicas) = match stats.job_type {
+                ProverJobType::SingleProof => (
+                    self.config.target_proof_latency(),
+                    &mut required.block_chunks_sizes,
+                ),
+                ProverJobType::AggregatedProof => (
+                    self.config.aggregation_deadline(),
+                    &mut required.aggregation_sizes,
+                ),
+            };
+            replicas.push(SizeReplicas {
+                size: stats.size,
+                needed_count: replicas_required(stats, target_latency, now),
+            });
+        }
+        required
+            .block_chunks_sizes
+            .sort_by_key(|replicas| replicas.size);
+        required
+            .aggregation_sizes
+            .sort_by_key(|replicas| replicas.size);
+
+        // Idle provers are kept for the smallest blocks, so the new blocks are picked up
+        // without waiting for the up-scaling.
+        if let Some(smallest) = required.block_chunks_sizes.first_mut() {
+            smallest.needed_count = std::cmp::max(smallest.needed_count, self.idle_provers);
+        }
+        let total = required
+            .block_chunks_sizes
+            .iter()
+            .chain(&required.aggregation_sizes)
+            .map(|replicas| replicas.needed_count)
+            .sum();
+        required.total = std::cmp::max(total, self.idle_provers);
+
+        required
+    }
+}
+
+/// Returns the amount of provers required to prove the pending jobs in `target_latency`
+/// since the creation of the oldest one.
+///
+/// Without the observed proving time every pending job gets its own prover.
+fn replicas_required(stats: &ProverJobsStats, target_latency: Duration, now: DateTime<Utc>) -> u32 {
+    if stats.pending_jobs == 0 {
+        return 0;
+    }
+    let proving_time = match stats.avg_proving_time {
+        Some(proving_time) if proving_time.as_secs_f64() > 0.0 => proving_time,
+        Some(_) => return 1,
+        None => return stats.pending_jobs,
+    };
+
+    let waiting_time = stats
+        .oldest_job_created_at
+        .and_then(|created_at| (now - created_at).to_std().ok())
+        .unwrap_or_default();
+    let time_left = target_latency.checked_sub(waiting_time).unwrap_or_default();
+    // Amount of the jobs a single prover manages to prove in time.
+    let jobs_per_prover = (time_left.as_secs_f64() / proving_time.as_secs_f64()).floor() as u32;
+    if jobs_per_prover == 0 {
+        // The target is missed anyway, so all the jobs are proven at once.
+        return stats.pending_jobs;
+    }
+
+    let replicas = (stats.pending_jobs + jobs_per_prover - 1) / jobs_per_prover;
+    replicas.clamp(1, stats.pending_jobs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::mock::MockDatabase;
+
+    fn stats(
+        pending_jobs: u32,
+        waiting_time: i64,
+        avg_proving_time: Option<u64>,
+        now: DateTime<Utc>,
+    ) -> ProverJobsStats {
+        ProverJobsStats {
+            job_type: ProverJobType::SingleProof,
+            size: 10,
+            pending_jobs,
+            oldest_job_created_at: Some(now - chrono::Duration::seconds(waiting_time)),
+            avg_proving_time: avg_proving_time.map(Duration::from_secs),
+        }
+    }
+
+    /// Checks that the amount of provers depends on the time left until the target latency.
+    #[test]
+    fn replicas_for_latency_target() {
+        let now = Utc::now();
+        let target = Duration::from_secs(600);
+
+        assert_eq!(
+            replicas_required(&stats(0, 0, Some(60), now), target, now),
+            0
+        );
+        // Each prover manages to prove 10 jobs in time.
+        assert_eq!(
+            replicas_required(&stats(10, 0, Some(60), now), target, now),
+            1
+        );
+        assert_eq!(
+            replicas_required(&stats(25, 0, Some(60), now), target, now),
+            3
+        );
+        // Jobs have been waiting for a while, so only 5 jobs per prover fit.
+        assert_eq!(
+            replicas_required(&stats(25, 300, Some(60), now), target, now),
+            5
+        );
+        // The target can't be met anymore.
+        assert_eq!(
+            replicas_required(&stats(25, 590, Some(60), now), target, now),
+            25
+        );
+        assert_eq!(
+            replicas_required(&stats(25, 900, Some(60), now), target, now),
+            25
+        );
+        // Proving time is unknown.
+        assert_eq!(replicas_required(&stats(25, 0, None, now), target, now), 25);
+    }
+
+    /// Checks that the provers are recommended per job size and the idle provers are kept for the smallest blocks.
+    #[test]
+    fn recommendation_by_size() {
+        let now = Utc::now();
+        let config = ScalerConfig {
+            target_proof_latency: 600,
+            aggregation_deadline: 1800,
+            proving_time_window: 86400,
+        };
+        let oracle = ScalerOracle::new(MockDatabase::new(), 2, config);
+
+        assert_eq!(
+            oracle.recommend(&[], now),
+            RequiredProvers {
+                total: 2,
+                ..Default::default()
+            }
+        );
 
-        Ok(provers_required)
+        let big_blocks = ProverJobsStats {
+            size: 100,
+            ..stats(5, 0, Some(300), now)
+        };
+        let small_blocks = stats(0, 0, Some(60), now);
+        let aggregation = ProverJobsStats {
+            job_type: ProverJobType::AggregatedProof,
+            size: 4,
+            ..stats(3, 0, Some(600), now)
+        };
+        assert_eq!(
+            oracle.recommend(&[big_blocks, aggregation, small_blocks], now),
+            RequiredProvers {
+                total: 6,
+                block_chunks_sizes: vec![
+                    SizeReplicas {
+                        size: 10,
+                        needed_count: 2
+                    },
+                    SizeReplicas {
+                        size: 100,
+                        needed_count: 3
+                    },
+                ],
+                aggregation_sizes: vec![SizeReplicas {
+                    size: 4,
+                    needed_count: 1
+                }],
+            }
+        );
     }
 }
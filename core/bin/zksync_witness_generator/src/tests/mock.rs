@@ -1,10 +1,11 @@
 // Built-in
 use std::clone::Clone;
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 // External uses
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 // Workspace uses
@@ -16,7 +17,7 @@ use zksync_storage::StorageProcessor;
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    prover::{ProverJob, ProverJobStatus, ProverJobType},
+    prover::{ProverJob, ProverJobFilter, ProverJobStatus, ProverJobType, ProverJobsStats},
     AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber,
 };
 // Local uses
@@ -28,6 +29,10 @@ pub struct MockDatabase {
     prover_job_queue: Arc<RwLock<(i32, Vec<StorageProverJobQueue>)>>,
     proofs: Arc<RwLock<Vec<StoredProof>>>,
     block_witness: Arc<RwLock<Vec<StorageBlockWitness>>>,
+    /// Witness generator instance and lease expiration time for the claimed blocks.
+    witness_leases: Arc<RwLock<HashMap<BlockNumber, (String, DateTime<Utc>)>>>,
+    /// Prover names by the hashes of their tokens.
+    prover_tokens: Arc<RwLock<HashMap<String, String>>>,
     blocks: Arc<RwLock<Vec<Block>>>,
     account_tree_cache: Arc<RwLock<AccountTreeCache>>,
     accounts_state: Arc<RwLock<(u32, AccountMap)>>,
@@ -42,6 +47,8 @@ impl MockDatabase {
             prover_job_queue: Arc::new(RwLock::new((0, Vec::new()))),
             proofs: Arc::new(RwLock::new(Vec::new())),
             block_witness: Arc::new(RwLock::new(Vec::new())),
+            witness_leases: Arc::new(RwLock::new(HashMap::new())),
+            prover_tokens: Arc::new(RwLock::new(HashMap::new())),
             blocks: Arc::new(RwLock::new(Vec::new())),
             account_tree_cache: Arc::new(RwLock::new(AccountTreeCache {
                 block: 0,
@@ -75,6 +82,13 @@ impl MockDatabase {
     pub async fn add_block(&self, block: Block) {
         self.blocks.write().await.push(block);
     }
+
+    pub async fn add_prover_token(&self, prover_name: &str, token_hash: &str) {
+        self.prover_tokens
+            .write()
+            .await
+            .insert(token_hash.to_string(), prover_name.to_string());
+    }
 }
 
 #[async_trait::async_trait]
@@ -109,6 +123,8 @@ impl DatabaseInterface for MockDatabase {
             id,
             job_priority,
             job_data,
+            attempts: 0,
+            lease_expires_at: None,
         };
 
         prover_job_queue.1.push(new_job);
@@ -148,6 +164,79 @@ impl DatabaseInterface for MockDatabase {
         Ok(count as u32)
     }
 
+    async fn load_idle_jobs_stats(
+        &self,
+        _: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<(u32, Option<DateTime<Utc>>)> {
+        let queue = self.prover_job_queue.read().await;
+        let is_idle =
+            |job: &&StorageProverJobQueue| job.job_status == ProverJobStatus::Idle.to_number();
+
+        let idle_jobs = queue.1.iter().filter(is_idle).count();
+        let last_activity = queue
+            .1
+            .iter()
+            .filter(|job| !is_idle(job))
+            .map(|job| job.updated_at)
+            .max();
+        let oldest_idle_job = queue
+            .1
+            .iter()
+            .filter(is_idle)
+            .map(|job| job.created_at)
+            .min();
+
+        Ok((idle_jobs as u32, last_activity.max(oldest_idle_job)))
+    }
+
+    async fn load_prover_jobs_stats(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        _proving_time_window: Duration,
+    ) -> anyhow::Result<Vec<ProverJobsStats>> {
+        // Proving time isn't tracked by the mock.
+        let blocks = self.blocks.read().await;
+        let mut stats: Vec<ProverJobsStats> = Vec::new();
+        for job in self.prover_job_queue.read().await.1.iter() {
+            if job.job_status == ProverJobStatus::Done.to_number()
+                || job.job_status == ProverJobStatus::Poisoned.to_number()
+            {
+                continue;
+            }
+            let (job_type, size) = if job.job_type == ProverJobType::SingleProof.to_string() {
+                let block_size = blocks
+                    .iter()
+                    .find(|block| *block.block_number == job.first_block as u32)
+                    .map(|block| block.block_chunks_size)
+                    .unwrap_or_default();
+                (ProverJobType::SingleProof, block_size)
+            } else {
+                let blocks_count = (job.last_block - job.first_block + 1) as usize;
+                (ProverJobType::AggregatedProof, blocks_count)
+            };
+
+            match stats
+                .iter_mut()
+                .find(|stats| stats.job_type == job_type && stats.size == size)
+            {
+                Some(stats) => {
+                    stats.pending_jobs += 1;
+                    stats.oldest_job_created_at =
+                        stats.oldest_job_created_at.min(Some(job.created_at));
+                }
+                None => stats.push(ProverJobsStats {
+                    job_type,
+                    size,
+                    pending_jobs: 1,
+                    oldest_job_created_at: Some(job.created_at),
+                    avg_proving_time: None,
+                }),
+            }
+        }
+
+        Ok(stats)
+    }
+
     async fn load_aggregated_op_that_affects_block(
         &self,
         _: &mut StorageProcessor<'_>,
@@ -171,15 +260,27 @@ impl DatabaseInterface for MockDatabase {
         Ok(single_proof)
     }
 
-    async fn mark_stale_jobs_as_idle(&self, _: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+    async fn mark_stale_jobs_as_idle(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        max_attempts: u32,
+    ) -> anyhow::Result<()> {
         let now = Utc::now();
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
 
         for job in prover_job_queue.iter_mut() {
-            if now - job.updated_at > chrono::Duration::seconds(10) {
-                job.job_status = ProverJobStatus::Idle.to_number();
+            let lease_expired =
+                matches!(job.lease_expires_at, Some(expires_at) if expires_at <= now);
+            if job.job_status == ProverJobStatus::InProgress.to_number() && lease_expired {
+                // Every lease of the mock job is considered failed.
+                job.job_status = if job.attempts as u32 >= max_attempts {
+                    ProverJobStatus::Poisoned.to_number()
+                } else {
+                    ProverJobStatus::Idle.to_number()
+                };
                 job.updated_at = now;
                 job.updated_by = "server_clean_idle".to_string();
+                job.lease_expires_at = None;
             }
         }
 
@@ -223,30 +324,73 @@ impl DatabaseInterface for MockDatabase {
 
     async fn load_idle_prover_job_from_job_queue(
         &self,
-        _: &mut StorageProcessor<'_>,
+        connection: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease: Duration,
     ) -> anyhow::Result<Option<ProverJob>> {
-        let prover_job_queue = &mut self.prover_job_queue.write().await.1;
-        let idle_prover_job = prover_job_queue
-            .iter_mut()
-            .filter(|job| job.job_status == ProverJobStatus::Idle.to_number())
-            .max_by_key(|job| (job.job_priority, job.id));
+        let jobs = self
+            .load_idle_prover_jobs_from_job_queue(
+                connection,
+                prover_name,
+                lease,
+                1,
+                ProverJobFilter::any(),
+            )
+            .await?;
+
+        Ok(jobs.into_iter().next())
+    }
 
-        let prover_job = if let Some(job) = idle_prover_job {
-            job.job_status = ProverJobStatus::InProgress.to_number();
-            job.updated_at = Utc::now();
-            job.updated_by = "server_give_job".to_string();
-
-            Some(ProverJob::new(
-                job.id,
-                BlockNumber(job.first_block as u32),
-                BlockNumber(job.last_block as u32),
-                job.job_data.clone(),
-            ))
-        } else {
-            None
+    async fn load_idle_prover_jobs_from_job_queue(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        prover_name: &str,
+        lease: Duration,
+        limit: u32,
+        filter: ProverJobFilter,
+    ) -> anyhow::Result<Vec<ProverJob>> {
+        let blocks = self.blocks.read().await;
+        let is_accepted = |job: &StorageProverJobQueue| {
+            if job.job_type != ProverJobType::SingleProof.to_string() {
+                return filter.aggregated_proofs;
+            }
+            match filter.max_block_chunks {
+                Some(max_chunks) => blocks
+                    .iter()
+                    .find(|block| *block.block_number == job.first_block as u32)
+                    .map_or(false, |block| block.block_chunks_size <= max_chunks),
+                None => true,
+            }
         };
 
-        Ok(prover_job)
+        let prover_job_queue = &mut self.prover_job_queue.write().await.1;
+        let mut idle_prover_jobs: Vec<_> = prover_job_queue
+            .iter_mut()
+            .filter(|job| job.job_status == ProverJobStatus::Idle.to_number() && is_accepted(job))
+            .collect();
+        idle_prover_jobs.sort_by_key(|job| (job.job_priority, job.first_block, job.id));
+
+        let now = Utc::now();
+        let prover_jobs = idle_prover_jobs
+            .into_iter()
+            .take(limit as usize)
+            .map(|job| {
+                job.job_status = ProverJobStatus::InProgress.to_number();
+                job.updated_at = now;
+                job.updated_by = prover_name.to_string();
+                job.attempts += 1;
+                job.lease_expires_at = Some(now + chrono::Duration::from_std(lease).unwrap());
+
+                ProverJob::new(
+                    job.id,
+                    BlockNumber(job.first_block as u32),
+                    BlockNumber(job.last_block as u32),
+                    job.job_data.clone(),
+                )
+            })
+            .collect();
+
+        Ok(prover_jobs)
     }
 
     async fn record_prover_is_working(
@@ -254,16 +398,62 @@ impl DatabaseInterface for MockDatabase {
         _: &mut StorageProcessor<'_>,
         job_id: i32,
         prover_name: &str,
-    ) -> anyhow::Result<()> {
+        lease: Duration,
+    ) -> anyhow::Result<bool> {
         let prover_job_queue = &mut self.prover_job_queue.write().await.1;
-        let prover_job = prover_job_queue.iter_mut().find(|job| job.id == job_id);
+        let prover_job = prover_job_queue.iter_mut().find(|job| {
+            job.id == job_id
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+                && job.updated_by == prover_name
+        });
 
         if let Some(job) = prover_job {
-            job.updated_at = Utc::now();
-            job.updated_by = prover_name.to_string();
+            let now = Utc::now();
+            job.updated_at = now;
+            job.lease_expires_at = Some(now + chrono::Duration::from_std(lease).unwrap());
+            return Ok(true);
         }
 
-        Ok(())
+        Ok(false)
+    }
+
+    async fn is_job_leased_to(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+    ) -> anyhow::Result<bool> {
+        let leased = self.prover_job_queue.read().await.1.iter().any(|job| {
+            job.id == job_id
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+                && job.updated_by == prover_name
+        });
+
+        Ok(leased)
+    }
+
+    async fn record_job_progress(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        job_id: i32,
+        prover_name: &str,
+        _progress: u8,
+    ) -> anyhow::Result<bool> {
+        // Attempts are not tracked by the mock, so only the job ownership is checked.
+        let prover_job_queue = &self.prover_job_queue.read().await.1;
+        Ok(prover_job_queue.iter().any(|job| {
+            job.id == job_id
+                && job.job_status == ProverJobStatus::InProgress.to_number()
+                && job.updated_by == prover_name
+        }))
+    }
+
+    async fn load_prover_by_token(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        token_hash: &str,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(self.prover_tokens.read().await.get(token_hash).cloned())
     }
 
     async fn store_proof(
@@ -280,6 +470,7 @@ impl DatabaseInterface for MockDatabase {
             job.updated_at = Utc::now();
             job.job_status = ProverJobStatus::Done.to_number();
             job.updated_by = "server_finish_job".to_string();
+            job.lease_expires_at = None;
         }
         let proof = StoredProof {
             block_number: i64::from(*block_number),
@@ -315,6 +506,7 @@ impl DatabaseInterface for MockDatabase {
             {
                 job.job_status = ProverJobStatus::Idle.to_number();
                 job.updated_at = Utc::now();
+                job.lease_expires_at = None;
             }
         }
 
@@ -368,7 +560,8 @@ impl DatabaseInterface for MockDatabase {
         let witness = block_witness
             .iter()
             .find(|witness| witness.block == *block_number as i64)
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness"));
+            .and_then(|w| w.witness.as_ref())
+            .map(|w| serde_json::from_str(w).expect("Failed to deserialize witness"));
 
         Ok(witness)
     }
@@ -388,10 +581,78 @@ impl DatabaseInterface for MockDatabase {
         if is_block_not_saved_yet {
             block_witness.push(StorageBlockWitness {
                 block: *block as i64,
-                witness: witness_str,
+                witness: Some(witness_str),
+                blob_hash: None,
             });
         }
 
         Ok(())
     }
+
+    async fn claim_block_for_witness(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        instance: &str,
+        from_block: BlockNumber,
+        lease: Duration,
+    ) -> anyhow::Result<Option<BlockNumber>> {
+        let blocks = self.blocks.read().await;
+        let block_witness = self.block_witness.read().await;
+        let mut leases = self.witness_leases.write().await;
+        let now = Utc::now();
+
+        let block = blocks
+            .iter()
+            .map(|block| block.block_number)
+            .filter(|&block_number| block_number >= from_block)
+            .filter(|block_number| {
+                !block_witness
+                    .iter()
+                    .any(|witness| witness.block == **block_number as i64)
+            })
+            .filter(|block_number| {
+                leases
+                    .get(block_number)
+                    .map_or(true, |(_, expires_at)| *expires_at <= now)
+            })
+            .min();
+
+        if let Some(block_number) = block {
+            let expires_at = now + chrono::Duration::from_std(lease).unwrap();
+            leases.insert(block_number, (instance.to_string(), expires_at));
+        }
+
+        Ok(block)
+    }
+
+    async fn renew_witness_block_lease(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+        instance: &str,
+        lease: Duration,
+    ) -> anyhow::Result<bool> {
+        let mut leases = self.witness_leases.write().await;
+        match leases.get_mut(&block) {
+            Some((owner, expires_at)) if owner == instance => {
+                *expires_at = Utc::now() + chrono::Duration::from_std(lease).unwrap();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn release_witness_block(
+        &self,
+        _: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+        instance: &str,
+    ) -> anyhow::Result<()> {
+        let mut leases = self.witness_leases.write().await;
+        if matches!(leases.get(&block), Some((owner, _)) if owner == instance) {
+            leases.remove(&block);
+        }
+
+        Ok(())
+    }
 }
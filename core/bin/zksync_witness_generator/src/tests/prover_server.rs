@@ -7,24 +7,31 @@ use num::BigUint;
 use zksync_config::{
     configs::{
         api::ProverApiConfig,
-        prover::{Core, Prover, WitnessGenerator},
+        prover::{Core, Prover, Scaler, WitnessGenerator, WitnessStore, WitnessStoreBackend},
     },
-    ProverConfig,
+    NotifierConfig, ProverConfig,
+};
+use zksync_crypto::{
+    franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr},
+    proof::SingleProof,
 };
-use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
 use zksync_prover::{client, ApiClient};
-use zksync_prover_utils::api::ProverInputRequest;
+use zksync_prover_utils::api::{
+    v2::{JobHeartbeat, JobsRequest},
+    JobResultData, ProverOutputRequest,
+};
 use zksync_types::{block::Block, AccountId, BlockNumber, TokenId, H256};
 // Local deps
 use super::mock::MockDatabase;
-use crate::{run_prover_server, DatabaseInterface};
+use crate::{api_v2::prover_token_hash, run_prover_server, DatabaseInterface};
 
 const CORRECT_PROVER_SECRET_AUTH: &str = "42";
-const INCORRECT_PROVER_SECRET_AUTH: &str = "123";
+const CORRECT_PROVER_TOKEN: &str = "42";
+const INCORRECT_PROVER_TOKEN: &str = "123";
 const SERVER_BIND_PORT: u16 = 8088;
 const SERVER_BIND_TO: &str = "127.0.0.1:8088";
 
-struct MockProverOptions(ProverApiConfig, ProverConfig);
+struct MockProverOptions(ProverApiConfig, ProverConfig, NotifierConfig);
 
 impl Default for MockProverOptions {
     fn default() -> Self {
@@ -39,25 +46,59 @@ impl Default for MockProverOptions {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                jobs_per_request: 1,
             },
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                max_job_attempts: 5,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 1,
+                block_lease_timeout: 300000,
+            },
+            witness_store: WitnessStore {
+                backend: WitnessStoreBackend::Postgres,
+                compression_level: 3,
+                path: String::new(),
+                s3_endpoint: String::new(),
+                s3_region: String::new(),
+                s3_bucket: String::new(),
+                s3_prefix: String::new(),
+                s3_access_key: String::new(),
+                s3_secret_key: String::new(),
+            },
+            scaler: Scaler {
+                target_proof_latency: 600,
+                aggregation_deadline: 1800,
+                proving_time_window: 86400,
             },
         };
 
-        MockProverOptions(api, prover)
+        let notifier = NotifierConfig {
+            mattermost_webhook_url: String::new(),
+            slack_webhook_url: String::new(),
+            webhook_url: String::new(),
+            log_enabled: false,
+            log_file: String::new(),
+            prover_idle_timeout: 1800,
+            priority_queue_backlog_timeout: 3600,
+        };
+
+        MockProverOptions(api, prover, notifier)
     }
 }
 
 async fn spawn_server(database: MockDatabase) {
     let prover_options = MockProverOptions::default();
 
-    run_prover_server(database, prover_options.0, prover_options.1);
+    run_prover_server(
+        database,
+        prover_options.0,
+        prover_options.1,
+        prover_options.2,
+    );
 }
 
 #[tokio::test]
@@ -65,47 +106,48 @@ async fn spawn_server(database: MockDatabase) {
 // TODO Fix test ZKS-859
 async fn test_api_client() {
     let database = MockDatabase::new();
+    database
+        .add_prover_token("test2", &prover_token_hash(CORRECT_PROVER_TOKEN))
+        .await;
     spawn_server(database.clone()).await;
-    test_api_client_with_incorrect_secret_auth("tests1").await;
-    test_api_client_simple_simulation("test2", database).await;
+    test_api_client_with_incorrect_token().await;
+    test_api_client_simple_simulation(database).await;
 }
 
-async fn test_api_client_with_incorrect_secret_auth(prover_name: &str) {
+fn jobs_request() -> JobsRequest {
+    JobsRequest {
+        max_jobs: 1,
+        capabilities: Default::default(),
+    }
+}
+
+async fn test_api_client_with_incorrect_token() {
     let client = client::ApiClient::new(
         &format!("http://{}", SERVER_BIND_TO).parse().unwrap(),
         Duration::from_secs(1),
-        INCORRECT_PROVER_SECRET_AUTH,
+        INCORRECT_PROVER_TOKEN,
     );
 
-    let get_job_error = &client
-        .get_job(ProverInputRequest {
-            prover_name: prover_name.to_string(),
-            aux_data: Default::default(),
-        })
+    let get_jobs_error = &client
+        .get_jobs(jobs_request())
         .await
         .err()
         .unwrap()
         .to_string();
 
-    assert!(get_job_error.contains("authorization error"));
+    assert!(get_jobs_error.contains("Unauthorized"));
 }
 
-async fn test_api_client_simple_simulation(prover_name: &str, database: MockDatabase) {
+async fn test_api_client_simple_simulation(database: MockDatabase) {
     let client = client::ApiClient::new(
         &format!("http://{}", SERVER_BIND_TO).parse().unwrap(),
         Duration::from_secs(1),
-        CORRECT_PROVER_SECRET_AUTH,
+        CORRECT_PROVER_TOKEN,
     );
 
-    // Call `get_job` and check that data is None.
-    let job = client
-        .get_job(ProverInputRequest {
-            prover_name: prover_name.to_string(),
-            aux_data: Default::default(),
-        })
-        .await
-        .unwrap();
-    assert!(job.data.is_none());
+    // Call `get_jobs` and check that there are no jobs.
+    let jobs = client.get_jobs(jobs_request()).await.unwrap();
+    assert!(jobs.is_empty());
 
     let block = get_test_block().await;
     database.add_block(block).await;
@@ -115,43 +157,52 @@ async fn test_api_client_simple_simulation(prover_name: &str, database: MockData
     MockDatabase::wait_for_stale_job_stale_idle().await;
 
     // Should return job.
-    let job = client
-        .get_job(ProverInputRequest {
-            prover_name: prover_name.to_string(),
-            aux_data: Default::default(),
-        })
-        .await
-        .unwrap();
+    let jobs = client.get_jobs(jobs_request()).await.unwrap();
 
     MockDatabase::wait_for_stale_job_stale_idle().await;
 
-    // Should return empty job.
-    let next_job = client
-        .get_job(ProverInputRequest {
-            prover_name: prover_name.to_string(),
-            aux_data: Default::default(),
-        })
+    // Should return no jobs.
+    let next_jobs = client.get_jobs(jobs_request()).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert!(next_jobs.is_empty());
+
+    // Lease is renewed for the job leased to the prover only.
+    let heartbeat = client
+        .heartbeat(vec![
+            JobHeartbeat {
+                job_id: jobs[0].job_id,
+                progress: Some(50),
+            },
+            JobHeartbeat {
+                job_id: jobs[0].job_id + 1,
+                progress: None,
+            },
+        ])
         .await
         .unwrap();
-    assert!(job.data.is_some());
-    assert!(next_job.data.is_none());
+    assert_eq!(heartbeat.lost_jobs, vec![jobs[0].job_id + 1]);
 
-    client
-        .prover_stopped(prover_name.to_string())
+    // Proof can be published only for the job leased to the prover.
+    let publish_error = client
+        .publish(ProverOutputRequest {
+            job_id: jobs[0].job_id + 1,
+            first_block: jobs[0].first_block,
+            last_block: jobs[0].last_block,
+            data: JobResultData::BlockProof(SingleProof::default()),
+        })
         .await
-        .unwrap();
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(publish_error.contains("JobNotLeased"));
+
+    client.prover_stopped().await.unwrap();
 
     MockDatabase::wait_for_stale_job_stale_idle().await;
 
     // Should return job.
-    let job = client
-        .get_job(ProverInputRequest {
-            prover_name: prover_name.to_string(),
-            aux_data: Default::default(),
-        })
-        .await
-        .unwrap();
-    assert!(job.data.is_some());
+    let jobs = client.get_jobs(jobs_request()).await.unwrap();
+    assert_eq!(jobs.len(), 1);
 
     let mut storage = database.acquire_connection().await.unwrap();
     let witness = database
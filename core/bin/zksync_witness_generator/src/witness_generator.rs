@@ -3,7 +3,10 @@ use std::time::Instant;
 use std::{thread, time};
 // External
 use futures::channel::mpsc;
-use tokio::time::sleep;
+use tokio::{
+    task::JoinHandle,
+    time::{interval, sleep},
+};
 use zksync_crypto::merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256;
 // Workspace deps
 use crate::database_interface::DatabaseInterface;
@@ -15,19 +18,31 @@ use zksync_types::block::Block;
 use zksync_types::BlockNumber;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+/// The lease of the claimed block is renewed this many times per lease period.
+const LEASE_RENEWALS_PER_PERIOD: u32 = 3;
+/// Leases are not renewed more often than this.
+const MIN_LEASE_RENEWAL_INTERVAL: time::Duration = time::Duration::from_secs(1);
+
 /// The essential part of this structure is `maintain` function
 /// which runs forever and adds data to the database.
 ///
-/// This will generate and store in db witnesses for blocks with indexes
-/// start_block, start_block + block_step, start_block + 2*block_step, ...
+/// Blocks to generate witnesses for are claimed in the database, so any number of
+/// the witness generators (in the same or different processes) can work simultaneously.
+/// Each claim is a lease: once it expires, the block is considered abandoned
+/// (e.g. the instance has crashed) and can be claimed by another witness generator.
+/// The lease is renewed while the witness is being generated, so slow blocks aren't
+/// generated twice.
 pub struct WitnessGenerator<DB: DatabaseInterface> {
     /// Connection to the database.
     database: DB,
     /// Routine refresh interval.
     rounds_interval: time::Duration,
-
-    start_block: BlockNumber,
-    block_step: BlockNumber,
+    /// Unique name of the witness generator, used to claim the blocks.
+    instance: String,
+    /// Blocks before this one are not processed.
+    from_block: BlockNumber,
+    /// Period the claimed block is leased for.
+    block_lease: time::Duration,
 }
 
 #[derive(Debug)]
@@ -42,14 +57,16 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
     pub fn new(
         database: DB,
         rounds_interval: time::Duration,
-        start_block: BlockNumber,
-        block_step: BlockNumber,
+        instance: String,
+        from_block: BlockNumber,
+        block_lease: time::Duration,
     ) -> Self {
         Self {
             database,
             rounds_interval,
-            start_block,
-            block_step,
+            instance,
+            from_block,
+            block_lease,
         }
     }
 
@@ -71,6 +88,74 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             .expect("failed to start provers server");
     }
 
+    /// Claims the next block to generate the witness for.
+    async fn claim_block(&self) -> anyhow::Result<Option<BlockNumber>> {
+        let start = Instant::now();
+        let mut storage = self.database.acquire_connection().await?;
+        let block = self
+            .database
+            .claim_block_for_witness(
+                &mut storage,
+                &self.instance,
+                self.from_block,
+                self.block_lease,
+            )
+            .await?;
+        metrics::histogram!("witness_generator", start.elapsed(), "stage" => "claim_block");
+        Ok(block)
+    }
+
+    /// Starts the task renewing the lease of the claimed block several times per lease period,
+    /// until it is aborted.
+    fn start_lease_renewal(&self, block_number: BlockNumber) -> JoinHandle<()> {
+        let database = self.database.clone();
+        let instance = self.instance.clone();
+        let lease = self.block_lease;
+        let renewal_interval = (lease / LEASE_RENEWALS_PER_PERIOD).max(MIN_LEASE_RENEWAL_INTERVAL);
+
+        tokio::spawn(async move {
+            let mut timer = interval(renewal_interval);
+            // The first tick completes immediately, and the lease has just been taken.
+            timer.tick().await;
+            loop {
+                timer.tick().await;
+                let renewed = match database.acquire_connection().await {
+                    Ok(mut storage) => {
+                        database
+                            .renew_witness_block_lease(&mut storage, block_number, &instance, lease)
+                            .await
+                    }
+                    Err(err) => Err(err),
+                };
+                match renewed {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        vlog::warn!(
+                            "Witness generator '{}' has lost the lease of block {}",
+                            instance,
+                            block_number
+                        );
+                        return;
+                    }
+                    Err(err) => vlog::warn!(
+                        "Witness generator '{}' failed to renew the lease of block {}: {}",
+                        instance,
+                        block_number,
+                        err
+                    ),
+                }
+            }
+        })
+    }
+
+    /// Releases the claimed block, so it can be claimed again if the witness wasn't stored.
+    async fn release_block(&self, block_number: BlockNumber) -> anyhow::Result<()> {
+        let mut storage = self.database.acquire_connection().await?;
+        self.database
+            .release_witness_block(&mut storage, block_number, &self.instance)
+            .await
+    }
+
     /// Returns status of witness for block with index block_number
     async fn should_work_on_block(
         &self,
@@ -245,30 +330,21 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
             block.block_number.0 as f64,
             "stage" => "witness_generator"
         );
+        metrics::gauge!(
+            "witness_generator.last_processed_block",
+            block.block_number.0 as f64,
+            "instance" => self.instance.clone()
+        );
         Ok(())
     }
 
-    /// Returns next block for generating witness
-    fn next_witness_block(
-        current_block: BlockNumber,
-        block_step: BlockNumber,
-        block_info: &BlockInfo,
-    ) -> BlockNumber {
-        match block_info {
-            BlockInfo::NotReadyBlock => current_block, // Keep waiting
-            BlockInfo::WithWitness | BlockInfo::NoWitness(_) => {
-                BlockNumber(*current_block + *block_step)
-            } // Go to the next block
-        }
-    }
-
     /// Updates witness data in database in an infinite loop,
     /// awaiting `rounds_interval` time between updates.
     async fn maintain(self) {
         vlog::info!(
-            "preparing prover data routine started with start_block({}), block_step({})",
-            *self.start_block,
-            *self.block_step
+            "preparing prover data routine started by instance '{}' from block {}",
+            self.instance,
+            *self.from_block
         );
 
         // Initialize counters for cache hits/misses.
@@ -276,77 +352,55 @@ impl<DB: DatabaseInterface> WitnessGenerator<DB> {
         metrics::register_counter!("witness_generator.cache_access", "type" => "off_by_1");
         metrics::register_counter!("witness_generator.cache_access", "type" => "miss");
 
-        let mut current_block = self.start_block;
         loop {
             sleep(self.rounds_interval).await;
-            let should_work = match self.should_work_on_block(current_block).await {
-                Ok(should_work) => should_work,
+            let block_number = match self.claim_block().await {
+                Ok(Some(block_number)) => block_number,
+                Ok(None) => continue, // No blocks to process, keep waiting.
                 Err(err) => {
-                    vlog::warn!("witness for block {} check failed: {}", current_block, err);
+                    vlog::warn!(
+                        "Witness generator '{}' failed to claim a block: {}",
+                        self.instance,
+                        err
+                    );
                     continue;
                 }
             };
 
-            let next_block = Self::next_witness_block(current_block, self.block_step, &should_work);
-            if let BlockInfo::NoWitness(block) = should_work {
-                let block_number = block.block_number;
-                if let Err(err) = self.prepare_witness_and_save_it(block).await {
-                    vlog::warn!("Witness generator ({},{}) failed to prepare witness for block: {}, err: {}",
-                        self.start_block, self.block_step, block_number, err);
-                    continue; // Retry the same block on the next iteration.
+            match self.should_work_on_block(block_number).await {
+                Ok(BlockInfo::NoWitness(block)) => {
+                    let lease_renewal = self.start_lease_renewal(block_number);
+                    let result = self.prepare_witness_and_save_it(block).await;
+                    lease_renewal.abort();
+
+                    if let Err(err) = result {
+                        vlog::warn!(
+                            "Witness generator '{}' failed to prepare witness for block: {}, err: {}",
+                            self.instance,
+                            block_number,
+                            err
+                        );
+                        metrics::increment_counter!("witness_generator.failed_blocks", "instance" => self.instance.clone());
+                    } else {
+                        metrics::increment_counter!("witness_generator.processed_blocks", "instance" => self.instance.clone());
+                    }
+                }
+                Ok(BlockInfo::WithWitness) | Ok(BlockInfo::NotReadyBlock) => {}
+                Err(err) => {
+                    vlog::warn!("witness for block {} check failed: {}", block_number, err);
                 }
             }
 
-            // Update current block.
-            current_block = next_block;
+            // The block is released even if the witness generation has failed,
+            // so it is retried on the next iteration (possibly by another instance).
+            if let Err(err) = self.release_block(block_number).await {
+                vlog::warn!(
+                    "Witness generator '{}' failed to release block {}: {}",
+                    self.instance,
+                    block_number,
+                    err
+                );
+            }
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::database::Database;
-    use zksync_crypto::Fr;
-    use zksync_types::{AccountId, H256, U256};
-
-    #[test]
-    fn test_next_witness_block() {
-        assert_eq!(
-            WitnessGenerator::<Database>::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::NotReadyBlock
-            ),
-            BlockNumber(3)
-        );
-        assert_eq!(
-            WitnessGenerator::<Database>::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::WithWitness
-            ),
-            BlockNumber(7)
-        );
-        let empty_block = Block::new(
-            BlockNumber(0),
-            Fr::default(),
-            AccountId(0),
-            vec![],
-            (0, 0),
-            0,
-            U256::default(),
-            U256::default(),
-            H256::default(),
-            0,
-        );
-        assert_eq!(
-            WitnessGenerator::<Database>::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::NoWitness(empty_block)
-            ),
-            BlockNumber(7)
-        );
-    }
-}
@@ -6,7 +6,10 @@
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::{forced_exit_requests::ForcedExitRequest, Address, TokenId};
+use zksync_types::{
+    forced_exit_requests::{ForcedExitRequest, ForcedExitRequestId, ForcedExitRequestInfo},
+    Address, TokenId,
+};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
 use num::BigUint;
@@ -61,4 +64,13 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn get_forced_exit_request(
+        &self,
+        id: ForcedExitRequestId,
+    ) -> ClientResult<ForcedExitRequestInfo> {
+        self.get_with_scope(FORCED_EXIT_REQUESTS_SCOPE, &format!("requests/{}", id))
+            .send()
+            .await
+    }
 }
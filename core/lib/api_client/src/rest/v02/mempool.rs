@@ -0,0 +1,45 @@
+use crate::rest::client::{Client, Result};
+
+use zksync_api_types::v02::{
+    pagination::{ApiEither, PaginationQuery},
+    Response,
+};
+use zksync_types::{tx::TxHash, SerialId};
+
+impl Client {
+    pub async fn mempool_txs(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "mempool/txs")
+            .query(pagination_query)
+            .send()
+            .await
+    }
+
+    pub async fn mempool_batches(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "mempool/batches")
+            .query(pagination_query)
+            .send()
+            .await
+    }
+
+    pub async fn mempool_priority_ops(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<SerialId>>,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "mempool/priorityOps")
+            .query(pagination_query)
+            .send()
+            .await
+    }
+
+    pub async fn mempool_stats(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "mempool/stats")
+            .send()
+            .await
+    }
+}
@@ -12,7 +12,28 @@ impl Client {
         signature: TxEthSignatureVariant,
     ) -> Result<Response> {
         self.post_with_scope(super::API_V02_SCOPE, "transactions")
-            .body(&TxWithSignature { tx, signature })
+            .body(&TxWithSignature {
+                tx,
+                signature,
+                fee_quote: None,
+            })
+            .send()
+            .await
+    }
+
+    /// Submits the transaction paying the fee guaranteed by the quote.
+    pub async fn submit_tx_with_fee_quote(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        fee_quote: String,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions")
+            .body(&TxWithSignature {
+                tx,
+                signature,
+                fee_quote: Some(fee_quote),
+            })
             .send()
             .await
     }
@@ -13,6 +13,9 @@ pub struct TxWithSignature {
     pub tx: ZkSyncTx,
     #[serde(default)]
     pub signature: TxEthSignatureVariant,
+    /// ID of the fee quote for the transaction. Ignored for transactions in batches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_quote: Option<String>,
 }
 
 /// Combined identifier of the priority operations for the lookup.
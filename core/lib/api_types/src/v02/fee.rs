@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
@@ -12,6 +13,9 @@ pub struct ApiFee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Quote guaranteeing that the `total_fee` is accepted until it expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<FeeQuote>,
 }
 
 impl From<Fee> for ApiFee {
@@ -20,6 +24,7 @@ impl From<Fee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            quote: None,
         }
     }
 }
@@ -30,10 +35,23 @@ impl From<BatchFee> for ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            quote: None,
         }
     }
 }
 
+/// Signed quote of the transaction fee.
+///
+/// The quote is bound to the transaction type, fee token and recipient address.
+/// Transactions submitted with the quote ID are accepted if they pay at least the quoted fee,
+/// even if the fee required by the server grows, until the quote expires.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeQuote {
+    pub quote_id: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ApiTxFeeTypes {
     /// Fee for the `Withdraw` transaction.
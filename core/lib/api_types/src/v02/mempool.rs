@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use zksync_types::TokenId;
+
+/// Summary of the mempool content.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolStats {
+    /// Amount of queued L2 transactions that are not a part of some batch.
+    pub queued_txs: u32,
+    /// Amount of queued batches.
+    pub queued_batches: u32,
+    /// Amount of priority operations awaiting for the execution.
+    pub priority_ops: u32,
+    /// Amount of L2 transactions (including batched ones) and priority operations per type.
+    pub count_by_type: BTreeMap<String, u32>,
+    /// Amount of L2 transactions (including batched ones) per fee token.
+    pub count_by_token: BTreeMap<TokenId, u32>,
+    /// Creation time of the oldest pending transaction or priority operation.
+    pub oldest_pending_created_at: Option<DateTime<Utc>>,
+    /// Age of the oldest pending transaction or priority operation in seconds.
+    pub oldest_pending_age: Option<u64>,
+}
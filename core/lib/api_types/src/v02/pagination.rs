@@ -139,3 +139,18 @@ pub struct AccountTxsRequest {
     pub token: Option<TokenId>,
     pub second_address: Option<Address>,
 }
+
+#[derive(Debug, Serialize)]
+pub struct MempoolTxsRequest {
+    pub tx_hash: ApiEither<TxHash>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MempoolBatchesRequest {
+    pub batch_hash: ApiEither<TxHash>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MempoolPriorityOpsRequest {
+    pub serial_id: ApiEither<SerialId>,
+}
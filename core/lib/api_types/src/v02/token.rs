@@ -1,4 +1,5 @@
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use zksync_types::{AccountId, Address, Token, TokenId, H256};
 
@@ -22,6 +23,34 @@ pub struct TokenPrice {
     pub price: BigDecimal,
 }
 
+/// Query parameters of the token price history request.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceHistoryQuery {
+    /// Earliest time of the price update, unbounded if not set.
+    pub from: Option<DateTime<Utc>>,
+    /// Latest time of the price update, unbounded if not set.
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum number of prices to return, `MAX_LIMIT` if not set.
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalPrice {
+    pub usd_price: BigDecimal,
+    pub last_updated: DateTime<Utc>,
+}
+
+/// Token prices in USD stored by the server, starting from the most recent one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceHistory {
+    pub token_id: TokenId,
+    pub token_symbol: String,
+    pub prices: Vec<HistoricalPrice>,
+}
+
 impl ApiToken {
     pub fn from_token_and_eligibility(token: Token, eligibility: bool) -> Self {
         ApiToken {
@@ -0,0 +1,99 @@
+// Built-in uses
+use std::{io, path::PathBuf};
+// External uses
+use tokio::fs;
+// Local uses
+use crate::BlobStore;
+
+/// Keeps the blobs in the local directory.
+///
+/// Blobs are spread over the subdirectories named after the first two characters
+/// of the key, so none of the directories grows too large.
+#[derive(Debug, Clone)]
+pub struct FileBlobStore {
+    root: PathBuf,
+}
+
+impl FileBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let shard = key.get(..2).unwrap_or("_");
+        self.root.join(shard).join(key)
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStore for FileBlobStore {
+    async fn put(&self, key: &str, data: Vec<u8>) -> anyhow::Result<()> {
+        let path = self.path(key);
+        fs::create_dir_all(path.parent().expect("Blob path always has a parent")).await?;
+
+        // The blob is renamed once written, so readers never see a partially written file.
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data).await?;
+        fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)).await {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlobRef, ContentStore};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zksync_blob_store_{}_{}", name, std::process::id()))
+    }
+
+    /// Checks that the stored value is loaded back, and that the corrupted blobs are rejected.
+    #[tokio::test]
+    async fn content_store_roundtrip() -> anyhow::Result<()> {
+        let root = temp_dir("roundtrip");
+        let backend = FileBlobStore::new(&root);
+        let store = ContentStore::new(backend.clone(), 3);
+
+        let value = serde_json::json!({ "operations": vec![42u64; 1000] });
+        let blob_ref = store.put_json(&value).await?;
+        assert_eq!(blob_ref.blob_hash.len(), 64);
+        assert_eq!(store.get_json::<serde_json::Value>(&blob_ref).await?, value);
+
+        // Same content is stored under the same key.
+        assert_eq!(store.put_json(&value).await?, blob_ref);
+
+        // The reference is distinguishable from the actual data.
+        assert_eq!(
+            BlobRef::from_json(&blob_ref.to_json()),
+            Some(blob_ref.clone())
+        );
+        assert_eq!(BlobRef::from_json(&value), None);
+
+        // Blob doesn't match the hash.
+        let key = format!("{}.json.zst", blob_ref.blob_hash);
+        let other = zstd::encode_all(b"{}".as_ref(), 3)?;
+        backend.put(&key, other).await?;
+        assert!(store
+            .get_json::<serde_json::Value>(&blob_ref)
+            .await
+            .is_err());
+
+        // Blob is missing.
+        let missing = BlobRef {
+            blob_hash: "00".repeat(32),
+        };
+        assert!(store.get_json::<serde_json::Value>(&missing).await.is_err());
+
+        fs::remove_dir_all(root).await?;
+        Ok(())
+    }
+}
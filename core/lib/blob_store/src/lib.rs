@@ -0,0 +1,112 @@
+//! Content-addressed storage for the large JSON blobs (block witnesses and prover job data),
+//! allowing to keep them out of the database.
+//!
+//! Blobs are compressed with zstd and stored under the SHA-256 hash of their (uncompressed)
+//! content, so the database only has to keep the hash, and the integrity of the blob can be
+//! checked on every read.
+
+// Built-in uses
+use std::{fmt, sync::Arc};
+// External uses
+use anyhow::{ensure, format_err};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+// Workspace uses
+use zksync_config::configs::prover::{WitnessStore as WitnessStoreConfig, WitnessStoreBackend};
+
+pub use self::{file_store::FileBlobStore, s3_store::S3BlobStore};
+
+mod file_store;
+mod s3_store;
+
+/// Backend keeping the blobs by their keys.
+#[async_trait::async_trait]
+pub trait BlobStore: fmt::Debug + Send + Sync {
+    async fn put(&self, key: &str, data: Vec<u8>) -> anyhow::Result<()>;
+
+    /// Returns `None` if there is no blob with such key.
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+}
+
+/// Reference to the blob kept in the store instead of the actual JSON value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BlobRef {
+    /// Hex-encoded SHA-256 hash of the blob content.
+    pub blob_hash: String,
+}
+
+impl BlobRef {
+    /// Returns the reference if the JSON value is a blob reference rather than the actual data.
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("Failed to serialize blob reference")
+    }
+}
+
+/// Compressing, content-addressed store on top of the `BlobStore` backend.
+#[derive(Debug, Clone)]
+pub struct ContentStore {
+    backend: Arc<dyn BlobStore>,
+    compression_level: i32,
+}
+
+impl ContentStore {
+    pub fn new(backend: impl BlobStore + 'static, compression_level: i32) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            compression_level,
+        }
+    }
+
+    /// Creates the store for the configured backend.
+    /// Returns `None` if the data should be kept in the database.
+    pub fn from_config(config: &WitnessStoreConfig) -> Option<Self> {
+        let store = match config.backend {
+            WitnessStoreBackend::Postgres => return None,
+            WitnessStoreBackend::FileSystem => {
+                Self::new(FileBlobStore::new(&config.path), config.compression_level)
+            }
+            WitnessStoreBackend::S3 => {
+                Self::new(S3BlobStore::from_config(config), config.compression_level)
+            }
+        };
+        Some(store)
+    }
+
+    /// Stores the value and returns the reference to it.
+    pub async fn put_json<T: Serialize>(&self, value: &T) -> anyhow::Result<BlobRef> {
+        let content = serde_json::to_vec(value)?;
+        let blob_hash = hex::encode(Sha256::digest(&content));
+        let compressed = zstd::encode_all(content.as_slice(), self.compression_level)?;
+        metrics::histogram!("blob_store.blob_size", content.len() as f64, "stage" => "uncompressed");
+        metrics::histogram!("blob_store.blob_size", compressed.len() as f64, "stage" => "compressed");
+
+        self.backend.put(&Self::key(&blob_hash), compressed).await?;
+        Ok(BlobRef { blob_hash })
+    }
+
+    /// Loads the referenced value, checking that its content matches the hash.
+    pub async fn get_json<T: DeserializeOwned>(&self, blob_ref: &BlobRef) -> anyhow::Result<T> {
+        let compressed = self
+            .backend
+            .get(&Self::key(&blob_ref.blob_hash))
+            .await?
+            .ok_or_else(|| format_err!("Blob {} is missing in the store", blob_ref.blob_hash))?;
+        let content = zstd::decode_all(compressed.as_slice())?;
+        ensure!(
+            hex::encode(Sha256::digest(&content)) == blob_ref.blob_hash,
+            "Blob {} is corrupted",
+            blob_ref.blob_hash
+        );
+
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    fn key(blob_hash: &str) -> String {
+        format!("{}.json.zst", blob_hash)
+    }
+}
@@ -2,6 +2,7 @@ use num::{rational::Ratio, BigUint};
 /// External uses
 use serde::Deserialize;
 /// Built-in uses
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::time::Duration;
 use zksync_utils::scaled_u64_to_ratio;
@@ -53,6 +54,27 @@ impl CommonApiConfig {
         scaled_u64_to_ratio(self.max_subsidy_usd_scaled)
     }
 
+    pub fn fee_quote_validity(&self) -> Duration {
+        Duration::from_secs(self.fee_quote_validity_secs)
+    }
+
+    /// Parses the per-method overrides of the rate limit.
+    pub fn rate_limit_method_quotas(&self) -> HashMap<String, u32> {
+        self.rate_limit_method_quotas
+            .iter()
+            .map(|quota| {
+                let (method, requests_per_sec) = quota
+                    .split_once('=')
+                    .unwrap_or_else(|| panic!("Invalid rate limit quota: {}", quota));
+                let requests_per_sec = requests_per_sec
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid rate limit quota: {}", quota));
+                (method.trim().to_owned(), requests_per_sec)
+            })
+            .collect()
+    }
+
     pub fn from_env() -> Self {
         envy_load!("common", "API_COMMON_")
     }
@@ -122,6 +144,26 @@ pub struct CommonApiConfig {
 
     /// The name of current subsidy. It is needed to conveniently fetch historical data regarding subsidies for different partners
     pub subsidy_name: String,
+
+    /// Secret for signing the fee quotes. If not set, fee quotes are not issued.
+    pub fee_quote_secret: Option<String>,
+    /// Time during which the quoted fee is accepted by the server.
+    /// Type of value is seconds.
+    pub fee_quote_validity_secs: u64,
+
+    /// Number of requests per second a single IP can make to each API method.
+    /// Zero disables the limit for the methods without a quota.
+    pub rate_limit_requests_per_sec: u32,
+    /// Number of requests that can be made at once before the rate is enforced.
+    pub rate_limit_burst: u32,
+    /// Per-method overrides of `rate_limit_requests_per_sec` in the `method=requests_per_sec` format.
+    /// REST API methods are named after their resource, e.g. `transactions`.
+    pub rate_limit_method_quotas: Vec<String>,
+    /// Number of transactions per second a single account can submit. Zero disables the limit.
+    pub rate_limit_account_txs_per_sec: u32,
+    /// Whether the API is served behind a proxy setting the `CF-Connecting-IP` header (e.g. Cloudflare).
+    /// The header identifies the caller only if it's set, otherwise the peer address is used.
+    pub rate_limit_behind_proxy: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -230,6 +272,10 @@ pub struct Web3Config {
     pub port: u16,
     /// URL to access web3 JSON RPC server.
     pub url: String,
+    /// Port to which the web3 WebSocket server is listening.
+    pub ws_port: u16,
+    /// URL to access web3 WebSocket server.
+    pub ws_url: String,
     /// Max difference between blocks in `eth_getLogs` method.
     pub max_block_range: u32,
     pub chain_id: u32,
@@ -239,6 +285,10 @@ impl Web3Config {
     pub fn bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
     }
+
+    pub fn ws_bind_addr(&self) -> SocketAddr {
+        SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -265,6 +315,16 @@ mod tests {
                 subsidized_ips: vec!["127.0.0.1".to_owned()],
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
+                fee_quote_secret: Some("sample".into()),
+                fee_quote_validity_secs: 60,
+                rate_limit_requests_per_sec: 20,
+                rate_limit_burst: 40,
+                rate_limit_method_quotas: vec![
+                    "tx_submit=5".to_owned(),
+                    "submit_txs_batch=2".to_owned(),
+                ],
+                rate_limit_account_txs_per_sec: 5,
+                rate_limit_behind_proxy: true,
             },
             admin: AdminApiConfig {
                 port: 8080,
@@ -285,6 +345,8 @@ mod tests {
             web3: Web3Config {
                 port: 3002,
                 url: "http://127.0.0.1:3002".into(),
+                ws_port: 3003,
+                ws_url: "ws://127.0.0.1:3003".into(),
                 max_block_range: 10,
                 chain_id: 33,
             },
@@ -316,6 +378,13 @@ API_COMMON_MAX_SUBSIDY_USD_SCALED=20000
 API_COMMON_SUBSIDY_NAME=PartnerName
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
+API_COMMON_FEE_QUOTE_SECRET="sample"
+API_COMMON_FEE_QUOTE_VALIDITY_SECS=60
+API_COMMON_RATE_LIMIT_REQUESTS_PER_SEC=20
+API_COMMON_RATE_LIMIT_BURST=40
+API_COMMON_RATE_LIMIT_METHOD_QUOTAS="tx_submit=5,submit_txs_batch=2"
+API_COMMON_RATE_LIMIT_ACCOUNT_TXS_PER_SEC=5
+API_COMMON_RATE_LIMIT_BEHIND_PROXY=true
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
@@ -328,6 +397,8 @@ API_JSON_RPC_WS_PORT="3031"
 API_JSON_RPC_WS_URL="ws://127.0.0.1:3031"
 API_WEB3_PORT="3002"
 API_WEB3_URL="http://127.0.0.1:3002"
+API_WEB3_WS_PORT="3003"
+API_WEB3_WS_URL="ws://127.0.0.1:3003"
 API_WEB3_CHAIN_ID="33"
 API_WEB3_MAX_BLOCK_RANGE="10"
 API_PRIVATE_PORT="8090"
@@ -374,5 +445,17 @@ API_USE_HTTPS=true
             config.web3.bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
+        assert_eq!(
+            config.web3.ws_bind_addr(),
+            SocketAddr::new(bind_broadcast_addr, config.web3.ws_port)
+        );
+        assert_eq!(config.common.fee_quote_validity(), Duration::from_secs(60));
+        assert_eq!(
+            config.common.rate_limit_method_quotas(),
+            HashMap::from([
+                ("tx_submit".to_owned(), 5),
+                ("submit_txs_batch".to_owned(), 2)
+            ])
+        );
     }
 }
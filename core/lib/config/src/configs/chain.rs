@@ -16,6 +16,8 @@ pub struct ChainConfig {
     pub eth: Eth,
     /// State keeper / block generating configuration.
     pub state_keeper: StateKeeper,
+    /// Mempool configuration.
+    pub mempool: Mempool,
 }
 
 impl ChainConfig {
@@ -24,6 +26,7 @@ impl ChainConfig {
             circuit: envy_load!("circuit", "CHAIN_CIRCUIT_"),
             eth: envy_load!("eth", "CHAIN_ETH_"),
             state_keeper: envy_load!("state_keeper", "CHAIN_STATE_KEEPER_"),
+            mempool: envy_load!("mempool", "CHAIN_MEMPOOL_"),
         }
     }
     pub fn max_blocks_to_aggregate(&self) -> u32 {
@@ -115,6 +118,40 @@ impl StateKeeper {
     }
 }
 
+/// Policy used to order the L2 transactions that are ready for execution.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum MempoolOrdering {
+    /// Transactions are proposed in the order they became ready for execution.
+    Fifo,
+    /// Transactions paying the highest fee in USD per chunk are proposed first.
+    /// Per-account nonce order and batch atomicity are preserved.
+    FeePriority,
+}
+
+/// Policy used to choose the transactions to evict once the mempool is full.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum MempoolEvictionPolicy {
+    /// The transactions that were added to the mempool first are evicted.
+    Oldest,
+    /// The transactions paying the lowest fee in USD per chunk are evicted.
+    LowestFee,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Mempool {
+    /// Ordering policy for the ready L2 transactions.
+    pub ordering: MempoolOrdering,
+    /// Maximum amount of transactions a single account can have in the mempool.
+    pub max_txs_per_account: usize,
+    /// Maximum amount of transactions in the mempool.
+    pub max_txs_total: usize,
+    /// Policy used to free space for new transactions once `max_txs_total` is reached.
+    pub eviction_policy: MempoolEvictionPolicy,
+    /// Minimal fee increase in percent required to replace a queued transaction
+    /// with a new one having the same account and nonce.
+    pub replacement_fee_bump_percent: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +185,13 @@ mod tests {
                 block_execute_deadline: 4_000,
                 max_aggregated_tx_gas: 4_000_000,
             },
+            mempool: Mempool {
+                ordering: MempoolOrdering::FeePriority,
+                max_txs_per_account: 100,
+                max_txs_total: 50_000,
+                eviction_policy: MempoolEvictionPolicy::LowestFee,
+                replacement_fee_bump_percent: 10,
+            },
         }
     }
 
@@ -175,6 +219,11 @@ CHAIN_STATE_KEEPER_BLOCK_COMMIT_DEADLINE="300"
 CHAIN_STATE_KEEPER_BLOCK_PROVE_DEADLINE="3000"
 CHAIN_STATE_KEEPER_BLOCK_EXECUTE_DEADLINE="4000"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
+CHAIN_MEMPOOL_ORDERING="FeePriority"
+CHAIN_MEMPOOL_MAX_TXS_PER_ACCOUNT="100"
+CHAIN_MEMPOOL_MAX_TXS_TOTAL="50000"
+CHAIN_MEMPOOL_EVICTION_POLICY="LowestFee"
+CHAIN_MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT="10"
         "#;
         set_env(config);
 
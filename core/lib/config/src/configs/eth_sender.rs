@@ -14,6 +14,10 @@ pub struct ETHSenderConfig {
     pub sender: Sender,
     /// Options related to the `gas_adjuster` submodule.
     pub gas_price_limit: GasLimit,
+    /// Options related to the dynamic fee (EIP-1559) transactions.
+    pub dynamic_fee: DynamicFee,
+    /// Options related to the operator balance watchdog.
+    pub balance_watchdog: BalanceWatchdog,
 }
 
 impl ETHSenderConfig {
@@ -34,6 +38,11 @@ impl ETHSenderConfig {
                 "eth_sender.gas_price_limit",
                 "ETH_SENDER_GAS_PRICE_LIMIT_"
             ),
+            dynamic_fee: envy_load!("eth_sender.dynamic_fee", "ETH_SENDER_DYNAMIC_FEE_"),
+            balance_watchdog: envy_load!(
+                "eth_sender.balance_watchdog",
+                "ETH_SENDER_BALANCE_WATCHDOG_"
+            ),
         }
     }
 }
@@ -89,6 +98,44 @@ impl GasLimit {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DynamicFee {
+    /// Whether dynamic fee (EIP-1559) transactions should be sent instead of the legacy ones.
+    pub enabled: bool,
+    /// Amount of the recent block headers to track the base fee for.
+    pub base_fee_samples: usize,
+    /// Multiplier applied to the tracked base fee to calculate the max fee per gas,
+    /// so the transaction stays valid if the base fee grows in the next blocks.
+    pub base_fee_multiplier: f64,
+    /// Lower bound for the priority fee per gas (in wei).
+    pub min_priority_fee: u64,
+    /// Upper bound for the priority fee per gas (in wei).
+    pub max_priority_fee: u64,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct BalanceWatchdog {
+    /// Whether the operator balance should be monitored.
+    pub enabled: bool,
+    /// Interval between the operator balance checks in seconds.
+    pub check_interval: u64,
+    /// Amount of the latest blocks used to estimate the gas spent per block.
+    pub blocks_sample: u32,
+    /// Remaining runway (in blocks) below which a warning is raised.
+    pub warning_runway_blocks: u64,
+    /// Remaining runway (in blocks) below which a critical alert is raised.
+    pub critical_runway_blocks: u64,
+    /// Dedicated Mattermost webhook for the alerts, used in addition to the `[notifier]` backends.
+    pub webhook_url: String,
+}
+
+impl BalanceWatchdog {
+    /// Converts `self.check_interval` into `Duration`.
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,6 +161,21 @@ mod tests {
                 sample_interval: 15,
                 scale_factor: 1.0f64,
             },
+            dynamic_fee: DynamicFee {
+                enabled: false,
+                base_fee_samples: 10,
+                base_fee_multiplier: 2.0f64,
+                min_priority_fee: 1000000000,
+                max_priority_fee: 5000000000,
+            },
+            balance_watchdog: BalanceWatchdog {
+                enabled: true,
+                check_interval: 60,
+                blocks_sample: 100,
+                warning_runway_blocks: 1000,
+                critical_runway_blocks: 100,
+                webhook_url: "http://127.0.0.1".to_string(),
+            },
         }
     }
 
@@ -132,6 +194,17 @@ ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_DYNAMIC_FEE_ENABLED="false"
+ETH_SENDER_DYNAMIC_FEE_BASE_FEE_SAMPLES="10"
+ETH_SENDER_DYNAMIC_FEE_BASE_FEE_MULTIPLIER="2"
+ETH_SENDER_DYNAMIC_FEE_MIN_PRIORITY_FEE="1000000000"
+ETH_SENDER_DYNAMIC_FEE_MAX_PRIORITY_FEE="5000000000"
+ETH_SENDER_BALANCE_WATCHDOG_ENABLED="true"
+ETH_SENDER_BALANCE_WATCHDOG_CHECK_INTERVAL="60"
+ETH_SENDER_BALANCE_WATCHDOG_BLOCKS_SAMPLE="100"
+ETH_SENDER_BALANCE_WATCHDOG_WARNING_RUNWAY_BLOCKS="1000"
+ETH_SENDER_BALANCE_WATCHDOG_CRITICAL_RUNWAY_BLOCKS="100"
+ETH_SENDER_BALANCE_WATCHDOG_WEBHOOK_URL="http://127.0.0.1"
 CHAIN_ETH_NETWORK="mainnet"
 ETH_CLIENT_CHAIN_ID=30
 ETH_CLIENT_GAS_PRICE_FACTOR="1"
@@ -161,6 +234,10 @@ ETH_CLIENT_WEB3_URL="http://127.0.0.1:4444"
             config.gas_price_limit.sample_interval(),
             Duration::from_secs(config.gas_price_limit.sample_interval)
         );
+        assert_eq!(
+            config.balance_watchdog.check_interval(),
+            Duration::from_secs(config.balance_watchdog.check_interval)
+        );
     }
 
     #[test]
@@ -179,6 +256,17 @@ ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
 ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
+ETH_SENDER_DYNAMIC_FEE_ENABLED="false"
+ETH_SENDER_DYNAMIC_FEE_BASE_FEE_SAMPLES="10"
+ETH_SENDER_DYNAMIC_FEE_BASE_FEE_MULTIPLIER="2"
+ETH_SENDER_DYNAMIC_FEE_MIN_PRIORITY_FEE="1000000000"
+ETH_SENDER_DYNAMIC_FEE_MAX_PRIORITY_FEE="5000000000"
+ETH_SENDER_BALANCE_WATCHDOG_ENABLED="true"
+ETH_SENDER_BALANCE_WATCHDOG_CHECK_INTERVAL="60"
+ETH_SENDER_BALANCE_WATCHDOG_BLOCKS_SAMPLE="100"
+ETH_SENDER_BALANCE_WATCHDOG_WARNING_RUNWAY_BLOCKS="1000"
+ETH_SENDER_BALANCE_WATCHDOG_CRITICAL_RUNWAY_BLOCKS="100"
+ETH_SENDER_BALANCE_WATCHDOG_WEBHOOK_URL="http://127.0.0.1"
 CHAIN_ETH_NETWORK="mainnet"
 ETH_CLIENT_CHAIN_ID=30
 ETH_CLIENT_GAS_PRICE_FACTOR="1"
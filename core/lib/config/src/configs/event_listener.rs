@@ -1,5 +1,6 @@
 // Built-in uses
 use std::net::SocketAddr;
+use std::time::Duration;
 
 // External uses
 use serde::Deserialize;
@@ -17,6 +18,21 @@ pub struct EventListenerConfig {
     /// PostgreSQL channel name to listen on. Must be equal to the one
     /// hardcoded into database migrations.
     pub channel_name: String,
+    /// Maximum number of events replayed to a subscriber resuming from the `from_event_id` cursor.
+    pub max_replay_events: u64,
+    /// Bearer token required by the webhook registration API.
+    pub webhook_api_token: String,
+    /// Maximum number of events sent to a webhook in a single request.
+    pub webhook_batch_size: u64,
+    /// Timeout of the requests to the webhooks.
+    /// Type of value is seconds.
+    pub webhook_request_timeout_secs: u64,
+    /// Delay before the first retry of a failed delivery, doubled after every failure.
+    /// Type of value is seconds.
+    pub webhook_min_retry_backoff_secs: u64,
+    /// Maximum delay between the delivery retries.
+    /// Type of value is seconds.
+    pub webhook_max_retry_backoff_secs: u64,
 }
 
 impl EventListenerConfig {
@@ -27,6 +43,18 @@ impl EventListenerConfig {
     pub fn ws_bind_addr(&self) -> SocketAddr {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
     }
+
+    pub fn webhook_request_timeout(&self) -> Duration {
+        Duration::from_secs(self.webhook_request_timeout_secs)
+    }
+
+    pub fn webhook_min_retry_backoff(&self) -> Duration {
+        Duration::from_secs(self.webhook_min_retry_backoff_secs)
+    }
+
+    pub fn webhook_max_retry_backoff(&self) -> Duration {
+        Duration::from_secs(self.webhook_max_retry_backoff_secs)
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +68,12 @@ mod tests {
             ws_port: 65535,
             ws_url: "ws://localhost:12345".into(),
             channel_name: "zksync_event_channel".into(),
+            max_replay_events: 10000,
+            webhook_api_token: "sample".into(),
+            webhook_batch_size: 100,
+            webhook_request_timeout_secs: 10,
+            webhook_min_retry_backoff_secs: 1,
+            webhook_max_retry_backoff_secs: 300,
         }
     }
 
@@ -49,6 +83,12 @@ mod tests {
 EVENT_LISTENER_WS_URL="ws://localhost:12345"
 EVENT_LISTENER_WS_PORT="65535"
 EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
+EVENT_LISTENER_MAX_REPLAY_EVENTS="10000"
+EVENT_LISTENER_WEBHOOK_API_TOKEN="sample"
+EVENT_LISTENER_WEBHOOK_BATCH_SIZE="100"
+EVENT_LISTENER_WEBHOOK_REQUEST_TIMEOUT_SECS="10"
+EVENT_LISTENER_WEBHOOK_MIN_RETRY_BACKOFF_SECS="1"
+EVENT_LISTENER_WEBHOOK_MAX_RETRY_BACKOFF_SECS="300"
         "#;
         set_env(config);
 
@@ -65,5 +105,8 @@ EVENT_LISTENER_CHANNEL_NAME="zksync_event_channel"
             config.ws_bind_addr(),
             SocketAddr::new(bind_addr, config.ws_port)
         );
+        assert_eq!(config.webhook_request_timeout(), Duration::from_secs(10));
+        assert_eq!(config.webhook_min_retry_backoff(), Duration::from_secs(1));
+        assert_eq!(config.webhook_max_retry_backoff(), Duration::from_secs(300));
     }
 }
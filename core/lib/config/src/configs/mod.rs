@@ -4,7 +4,7 @@ pub use self::{
     dev_ticker::DevTickerConfig, eth_client::ETHClientConfig, eth_sender::ETHSenderConfig,
     eth_watch::ETHWatchConfig, event_listener::EventListenerConfig,
     forced_exit_requests::ForcedExitRequestsConfig, gateway_watcher::GatewayWatcherConfig,
-    misc::MiscConfig, prover::ProverConfig, ticker::TickerConfig,
+    misc::MiscConfig, notifier::NotifierConfig, prover::ProverConfig, ticker::TickerConfig,
     token_handler::TokenHandlerConfig,
 };
 
@@ -20,6 +20,7 @@ pub mod event_listener;
 pub mod forced_exit_requests;
 pub mod gateway_watcher;
 pub mod misc;
+pub mod notifier;
 pub mod prover;
 pub mod ticker;
 pub mod token_handler;
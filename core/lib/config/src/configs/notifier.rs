@@ -0,0 +1,80 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the operator notifications.
+///
+/// Every backend with a non-empty URL receives all the notifications raised by the server.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NotifierConfig {
+    /// Mattermost incoming webhook.
+    pub mattermost_webhook_url: String,
+    /// Slack (or Slack-compatible) incoming webhook.
+    pub slack_webhook_url: String,
+    /// Generic webhook receiving the notifications as JSON objects.
+    pub webhook_url: String,
+    /// Whether the notifications should be written to the server logs.
+    pub log_enabled: bool,
+    /// File to append the notifications to (as JSON lines). Not used if empty or if logging is disabled.
+    pub log_file: String,
+    /// Provers are considered idle if none of them was active for this period in seconds,
+    /// while there are jobs waiting to be proven.
+    pub prover_idle_timeout: u64,
+    /// Priority queue is considered backlogged if the oldest unprocessed operation
+    /// is older than this period in seconds.
+    pub priority_queue_backlog_timeout: u64,
+}
+
+impl NotifierConfig {
+    pub fn from_env() -> Self {
+        envy_load!("notifier", "NOTIFIER_")
+    }
+
+    /// Converts `self.prover_idle_timeout` into `Duration`.
+    pub fn prover_idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.prover_idle_timeout)
+    }
+
+    /// Converts `self.priority_queue_backlog_timeout` into `Duration`.
+    pub fn priority_queue_backlog_timeout(&self) -> Duration {
+        Duration::from_secs(self.priority_queue_backlog_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> NotifierConfig {
+        NotifierConfig {
+            mattermost_webhook_url: "http://127.0.0.1:8065/hooks/mattermost".to_string(),
+            slack_webhook_url: "".to_string(),
+            webhook_url: "http://127.0.0.1:3030/alerts".to_string(),
+            log_enabled: true,
+            log_file: "/var/log/zksync/notifications.jsonl".to_string(),
+            prover_idle_timeout: 1800,
+            priority_queue_backlog_timeout: 3600,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+NOTIFIER_MATTERMOST_WEBHOOK_URL="http://127.0.0.1:8065/hooks/mattermost"
+NOTIFIER_SLACK_WEBHOOK_URL=""
+NOTIFIER_WEBHOOK_URL="http://127.0.0.1:3030/alerts"
+NOTIFIER_LOG_ENABLED="true"
+NOTIFIER_LOG_FILE="/var/log/zksync/notifications.jsonl"
+NOTIFIER_PROVER_IDLE_TIMEOUT="1800"
+NOTIFIER_PRIORITY_QUEUE_BACKLOG_TIMEOUT="3600"
+        "#;
+        set_env(config);
+
+        let actual = NotifierConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+}
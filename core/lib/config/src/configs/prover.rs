@@ -11,6 +11,8 @@ pub struct ProverConfig {
     pub prover: Prover,
     pub core: Core,
     pub witness_generator: WitnessGenerator,
+    pub witness_store: WitnessStore,
+    pub scaler: Scaler,
 }
 
 impl ProverConfig {
@@ -19,6 +21,8 @@ impl ProverConfig {
             prover: envy_load!("prover.prover", "PROVER_PROVER_"),
             core: envy_load!("prover.core", "PROVER_CORE_"),
             witness_generator: envy_load!("prover.witness_generator", "PROVER_WITNESS_GENERATOR_"),
+            witness_store: envy_load!("prover.witness_store", "PROVER_WITNESS_STORE_"),
+            scaler: envy_load!("prover.scaler", "PROVER_SCALER_"),
         }
     }
 }
@@ -34,6 +38,9 @@ pub struct Prover {
     pub request_timeout: u64,
     /// Flag for dying after proving cycle
     pub die_after_proof: bool,
+    /// Maximum amount of jobs requested at once, their leases are renewed while the prover
+    /// works on the previous jobs of the batch.
+    pub jobs_per_request: u32,
 }
 
 impl Prover {
@@ -57,9 +64,12 @@ impl Prover {
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Core {
     /// Timeout to consider prover gone in ms.
+    /// Jobs are leased to the provers for this period, and the lease is renewed on every heartbeat.
     pub gone_timeout: u64,
     /// Amount of provers in the cluser if there is no pending jobs.
     pub idle_provers: u32,
+    /// Amount of expired leases after which the job is considered poisoned and isn't handed out anymore.
+    pub max_job_attempts: u32,
 }
 
 impl Core {
@@ -75,6 +85,9 @@ pub struct WitnessGenerator {
     pub prepare_data_interval: u64,
     /// Amount of witness generator threads.
     pub witness_generators: usize,
+    /// Period the block is leased to the witness generator instance for in ms.
+    /// Blocks not processed in time are considered abandoned and are claimed by other instances.
+    pub block_lease_timeout: u64,
 }
 
 impl WitnessGenerator {
@@ -82,6 +95,70 @@ impl WitnessGenerator {
     pub fn prepare_data_interval(&self) -> Duration {
         Duration::from_millis(self.prepare_data_interval)
     }
+
+    /// Converts `self.block_lease_timeout` into `Duration`.
+    pub fn block_lease_timeout(&self) -> Duration {
+        Duration::from_millis(self.block_lease_timeout)
+    }
+}
+
+/// Storage for the block witnesses and the prover job data.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum WitnessStoreBackend {
+    /// Data is kept in the Postgres tables.
+    Postgres,
+    /// Compressed blobs are kept in the local directory.
+    FileSystem,
+    /// Compressed blobs are kept in the S3-compatible object storage.
+    S3,
+}
+
+/// Witness blob store settings.
+///
+/// Unless the `Postgres` backend is used, the database holds only the hashes of the blobs.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WitnessStore {
+    pub backend: WitnessStoreBackend,
+    /// zstd compression level of the blobs.
+    pub compression_level: i32,
+    /// Directory for the blobs, used by the `FileSystem` backend.
+    pub path: String,
+    /// URL of the S3-compatible storage, e.g. `https://s3.us-east-1.amazonaws.com`.
+    pub s3_endpoint: String,
+    pub s3_region: String,
+    pub s3_bucket: String,
+    /// Prefix of the blob keys in the bucket.
+    pub s3_prefix: String,
+    pub s3_access_key: String,
+    pub s3_secret_key: String,
+}
+
+/// Settings of the scaler oracle, which recommends the amount of prover replicas.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Scaler {
+    /// Time in seconds the block is expected to be proven in since its prover job is created.
+    pub target_proof_latency: u64,
+    /// Time in seconds the aggregated proof is expected to be created in since its prover job is created.
+    pub aggregation_deadline: u64,
+    /// Period in seconds the observed proving time is averaged over.
+    pub proving_time_window: u64,
+}
+
+impl Scaler {
+    /// Converts `self.target_proof_latency` into `Duration`.
+    pub fn target_proof_latency(&self) -> Duration {
+        Duration::from_secs(self.target_proof_latency)
+    }
+
+    /// Converts `self.aggregation_deadline` into `Duration`.
+    pub fn aggregation_deadline(&self) -> Duration {
+        Duration::from_secs(self.aggregation_deadline)
+    }
+
+    /// Converts `self.proving_time_window` into `Duration`.
+    pub fn proving_time_window(&self) -> Duration {
+        Duration::from_secs(self.proving_time_window)
+    }
 }
 
 #[cfg(test)]
@@ -96,14 +173,33 @@ mod tests {
                 cycle_wait: 500,
                 request_timeout: 10,
                 die_after_proof: false,
+                jobs_per_request: 2,
             },
             core: Core {
                 gone_timeout: 60000,
                 idle_provers: 1,
+                max_job_attempts: 5,
             },
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 2,
+                block_lease_timeout: 300000,
+            },
+            witness_store: WitnessStore {
+                backend: WitnessStoreBackend::S3,
+                compression_level: 3,
+                path: "./witnesses".into(),
+                s3_endpoint: "http://127.0.0.1:9000".into(),
+                s3_region: "us-east-1".into(),
+                s3_bucket: "witnesses".into(),
+                s3_prefix: "localhost/".into(),
+                s3_access_key: "minioadmin".into(),
+                s3_secret_key: "minioadmin".into(),
+            },
+            scaler: Scaler {
+                target_proof_latency: 600,
+                aggregation_deadline: 1800,
+                proving_time_window: 86400,
             },
         }
     }
@@ -115,10 +211,25 @@ PROVER_PROVER_HEARTBEAT_INTERVAL="1000"
 PROVER_PROVER_CYCLE_WAIT="500"
 PROVER_PROVER_REQUEST_TIMEOUT="10"
 PROVER_PROVER_DIE_AFTER_PROOF=false
+PROVER_PROVER_JOBS_PER_REQUEST="2"
 PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
+PROVER_CORE_MAX_JOB_ATTEMPTS="5"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_WITNESS_GENERATOR_BLOCK_LEASE_TIMEOUT="300000"
+PROVER_WITNESS_STORE_BACKEND="S3"
+PROVER_WITNESS_STORE_COMPRESSION_LEVEL="3"
+PROVER_WITNESS_STORE_PATH="./witnesses"
+PROVER_WITNESS_STORE_S3_ENDPOINT="http://127.0.0.1:9000"
+PROVER_WITNESS_STORE_S3_REGION="us-east-1"
+PROVER_WITNESS_STORE_S3_BUCKET="witnesses"
+PROVER_WITNESS_STORE_S3_PREFIX="localhost/"
+PROVER_WITNESS_STORE_S3_ACCESS_KEY="minioadmin"
+PROVER_WITNESS_STORE_S3_SECRET_KEY="minioadmin"
+PROVER_SCALER_TARGET_PROOF_LATENCY="600"
+PROVER_SCALER_AGGREGATION_DEADLINE="1800"
+PROVER_SCALER_PROVING_TIME_WINDOW="86400"
         "#;
         set_env(config);
 
@@ -153,5 +264,22 @@ PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
             config.witness_generator.prepare_data_interval(),
             Duration::from_millis(config.witness_generator.prepare_data_interval)
         );
+        assert_eq!(
+            config.witness_generator.block_lease_timeout(),
+            Duration::from_millis(config.witness_generator.block_lease_timeout)
+        );
+
+        assert_eq!(
+            config.scaler.target_proof_latency(),
+            Duration::from_secs(config.scaler.target_proof_latency)
+        );
+        assert_eq!(
+            config.scaler.aggregation_deadline(),
+            Duration::from_secs(config.scaler.aggregation_deadline)
+        );
+        assert_eq!(
+            config.scaler.proving_time_window(),
+            Duration::from_secs(config.scaler.proving_time_window)
+        );
     }
 }
@@ -1,5 +1,7 @@
-use num::{rational::Ratio, BigUint};
+// Built-in uses
+use std::time::Duration;
 // External uses
+use num::{rational::Ratio, BigUint};
 use serde::Deserialize;
 // Workspace uses
 use zksync_types::Address;
@@ -11,6 +13,12 @@ use crate::envy_load;
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    /// Local JSON or CSV file with the token prices.
+    File,
+    /// Generic HTTP endpoint returning JSON with the token price.
+    Http,
+    /// Median of the prices reported by the `median_sources`.
+    Median,
 }
 
 /// Configuration for the fee ticker.
@@ -22,6 +30,21 @@ pub struct TickerConfig {
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
     pub coingecko_base_url: String,
+    /// Path to the JSON or CSV file with the token prices, used by the `File` source.
+    pub price_file_path: String,
+    /// Address of the account that must sign the price file.
+    /// If not set, the signature of the price file is not checked.
+    pub price_file_signer: Option<Address>,
+    /// URL of the endpoint used by the `Http` source.
+    /// `{symbol}` and `{address}` placeholders are replaced with the token symbol and address.
+    pub http_price_url: String,
+    /// JSONPath-style selector of the price in the `Http` source response, e.g. `$.data[0].price`.
+    /// Supports the same placeholders as `http_price_url`.
+    pub http_price_selector: String,
+    /// Sources combined by the `Median` source.
+    pub median_sources: Vec<TokenPriceSource>,
+    /// Prices deviating from the median by more than this percent are dropped by the `Median` source.
+    pub median_max_deviation_percent: u32,
     /// Coefficient for scaling all fees in percent.
     pub scale_fee_percent: u32,
     /// Coefficient for the fee price for fast withdrawal requests.
@@ -38,6 +61,13 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// Subsidized price for ChangePubKey in cents scaled by SUBSIDY_USD_AMOUNTS_SCALE
     pub subsidy_cpk_price_usd_scaled: u64,
+    /// Token prices older than this number of hours are downsampled in the price history.
+    pub price_history_downsample_after_hours: u64,
+    /// Only one token price per this number of seconds is kept for the downsampled price history.
+    pub price_history_downsample_interval_secs: u64,
+    /// Token prices older than this number of days are removed from the price history.
+    /// If set to 0, the prices are kept forever.
+    pub price_history_retention_days: u64,
 }
 
 impl TickerConfig {
@@ -49,13 +79,41 @@ impl TickerConfig {
         envy_load!("fee_ticker", "FEE_TICKER_")
     }
 
+    /// Age of the token prices starting from which the price history is downsampled.
+    pub fn price_history_downsample_after(&self) -> Duration {
+        Duration::from_secs(self.price_history_downsample_after_hours * 60 * 60)
+    }
+
+    /// Age of the token prices starting from which they are removed from the price history.
+    pub fn price_history_retention(&self) -> Option<Duration> {
+        if self.price_history_retention_days == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(
+                self.price_history_retention_days * 24 * 60 * 60,
+            ))
+        }
+    }
+
     /// Returns the token price source type and the corresponding API URL.
+    /// For the `File` source the path to the price file is returned instead,
+    /// and the `Median` source doesn't have a single URL.
     pub fn price_source(&self) -> (TokenPriceSource, String) {
-        let url = match self.token_price_source {
+        (
+            self.token_price_source,
+            self.source_url(self.token_price_source),
+        )
+    }
+
+    /// Returns the API URL (or the file path) of the given token price source.
+    pub fn source_url(&self, source: TokenPriceSource) -> String {
+        match source {
             TokenPriceSource::CoinGecko => self.coingecko_base_url.clone(),
             TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.clone(),
-        };
-        (self.token_price_source, url)
+            TokenPriceSource::File => self.price_file_path.clone(),
+            TokenPriceSource::Http => self.http_price_url.clone(),
+            TokenPriceSource::Median => String::new(),
+        }
     }
 }
 
@@ -69,6 +127,12 @@ mod tests {
             token_price_source: TokenPriceSource::CoinGecko,
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
+            price_file_path: "etc/prices/prices.json".into(),
+            price_file_signer: Some(addr("fe2b2f2b7c2e2d5e5b5c4c2e6cbb1b0b23a7a8ea")),
+            http_price_url: "http://127.0.0.1:9876/price?symbol={symbol}".into(),
+            http_price_selector: "$.data.{symbol}.price".into(),
+            median_sources: vec![TokenPriceSource::CoinGecko, TokenPriceSource::CoinMarketCap],
+            median_max_deviation_percent: 10,
             scale_fee_percent: 100,
             fast_processing_coeff: 10.0f64,
             liquidity_volume: 100.0,
@@ -77,6 +141,9 @@ mod tests {
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
             subsidy_cpk_price_usd_scaled: 100,
+            price_history_downsample_after_hours: 24,
+            price_history_downsample_interval_secs: 3600,
+            price_history_retention_days: 365,
         }
     }
 
@@ -86,6 +153,12 @@ mod tests {
 FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
+FEE_TICKER_PRICE_FILE_PATH="etc/prices/prices.json"
+FEE_TICKER_PRICE_FILE_SIGNER="0xfe2b2f2b7c2e2d5e5b5c4c2e6cbb1b0b23a7a8ea"
+FEE_TICKER_HTTP_PRICE_URL="http://127.0.0.1:9876/price?symbol={symbol}"
+FEE_TICKER_HTTP_PRICE_SELECTOR="$.data.{symbol}.price"
+FEE_TICKER_MEDIAN_SOURCES="CoinGecko,CoinMarketCap"
+FEE_TICKER_MEDIAN_MAX_DEVIATION_PERCENT=10
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
 FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS=1000
 FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
@@ -95,6 +168,9 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
 FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
+FEE_TICKER_PRICE_HISTORY_DOWNSAMPLE_AFTER_HOURS=24
+FEE_TICKER_PRICE_HISTORY_DOWNSAMPLE_INTERVAL_SECS=3600
+FEE_TICKER_PRICE_HISTORY_RETENTION_DAYS=365
         "#;
         set_env(config);
 
@@ -124,5 +200,22 @@ FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())
         );
+
+        config.token_price_source = TokenPriceSource::File;
+        assert_eq!(
+            config.price_source(),
+            (TokenPriceSource::File, "etc/prices/prices.json".into())
+        );
+
+        assert_eq!(
+            config.price_history_downsample_after(),
+            Duration::from_secs(24 * 60 * 60)
+        );
+        assert_eq!(
+            config.price_history_retention(),
+            Some(Duration::from_secs(365 * 24 * 60 * 60))
+        );
+        config.price_history_retention_days = 0;
+        assert_eq!(config.price_history_retention(), None);
     }
 }
@@ -1,7 +1,8 @@
 pub use crate::configs::{
     ApiConfig, ChainConfig, ContractsConfig, DBConfig, DevTickerConfig, ETHClientConfig,
     ETHSenderConfig, ETHWatchConfig, EventListenerConfig, ForcedExitRequestsConfig,
-    GatewayWatcherConfig, MiscConfig, ProverConfig, TickerConfig, TokenHandlerConfig,
+    GatewayWatcherConfig, MiscConfig, NotifierConfig, ProverConfig, TickerConfig,
+    TokenHandlerConfig,
 };
 
 pub mod configs;
@@ -19,6 +20,7 @@ pub struct ZkSyncConfig {
     pub token_handler: TokenHandlerConfig,
     pub event_listener: EventListenerConfig,
     pub gateway_watcher: GatewayWatcherConfig,
+    pub notifier: NotifierConfig,
     pub prover: ProverConfig,
     pub ticker: TickerConfig,
     pub forced_exit_requests: ForcedExitRequestsConfig,
@@ -37,6 +39,7 @@ impl ZkSyncConfig {
             token_handler: TokenHandlerConfig::from_env(),
             event_listener: EventListenerConfig::from_env(),
             gateway_watcher: GatewayWatcherConfig::from_env(),
+            notifier: NotifierConfig::from_env(),
             prover: ProverConfig::from_env(),
             ticker: TickerConfig::from_env(),
             forced_exit_requests: ForcedExitRequestsConfig::from_env(),
@@ -20,7 +20,10 @@ use web3::{
 };
 
 // Workspace uses
-use zksync_eth_signer::{raw_rootstock_tx::RawTransaction, EthereumSigner};
+use zksync_eth_signer::{
+    raw_rootstock_tx::{RawTransaction, EIP1559_TX_ID},
+    EthereumSigner,
+};
 
 use crate::rootstock_gateway::{ExecutedTxStatus, FailureInfo, SignedCallResult};
 use sha3::Digest;
@@ -156,6 +159,18 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(network_gas_price)
     }
 
+    pub async fn get_base_fee(&self) -> Result<Option<U256>, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let base_fee = self
+            .block(BlockId::Number(BlockNumber::Latest))
+            .await?
+            .and_then(|block| block.base_fee_per_gas);
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.get_base_fee", start.elapsed());
+        Ok(base_fee)
+    }
+
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
@@ -174,11 +189,18 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         #[cfg(feature = "with-metrics")]
         let start = Instant::now();
 
-        // fetch current gas_price
-        let gas_price = match options.gas_price {
+        // fetch current gas_price, the max fee is used instead for the dynamic fee transactions
+        let gas_price = match options.max_fee_per_gas.or(options.gas_price) {
             Some(gas_price) => gas_price,
             None => self.get_gas_price().await?,
         };
+        let (transaction_type, max_priority_fee_per_gas) = match options.max_fee_per_gas {
+            Some(_) => (
+                Some(U64::from(EIP1559_TX_ID)),
+                Some(options.max_priority_fee_per_gas.unwrap_or_default()),
+            ),
+            None => (None, None),
+        };
 
         let nonce = match options.nonce {
             Some(nonce) => nonce,
@@ -203,16 +225,16 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         // form and sign tx
         let tx = RawTransaction {
             chain_id: self.inner.chain_id,
-            transaction_type: None,
+            transaction_type,
             access_list: None,
-            max_fee_per_gas: Default::default(),
+            max_fee_per_gas: options.max_fee_per_gas,
             nonce,
             to: Some(contract_addr),
             value: options.value.unwrap_or_default(),
             gas_price,
             gas,
             data,
-            max_priority_fee_per_gas: Default::default(),
+            max_priority_fee_per_gas,
         };
 
         let signed_tx = self.inner.eth_signer.sign_transaction(tx).await?;
@@ -230,6 +252,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(SignedCallResult {
             raw_tx: signed_tx,
             gas_price,
+            max_priority_fee_per_gas,
             nonce,
             hash,
         })
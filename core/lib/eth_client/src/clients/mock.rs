@@ -20,6 +20,7 @@ use crate::{
 struct MockEthereumInner {
     block_number: u64,
     gas_price: U256,
+    base_fee: Option<U256>,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
 }
@@ -35,6 +36,7 @@ impl Default for MockEthereumInner {
         Self {
             block_number: 1,
             gas_price: 100.into(),
+            base_fee: None,
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
         }
@@ -124,6 +126,15 @@ impl MockEthereum {
         Ok(self.inner.gas_price)
     }
 
+    pub async fn get_base_fee(&self) -> anyhow::Result<Option<U256>> {
+        Ok(self.inner.base_fee)
+    }
+
+    pub async fn set_base_fee(&mut self, val: Option<U256>) -> anyhow::Result<Option<U256>> {
+        Arc::get_mut(&mut self.inner).unwrap().base_fee = val;
+        Ok(self.inner.base_fee)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -137,7 +148,10 @@ impl MockEthereum {
         raw_tx: Vec<u8>,
         options: Options,
     ) -> anyhow::Result<SignedCallResult> {
-        let gas_price = options.gas_price.unwrap_or(self.inner.gas_price);
+        let gas_price = options
+            .max_fee_per_gas
+            .or(options.gas_price)
+            .unwrap_or(self.inner.gas_price);
         let nonce = options.nonce.expect("Nonce must be set for every tx");
 
         // Nonce and gas_price are appended to distinguish the same transactions
@@ -152,6 +166,7 @@ impl MockEthereum {
         Ok(SignedCallResult {
             raw_tx: new_raw_tx,
             gas_price,
+            max_priority_fee_per_gas: options.max_priority_fee_per_gas,
             nonce,
             hash,
         })
@@ -106,6 +106,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, get_gas_price());
     }
 
+    pub async fn get_base_fee(&self) -> Result<Option<U256>, anyhow::Error> {
+        multiple_call!(self, get_base_fee());
+    }
+
     pub async fn sender_rbtc_balance(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, sender_rbtc_balance());
     }
@@ -16,7 +16,10 @@ use crate::ETHDirectClient;
 #[derive(Debug, Clone, PartialEq)]
 pub struct SignedCallResult {
     pub raw_tx: Vec<u8>,
+    /// Gas price of the legacy transaction, or the max fee per gas of the dynamic fee transaction.
     pub gas_price: U256,
+    /// Priority fee per gas, set only for the dynamic fee (EIP-1559) transactions.
+    pub max_priority_fee_per_gas: Option<U256>,
     pub nonce: U256,
     pub hash: H256,
 }
@@ -126,6 +129,12 @@ impl RootstockGateway {
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
+
+    /// Returns the base fee per gas of the latest block, or `None` if the network
+    /// doesn't support EIP-1559.
+    pub async fn get_base_fee(&self) -> Result<Option<U256>, anyhow::Error> {
+        delegate_call!(self.get_base_fee())
+    }
     /// Returns the account balance.
     pub async fn sender_rbtc_balance(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.sender_rbtc_balance())
@@ -133,6 +142,7 @@ impl RootstockGateway {
 
     /// Signs the transaction given the previously encoded data.
     /// Fills in gas/nonce if not supplied inside options.
+    /// Dynamic fee (EIP-1559) transaction is created if `max_fee_per_gas` is set in options.
     pub async fn sign_prepared_tx(
         &self,
         data: Vec<u8>,
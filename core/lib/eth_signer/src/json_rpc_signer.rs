@@ -341,27 +341,57 @@ mod messages {
         pub fn sign_transaction(from: Address, tx_data: RawTransaction) -> Self {
             let mut params = Vec::new();
 
+            let mut tx = serde_json::json!({
+                "from": serde_json::to_value(from).expect("serialization fail"),
+                "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
+                "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
+                "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
+                "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
+            });
+            let fields = tx.as_object_mut().expect("transaction is an object");
+
             // Parameter `To` is optional, so we add it only if it is not None
-            let tx = if let Some(to) = tx_data.to {
-                serde_json::json!({
-                    "from": serde_json::to_value(from).expect("serialization fail"),
-                    "to": serde_json::to_value(to).expect("serialization fail"),
-                    "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
-                    "gasPrice": serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
-                    "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
-                    "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
-                    "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
-                })
-            } else {
-                serde_json::json!({
-                    "from": serde_json::to_value(from).expect("serialization fail"),
-                    "gas": serde_json::to_value(tx_data.gas).expect("serialization fail"),
-                    "gasPrice": serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
-                    "value": serde_json::to_value(tx_data.value).expect("serialization fail"),
-                    "data": serde_json::to_value(format!("0x{}", encode(tx_data.data))).expect("serialization fail"),
-                    "nonce": serde_json::to_value(tx_data.nonce).expect("serialization fail"),
-                })
-            };
+            if let Some(to) = tx_data.to {
+                fields.insert(
+                    "to".to_owned(),
+                    serde_json::to_value(to).expect("serialization fail"),
+                );
+            }
+
+            // Typed transactions carry their type, and the dynamic fee ones are priced
+            // with the fee caps instead of the gas price.
+            if let Some(transaction_type) = tx_data.transaction_type {
+                fields.insert(
+                    "type".to_owned(),
+                    serde_json::to_value(transaction_type).expect("serialization fail"),
+                );
+                if let Some(access_list) = tx_data.access_list {
+                    fields.insert(
+                        "accessList".to_owned(),
+                        serde_json::to_value(access_list).expect("serialization fail"),
+                    );
+                }
+            }
+            match tx_data.max_fee_per_gas {
+                Some(max_fee_per_gas) if tx_data.transaction_type.is_some() => {
+                    fields.insert(
+                        "maxFeePerGas".to_owned(),
+                        serde_json::to_value(max_fee_per_gas).expect("serialization fail"),
+                    );
+                    fields.insert(
+                        "maxPriorityFeePerGas".to_owned(),
+                        serde_json::to_value(tx_data.max_priority_fee_per_gas.unwrap_or_default())
+                            .expect("serialization fail"),
+                    );
+                }
+                _ => {
+                    fields.insert(
+                        "gasPrice".to_owned(),
+                        serde_json::to_value(tx_data.gas_price).expect("serialization fail"),
+                    );
+                }
+            }
+
             params.push(tx);
             Self::create("eth_signTransaction", params)
         }
@@ -376,13 +406,14 @@ mod tests {
     use parity_crypto::publickey::{Generator, KeyPair, Random};
     use serde_json::json;
 
+    use web3::types::U64;
     use zksync_types::{
         tx::{PackedEthSignature, TxEthSignature},
-        Address,
+        Address, U256,
     };
 
     use super::{is_signature_from_address, messages::JsonRpcRequest};
-    use crate::{EthereumSigner, JsonRpcSigner, RawTransaction};
+    use crate::{raw_rootstock_tx::EIP1559_TX_ID, EthereumSigner, JsonRpcSigner, RawTransaction};
 
     #[post("/")]
     async fn index(req: web::Json<JsonRpcRequest>, state: web::Data<State>) -> impl Responder {
@@ -501,4 +532,59 @@ mod tests {
         assert_ne!(transaction_signature.len(), 0);
         abort_handle.abort();
     }
+
+    #[test]
+    fn sign_dynamic_fee_transaction_request() {
+        let from = Address::random();
+        let request = JsonRpcRequest::sign_transaction(
+            from,
+            RawTransaction {
+                chain_id: 270,
+                transaction_type: Some(U64::from(EIP1559_TX_ID)),
+                access_list: None,
+                max_fee_per_gas: Some(U256::from(30u32)),
+                nonce: U256::from(1u32),
+                to: Some(Address::zero()),
+                value: Default::default(),
+                gas_price: Default::default(),
+                gas: U256::from(21000u32),
+                data: vec![1, 2, 3],
+                max_priority_fee_per_gas: Some(U256::from(2u32)),
+            },
+        );
+
+        let tx = &request.params[0];
+        assert_eq!(tx["from"], json!(from));
+        assert_eq!(tx["to"], json!(Address::zero()));
+        assert_eq!(tx["type"], json!(U64::from(EIP1559_TX_ID)));
+        assert_eq!(tx["maxFeePerGas"], json!(U256::from(30u32)));
+        assert_eq!(tx["maxPriorityFeePerGas"], json!(U256::from(2u32)));
+        assert!(tx.get("gasPrice").is_none());
+    }
+
+    #[test]
+    fn sign_legacy_transaction_request() {
+        let request = JsonRpcRequest::sign_transaction(
+            Address::random(),
+            RawTransaction {
+                chain_id: 270,
+                transaction_type: None,
+                access_list: None,
+                max_fee_per_gas: None,
+                nonce: U256::from(1u32),
+                to: None,
+                value: Default::default(),
+                gas_price: U256::from(10u32),
+                gas: U256::from(21000u32),
+                data: vec![],
+                max_priority_fee_per_gas: None,
+            },
+        );
+
+        let tx = &request.params[0];
+        assert_eq!(tx["gasPrice"], json!(U256::from(10u32)));
+        assert!(tx.get("to").is_none());
+        assert!(tx.get("type").is_none());
+        assert!(tx.get("maxFeePerGas").is_none());
+    }
 }
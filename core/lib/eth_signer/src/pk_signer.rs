@@ -56,6 +56,7 @@ impl EthereumSigner for PrivateKeySigner {
             data: raw_tx.data,
             transaction_type: raw_tx.transaction_type,
             access_list: raw_tx.access_list.unwrap_or_default(),
+            max_fee_per_gas: gas_price,
             max_priority_fee_per_gas: raw_tx.max_priority_fee_per_gas.unwrap_or_default(),
         };
 
@@ -68,7 +69,10 @@ impl EthereumSigner for PrivateKeySigner {
 mod test {
     use super::PrivateKeySigner;
     use super::RawTransaction;
+    use crate::raw_rootstock_tx::EIP1559_TX_ID;
     use crate::EthereumSigner;
+    use rlp::{Rlp, RlpStream};
+    use web3::signing::{keccak256, recover};
     use web3::types::U64;
     use zksync_types::{H160, H256, U256};
 
@@ -105,4 +109,50 @@ mod test {
         ];
         assert_eq!(raw_tx, precalculated_raw_tx);
     }
+
+    #[tokio::test]
+    async fn test_generating_signed_dynamic_fee_transaction() {
+        let private_key = H256::from([5; 32]);
+        let signer = PrivateKeySigner::new(private_key);
+        let raw_transaction = RawTransaction {
+            nonce: U256::from(1u32),
+            to: Some(H160::default()),
+            gas: U256::from(21000u32),
+            gas_price: Default::default(),
+            max_fee_per_gas: Some(U256::from(30u32)),
+            max_priority_fee_per_gas: Some(U256::from(2u32)),
+            value: Default::default(),
+            data: vec![1, 2, 3],
+            chain_id: 270,
+            transaction_type: Some(U64::from(EIP1559_TX_ID)),
+            access_list: None,
+        };
+        let raw_tx = signer.sign_transaction(raw_transaction).await.unwrap();
+
+        // Typed envelope: `0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas, ...])`.
+        assert_eq!(raw_tx[0], EIP1559_TX_ID as u8);
+        let payload = Rlp::new(&raw_tx[1..]);
+        assert_eq!(payload.item_count().unwrap(), 12);
+        assert_eq!(payload.val_at::<u64>(0).unwrap(), 270);
+        assert_eq!(payload.val_at::<U256>(1).unwrap(), U256::from(1u32));
+        assert_eq!(payload.val_at::<U256>(2).unwrap(), U256::from(2u32));
+        assert_eq!(payload.val_at::<U256>(3).unwrap(), U256::from(30u32));
+        assert_eq!(payload.val_at::<U256>(4).unwrap(), U256::from(21000u32));
+
+        // Signature must be made over the unsigned payload of the same envelope.
+        let mut unsigned = RlpStream::new_list(9);
+        for i in 0..9 {
+            unsigned.append_raw(payload.at(i).unwrap().as_raw(), 1);
+        }
+        let message = keccak256(&[&[EIP1559_TX_ID as u8], unsigned.as_raw()].concat());
+        let recovery_id: u64 = payload.val_at(9).unwrap();
+        let r: U256 = payload.val_at(10).unwrap();
+        let s: U256 = payload.val_at(11).unwrap();
+        let mut signature = [0u8; 64];
+        r.to_big_endian(&mut signature[..32]);
+        s.to_big_endian(&mut signature[32..]);
+
+        let signer_address = recover(&message, &signature, recovery_id as i32).unwrap();
+        assert_eq!(signer_address, signer.get_address().await.unwrap());
+    }
 }
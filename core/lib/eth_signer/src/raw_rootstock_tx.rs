@@ -14,9 +14,9 @@ use web3::{
     types::{AccessList, Address, SignedTransaction, U256, U64},
 };
 
-const LEGACY_TX_ID: u64 = 0;
-const ACCESSLISTS_TX_ID: u64 = 1;
-const EIP1559_TX_ID: u64 = 2;
+pub const LEGACY_TX_ID: u64 = 0;
+pub const ACCESSLISTS_TX_ID: u64 = 1;
+pub const EIP1559_TX_ID: u64 = 2;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct RawTransaction {
@@ -55,6 +55,7 @@ pub struct Transaction {
     pub data: Vec<u8>,
     pub transaction_type: Option<U64>,
     pub access_list: AccessList,
+    pub max_fee_per_gas: U256,
     pub max_priority_fee_per_gas: U256,
 }
 
@@ -123,7 +124,7 @@ impl Transaction {
 
         stream.append(&self.nonce);
         stream.append(&self.max_priority_fee_per_gas);
-        stream.append(&self.gas_price);
+        stream.append(&self.max_fee_per_gas);
         stream.append(&self.gas);
         if let Some(to) = self.to {
             stream.append(&to);
@@ -0,0 +1,161 @@
+//! Fee-priority ordering for the ready L2 transactions.
+//!
+//! Block elements (single transactions or batches) are ordered by the fee they pay
+//! in USD per chunk, but an element can only be proposed after all the elements
+//! containing lower nonces of the same account. Batches are never split.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use num::{rational::Ratio, BigUint};
+
+use zksync_types::{mempool::SignedTxVariant, AccountId, Nonce};
+
+/// Reorders block elements so that the ones with the highest priority go first.
+///
+/// Elements with equal priority keep their original relative order. If nonce
+/// dependencies between elements form a cycle (which can only happen for
+/// malformed batches), the affected elements are appended in their original order.
+pub(crate) fn order_by_priority(
+    txs: Vec<(SignedTxVariant, Ratio<BigUint>)>,
+) -> Vec<(SignedTxVariant, Ratio<BigUint>)> {
+    let elements_count = txs.len();
+
+    // For each account collect the elements containing its transactions.
+    let mut account_nonces: HashMap<AccountId, Vec<(Nonce, usize)>> = HashMap::new();
+    for (idx, (element, _)) in txs.iter().enumerate() {
+        for tx in element.get_transactions() {
+            // `Close` operations are disabled, so they do not create any dependencies.
+            if let Ok(account_id) = tx.tx.account_id() {
+                account_nonces
+                    .entry(account_id)
+                    .or_default()
+                    .push((tx.tx.nonce(), idx));
+            }
+        }
+    }
+
+    // Element `a` must precede element `b` if `a` contains the previous nonce
+    // of some account used in `b`.
+    let mut dependents = vec![Vec::new(); elements_count];
+    let mut in_degree = vec![0usize; elements_count];
+    for nonces in account_nonces.values_mut() {
+        nonces.sort_unstable();
+        for pair in nonces.windows(2) {
+            let (from, to) = (pair[0].1, pair[1].1);
+            if from != to {
+                dependents[from].push(to);
+                in_degree[to] += 1;
+            }
+        }
+    }
+
+    let mut available: BinaryHeap<_> = (0..elements_count)
+        .filter(|&idx| in_degree[idx] == 0)
+        .map(|idx| (&txs[idx].1, Reverse(idx)))
+        .collect();
+
+    let mut order = Vec::with_capacity(elements_count);
+    let mut placed = vec![false; elements_count];
+    while let Some((_, Reverse(idx))) = available.pop() {
+        order.push(idx);
+        placed[idx] = true;
+        for &next in &dependents[idx] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                available.push((&txs[next].1, Reverse(next)));
+            }
+        }
+    }
+
+    if order.len() < elements_count {
+        vlog::warn!("Cyclic nonce dependencies were found in the mempool transactions");
+        order.extend((0..elements_count).filter(|&idx| !placed[idx]));
+    }
+
+    let mut txs: Vec<_> = txs.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .map(|idx| txs[idx].take().expect("Each element is placed only once"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::tx::TxHash;
+
+    use crate::test_utils::transfer;
+
+    use super::*;
+
+    fn priority(value: u32) -> Ratio<BigUint> {
+        Ratio::from_integer(BigUint::from(value))
+    }
+
+    /// Orders the elements with the given priorities and returns hashes of the ordered elements.
+    fn ordered_hashes(txs: &[SignedTxVariant], priorities: &[u32]) -> Vec<Vec<TxHash>> {
+        let txs = txs
+            .iter()
+            .cloned()
+            .zip(priorities.iter().copied().map(priority))
+            .collect();
+        order_by_priority(txs)
+            .iter()
+            .map(|(tx, _)| tx.hashes())
+            .collect()
+    }
+
+    /// Checks that elements are ordered by their priority, and elements with
+    /// equal priority keep the arrival order.
+    #[test]
+    fn orders_by_priority() {
+        let txs: Vec<SignedTxVariant> = vec![
+            transfer(1, 0).into(),
+            transfer(2, 0).into(),
+            transfer(3, 0).into(),
+            transfer(4, 0).into(),
+        ];
+        let hashes: Vec<_> = txs.iter().map(|tx| tx.hashes()).collect();
+
+        assert_eq!(
+            ordered_hashes(&txs, &[1, 5, 1, 10]),
+            vec![
+                hashes[3].clone(),
+                hashes[1].clone(),
+                hashes[0].clone(),
+                hashes[2].clone()
+            ]
+        );
+    }
+
+    /// Checks that a high-fee transaction is not placed before a transaction
+    /// with the lower nonce of the same account.
+    #[test]
+    fn respects_nonce_order() {
+        let txs: Vec<SignedTxVariant> = vec![
+            transfer(1, 1).into(),
+            transfer(2, 0).into(),
+            transfer(1, 0).into(),
+        ];
+        let hashes: Vec<_> = txs.iter().map(|tx| tx.hashes()).collect();
+
+        assert_eq!(
+            ordered_hashes(&txs, &[100, 50, 1]),
+            vec![hashes[1].clone(), hashes[2].clone(), hashes[0].clone()]
+        );
+    }
+
+    /// Checks that batches are kept atomic and wait for the lower nonces
+    /// of every account they contain.
+    #[test]
+    fn batch_depends_on_all_accounts() {
+        let batch = SignedTxVariant::batch(vec![transfer(1, 0), transfer(2, 1)], 1, Vec::new());
+        let txs: Vec<SignedTxVariant> = vec![batch, transfer(2, 0).into(), transfer(3, 0).into()];
+        let hashes: Vec<_> = txs.iter().map(|tx| tx.hashes()).collect();
+
+        assert_eq!(
+            ordered_hashes(&txs, &[100, 1, 10]),
+            vec![hashes[2].clone(), hashes[1].clone(), hashes[0].clone()]
+        );
+    }
+}
@@ -16,6 +16,7 @@ use futures::channel::mpsc;
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::chain::Mempool as MempoolConfig;
 use zksync_storage::ConnectionPool;
 
 // Local uses
@@ -27,10 +28,14 @@ pub use crate::transactions_handler::MempoolTransactionRequest;
 use crate::transactions_handler::MempoolTransactionsHandler;
 
 mod block_handler;
+mod fee_priority;
 mod mempool_transactions_queue;
 mod state;
 mod transactions_handler;
 
+#[cfg(test)]
+mod test_utils;
+
 // Due channel based nature, for better performance,
 // you need to run independent mempool_tx_handler for each actor, e.g. for each API actor
 #[must_use]
@@ -38,8 +43,9 @@ pub fn run_mempool_tx_handler(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
     block_chunk_sizes: Vec<usize>,
+    config: MempoolConfig,
 ) -> JoinHandle<()> {
-    let mempool_state = MempoolState::new(db_pool.clone());
+    let mempool_state = MempoolState::new(db_pool.clone(), config.ordering);
     let max_block_size_chunks = *block_chunk_sizes
         .iter()
         .max()
@@ -49,6 +55,7 @@ pub fn run_mempool_tx_handler(
         mempool_state,
         requests: tx_requests,
         max_block_size_chunks,
+        config,
     };
     tokio::spawn(handler.run())
 }
@@ -58,8 +65,9 @@ pub fn run_mempool_block_handler(
     db_pool: ConnectionPool,
     block_requests: mpsc::Receiver<MempoolBlocksRequest>,
     block_chunk_sizes: Vec<usize>,
+    config: MempoolConfig,
 ) -> JoinHandle<()> {
-    let mempool_state = MempoolState::new(db_pool);
+    let mempool_state = MempoolState::new(db_pool, config.ordering);
     let max_block_size_chunks = *block_chunk_sizes
         .iter()
         .max()
@@ -1,6 +1,9 @@
+use crate::fee_priority::order_by_priority;
 use crate::MempoolState;
+use num::{rational::Ratio, BigUint};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, VecDeque};
+use zksync_config::configs::chain::MempoolOrdering;
 use zksync_types::mempool::SignedTxVariant;
 use zksync_types::tx::error::TxAddError;
 use zksync_types::PriorityOp;
@@ -9,6 +12,8 @@ use zksync_types::PriorityOp;
 struct MempoolPendingTransaction {
     valid_from: u64,
     tx: SignedTxVariant,
+    /// Fee paid by the transaction in USD per chunk, stored along with the transaction.
+    fee_priority: Ratio<BigUint>,
 }
 
 impl Eq for MempoolPendingTransaction {}
@@ -37,34 +42,39 @@ impl PartialOrd for MempoolPendingTransaction {
 
 #[derive(Debug, Clone)]
 pub(crate) struct MempoolTransactionsQueue {
-    /// Transactions ready for execution.
-    ready_l2_transactions: VecDeque<SignedTxVariant>,
+    /// Transactions ready for execution, along with their fee priorities.
+    /// Kept ordered according to the `ordering` policy.
+    ready_l2_transactions: VecDeque<(SignedTxVariant, Ratio<BigUint>)>,
     /// Transactions that are not ready yet because of the `valid_from` field.
     pending_l2_transactions: BinaryHeap<MempoolPendingTransaction>,
 
     l1_transactions: VecDeque<PriorityOp>,
+    /// Policy used to order the ready transactions.
+    ordering: MempoolOrdering,
 }
 
 impl MempoolTransactionsQueue {
     pub(crate) fn new(
         l1_transactions: VecDeque<PriorityOp>,
-        l2_transactions: VecDeque<SignedTxVariant>,
+        l2_transactions: VecDeque<(SignedTxVariant, Ratio<BigUint>)>,
+        ordering: MempoolOrdering,
     ) -> Self {
         let mut res = Self {
             ready_l2_transactions: Default::default(),
             pending_l2_transactions: Default::default(),
             l1_transactions,
+            ordering,
         };
         // Due to complexity of json structure in database for transactions it's easier and safer
         // to add even not ready txs to mempool and prepare them before when it's needed.
-        for tx in l2_transactions {
-            res.add_l2_transaction(tx)
+        for (tx, fee_priority) in l2_transactions {
+            res.add_l2_transaction(tx, fee_priority)
         }
         res
     }
 
     fn pop_l2_transactions_front(&mut self) -> Option<SignedTxVariant> {
-        self.ready_l2_transactions.pop_front()
+        self.ready_l2_transactions.pop_front().map(|(tx, _)| tx)
     }
 
     fn pop_front_l1_transactions(&mut self) -> Option<PriorityOp> {
@@ -79,7 +89,7 @@ impl MempoolTransactionsQueue {
         }
     }
 
-    fn add_l2_transaction(&mut self, tx: SignedTxVariant) {
+    fn add_l2_transaction(&mut self, tx: SignedTxVariant, fee_priority: Ratio<BigUint>) {
         self.pending_l2_transactions
             .push(MempoolPendingTransaction {
                 valid_from: tx
@@ -89,6 +99,7 @@ impl MempoolTransactionsQueue {
                     .max()
                     .unwrap_or(0),
                 tx,
+                fee_priority,
             });
     }
 
@@ -99,7 +110,8 @@ impl MempoolTransactionsQueue {
 
             while let Some(pending_tx) = self.pending_l2_transactions.peek() {
                 if pending_tx.valid_from <= block_timestamp {
-                    ready_pending_l2_operations.push(pending_tx.tx.clone());
+                    ready_pending_l2_operations
+                        .push((pending_tx.tx.clone(), pending_tx.fee_priority.clone()));
                     self.pending_l2_transactions.pop();
                 } else {
                     break;
@@ -108,7 +120,7 @@ impl MempoolTransactionsQueue {
 
             // Now transactions should be sorted by the nonce (transaction natural order)
             // According to our convention in batch `fee transaction` would be the last one, so we would use nonce from it as a key for sort
-            ready_pending_l2_operations.sort_by_key(|tx| match tx {
+            ready_pending_l2_operations.sort_by_key(|(tx, _)| match tx {
                 SignedTxVariant::Tx(tx) => tx.tx.nonce(),
                 SignedTxVariant::Batch(batch) => batch
                     .txs
@@ -118,11 +130,24 @@ impl MempoolTransactionsQueue {
                     .nonce(),
             });
 
-            VecDeque::<SignedTxVariant>::from(ready_pending_l2_operations)
+            VecDeque::from(ready_pending_l2_operations)
         };
 
+        if ready_pending_l2_operations.is_empty() {
+            return;
+        }
         self.ready_l2_transactions
             .append(&mut ready_pending_l2_operations);
+        if self.ordering == MempoolOrdering::FeePriority {
+            self.order_ready_l2_transactions_by_fee();
+        }
+    }
+
+    /// Reorders the ready transactions according to the fee they pay in USD per chunk.
+    /// Uses the fee priorities stored along with the transactions, so no database access is needed.
+    fn order_ready_l2_transactions_by_fee(&mut self) {
+        let txs = self.ready_l2_transactions.drain(..).collect();
+        self.ready_l2_transactions = order_by_priority(txs).into();
     }
 
     /// Collect txs depending on desired chunks and execution time
@@ -209,6 +234,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::test_utils::transfer;
 
     fn get_transfer_with_timestamps(valid_from: u64, valid_until: u64) -> SignedTxVariant {
         let transfer = Transfer::new(
@@ -230,6 +256,10 @@ mod tests {
         })
     }
 
+    fn zero() -> Ratio<BigUint> {
+        Ratio::from_integer(BigUint::from(0u32))
+    }
+
     fn get_withdraw() -> SignedTxVariant {
         let withdraw = Withdraw::new(
             AccountId(3),
@@ -256,6 +286,7 @@ mod tests {
             ready_l2_transactions: VecDeque::new(),
             pending_l2_transactions: BinaryHeap::new(),
             l1_transactions: Default::default(),
+            ordering: MempoolOrdering::Fifo,
         };
 
         transactions_queue.add_l1_transactions(vec![
@@ -360,6 +391,7 @@ mod tests {
             ready_l2_transactions: VecDeque::new(),
             pending_l2_transactions: BinaryHeap::new(),
             l1_transactions: Default::default(),
+            ordering: MempoolOrdering::Fifo,
         };
 
         let withdraw0 = get_withdraw();
@@ -368,7 +400,7 @@ mod tests {
 
         // Insert transactions to the mempool transcations queue
         {
-            transactions_queue.add_l2_transaction(withdraw0.clone());
+            transactions_queue.add_l2_transaction(withdraw0.clone(), zero());
             assert_eq!(
                 transactions_queue
                     .pending_l2_transactions
@@ -379,8 +411,8 @@ mod tests {
             );
 
             // Some "random" order for trancsactions
-            transactions_queue.add_l2_transaction(transfer2.clone());
-            transactions_queue.add_l2_transaction(transfer1.clone());
+            transactions_queue.add_l2_transaction(transfer2.clone(), zero());
+            transactions_queue.add_l2_transaction(transfer1.clone(), zero());
         }
 
         // At first we should have only one transaction ready
@@ -389,7 +421,7 @@ mod tests {
 
             assert_eq!(transactions_queue.ready_l2_transactions.len(), 1);
             assert_eq!(
-                transactions_queue.ready_l2_transactions[0].hashes(),
+                transactions_queue.ready_l2_transactions[0].0.hashes(),
                 withdraw0.hashes()
             );
         }
@@ -400,7 +432,7 @@ mod tests {
 
             assert_eq!(transactions_queue.ready_l2_transactions.len(), 2);
             assert_eq!(
-                transactions_queue.ready_l2_transactions[1].hashes(),
+                transactions_queue.ready_l2_transactions[1].0.hashes(),
                 transfer1.hashes()
             );
         }
@@ -411,9 +443,53 @@ mod tests {
 
             assert_eq!(transactions_queue.ready_l2_transactions.len(), 3);
             assert_eq!(
-                transactions_queue.ready_l2_transactions[2].hashes(),
+                transactions_queue.ready_l2_transactions[2].0.hashes(),
                 transfer2.hashes()
             );
         }
     }
+
+    /// Checks that with the fee priority ordering the ready transactions are ordered
+    /// by their stored priorities as soon as they become ready.
+    #[test]
+    fn test_fee_priority_ordering() {
+        let mut transactions_queue = MempoolTransactionsQueue {
+            ready_l2_transactions: VecDeque::new(),
+            pending_l2_transactions: BinaryHeap::new(),
+            l1_transactions: Default::default(),
+            ordering: MempoolOrdering::FeePriority,
+        };
+
+        let cheap_tx: SignedTxVariant = transfer(1, 0).into();
+        let expensive_tx: SignedTxVariant = transfer(2, 0).into();
+        let delayed_tx = get_transfer_with_timestamps(5, 13);
+
+        transactions_queue.add_l2_transaction(cheap_tx.clone(), Ratio::from_integer(1u32.into()));
+        transactions_queue
+            .add_l2_transaction(expensive_tx.clone(), Ratio::from_integer(10u32.into()));
+        transactions_queue.add_l2_transaction(delayed_tx.clone(), Ratio::from_integer(5u32.into()));
+
+        transactions_queue.prepare_new_ready_l2_transactions(3);
+        let ready: Vec<_> = transactions_queue
+            .ready_l2_transactions
+            .iter()
+            .map(|(tx, _)| tx.hashes())
+            .collect();
+        assert_eq!(ready, vec![expensive_tx.hashes(), cheap_tx.hashes()]);
+
+        transactions_queue.prepare_new_ready_l2_transactions(9);
+        let ready: Vec<_> = transactions_queue
+            .ready_l2_transactions
+            .iter()
+            .map(|(tx, _)| tx.hashes())
+            .collect();
+        assert_eq!(
+            ready,
+            vec![
+                expensive_tx.hashes(),
+                delayed_tx.hashes(),
+                cheap_tx.hashes()
+            ]
+        );
+    }
 }
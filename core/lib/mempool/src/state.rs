@@ -1,11 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+use num::{rational::Ratio, BigUint};
+
+use zksync_config::configs::chain::MempoolOrdering;
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     mempool::{SignedTxVariant, SignedTxsBatch},
     tx::{error::TxAddError, TxHash},
-    Address, TransferOp, TransferToNewOp, ZkSyncTx,
+    Address, TokenId, TokenLike, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
 use crate::MempoolTransactionsQueue;
@@ -13,6 +16,7 @@ use crate::MempoolTransactionsQueue;
 #[derive(Debug, Clone)]
 pub(crate) struct MempoolState {
     db_pool: ConnectionPool,
+    ordering: MempoolOrdering,
 }
 
 impl MempoolState {
@@ -98,6 +102,89 @@ impl MempoolState {
         }
     }
 
+    /// Returns the USD price of the smallest unit of the token, based on the price
+    /// stored by the fee ticker, or `None` if the price is not known.
+    async fn token_unit_price(
+        &self,
+        token_id: TokenId,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<Option<Ratio<BigUint>>, TxAddError> {
+        let token = storage
+            .tokens_schema()
+            .get_token(TokenLike::Id(token_id))
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let price = storage
+            .tokens_schema()
+            .get_historical_ticker_price(token_id)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let unit_price = match (token, price) {
+            (Some(token), Some(price)) => {
+                Some(price.usd_price / BigUint::from(10u32).pow(token.decimals as u32))
+            }
+            _ => None,
+        };
+        Ok(unit_price)
+    }
+
+    /// Returns the total fee paid by the block element in USD, or `None` if the price
+    /// of any fee token is not known.
+    /// `token_prices` caches the unit prices of tokens between calls.
+    pub async fn fee_in_usd(
+        &self,
+        element: &SignedTxVariant,
+        storage: &mut StorageProcessor<'_>,
+        token_prices: &mut HashMap<TokenId, Option<Ratio<BigUint>>>,
+    ) -> Result<Option<Ratio<BigUint>>, TxAddError> {
+        let mut total_fee = Ratio::from_integer(BigUint::from(0u32));
+        for tx in element.get_transactions() {
+            if let Some((_, TokenLike::Id(token_id), _, fee)) = tx.tx.get_fee_info() {
+                let unit_price = match token_prices.get(&token_id) {
+                    Some(price) => price.clone(),
+                    None => {
+                        let price = self.token_unit_price(token_id, storage).await?;
+                        token_prices.insert(token_id, price.clone());
+                        price
+                    }
+                };
+                match unit_price {
+                    Some(unit_price) => total_fee += unit_price * fee,
+                    None => return Ok(None),
+                }
+            }
+        }
+        Ok(Some(total_fee))
+    }
+
+    /// Returns the fee paid by the block element in USD per chunk.
+    ///
+    /// It is computed once the element is added to the mempool and stored along with it,
+    /// so the block proposer doesn't have to access the database to order transactions.
+    pub async fn fee_per_chunk(
+        &self,
+        element: &SignedTxVariant,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<Ratio<BigUint>, TxAddError> {
+        let start = Instant::now();
+        let mut existing_accounts = HashSet::new();
+        let mut chunks = 0;
+        for tx in element.get_transactions() {
+            chunks += self
+                .chunks_for_tx_with_cache(&tx.tx, storage, &mut existing_accounts)
+                .await?;
+        }
+        // Elements paying in tokens without a known price get the lowest priority.
+        let total_fee = self
+            .fee_in_usd(element, storage, &mut HashMap::new())
+            .await?
+            .unwrap_or_else(|| Ratio::from_integer(BigUint::from(0u32)));
+
+        metrics::histogram!("mempool_state.fee_per_chunk", start.elapsed());
+        Ok(total_fee / BigUint::from(chunks.max(1)))
+    }
+
     pub async fn collect_garbage(&self) {
         let mut storage = self.db_pool.access_storage().await.expect("Db error");
         // Remove any possible duplicates of already executed transactions
@@ -110,8 +197,8 @@ impl MempoolState {
             .expect("Db error");
     }
 
-    pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+    pub fn new(db_pool: ConnectionPool, ordering: MempoolOrdering) -> Self {
+        Self { db_pool, ordering }
     }
 
     pub async fn get_transaction_queue(
@@ -140,11 +227,12 @@ impl MempoolState {
         let mempool_txs = transaction
             .chain()
             .mempool_schema()
-            .load_txs(executed_txs)
+            .load_prioritized_txs(executed_txs)
             .await
             .map_err(|_| TxAddError::DbError)?;
 
-        let transactions_queue = MempoolTransactionsQueue::new(priority_ops, mempool_txs);
+        let transactions_queue =
+            MempoolTransactionsQueue::new(priority_ops, mempool_txs, self.ordering);
 
         Ok(transactions_queue)
     }
@@ -0,0 +1,27 @@
+//! Helpers shared by the mempool unit tests.
+
+use chrono::Utc;
+
+use zksync_types::tx::Transfer;
+use zksync_types::{AccountId, Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx};
+
+/// Creates a transfer of the given account with the given nonce.
+pub(crate) fn transfer(account_id: u32, nonce: u32) -> SignedZkSyncTx {
+    let transfer = Transfer::new(
+        AccountId(account_id),
+        Address::random(),
+        Address::random(),
+        TokenId(0),
+        500u32.into(),
+        20u32.into(),
+        Nonce(nonce),
+        Default::default(),
+        None,
+    );
+
+    SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(transfer)),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    }
+}
@@ -1,11 +1,16 @@
+use std::collections::HashMap;
+
+use num::{rational::Ratio, BigUint};
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
 
-use zksync_storage::ConnectionPool;
+use zksync_config::configs::chain::{Mempool as MempoolConfig, MempoolEvictionPolicy};
+use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
-    mempool::SignedTxsBatch,
+    mempool::{SignedTxVariant, SignedTxsBatch},
     tx::{error::TxAddError, TxEthSignature},
-    PriorityOp, SignedZkSyncTx,
+    AccountId, PriorityOp, SignedZkSyncTx,
 };
 
 use crate::state::MempoolState;
@@ -39,9 +44,171 @@ pub(crate) struct MempoolTransactionsHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolTransactionRequest>,
     pub max_block_size_chunks: usize,
+    pub config: MempoolConfig,
 }
 
 impl MempoolTransactionsHandler {
+    /// Checks that the new block element fits into the mempool limits.
+    ///
+    /// Fails if any account used by the element exceeds its limit. If the whole mempool
+    /// is full, evicts transactions according to the configured policy to free the space.
+    /// Since several handlers may work with the same database, limits are not strict.
+    async fn ensure_mempool_capacity(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        element: &SignedTxVariant,
+        fee_priority: &Ratio<BigUint>,
+    ) -> Result<(), TxAddError> {
+        let txs = element.get_transactions();
+
+        let mut new_account_txs: HashMap<AccountId, usize> = HashMap::new();
+        for tx in &txs {
+            if let Ok(account_id) = tx.account_id() {
+                *new_account_txs.entry(account_id).or_default() += 1;
+            }
+        }
+        for (account_id, new_txs) in new_account_txs {
+            let queued_txs = storage
+                .chain()
+                .mempool_schema()
+                .get_account_txs_count(account_id)
+                .await
+                .map_err(|_| TxAddError::DbError)? as usize;
+
+            if queued_txs + new_txs > self.config.max_txs_per_account {
+                metrics::increment_counter!("mempool.limit_reached", "limit" => "account");
+                return Err(TxAddError::AccountTxsLimitReached);
+            }
+        }
+
+        let mempool_size = storage
+            .chain()
+            .mempool_schema()
+            .get_mempool_size()
+            .await
+            .map_err(|_| TxAddError::DbError)? as usize;
+        let excess = (mempool_size + txs.len()).saturating_sub(self.config.max_txs_total);
+        if excess > 0 {
+            self.evict_txs(storage, element, fee_priority, excess)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes at least `txs_count` transactions from the mempool to free the space
+    /// for the new block element. Fails if there are not enough transactions that can be evicted.
+    /// Must be called within the database transaction that inserts the new element,
+    /// so the evicted transactions are kept if the insertion fails.
+    async fn evict_txs(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        element: &SignedTxVariant,
+        fee_priority: &Ratio<BigUint>,
+        mut txs_count: usize,
+    ) -> Result<(), TxAddError> {
+        // Accounts used by the new element are never evicted, since it would leave
+        // a nonce gap in front of the new element.
+        let protected_accounts: Vec<_> = element
+            .get_transactions()
+            .iter()
+            .filter_map(|tx| tx.tx.account_id().ok())
+            .collect();
+        // Transactions paying more than the new one are never evicted.
+        let fee_priority_below = match self.config.eviction_policy {
+            MempoolEvictionPolicy::Oldest => None,
+            MempoolEvictionPolicy::LowestFee => Some(fee_priority),
+        };
+        // Each candidate contains at least one transaction, so `txs_count` candidates are enough.
+        let candidates = storage
+            .chain()
+            .mempool_schema()
+            .get_eviction_candidates(&protected_accounts, fee_priority_below, txs_count)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+
+        let mut evicted_hashes = Vec::new();
+        for hashes in candidates {
+            if txs_count == 0 {
+                break;
+            }
+            txs_count = txs_count.saturating_sub(hashes.len());
+            evicted_hashes.extend(hashes);
+        }
+
+        if txs_count > 0 {
+            metrics::increment_counter!("mempool.limit_reached", "limit" => "total");
+            return Err(TxAddError::MempoolIsFull);
+        }
+
+        storage
+            .chain()
+            .mempool_schema()
+            .remove_txs(&evicted_hashes)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+
+        vlog::info!(
+            "Evicted {} transactions from the full mempool",
+            evicted_hashes.len()
+        );
+        metrics::counter!("mempool.evicted_txs", evicted_hashes.len() as u64);
+        Ok(())
+    }
+
+    /// Checks whether the new transaction pays enough to replace the queued one
+    /// with the same account and nonce.
+    ///
+    /// If both transactions pay fee in the same token, raw amounts are compared.
+    /// Otherwise, fees are converted to USD using the prices stored by the fee ticker,
+    /// and the replacement is rejected if any of the prices is not known.
+    async fn check_replacement_fee(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        replaced_tx: &SignedZkSyncTx,
+        new_tx: &SignedZkSyncTx,
+    ) -> Result<(), TxAddError> {
+        let bump_percent = BigUint::from(100 + self.config.replacement_fee_bump_percent);
+        let hundred = BigUint::from(100u32);
+
+        let enough = match (replaced_tx.get_fee_info(), new_tx.get_fee_info()) {
+            (Some((_, replaced_token, _, replaced_fee)), Some((_, new_token, _, new_fee)))
+                if replaced_token == new_token =>
+            {
+                new_fee * hundred >= replaced_fee * bump_percent
+            }
+            _ => {
+                let mut token_prices = HashMap::new();
+                let replaced_fee = self
+                    .mempool_state
+                    .fee_in_usd(&replaced_tx.clone().into(), storage, &mut token_prices)
+                    .await?;
+                let new_fee = self
+                    .mempool_state
+                    .fee_in_usd(&new_tx.clone().into(), storage, &mut token_prices)
+                    .await?;
+                match (replaced_fee, new_fee) {
+                    (Some(replaced_fee), Some(new_fee)) => {
+                        new_fee * hundred >= replaced_fee * bump_percent
+                    }
+                    _ => return Err(TxAddError::ReplacementFeeUnknown),
+                }
+            }
+        };
+
+        if enough {
+            Ok(())
+        } else {
+            Err(TxAddError::ReplacementFeeTooLow)
+        }
+    }
+
     async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
         // Correctness should be checked by `signature_checker`, thus
         // `tx.check_correctness()` is not invoked here.
@@ -63,15 +230,80 @@ impl MempoolTransactionsHandler {
             return Err(TxAddError::NonceMismatch);
         }
 
-        storage
+        // A queued transaction with the same nonce can be replaced if the new one
+        // pays a higher fee. Transactions from batches are never replaced, as well as
+        // transactions that are already included into the pending block.
+        let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
+        let nonce_used = storage
+            .chain()
+            .block_schema()
+            .is_nonce_used_in_pending_block(account_id, tx.nonce())
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        if nonce_used {
+            return Err(TxAddError::NonceUsedInPendingBlock);
+        }
+        let replaced_tx = storage
             .chain()
             .mempool_schema()
-            .insert_tx(&tx)
+            .get_queued_tx_by_nonce(account_id, tx.nonce())
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        let element = SignedTxVariant::Tx(tx.clone());
+        let fee_priority = self
+            .mempool_state
+            .fee_per_chunk(&element, &mut storage)
+            .await?;
+        if let Some(replaced_tx) = &replaced_tx {
+            self.check_replacement_fee(&mut storage, replaced_tx, &tx)
+                .await?;
+        }
+
+        // Eviction and insertion are done atomically, so a failed insertion
+        // doesn't drop the evicted transactions.
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        if let Some(replaced_tx) = replaced_tx {
+            transaction
+                .chain()
+                .mempool_schema()
+                .replace_tx(&replaced_tx, &tx)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+            metrics::increment_counter!("mempool.replaced_txs");
+        } else {
+            self.ensure_mempool_capacity(&mut transaction, &element, &fee_priority)
+                .await?;
+
+            transaction
+                .chain()
+                .mempool_schema()
+                .insert_tx(&tx)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
+        transaction
+            .chain()
+            .mempool_schema()
+            .set_fee_priority(&element.hashes(), &fee_priority)
             .await
             .map_err(|err| {
                 vlog::error!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        transaction.commit().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
 
         let labels = vec![
             ("stage", "mempool".to_string()),
@@ -170,6 +402,12 @@ impl MempoolTransactionsHandler {
             return Err(TxAddError::BatchTooBig);
         }
 
+        let element = SignedTxVariant::Batch(batch.clone());
+        let fee_priority = self
+            .mempool_state
+            .fee_per_chunk(&element, &mut storage)
+            .await?;
+
         for tx in &batch.txs {
             let labels = vec![
                 ("stage", "mempool".to_string()),
@@ -180,7 +418,16 @@ impl MempoolTransactionsHandler {
             metrics::histogram!("process_tx", tx.elapsed(), &labels);
         }
 
-        storage
+        // Eviction and insertion are done atomically, so a failed insertion
+        // doesn't drop the evicted transactions.
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            vlog::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        self.ensure_mempool_capacity(&mut transaction, &element, &fee_priority)
+            .await?;
+
+        transaction
             .chain()
             .mempool_schema()
             .insert_batch(&batch.txs, eth_signatures)
@@ -189,6 +436,19 @@ impl MempoolTransactionsHandler {
                 vlog::warn!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
+        transaction
+            .chain()
+            .mempool_schema()
+            .set_fee_priority(&element.hashes(), &fee_priority)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        transaction.commit().await.map_err(|err| {
+            vlog::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
 
         Ok(())
     }
@@ -1,29 +1,162 @@
-use matter_most_notifier::MatterMostNotifier;
+//! Notifier sends the operator alerts raised by the server components
+//! to the configured third-party services.
+
+use std::path::PathBuf;
+
 use reqwest::Url;
+use zksync_config::NotifierConfig;
 use zksync_types::tokens::Token;
 
+pub use self::{
+    log_notifier::LogNotifier,
+    matter_most_notifier::MatterMostNotifier,
+    notification::{Notification, Severity},
+    slack_notifier::SlackNotifier,
+    webhook_notifier::WebhookNotifier,
+};
+
+mod log_notifier;
 mod matter_most_notifier;
+mod notification;
+mod slack_notifier;
+mod webhook_notifier;
+
+/// Service capable of delivering the notifications.
+#[async_trait::async_trait]
+pub trait NotifierBackend: Send + Sync {
+    async fn send(&self, notification: &Notification) -> anyhow::Result<()>;
+}
 
+/// Broadcasts the notifications to all the backends it was configured with.
+#[derive(Default)]
 pub struct Notifier {
-    matter_most_notifier: MatterMostNotifier,
+    backends: Vec<Box<dyn NotifierBackend>>,
 }
 
 impl Notifier {
-    pub fn with_mattermost(webhook_url: Url) -> Self {
-        Self {
-            matter_most_notifier: MatterMostNotifier::new(webhook_url),
+    /// Creates a notifier with every backend enabled in the config.
+    /// Empty or malformed webhook URLs are ignored.
+    pub fn from_config(config: &NotifierConfig) -> Self {
+        let mut notifier = Self::default();
+
+        if let Ok(url) = Url::parse(&config.mattermost_webhook_url) {
+            notifier = notifier.with_backend(MatterMostNotifier::new(url));
+        }
+        if let Ok(url) = Url::parse(&config.slack_webhook_url) {
+            notifier = notifier.with_backend(SlackNotifier::new(url));
+        }
+        if let Ok(url) = Url::parse(&config.webhook_url) {
+            notifier = notifier.with_backend(WebhookNotifier::new(url));
         }
+        if config.log_enabled {
+            let file = Some(&config.log_file)
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from);
+            notifier = notifier.with_backend(LogNotifier::new(file));
+        }
+
+        notifier
+    }
+
+    /// Adds another backend to deliver the notifications to.
+    pub fn with_backend(mut self, backend: impl NotifierBackend + 'static) -> Self {
+        self.backends.push(Box::new(backend));
+        self
+    }
+
+    /// Returns `true` if the notifications are not delivered anywhere.
+    pub fn is_empty(&self) -> bool {
+        self.backends.is_empty()
+    }
+
+    /// Sends the notification to every backend.
+    ///
+    /// Failure of one backend doesn't prevent the delivery to the other ones,
+    /// the first encountered error is returned once all the backends are tried.
+    pub async fn send(&self, notification: Notification) -> anyhow::Result<()> {
+        let mut result = Ok(());
+        for backend in &self.backends {
+            if let Err(err) = backend.send(&notification).await {
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+
+        result
     }
 
     pub async fn send_new_token_notify(&self, token: Token) -> anyhow::Result<()> {
-        let token_info_msg = format!(
-            "New token: id = {}, address = {}, symbol = {}, decimals = {}",
-            token.id, token.address, token.symbol, token.decimals,
+        self.send(Notification::NewToken(token)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default, Clone)]
+    struct RecordingBackend {
+        fail: bool,
+        received: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl NotifierBackend for RecordingBackend {
+        async fn send(&self, notification: &Notification) -> anyhow::Result<()> {
+            self.received.lock().unwrap().push(notification.to_string());
+            if self.fail {
+                anyhow::bail!("backend is down");
+            }
+            Ok(())
+        }
+    }
+
+    /// Checks that the notification is delivered to every backend, even if one of them fails.
+    #[tokio::test]
+    async fn broadcast() {
+        let failing = RecordingBackend {
+            fail: true,
+            ..Default::default()
+        };
+        let working = RecordingBackend::default();
+        let notifier = Notifier::default()
+            .with_backend(failing.clone())
+            .with_backend(working.clone());
+
+        let token = Token {
+            symbol: "RDOC".to_string(),
+            decimals: 18,
+            ..Default::default()
+        };
+        let result = notifier.send_new_token_notify(token).await;
+        assert!(result.is_err());
+
+        let expected = format!(
+            "New token: id = 0, address = {}, symbol = RDOC, decimals = 18",
+            zksync_types::Address::zero()
         );
-        self.matter_most_notifier
-            .send_notify("token_handler_bot", &token_info_msg)
-            .await?;
+        assert_eq!(*failing.received.lock().unwrap(), vec![expected.clone()]);
+        assert_eq!(*working.received.lock().unwrap(), vec![expected]);
+    }
+
+    /// Checks that only the configured backends are enabled.
+    #[test]
+    fn from_config() {
+        let mut config = NotifierConfig {
+            mattermost_webhook_url: String::new(),
+            slack_webhook_url: String::new(),
+            webhook_url: String::new(),
+            log_enabled: false,
+            log_file: String::new(),
+            prover_idle_timeout: 1800,
+            priority_queue_backlog_timeout: 3600,
+        };
+        assert!(Notifier::from_config(&config).is_empty());
 
-        Ok(())
+        config.slack_webhook_url = "https://hooks.slack.com/services/T0/B0/XXX".to_string();
+        config.log_enabled = true;
+        assert_eq!(Notifier::from_config(&config).backends.len(), 2);
     }
 }
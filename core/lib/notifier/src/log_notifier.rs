@@ -0,0 +1,102 @@
+// Built-in uses
+use std::path::PathBuf;
+// External uses
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+// Local uses
+use crate::{Notification, NotifierBackend, Severity};
+
+/// Writes the notifications to the server logs and, if the file is provided,
+/// appends them to this file as JSON lines.
+///
+/// Useful for the local development or as a fallback when no external service is configured.
+pub struct LogNotifier {
+    file: Option<PathBuf>,
+}
+
+impl LogNotifier {
+    pub fn new(file: Option<PathBuf>) -> Self {
+        Self { file }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotifierBackend for LogNotifier {
+    async fn send(&self, notification: &Notification) -> anyhow::Result<()> {
+        match notification.severity() {
+            Severity::Info => vlog::info!("[{}] {}", notification.source(), notification),
+            Severity::Warning => vlog::warn!("[{}] {}", notification.source(), notification),
+            Severity::Critical => vlog::error!("[{}] {}", notification.source(), notification),
+        }
+
+        if let Some(path) = &self.file {
+            let mut line = serde_json::to_string(&serde_json::json!({
+                "source": notification.source(),
+                "severity": notification.severity(),
+                "message": notification.to_string(),
+                "notification": notification,
+            }))?;
+            line.push('\n');
+
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await?;
+            file.write_all(line.as_bytes()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use zksync_types::BlockNumber;
+
+    /// Checks that the notifications are appended to the file as JSON lines.
+    #[tokio::test]
+    async fn file_sink() {
+        let path =
+            std::env::temp_dir().join(format!("zksync_notifications_{}.jsonl", std::process::id()));
+        let notifier = LogNotifier::new(Some(path.clone()));
+
+        notifier
+            .send(&Notification::BlockRevert {
+                last_correct_block: BlockNumber(10),
+                reverted_blocks: 2,
+            })
+            .await
+            .unwrap();
+        notifier
+            .send(&Notification::ProverIdle {
+                idle_time: Duration::from_secs(600),
+                pending_blocks: 5,
+            })
+            .await
+            .unwrap();
+
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0]["source"], "block_revert_bot");
+        assert_eq!(lines[0]["severity"], "critical");
+        assert_eq!(lines[0]["notification"]["type"], "blockRevert");
+        assert_eq!(lines[0]["notification"]["lastCorrectBlock"], 10);
+
+        assert_eq!(lines[1]["severity"], "warning");
+        assert_eq!(lines[1]["notification"]["type"], "proverIdle");
+        assert_eq!(lines[1]["notification"]["pendingBlocks"], 5);
+        assert_eq!(
+            lines[1]["message"],
+            "Prover is idle: no jobs were taken for 600s, pending blocks = 5"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use reqwest::{Client, Url};
 
+use crate::{Notification, NotifierBackend};
+
 pub struct MatterMostNotifier {
     webhook_url: Url,
     client: Client,
@@ -28,3 +30,11 @@ impl MatterMostNotifier {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl NotifierBackend for MatterMostNotifier {
+    async fn send(&self, notification: &Notification) -> anyhow::Result<()> {
+        self.send_notify(notification.source(), &notification.to_string())
+            .await
+    }
+}
@@ -0,0 +1,159 @@
+// Built-in uses
+use std::{fmt, time::Duration};
+// External uses
+use serde::Serialize;
+// Workspace uses
+use zksync_types::{tokens::Token, Address, BlockNumber, H256, U256};
+
+/// Importance of the notification, used by the backends to choose the formatting
+/// (or the log level) of the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        };
+        write!(f, "{}", severity)
+    }
+}
+
+/// Operator alerts which can be raised by the server components.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Notification {
+    /// Blocks after `last_correct_block` were reverted.
+    #[serde(rename_all = "camelCase")]
+    BlockRevert {
+        last_correct_block: BlockNumber,
+        reverted_blocks: u32,
+    },
+    /// Rootstock transaction was not mined in time and had to be resent.
+    #[serde(rename_all = "camelCase")]
+    EthTxStuck {
+        eth_op_id: i64,
+        tx_hash: H256,
+        gas_price: U256,
+    },
+    /// Prover did not take any job for a while, though there are blocks waiting to be proven.
+    #[serde(rename_all = "camelCase")]
+    ProverIdle {
+        idle_time: Duration,
+        pending_blocks: u64,
+    },
+    /// Priority operations are piling up in the queue without being processed.
+    #[serde(rename_all = "camelCase")]
+    PriorityQueueBacklog {
+        unprocessed_ops: u64,
+        oldest_op_age: Duration,
+    },
+    /// New token was added to the database.
+    NewToken(Token),
+    /// Operator balance is getting low (or got back to normal).
+    #[serde(rename_all = "camelCase")]
+    OperatorBalance {
+        severity: Severity,
+        operator: Address,
+        balance: U256,
+        runway_blocks: Option<U256>,
+    },
+}
+
+impl Notification {
+    /// Name of the component that raised the notification.
+    pub fn source(&self) -> &'static str {
+        match self {
+            Self::BlockRevert { .. } => "block_revert_bot",
+            Self::EthTxStuck { .. } | Self::OperatorBalance { .. } => "eth_sender_bot",
+            Self::ProverIdle { .. } => "witness_generator_bot",
+            Self::PriorityQueueBacklog { .. } => "priority_queue_bot",
+            Self::NewToken(..) => "token_handler_bot",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::BlockRevert { .. } => Severity::Critical,
+            Self::EthTxStuck { .. }
+            | Self::ProverIdle { .. }
+            | Self::PriorityQueueBacklog { .. } => Severity::Warning,
+            Self::NewToken(..) => Severity::Info,
+            Self::OperatorBalance { severity, .. } => *severity,
+        }
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlockRevert {
+                last_correct_block,
+                reverted_blocks,
+            } => write!(
+                f,
+                "Blocks were reverted: last correct block = {}, reverted blocks = {}",
+                last_correct_block, reverted_blocks,
+            ),
+            Self::EthTxStuck {
+                eth_op_id,
+                tx_hash,
+                gas_price,
+            } => write!(
+                f,
+                "Rootstock transaction is stuck: operation id = {}, tx hash = {:?}, gas price = {}",
+                eth_op_id, tx_hash, gas_price,
+            ),
+            Self::ProverIdle {
+                idle_time,
+                pending_blocks,
+            } => write!(
+                f,
+                "Prover is idle: no jobs were taken for {}s, pending blocks = {}",
+                idle_time.as_secs(),
+                pending_blocks,
+            ),
+            Self::PriorityQueueBacklog {
+                unprocessed_ops,
+                oldest_op_age,
+            } => write!(
+                f,
+                "Priority queue backlog: unprocessed operations = {}, oldest operation age = {}s",
+                unprocessed_ops,
+                oldest_op_age.as_secs(),
+            ),
+            Self::NewToken(token) => write!(
+                f,
+                "New token: id = {}, address = {}, symbol = {}, decimals = {}",
+                token.id, token.address, token.symbol, token.decimals,
+            ),
+            Self::OperatorBalance {
+                severity,
+                operator,
+                balance,
+                runway_blocks,
+            } => {
+                let status = match severity {
+                    Severity::Info => "is back to normal",
+                    Severity::Warning => "is low",
+                    Severity::Critical => "is critically low",
+                };
+                let runway = runway_blocks
+                    .map(|blocks| blocks.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                write!(
+                    f,
+                    "Operator balance {}: address = {:?}, balance = {} wei, runway = {} blocks",
+                    status, operator, balance, runway,
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use reqwest::{Client, Url};
+
+use crate::{Notification, NotifierBackend, Severity};
+
+/// Posts the notifications to the Slack (or any Slack-compatible) incoming webhook.
+pub struct SlackNotifier {
+    webhook_url: Url,
+    client: Client,
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url: Url) -> Self {
+        Self {
+            webhook_url,
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotifierBackend for SlackNotifier {
+    async fn send(&self, notification: &Notification) -> anyhow::Result<()> {
+        let icon = match notification.severity() {
+            Severity::Info => ":information_source:",
+            Severity::Warning => ":warning:",
+            Severity::Critical => ":rotating_light:",
+        };
+        let parameters = serde_json::json!({
+            "username": notification.source(),
+            "text": format!("{} {}", icon, notification),
+        });
+
+        self.client
+            .post(self.webhook_url.clone())
+            .json(&parameters)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use reqwest::{Client, Url};
+
+use crate::{Notification, NotifierBackend};
+
+/// Posts the notifications as JSON objects to an arbitrary webhook,
+/// so they can be processed by the external alerting systems.
+///
+/// Besides the human-readable message, the payload contains the notification itself,
+/// tagged with its `type`.
+pub struct WebhookNotifier {
+    webhook_url: Url,
+    client: Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(webhook_url: Url) -> Self {
+        Self {
+            webhook_url,
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotifierBackend for WebhookNotifier {
+    async fn send(&self, notification: &Notification) -> anyhow::Result<()> {
+        let parameters = serde_json::json!({
+            "source": notification.source(),
+            "severity": notification.severity(),
+            "message": notification.to_string(),
+            "notification": notification,
+        });
+
+        self.client
+            .post(self.webhook_url.clone())
+            .json(&parameters)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@ use zksync_circuit::serialization::ProverData;
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_types::BlockNumber;
 
+pub mod v2;
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ProverInputRequest {
     pub prover_name: String,
@@ -0,0 +1,112 @@
+//! Types of the prover server API v2.
+//!
+//! Provers authenticate with the personal tokens registered on the server, so the prover
+//! is identified by its token and its name is not passed in the requests.
+//! Unlike v1, a prover can take several jobs at once, and the errors are reported as `ApiError`.
+
+// Built-in deps
+use std::fmt;
+// External deps
+use serde::{Deserialize, Serialize};
+// Workspace deps
+use zksync_types::BlockNumber;
+// Local deps
+use super::JobRequestData;
+
+/// Jobs the prover is able to prove.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProverCapabilities {
+    /// Maximum size (in chunks) of the blocks the prover has setup for.
+    /// `None` if blocks of any size are supported.
+    pub max_block_chunks: Option<usize>,
+    /// Whether the prover is able to aggregate proofs.
+    pub aggregated_proofs: bool,
+}
+
+impl Default for ProverCapabilities {
+    fn default() -> Self {
+        Self {
+            max_block_chunks: None,
+            aggregated_proofs: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobsRequest {
+    /// Maximum amount of jobs to hand out.
+    pub max_jobs: u32,
+    pub capabilities: ProverCapabilities,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub job_id: i32,
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    pub data: JobRequestData,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobsResponse {
+    /// Leased jobs, empty if there is nothing to prove.
+    pub jobs: Vec<Job>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobHeartbeat {
+    pub job_id: i32,
+    /// Progress of the job in percent, if the prover is able to estimate it.
+    pub progress: Option<u8>,
+}
+
+/// Renews the leases of the jobs the prover holds.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeartbeatRequest {
+    pub jobs: Vec<JobHeartbeat>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HeartbeatResponse {
+    /// Jobs that are not leased to the prover anymore, the prover should abandon them.
+    pub lost_jobs: Vec<i32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Token is missing, unknown or revoked.
+    Unauthorized,
+    /// Request is malformed or its parameters are out of range.
+    InvalidRequest,
+    /// Proof for the job has already been stored.
+    DuplicateProof,
+    /// Job is not in progress or is leased to another prover.
+    JobNotLeased,
+    /// Server failed to access its storage, the request may be retried.
+    StorageError,
+}
+
+/// Error returned by all the v2 endpoints.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl ToString) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
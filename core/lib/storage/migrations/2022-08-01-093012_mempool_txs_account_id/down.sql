@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS mempool_txs_account_id_idx;
+ALTER TABLE mempool_txs DROP COLUMN account_id;
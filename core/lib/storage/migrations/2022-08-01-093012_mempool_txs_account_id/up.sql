@@ -0,0 +1,11 @@
+-- Account that owns the transaction nonce, used to limit the amount of transactions per account.
+ALTER TABLE mempool_txs ADD COLUMN account_id BIGINT;
+
+UPDATE mempool_txs SET account_id = COALESCE(
+    (tx->>'accountId')::bigint,
+    (tx->>'initiatorAccountId')::bigint,
+    (tx->>'creatorId')::bigint,
+    (tx->>'submitterId')::bigint
+);
+
+CREATE INDEX IF NOT EXISTS mempool_txs_account_id_idx ON mempool_txs (account_id);
@@ -0,0 +1,13 @@
+-- Every token price sample received by the fee ticker.
+-- `ticker_price` keeps only the latest price of each token.
+CREATE TABLE ticker_price_history (
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    usd_price NUMERIC NOT NULL,
+    last_updated TIMESTAMP with time zone NOT NULL,
+    PRIMARY KEY (token_id, last_updated)
+);
+
+CREATE INDEX IF NOT EXISTS ticker_price_history_last_updated_idx ON ticker_price_history (last_updated);
+
+INSERT INTO ticker_price_history (token_id, usd_price, last_updated)
+SELECT token_id, usd_price, last_updated FROM ticker_price;
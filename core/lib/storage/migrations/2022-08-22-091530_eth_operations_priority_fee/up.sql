@@ -0,0 +1,3 @@
+-- Priority fee of the last sent dynamic fee (EIP-1559) transaction.
+-- `NULL` for the legacy transactions.
+ALTER TABLE eth_operations ADD COLUMN last_used_priority_fee NUMERIC;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS prover_job_attempts;
+ALTER TABLE prover_job_queue DROP COLUMN IF EXISTS lease_expires_at;
+ALTER TABLE prover_job_queue DROP COLUMN IF EXISTS attempts;
@@ -0,0 +1,22 @@
+-- Number of times the job was handed out to the provers.
+ALTER TABLE prover_job_queue ADD COLUMN attempts INT NOT NULL DEFAULT 0;
+-- Time until which the job is leased to the prover that is working on it.
+-- `NULL` for the jobs that are not in progress.
+ALTER TABLE prover_job_queue ADD COLUMN lease_expires_at TIMESTAMP WITH TIME ZONE;
+-- Jobs in progress keep the old (120 seconds) timeout.
+UPDATE prover_job_queue SET lease_expires_at = updated_at + INTERVAL '120 seconds' WHERE job_status = 1;
+
+-- History of the prover job attempts, allows to tell a job that fails on every prover
+-- apart from a prover that fails on every job.
+CREATE TABLE prover_job_attempts
+(
+    id          serial PRIMARY KEY,
+    job_id      int                      NOT NULL REFERENCES prover_job_queue (id) ON DELETE CASCADE,
+    prover_name text                     NOT NULL,
+    started_at  timestamp with time zone NOT NULL DEFAULT now(),
+    finished_at timestamp with time zone,
+    -- `NULL` while the attempt is in progress.
+    outcome     text
+);
+CREATE INDEX IF NOT EXISTS prover_job_attempts_job_id_idx ON prover_job_attempts (job_id);
+CREATE INDEX IF NOT EXISTS prover_job_attempts_prover_name_idx ON prover_job_attempts (prover_name);
@@ -0,0 +1,10 @@
+-- Blocks claimed by the witness generator instances.
+-- Blocks with expired leases are considered abandoned and can be claimed by any other instance.
+CREATE TABLE witness_generator_leases
+(
+    block_number BIGINT REFERENCES blocks (number) ON UPDATE CASCADE ON DELETE CASCADE,
+    instance TEXT NOT NULL,
+    claimed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    lease_expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (block_number)
+);
@@ -0,0 +1,12 @@
+-- Witnesses kept in the blob store can't be restored from the database alone,
+-- so the migration can only be reverted once they are stored inline again.
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM block_witness WHERE witness IS NULL) THEN
+        RAISE EXCEPTION 'block_witness contains witnesses kept in the blob store, store them inline before reverting';
+    END IF;
+END
+$$;
+ALTER TABLE block_witness DROP CONSTRAINT IF EXISTS block_witness_data_check;
+ALTER TABLE block_witness DROP COLUMN IF EXISTS blob_hash;
+ALTER TABLE block_witness ALTER COLUMN witness SET NOT NULL;
@@ -0,0 +1,4 @@
+-- Witnesses can be kept in the blob store, in which case only the hash of the blob is stored.
+ALTER TABLE block_witness ALTER COLUMN witness DROP NOT NULL;
+ALTER TABLE block_witness ADD COLUMN blob_hash TEXT;
+ALTER TABLE block_witness ADD CONSTRAINT block_witness_data_check CHECK (witness IS NOT NULL OR blob_hash IS NOT NULL);
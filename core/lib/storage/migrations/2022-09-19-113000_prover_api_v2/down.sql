@@ -0,0 +1,2 @@
+ALTER TABLE prover_job_attempts DROP COLUMN IF EXISTS progress;
+DROP TABLE IF EXISTS prover_tokens;
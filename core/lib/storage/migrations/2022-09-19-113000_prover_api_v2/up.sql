@@ -0,0 +1,12 @@
+-- Tokens of the provers using the prover API v2. Only the SHA-256 hashes of the tokens are stored.
+CREATE TABLE prover_tokens
+(
+    prover_name text PRIMARY KEY,
+    token_hash  text                     NOT NULL UNIQUE,
+    created_at  timestamp with time zone NOT NULL DEFAULT now(),
+    -- Revoked tokens are kept, so it is clear why the prover can't authenticate.
+    revoked_at  timestamp with time zone
+);
+
+-- Progress of the attempt in percent, as reported by the prover.
+ALTER TABLE prover_job_attempts ADD COLUMN progress smallint;
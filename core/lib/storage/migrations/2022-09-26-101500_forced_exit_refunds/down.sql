@@ -0,0 +1,3 @@
+ALTER TABLE forced_exit_requests DROP COLUMN IF EXISTS payer;
+DROP TABLE IF EXISTS forced_exit_requests_refunds;
+DROP TABLE IF EXISTS forced_exit_requests_exits;
@@ -0,0 +1,27 @@
+-- Status of the forced exit of every token of the request.
+CREATE TABLE forced_exit_requests_exits
+(
+    request_id bigint NOT NULL REFERENCES forced_exit_requests (id) ON DELETE CASCADE,
+    token_id   integer NOT NULL,
+    status     text    NOT NULL,
+    tx_hash    text,
+    PRIMARY KEY (request_id, token_id)
+);
+
+-- Returns of the part of the payment for the tokens that couldn't be exited.
+CREATE TABLE forced_exit_requests_refunds
+(
+    request_id bigint PRIMARY KEY REFERENCES forced_exit_requests (id) ON DELETE CASCADE,
+    recipient  text                     NOT NULL,
+    amount     numeric                  NOT NULL,
+    status     text                     NOT NULL,
+    tx_hash    text,
+    -- Signed refund transfer, saved before it's sent so a retry resubmits the same transaction.
+    tx         jsonb,
+    created_at timestamp with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX forced_exit_requests_refunds_status_idx ON forced_exit_requests_refunds (status);
+
+-- Sender of the L1 transaction which paid for the request, the recipient of the refund.
+ALTER TABLE forced_exit_requests ADD COLUMN payer text;
@@ -0,0 +1,9 @@
+-- Addresses allowed or denied to submit transactions through the API.
+CREATE TABLE address_lists
+(
+    address    bytea                    NOT NULL,
+    list_type  text                     NOT NULL,
+    reason     text                     NOT NULL DEFAULT '',
+    created_at timestamp with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY (address, list_type)
+);
@@ -0,0 +1,12 @@
+-- Webhooks receiving the events of the event listener.
+CREATE TABLE event_webhooks
+(
+    id                      bigserial PRIMARY KEY,
+    url                     text                     NOT NULL,
+    secret                  text                     NOT NULL,
+    -- Subscriber filters in the same format as the WebSocket subscribers use.
+    filters                 jsonb                    NOT NULL,
+    -- Events up to this id (inclusive) are delivered to the webhook.
+    last_delivered_event_id bigint                   NOT NULL,
+    created_at              timestamp with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,3 @@
+-- Fee paid by the block element in USD per chunk, computed once the element is added to the mempool.
+-- Transactions of the same batch share the value of the whole batch.
+ALTER TABLE mempool_txs ADD COLUMN fee_priority NUMERIC;
@@ -0,0 +1,8 @@
+-- Fee quotes that were already used to submit a transaction.
+-- Every quote is accepted only once; the rows are removed once the quote expires.
+CREATE TABLE used_fee_quotes (
+    quote_id TEXT PRIMARY KEY,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX used_fee_quotes_expires_at_idx ON used_fee_quotes (expires_at);
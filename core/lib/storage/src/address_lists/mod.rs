@@ -0,0 +1,95 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{
+    address_lists::{AddressListEntry, AddressListType},
+    Address,
+};
+// Local imports
+use self::records::StorageAddressListEntry;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Address lists schema stores the addresses that are allowed or denied
+/// to submit transactions through the API.
+#[derive(Debug)]
+pub struct AddressListsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AddressListsSchema<'a, 'c> {
+    /// Loads the entries of all the lists.
+    pub async fn load_entries(&mut self) -> QueryResult<Vec<AddressListEntry>> {
+        let start = Instant::now();
+
+        let entries = sqlx::query_as!(
+            StorageAddressListEntry,
+            "SELECT * FROM address_lists ORDER BY list_type, created_at, address"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|entry| entry.into())
+        .collect();
+
+        metrics::histogram!("sql.address_lists.load_entries", start.elapsed());
+        Ok(entries)
+    }
+
+    /// Adds the addresses to the list. The reason of the already listed addresses is updated.
+    pub async fn add_entries(
+        &mut self,
+        list: AddressListType,
+        addresses: &[Address],
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO address_lists ( address, list_type, reason )
+            SELECT address, $2, $3 FROM UNNEST($1::bytea[]) AS address
+            ON CONFLICT (address, list_type) DO UPDATE SET reason = $3
+            "#,
+            &addresses,
+            list.to_string(),
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.address_lists.add_entries", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the addresses from the list, returns the number of the removed entries.
+    pub async fn remove_entries(
+        &mut self,
+        list: AddressListType,
+        addresses: &[Address],
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+
+        let removed = sqlx::query!(
+            "DELETE FROM address_lists WHERE list_type = $1 AND address = ANY($2)",
+            list.to_string(),
+            &addresses
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.address_lists.remove_entries", start.elapsed());
+        Ok(removed)
+    }
+}
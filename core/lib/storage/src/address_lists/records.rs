@@ -0,0 +1,30 @@
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+use zksync_types::{
+    address_lists::{AddressListEntry, AddressListType},
+    Address,
+};
+// Local imports
+
+#[derive(Debug, Clone)]
+pub struct StorageAddressListEntry {
+    pub address: Vec<u8>,
+    pub list_type: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StorageAddressListEntry> for AddressListEntry {
+    fn from(val: StorageAddressListEntry) -> Self {
+        AddressListEntry {
+            address: Address::from_slice(&val.address),
+            list: val
+                .list_type
+                .parse::<AddressListType>()
+                .expect("Invalid address list type has been stored"),
+            reason: val.reason,
+            created_at: val.created_at,
+        }
+    }
+}
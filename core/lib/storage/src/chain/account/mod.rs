@@ -535,6 +535,57 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Returns the nonce of the account after the given block was applied.
+    /// Nonce of the unknown account is zero.
+    pub async fn get_account_nonce_for_block(
+        &mut self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> QueryResult<Nonce> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let account_id = transaction
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await?;
+        let account_id = match account_id {
+            Some(id) => id,
+            None => {
+                return Ok(Nonce(0));
+            }
+        };
+
+        // Nonce is changed by both balance and public key updates and never decreases.
+        let nonce = sqlx::query!(
+            r#"
+                SELECT GREATEST(
+                    (SELECT MAX(new_nonce) FROM account_balance_updates
+                        WHERE account_id = $1 AND block_number <= $2
+                    ),
+                    (SELECT MAX(new_nonce) FROM account_pubkey_updates
+                        WHERE account_id = $1 AND block_number <= $2
+                    )
+                ) AS nonce
+            "#,
+            i64::from(account_id.0),
+            i64::from(block_number.0)
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .nonce
+        .unwrap_or(0);
+
+        transaction.commit().await?;
+        metrics::histogram!(
+            "sql.chain.account.get_account_nonce_for_block",
+            start.elapsed()
+        );
+
+        Ok(Nonce(nonce as u32))
+    }
+
     pub async fn get_account_nft_balance(&mut self, address: Address) -> QueryResult<u32> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -14,7 +14,7 @@ use zksync_types::{
     aggregated_operations::AggregatedActionType,
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
     event::block::BlockStatus,
-    AccountId, BlockNumber, Fr, ZkSyncOp, H256, U256,
+    AccountId, BlockNumber, Fr, Nonce, ZkSyncOp, H256, U256,
 };
 // Local imports
 use self::records::{
@@ -638,6 +638,36 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         result
     }
 
+    /// Returns the average gas limit (commit and verify combined) of the latest `blocks_count` blocks.
+    /// Returns `None` if there are no blocks in the database.
+    pub async fn get_average_block_gas_limit(
+        &mut self,
+        blocks_count: u32,
+    ) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let average_gas_limit = sqlx::query!(
+            r#"
+            SELECT AVG(commit_gas_limit + verify_gas_limit)::BIGINT AS "average_gas_limit?"
+            FROM (
+                SELECT commit_gas_limit, verify_gas_limit FROM blocks
+                ORDER BY number DESC
+                LIMIT $1
+            ) AS recent_blocks
+            "#,
+            i64::from(blocks_count)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .average_gas_limit
+        .map(|gas_limit| gas_limit as u64);
+
+        metrics::histogram!(
+            "sql.chain.block.get_average_block_gas_limit",
+            start.elapsed()
+        );
+        Ok(average_gas_limit)
+    }
+
     /// Returns the number of existing incomplete block.
     /// Returns `None` if there are no incomplte blocks in the database.
     ///
@@ -812,6 +842,42 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Returns `true` if the pending block contains a successfully executed transaction
+    /// of the given account with the given nonce.
+    pub async fn is_nonce_used_in_pending_block(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let used = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM executed_transactions
+                WHERE block_number = (SELECT MAX(number) FROM pending_block)
+                    AND success = true AND nonce = $2
+                    AND COALESCE(
+                        (tx->>'accountId')::bigint,
+                        (tx->>'initiatorAccountId')::bigint,
+                        (tx->>'creatorId')::bigint,
+                        (tx->>'submitterId')::bigint
+                    ) = $1
+            ) AS "used!"
+            "#,
+            i64::from(*account_id),
+            i64::from(*nonce)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .used;
+
+        metrics::histogram!(
+            "sql.chain.block.is_nonce_used_in_pending_block",
+            start.elapsed()
+        );
+        Ok(used)
+    }
+
     /// Stores given pending block into the database.
     pub async fn save_pending_block(&mut self, pending_block: PendingBlock) -> QueryResult<()> {
         let start = Instant::now();
@@ -1476,6 +1542,88 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Returns the amount of rows removed or updated in each table when the blocks
+    /// with numbers greater than `last_block` are reverted.
+    pub async fn count_rows_to_revert(
+        &mut self,
+        last_block: BlockNumber,
+    ) -> QueryResult<Vec<(&'static str, u64)>> {
+        let start = Instant::now();
+        let counts = sqlx::query!(
+            r#"
+            WITH reverted_ops AS (
+                SELECT id FROM aggregate_operations WHERE from_block > $1
+            ), reverted_eth_ops AS (
+                SELECT eth_op_id FROM eth_aggregated_ops_binding WHERE op_id IN (SELECT id FROM reverted_ops)
+            )
+            SELECT
+                (SELECT COUNT(*) FROM executed_transactions WHERE block_number > $1) AS "executed_transactions!",
+                (SELECT COUNT(*) FROM executed_priority_operations WHERE block_number > $1) AS "executed_priority_operations!",
+                (SELECT COUNT(*) FROM committed_nonce WHERE block_number > $1) AS "committed_nonce!",
+                (SELECT COUNT(*) FROM blocks WHERE number > $1) AS "blocks!",
+                (SELECT COUNT(*) FROM incomplete_blocks WHERE number > $1) AS "incomplete_blocks!",
+                (SELECT COUNT(*) FROM block_metadata WHERE block_number > $1) AS "block_metadata!",
+                (SELECT COUNT(*) FROM pending_block) AS "pending_block!",
+                (SELECT COUNT(*) FROM account_tree_cache WHERE block > $1) AS "account_tree_cache!",
+                (SELECT COUNT(*) FROM account_balance_updates WHERE block_number > $1) AS "account_balance_updates!",
+                (SELECT COUNT(*) FROM account_creates WHERE block_number > $1) AS "account_creates!",
+                (SELECT COUNT(*) FROM account_pubkey_updates WHERE block_number > $1) AS "account_pubkey_updates!",
+                (SELECT COUNT(*) FROM mint_nft_updates WHERE block_number > $1) AS "mint_nft_updates!",
+                (SELECT COUNT(*) FROM eth_unprocessed_aggregated_ops) AS "eth_unprocessed_aggregated_ops!",
+                (SELECT COUNT(*) FROM aggregate_operations WHERE to_block > $1) AS "aggregate_operations!",
+                (SELECT COUNT(*) FROM eth_aggregated_ops_binding WHERE op_id IN (SELECT id FROM reverted_ops)) AS "eth_aggregated_ops_binding!",
+                (SELECT COUNT(*) FROM eth_operations WHERE id IN (SELECT eth_op_id FROM reverted_eth_ops)) AS "eth_operations!",
+                (SELECT COUNT(*) FROM eth_tx_hashes WHERE eth_op_id IN (SELECT eth_op_id FROM reverted_eth_ops)) AS "eth_tx_hashes!",
+                (SELECT COUNT(*) FROM block_witness WHERE block > $1) AS "block_witness!",
+                (SELECT COUNT(*) FROM proofs WHERE block_number > $1) AS "proofs!",
+                (SELECT COUNT(*) FROM aggregated_proofs WHERE last_block > $1) AS "aggregated_proofs!",
+                (SELECT COUNT(*) FROM prover_job_queue WHERE last_block > $1) AS "prover_job_queue!"
+            "#,
+            i64::from(*last_block)
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        let counts = vec![
+            ("executed_transactions", counts.executed_transactions),
+            (
+                "executed_priority_operations",
+                counts.executed_priority_operations,
+            ),
+            ("committed_nonce", counts.committed_nonce),
+            ("blocks", counts.blocks),
+            ("incomplete_blocks", counts.incomplete_blocks),
+            ("block_metadata", counts.block_metadata),
+            ("pending_block", counts.pending_block),
+            ("account_tree_cache", counts.account_tree_cache),
+            ("account_balance_updates", counts.account_balance_updates),
+            ("account_creates", counts.account_creates),
+            ("account_pubkey_updates", counts.account_pubkey_updates),
+            ("mint_nft_updates", counts.mint_nft_updates),
+            (
+                "eth_unprocessed_aggregated_ops",
+                counts.eth_unprocessed_aggregated_ops,
+            ),
+            ("aggregate_operations", counts.aggregate_operations),
+            (
+                "eth_aggregated_ops_binding",
+                counts.eth_aggregated_ops_binding,
+            ),
+            ("eth_operations", counts.eth_operations),
+            ("eth_tx_hashes", counts.eth_tx_hashes),
+            ("block_witness", counts.block_witness),
+            ("proofs", counts.proofs),
+            ("aggregated_proofs", counts.aggregated_proofs),
+            ("prover_job_queue", counts.prover_job_queue),
+        ];
+
+        metrics::histogram!("sql.chain.block.count_rows_to_revert", start.elapsed());
+        Ok(counts
+            .into_iter()
+            .map(|(table, count)| (table, count as u64))
+            .collect())
+    }
+
     pub async fn store_factories_for_block_withdraw_nfts(
         &mut self,
         from_block: BlockNumber,
@@ -1,29 +1,47 @@
 // Built-in deps
-use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    convert::TryFrom,
+    str::FromStr,
+    time::Instant,
+};
 // External imports
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
+use num::{rational::Ratio, BigUint};
 // Workspace imports
-use zksync_api_types::v02::pagination::PaginationDirection;
+use zksync_api_types::v02::mempool::MempoolStats;
+use zksync_api_types::v02::pagination::{PaginationDirection, PaginationQuery};
 use zksync_api_types::v02::transaction::{
-    ApiTxBatch, BatchStatus, TxHashSerializeWrapper, TxInBlockStatus,
+    ApiTxBatch, BatchStatus, Transaction, TxHashSerializeWrapper, TxInBlockStatus,
 };
 use zksync_types::{
     block::IncompleteBlock,
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
-    PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Nonce,
+    PriorityOp, SerialId, SignedZkSyncTx, TokenId, ZkSyncPriorityOp, H256,
 };
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
-use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
+use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatch, QueuedBatchTx, RevertedBlock};
 use crate::{QueryResult, StorageProcessor};
 
 use crate::chain::operations::records::{
     StoredExecutedPriorityOperation, StoredExecutedTransaction,
 };
+use crate::chain::operations_ext::records::StorageTxData;
 
 pub mod records;
 
+/// Precision of the fee priority stored in the database.
+pub const STORED_FEE_PRIORITY_PRECISION: usize = 18;
+
+/// Returns the account which nonce is used by the transaction, in the form it is stored in the database.
+fn stored_account_id(tx: &SignedZkSyncTx) -> Option<i64> {
+    tx.account_id().ok().map(|account_id| *account_id as i64)
+}
+
 /// Schema for persisting transactions awaiting for the execution.
 ///
 /// This schema holds the transactions that are received by the `mempool` module, but not yet have
@@ -42,6 +60,21 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         &mut self,
         executed_txs: &[TxHash],
     ) -> QueryResult<VecDeque<SignedTxVariant>> {
+        let txs = self
+            .load_prioritized_txs(executed_txs)
+            .await?
+            .into_iter()
+            .map(|(tx, _)| tx)
+            .collect();
+        Ok(txs)
+    }
+
+    /// Same as `load_txs`, but also returns the fee priority of each block element,
+    /// see `set_fee_priority`. Elements without a stored priority get zero.
+    pub async fn load_prioritized_txs(
+        &mut self,
+        executed_txs: &[TxHash],
+    ) -> QueryResult<VecDeque<(SignedTxVariant, Ratio<BigUint>)>> {
         let start = Instant::now();
         // Load the transactions from mempool along with corresponding batch IDs.
         let excluded_txs: Vec<String> = executed_txs.iter().map(|tx| tx.to_string()).collect();
@@ -73,27 +106,39 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             prev_batch_id
         });
 
+        fn fee_priority(mempool_tx: &MempoolTx) -> Ratio<BigUint> {
+            mempool_tx
+                .fee_priority
+                .as_ref()
+                .map(|priority| big_decimal_to_ratio(priority).expect("Fee can't be negative"))
+                .unwrap_or_else(|| Ratio::from_integer(BigUint::from(0u32)))
+        }
+
         let mut txs = Vec::new();
 
         for (batch_id, group) in grouped_txs.into_iter() {
             if let Some(batch_id) = batch_id {
+                let group: Vec<_> = group.collect();
+                let priority = fee_priority(&group[0]);
                 let deserialized_txs = group
+                    .into_iter()
                     .map(SignedZkSyncTx::try_from)
                     .collect::<Result<Vec<SignedZkSyncTx>, serde_json::Error>>()?;
                 let variant = SignedTxVariant::batch(deserialized_txs, batch_id, vec![]);
 
-                txs.push(variant);
+                txs.push((variant, priority));
             } else {
                 for mempool_tx in group {
+                    let priority = fee_priority(&mempool_tx);
                     let signed_tx = SignedZkSyncTx::try_from(mempool_tx)?;
                     let variant = SignedTxVariant::Tx(signed_tx);
-                    txs.push(variant);
+                    txs.push((variant, priority));
                 }
             }
         }
 
         // Load signatures for batches.
-        for tx in txs.iter_mut() {
+        for (tx, _) in txs.iter_mut() {
             if let SignedTxVariant::Batch(batch) = tx {
                 let eth_signatures: Vec<TxEthSignature> = sqlx::query!(
                     "SELECT eth_signature FROM txs_batches_signatures
@@ -167,12 +212,13 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data)
-                VALUES ($1, $2, $3, $4)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, account_id)
+                VALUES ($1, $2, $3, $4, $5)",
                 tx_hash,
                 tx,
                 first_tx_data.created_at,
                 eth_sign_data,
+                stored_account_id(&first_tx_data),
             )
             .execute(transaction.conn())
             .await?;
@@ -201,13 +247,14 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5)",
+                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, account_id)
+                VALUES ($1, $2, $3, $4, $5, $6)",
                 tx_hash,
                 tx,
                 tx_data.created_at,
                 eth_sign_data,
-                batch_id
+                batch_id,
+                stored_account_id(tx_data),
             )
             .execute(transaction.conn())
             .await?;
@@ -253,13 +300,14 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
 
         sqlx::query!(
-            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
-            VALUES ($1, $2, $3, $4, $5)",
+            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id, account_id)
+            VALUES ($1, $2, $3, $4, $5, $6)",
             tx_hash,
             tx,
             tx_data.created_at,
             eth_sign_data,
             batch_id,
+            stored_account_id(tx_data),
         )
         .execute(self.0.conn())
         .await?;
@@ -268,6 +316,30 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores the fee priority of the queued block element, i.e. the fee it pays in USD per chunk.
+    /// `tx_hashes` must contain all the transactions of the element.
+    pub async fn set_fee_priority(
+        &mut self,
+        tx_hashes: &[TxHash],
+        fee_priority: &Ratio<BigUint>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(hex::encode).collect();
+        let fee_priority = ratio_to_big_decimal(fee_priority, STORED_FEE_PRIORITY_PRECISION);
+
+        sqlx::query!(
+            "UPDATE mempool_txs SET fee_priority = $2
+            WHERE tx_hash = ANY($1)",
+            &tx_hashes,
+            fee_priority
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "set_fee_priority");
+        Ok(())
+    }
+
     pub async fn remove_tx(&mut self, tx: &[u8]) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx);
@@ -575,6 +647,124 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Returns the amount of transactions of the given account awaiting for the execution.
+    pub async fn get_account_txs_count(&mut self, account_id: AccountId) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let count = sqlx::query!(
+            r#"
+            SELECT COUNT(*) FROM mempool_txs
+            WHERE account_id = $1 AND reverted = false
+            "#,
+            *account_id as i64
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_account_txs_count");
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    /// Removes the queued transactions of the given account, along with the whole batches
+    /// they are part of. Transactions returned from the reverted blocks are kept.
+    /// Returns hashes of the removed transactions.
+    pub async fn purge_account_txs(&mut self, account_id: AccountId) -> QueryResult<Vec<TxHash>> {
+        let start = Instant::now();
+
+        let tx_hashes = sqlx::query!(
+            r#"
+            DELETE FROM mempool_txs
+            WHERE reverted = false AND (
+                account_id = $1 OR batch_id IN (
+                    SELECT batch_id FROM mempool_txs
+                    WHERE account_id = $1 AND batch_id <> 0
+                )
+            )
+            RETURNING tx_hash
+            "#,
+            *account_id as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| TxHash::from_str(&format!("0x{}", row.tx_hash)).unwrap())
+        .collect();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "purge_account_txs");
+        Ok(tx_hashes)
+    }
+
+    /// Returns the queued transaction of the given account with the given nonce.
+    /// Transactions that are part of a batch are not taken into account.
+    pub async fn get_queued_tx_by_nonce(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> QueryResult<Option<SignedZkSyncTx>> {
+        let start = Instant::now();
+
+        let mempool_tx = sqlx::query_as!(
+            MempoolTx,
+            r#"
+            SELECT * FROM mempool_txs
+            WHERE account_id = $1 AND (tx->>'nonce')::bigint = $2
+                AND batch_id = 0 AND reverted = false
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+            *account_id as i64,
+            *nonce as i64
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_tx_by_nonce");
+        mempool_tx
+            .map(SignedZkSyncTx::try_from)
+            .transpose()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Atomically replaces the queued transaction with the new one and stores
+    /// the corresponding `Replaced` transaction event.
+    pub async fn replace_tx(
+        &mut self,
+        replaced_tx: &SignedZkSyncTx,
+        new_tx: &SignedZkSyncTx,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        transaction
+            .chain()
+            .mempool_schema()
+            .remove_tx(replaced_tx.hash().as_ref())
+            .await?;
+        transaction
+            .chain()
+            .mempool_schema()
+            .insert_tx(new_tx)
+            .await?;
+
+        // The replaced transaction could be included into the next block at most.
+        let block_number = transaction
+            .chain()
+            .block_schema()
+            .get_last_saved_block()
+            .await?
+            + 1;
+        transaction
+            .event_schema()
+            .store_replaced_transaction_event(block_number, replaced_tx, new_tx.hash())
+            .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.mempool.replace_tx", start.elapsed());
+        Ok(())
+    }
+
     /// Returns mempool size.
     pub async fn get_mempool_size(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
@@ -588,6 +778,67 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(size.unwrap_or(0) as u32)
     }
 
+    /// Returns the queued block elements that can be evicted from the full mempool,
+    /// each represented by the hashes of its transactions.
+    ///
+    /// An element can be evicted only if it holds the highest queued nonce of every account
+    /// it uses, so that the eviction doesn't leave a nonce gap in front of other queued
+    /// transactions. Elements using any of the `protected_accounts` are never returned.
+    /// If `fee_priority_below` is set, only the elements with a lower fee priority are returned,
+    /// the cheapest ones first. Otherwise, the oldest elements go first.
+    pub async fn get_eviction_candidates(
+        &mut self,
+        protected_accounts: &[AccountId],
+        fee_priority_below: Option<&Ratio<BigUint>>,
+        limit: usize,
+    ) -> QueryResult<Vec<Vec<TxHash>>> {
+        let start = Instant::now();
+        let protected_accounts: Vec<_> = protected_accounts
+            .iter()
+            .map(|account_id| **account_id as i64)
+            .collect();
+        let fee_priority_below = fee_priority_below
+            .map(|priority| ratio_to_big_decimal(priority, STORED_FEE_PRIORITY_PRECISION));
+
+        let candidates = sqlx::query!(
+            r#"
+            SELECT ARRAY_AGG(m.tx_hash) AS "tx_hashes!" FROM mempool_txs m
+            WHERE m.reverted = false
+            GROUP BY m.batch_id, CASE WHEN m.batch_id = 0 THEN m.id ELSE 0 END
+            HAVING BOOL_AND(
+                m.account_id IS NOT NULL
+                AND m.account_id <> ALL($1)
+                AND NOT EXISTS (
+                    SELECT 1 FROM mempool_txs o
+                    WHERE o.account_id = m.account_id AND o.reverted = false
+                        AND o.id <> m.id AND (m.batch_id = 0 OR o.batch_id <> m.batch_id)
+                        AND (o.tx->>'nonce')::bigint > (m.tx->>'nonce')::bigint
+                )
+            ) AND ($2::numeric IS NULL OR COALESCE(MAX(m.fee_priority), 0) < $2)
+            ORDER BY
+                CASE WHEN $2::numeric IS NULL THEN 0 ELSE COALESCE(MAX(m.fee_priority), 0) END,
+                MIN(m.id)
+            LIMIT $3
+            "#,
+            &protected_accounts,
+            fee_priority_below,
+            limit as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| {
+            row.tx_hashes
+                .iter()
+                .map(|tx_hash| TxHash::from_str(&format!("0x{}", tx_hash)).unwrap())
+                .collect()
+        })
+        .collect();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_eviction_candidates");
+        Ok(candidates)
+    }
+
     /// Get info about batch in mempool.
     pub async fn get_queued_batch_info(
         &mut self,
@@ -634,6 +885,368 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Returns the hash of the latest queued transaction that is not a part of some batch.
+    pub async fn get_last_queued_tx_hash(&mut self) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+
+        let tx_hash = sqlx::query!(
+            r#"
+            SELECT tx_hash FROM mempool_txs
+            WHERE batch_id = 0 AND reverted = false
+            ORDER BY id DESC
+            LIMIT 1
+            "#
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| TxHash::from_str(&format!("0x{}", record.tx_hash)))
+        .transpose()?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_last_queued_tx_hash");
+        Ok(tx_hash)
+    }
+
+    /// Loads the page of queued transactions that are not a part of some batch.
+    /// Returns `None` if there is no such transaction with the hash from the query.
+    pub async fn load_queued_txs_page(
+        &mut self,
+        query: &PaginationQuery<TxHash>,
+    ) -> QueryResult<Option<Vec<Transaction>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let id = sqlx::query!(
+            r#"
+            SELECT id FROM mempool_txs
+            WHERE tx_hash = $1 AND batch_id = 0 AND reverted = false
+            "#,
+            hex::encode(query.from.as_ref())
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|record| record.id);
+
+        let result = if let Some(id) = id {
+            let sql_query = "SELECT * FROM mempool_txs WHERE batch_id = 0 AND reverted = false";
+            let sql_query = match query.direction {
+                PaginationDirection::Newer => {
+                    format!("{} AND id >= $1 ORDER BY id LIMIT $2", sql_query)
+                }
+                PaginationDirection::Older => {
+                    format!("{} AND id <= $1 ORDER BY id DESC LIMIT $2", sql_query)
+                }
+            };
+            let txs: Vec<MempoolTx> = sqlx::query_as(sql_query.as_str())
+                .bind(id)
+                .bind(i64::from(query.limit))
+                .fetch_all(transaction.conn())
+                .await?;
+
+            let txs = txs
+                .into_iter()
+                .map(|mempool_tx| {
+                    let created_at = mempool_tx.created_at;
+                    let tx = SignedZkSyncTx::try_from(mempool_tx)?;
+                    Ok(Transaction {
+                        tx_hash: tx.hash(),
+                        block_index: None,
+                        block_number: None,
+                        op: StorageTxData::tx_data_from_zksync_tx(tx.tx, None),
+                        status: TxInBlockStatus::Queued,
+                        fail_reason: None,
+                        created_at: Some(created_at),
+                        batch_id: None,
+                    })
+                })
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            Some(txs)
+        } else {
+            None
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_queued_txs_page");
+        Ok(result)
+    }
+
+    /// Returns the amount of queued transactions that are not a part of some batch.
+    pub async fn get_queued_txs_count(&mut self) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let count = sqlx::query!(
+            "SELECT COUNT(*) FROM mempool_txs WHERE batch_id = 0 AND reverted = false"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_txs_count");
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    /// Returns the hash of the latest queued batch.
+    pub async fn get_last_queued_batch_hash(&mut self) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+
+        let batch_hash = sqlx::query!(
+            r#"
+            SELECT batch_hash FROM txs_batches_hashes
+            WHERE batch_id = (
+                SELECT MAX(batch_id) FROM mempool_txs
+                WHERE batch_id <> 0 AND reverted = false
+            )
+            "#
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| TxHash::from_slice(&record.batch_hash).unwrap());
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_last_queued_batch_hash");
+        Ok(batch_hash)
+    }
+
+    /// Loads the page of queued batches.
+    /// Returns `None` if there is no such batch with the hash from the query.
+    pub async fn load_queued_batches_page(
+        &mut self,
+        query: &PaginationQuery<TxHash>,
+    ) -> QueryResult<Option<Vec<ApiTxBatch>>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let batch_id = sqlx::query!(
+            "SELECT batch_id FROM txs_batches_hashes WHERE batch_hash = $1",
+            query.from.as_ref()
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|record| record.batch_id);
+
+        let result = if let Some(batch_id) = batch_id {
+            let sql_query = "SELECT DISTINCT mempool_txs.batch_id, batch_hash FROM mempool_txs
+                INNER JOIN txs_batches_hashes
+                ON txs_batches_hashes.batch_id = mempool_txs.batch_id
+                WHERE mempool_txs.batch_id <> 0 AND reverted = false";
+            let sql_query = match query.direction {
+                PaginationDirection::Newer => format!(
+                    "{} AND mempool_txs.batch_id >= $1 ORDER BY batch_id LIMIT $2",
+                    sql_query
+                ),
+                PaginationDirection::Older => format!(
+                    "{} AND mempool_txs.batch_id <= $1 ORDER BY batch_id DESC LIMIT $2",
+                    sql_query
+                ),
+            };
+            let batches: Vec<QueuedBatch> = sqlx::query_as(sql_query.as_str())
+                .bind(batch_id)
+                .bind(i64::from(query.limit))
+                .fetch_all(transaction.conn())
+                .await?;
+
+            let mut result = Vec::with_capacity(batches.len());
+            for batch in batches {
+                let batch_hash = TxHash::from_slice(&batch.batch_hash).unwrap();
+                if let Some(batch_info) = transaction
+                    .chain()
+                    .mempool_schema()
+                    .get_queued_batch_info(batch_hash)
+                    .await?
+                {
+                    result.push(batch_info);
+                }
+            }
+            Some(result)
+        } else {
+            None
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_queued_batches_page");
+        Ok(result)
+    }
+
+    /// Returns the amount of queued batches.
+    pub async fn get_queued_batches_count(&mut self) -> QueryResult<u32> {
+        let start = Instant::now();
+
+        let count = sqlx::query!(
+            "SELECT COUNT(DISTINCT batch_id) FROM mempool_txs WHERE batch_id <> 0 AND reverted = false"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .count;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_batches_count");
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    /// Returns the maximal serial id of the priority operations awaiting for the execution.
+    pub async fn get_max_priority_op_serial_id(&mut self) -> QueryResult<Option<SerialId>> {
+        let serial_id = sqlx::query!(
+            "SELECT max(serial_id) FROM mempool_priority_operations WHERE reverted = false"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+        Ok(serial_id.map(|v| v as u64))
+    }
+
+    /// Loads the page of priority operations awaiting for the execution.
+    pub async fn get_priority_ops_page(
+        &mut self,
+        start_serial_id: SerialId,
+        limit: u32,
+        direction: PaginationDirection,
+    ) -> QueryResult<Vec<(PriorityOp, DateTime<Utc>)>> {
+        let query = "SELECT serial_id,data,deadline_block,eth_hash,tx_hash,eth_block,eth_block_index,created_at FROM mempool_priority_operations WHERE reverted = false";
+        let query = match direction {
+            PaginationDirection::Newer => {
+                format!("{} AND serial_id >= $1 ORDER BY serial_id LIMIT $2", query)
+            }
+            PaginationDirection::Older => {
+                format!(
+                    "{} AND serial_id <= $1 ORDER BY serial_id DESC LIMIT $2",
+                    query
+                )
+            }
+        };
+        let ops: Vec<MempoolPriorityOp> = sqlx::query_as(query.as_str())
+            .bind(start_serial_id as i64)
+            .bind(limit as i64)
+            .fetch_all(self.0.conn())
+            .await?;
+        Ok(ops
+            .into_iter()
+            .map(|op| {
+                let created_at = op.created_at;
+                (op.into(), created_at)
+            })
+            .collect())
+    }
+
+    /// Returns the amount of priority operations awaiting for the execution.
+    pub async fn get_priority_ops_count(&mut self) -> QueryResult<u32> {
+        let count =
+            sqlx::query!("SELECT COUNT(*) FROM mempool_priority_operations WHERE reverted = false")
+                .fetch_one(self.0.conn())
+                .await?
+                .count;
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    /// Returns the amount of confirmed priority operations awaiting for the execution
+    /// and the creation time of the oldest one.
+    pub async fn get_priority_ops_backlog(&mut self) -> QueryResult<(u32, Option<DateTime<Utc>>)> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT COUNT(*) AS "count!", MIN(created_at) AS oldest FROM mempool_priority_operations
+            WHERE confirmed AND reverted = false
+            "#
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_priority_ops_backlog");
+        Ok((record.count as u32, record.oldest))
+    }
+
+    /// Collects the summary of the mempool content.
+    pub async fn get_mempool_stats(&mut self) -> QueryResult<MempoolStats> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let queued_txs = transaction
+            .chain()
+            .mempool_schema()
+            .get_queued_txs_count()
+            .await?;
+        let queued_batches = transaction
+            .chain()
+            .mempool_schema()
+            .get_queued_batches_count()
+            .await?;
+        let priority_ops = transaction
+            .chain()
+            .mempool_schema()
+            .get_priority_ops_count()
+            .await?;
+
+        let mut count_by_type = BTreeMap::new();
+        let txs_by_type = sqlx::query!(
+            r#"
+            SELECT tx->>'type' AS "tx_type!", COUNT(*) AS "count!" FROM mempool_txs
+            WHERE reverted = false
+            GROUP BY tx->>'type'
+            "#
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        for record in txs_by_type {
+            count_by_type.insert(record.tx_type, record.count as u32);
+        }
+        let ops_by_type = sqlx::query!(
+            r#"
+            SELECT type AS "op_type!", COUNT(*) AS "count!" FROM mempool_priority_operations
+            WHERE reverted = false
+            GROUP BY type
+            "#
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        for record in ops_by_type {
+            *count_by_type.entry(record.op_type).or_insert(0) += record.count as u32;
+        }
+
+        // Every transaction type except `Close` has either `feeToken` or `token` field
+        // holding the token the fee is paid in.
+        let count_by_token = sqlx::query!(
+            r#"
+            SELECT COALESCE(tx->>'feeToken', tx->>'token')::integer AS token_id, COUNT(*) AS "count!"
+            FROM mempool_txs
+            WHERE reverted = false
+            GROUP BY 1
+            "#
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .filter_map(|record| {
+            record
+                .token_id
+                .map(|token_id| (TokenId(token_id as u32), record.count as u32))
+        })
+        .collect();
+
+        let oldest_pending_created_at = sqlx::query!(
+            r#"
+            SELECT MIN(created_at) AS oldest FROM (
+                SELECT created_at FROM mempool_txs WHERE reverted = false
+                UNION ALL
+                SELECT created_at FROM mempool_priority_operations WHERE reverted = false
+            ) AS pending
+            "#
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .oldest;
+        let oldest_pending_age = oldest_pending_created_at
+            .map(|created_at| (Utc::now() - created_at).num_seconds().max(0) as u64);
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_mempool_stats");
+        Ok(MempoolStats {
+            queued_txs,
+            queued_batches,
+            priority_ops,
+            count_by_type,
+            count_by_token,
+            oldest_pending_created_at,
+            oldest_pending_age,
+        })
+    }
+
     pub async fn get_reverted_blocks(
         &mut self,
         available_block_sizes: &[usize],
@@ -739,6 +1352,39 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(incomplete_blocks)
     }
 
+    /// Returns the operations executed after `last_block_number`, grouped by blocks:
+    /// the operations of the sealed, incomplete and pending blocks, up to the first block
+    /// without executed operations.
+    ///
+    /// These are exactly the operations handled by `return_executed_txs_to_mempool`.
+    pub async fn get_executed_ops_to_revert(
+        &mut self,
+        last_block_number: BlockNumber,
+    ) -> QueryResult<Vec<(BlockNumber, Vec<ExecutedOperations>)>> {
+        let start = Instant::now();
+        let mut blocks = Vec::new();
+        let mut block_number = last_block_number + 1;
+        loop {
+            let block_operations = self
+                .0
+                .chain()
+                .block_schema()
+                .get_block_executed_ops(block_number)
+                .await?;
+            if block_operations.is_empty() {
+                break;
+            }
+            blocks.push((block_number, block_operations));
+            block_number = block_number + 1;
+        }
+
+        metrics::histogram!(
+            "sql.chain.mempool.get_executed_ops_to_revert",
+            start.elapsed()
+        );
+        Ok(blocks)
+    }
+
     // Returns executed txs back to mempool for blocks with number greater than `last_block`
     pub async fn return_executed_txs_to_mempool(
         &mut self,
@@ -757,19 +1403,13 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         let mut reverted_txs = Vec::new();
         let mut reverted_operations = Vec::new();
         let mut next_priority_op_serial_id = last_block.processed_priority_ops.1;
-        let mut block_number = last_block_number + 1;
-
-        loop {
-            let block_transactions = transaction
-                .chain()
-                .block_schema()
-                .get_block_executed_ops(block_number)
-                .await?;
-
-            if block_transactions.is_empty() {
-                break;
-            }
 
+        let blocks_to_revert = transaction
+            .chain()
+            .mempool_schema()
+            .get_executed_ops_to_revert(last_block_number)
+            .await?;
+        for (block_number, block_transactions) in blocks_to_revert {
             let block_for_revert = transaction
                 .chain()
                 .block_schema()
@@ -852,8 +1492,6 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                     }
                 }
             }
-
-            block_number = block_number + 1;
         }
 
         for (reverted_tx, block_number, next_priority_op_serial_id) in reverted_txs {
@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 
 // External imports
 use chrono::{DateTime, Utc};
-use sqlx::FromRow;
+use sqlx::{types::BigDecimal, FromRow};
 
 // Workspace imports
 use zksync_types::{PriorityOp, SignedZkSyncTx, H256};
@@ -33,6 +33,9 @@ pub(crate) struct MempoolTx {
     pub next_priority_op_serial_id: Option<i64>,
     #[allow(dead_code)]
     pub reverted: bool,
+    #[allow(dead_code)]
+    pub account_id: Option<i64>,
+    pub fee_priority: Option<BigDecimal>,
 }
 
 impl TryFrom<MempoolTx> for SignedZkSyncTx {
@@ -56,6 +59,12 @@ pub(crate) struct QueuedBatchTx {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, FromRow, PartialEq)]
+pub(crate) struct QueuedBatch {
+    pub batch_id: i64,
+    pub batch_hash: Vec<u8>,
+}
+
 #[derive(Debug, FromRow)]
 pub(crate) struct MempoolPriorityOp {
     pub serial_id: i64,
@@ -63,7 +72,6 @@ pub(crate) struct MempoolPriorityOp {
     pub tx_hash: String,
     pub eth_hash: Vec<u8>,
     pub data: serde_json::Value,
-    #[allow(dead_code)]
     pub created_at: DateTime<Utc>,
     pub eth_block: i64,
     pub eth_block_index: Option<i32>,
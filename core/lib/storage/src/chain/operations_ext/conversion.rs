@@ -58,7 +58,7 @@ impl StorageTxReceipt {
 }
 
 impl StorageTxData {
-    pub(super) fn tx_data_from_zksync_tx(
+    pub(crate) fn tx_data_from_zksync_tx(
         tx: ZkSyncTx,
         complete_withdrawals_tx_hash: Option<H256>,
     ) -> TransactionData {
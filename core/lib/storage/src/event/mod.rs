@@ -13,6 +13,7 @@ use zksync_types::{
         transaction::{TransactionEvent, TransactionStatus},
         EventId,
     },
+    tx::{SignedZkSyncTx, TxHash},
     BlockNumber,
 };
 // Local uses
@@ -94,6 +95,35 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         Ok(events)
     }
 
+    /// Load at most `limit` events from the database with the `id` greater than `from`.
+    pub async fn fetch_events_page(
+        &mut self,
+        from: EventId,
+        limit: u64,
+    ) -> QueryResult<Vec<StoredEvent>> {
+        let start = Instant::now();
+        let events = sqlx::query_as!(
+            StoredEvent,
+            r#"
+            SELECT
+                id,
+                block_number,
+                event_type as "event_type!: EventType",
+                event_data
+            FROM events WHERE id > $1
+            ORDER BY id ASC
+            LIMIT $2
+            "#,
+            *from as i64,
+            limit as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.event.fetch_events_page", start.elapsed());
+        Ok(events)
+    }
+
     /// Load the id of the latest event in the database.
     /// Returns `None` if the `events` table is empty.
     pub async fn get_last_event_id(&mut self) -> QueryResult<Option<EventId>> {
@@ -286,4 +316,36 @@ impl<'a, 'c> EventSchema<'a, 'c> {
         metrics::histogram!("sql.event.store_queued_transaction_event", start.elapsed());
         Ok(())
     }
+
+    /// Stores the `Replaced` event for the queued transaction that was replaced
+    /// by another one with the same nonce.
+    pub async fn store_replaced_transaction_event(
+        &mut self,
+        block_number: BlockNumber,
+        replaced_tx: &SignedZkSyncTx,
+        new_tx_hash: TxHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let transaction_event =
+            match TransactionEvent::from_replaced_tx(replaced_tx, new_tx_hash, block_number) {
+                Some(transaction_event) => transaction_event,
+                None => return Ok(()),
+            };
+        let event_data =
+            serde_json::to_value(transaction_event).expect("couldn't serialize transaction event");
+
+        self.store_event_data(
+            block_number,
+            EventType::Transaction,
+            slice::from_ref(&event_data),
+        )
+        .await?;
+
+        metrics::histogram!(
+            "sql.event.store_replaced_transaction_event",
+            start.elapsed()
+        );
+        Ok(())
+    }
 }
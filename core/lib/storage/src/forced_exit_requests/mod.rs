@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 // Built-in deps
-use num::BigInt;
+use num::{BigInt, BigUint};
 use sqlx::types::BigDecimal;
 use std::{ops::Sub, time::Instant};
 // External imports
@@ -8,18 +8,19 @@ use std::{ops::Sub, time::Instant};
 // Local imports
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::forced_exit_requests::{
-    ForcedExitRequest, ForcedExitRequestId, SaveForcedExitRequestQuery,
+    ForcedExitRefund, ForcedExitRefundStatus, ForcedExitRequest, ForcedExitRequestId,
+    ForcedExitTokenExit, ForcedExitTokenStatus, SaveForcedExitRequestQuery,
 };
 
-use zksync_types::tx::TxHash;
+use zksync_types::{tx::TxHash, Address, SignedZkSyncTx, TokenId};
 
 pub mod records;
 
 mod utils;
 
-use records::DbForcedExitRequest;
+use records::{DbForcedExitRefund, DbForcedExitRequest, DbForcedExitTokenExit};
 
-use crate::utils::address_to_stored_string;
+use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 
 /// ForcedExitRequests schema handles the `forced_exit_requests` table, providing methods to
 #[derive(Debug)]
@@ -193,7 +194,9 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
     pub async fn delete_old_unfulfilled_requests(
         &mut self,
         // The time that has to be passed since the
-        // request has been considered invalid to delete it
+        // request has been considered invalid to delete it.
+        // Processed requests are kept even if nothing was sent for them,
+        // since they may have a refund
         deleting_threshold: chrono::Duration,
     ) -> QueryResult<()> {
         let start = Instant::now();
@@ -203,7 +206,7 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
         sqlx::query!(
             r#"
             DELETE FROM forced_exit_requests
-            WHERE fulfilled_by IS NULL AND valid_until < $1
+            WHERE fulfilled_at IS NULL AND fulfilled_by IS NULL AND valid_until < $1
             "#,
             oldest_allowed
         )
@@ -217,4 +220,260 @@ impl<'a, 'c> ForcedExitRequestsSchema<'a, 'c> {
 
         Ok(())
     }
+
+    /// Records the status of the forced exit of the request token.
+    pub async fn set_token_exit_status(
+        &mut self,
+        id: ForcedExitRequestId,
+        token: TokenId,
+        status: ForcedExitTokenStatus,
+        tx_hash: Option<TxHash>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO forced_exit_requests_exits ( request_id, token_id, status, tx_hash )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT (request_id, token_id)
+            DO UPDATE SET status = $3, tx_hash = COALESCE($4, forced_exit_requests_exits.tx_hash)
+            "#,
+            id,
+            token.0 as i32,
+            status.to_string(),
+            tx_hash.map(|hash| hash.to_string()),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.set_token_exit_status",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    pub async fn get_token_exits(
+        &mut self,
+        id: ForcedExitRequestId,
+    ) -> QueryResult<Vec<ForcedExitTokenExit>> {
+        let start = Instant::now();
+
+        let exits = sqlx::query_as!(
+            DbForcedExitTokenExit,
+            r#"
+            SELECT token_id, status, tx_hash FROM forced_exit_requests_exits
+            WHERE request_id = $1
+            ORDER BY token_id
+            "#,
+            id
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|exit| exit.into())
+        .collect();
+
+        metrics::histogram!("sql.forced_exit_requests.get_token_exits", start.elapsed());
+        Ok(exits)
+    }
+
+    /// Stores the sender of the L1 transaction which paid for the request.
+    pub async fn set_payer(&mut self, id: ForcedExitRequestId, payer: Address) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests
+                SET payer = $2
+                WHERE id = $1
+            "#,
+            id,
+            address_to_stored_string(&payer),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.set_payer", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the payer of the request, `None` if the request was not paid yet.
+    pub async fn get_payer(&mut self, id: ForcedExitRequestId) -> QueryResult<Option<Address>> {
+        let start = Instant::now();
+
+        let payer = sqlx::query!(
+            r#"
+            SELECT payer FROM forced_exit_requests
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|request| request.payer)
+        .map(|payer| stored_str_address_to_address(&payer));
+
+        metrics::histogram!("sql.forced_exit_requests.get_payer", start.elapsed());
+        Ok(payer)
+    }
+
+    /// Stores the pending refund for the request. Does nothing if the request
+    /// already has a refund, so the payment is never returned twice.
+    pub async fn store_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+        recipient: Address,
+        amount: BigUint,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        let amount = BigDecimal::from(BigInt::from(amount));
+
+        sqlx::query!(
+            r#"
+            INSERT INTO forced_exit_requests_refunds ( request_id, recipient, amount, status )
+            VALUES ( $1, $2, $3, $4 )
+            ON CONFLICT (request_id) DO NOTHING
+            "#,
+            id,
+            address_to_stored_string(&recipient),
+            amount,
+            ForcedExitRefundStatus::Pending.to_string(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.store_refund", start.elapsed());
+        Ok(())
+    }
+
+    pub async fn set_refund_status(
+        &mut self,
+        id: ForcedExitRequestId,
+        status: ForcedExitRefundStatus,
+        tx_hash: Option<TxHash>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests_refunds
+                SET status = $2, tx_hash = COALESCE($3, tx_hash)
+                WHERE request_id = $1
+            "#,
+            id,
+            status.to_string(),
+            tx_hash.map(|hash| hash.to_string()),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.set_refund_status",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Saves the signed refund transfer and marks the refund as sent.
+    /// The transfer is saved before it's submitted to the mempool, so the refund
+    /// is never sent with a different transaction.
+    pub async fn save_refund_tx(
+        &mut self,
+        id: ForcedExitRequestId,
+        tx: &SignedZkSyncTx,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            UPDATE forced_exit_requests_refunds
+                SET status = $2, tx_hash = $3, tx = $4
+                WHERE request_id = $1
+            "#,
+            id,
+            ForcedExitRefundStatus::Sent.to_string(),
+            tx.hash().to_string(),
+            serde_json::to_value(tx)?,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.forced_exit_requests.save_refund_tx", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the refund transfer saved by `save_refund_tx`.
+    pub async fn get_refund_tx(
+        &mut self,
+        id: ForcedExitRequestId,
+    ) -> QueryResult<Option<SignedZkSyncTx>> {
+        let start = Instant::now();
+
+        let tx = sqlx::query!(
+            r#"
+            SELECT tx FROM forced_exit_requests_refunds
+            WHERE request_id = $1
+            "#,
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|refund| refund.tx)
+        .map(serde_json::from_value)
+        .transpose()?;
+
+        metrics::histogram!("sql.forced_exit_requests.get_refund_tx", start.elapsed());
+        Ok(tx)
+    }
+
+    pub async fn get_refund(
+        &mut self,
+        id: ForcedExitRequestId,
+    ) -> QueryResult<Option<ForcedExitRefund>> {
+        let start = Instant::now();
+
+        let refund = sqlx::query_as!(
+            DbForcedExitRefund,
+            r#"
+            SELECT * FROM forced_exit_requests_refunds
+            WHERE request_id = $1
+            "#,
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|refund| refund.into());
+
+        metrics::histogram!("sql.forced_exit_requests.get_refund", start.elapsed());
+        Ok(refund)
+    }
+
+    /// Loads the refunds which were either not sent yet or not confirmed yet, oldest first.
+    pub async fn get_unfinished_refunds(&mut self) -> QueryResult<Vec<ForcedExitRefund>> {
+        let start = Instant::now();
+
+        let refunds = sqlx::query_as!(
+            DbForcedExitRefund,
+            r#"
+            SELECT * FROM forced_exit_requests_refunds
+            WHERE status = $1 OR status = $2
+            ORDER BY created_at, request_id
+            "#,
+            ForcedExitRefundStatus::Pending.to_string(),
+            ForcedExitRefundStatus::Sent.to_string(),
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|refund| refund.into())
+        .collect();
+
+        metrics::histogram!(
+            "sql.forced_exit_requests.get_unfinished_refunds",
+            start.elapsed()
+        );
+        Ok(refunds)
+    }
 }
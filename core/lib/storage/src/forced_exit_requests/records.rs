@@ -2,7 +2,15 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use num::{bigint::ToBigInt, BigInt};
 use sqlx::types::BigDecimal;
-use zksync_types::{forced_exit_requests::ForcedExitRequest, tx::TxHash, TokenId};
+use std::str::FromStr;
+use zksync_types::{
+    forced_exit_requests::{
+        ForcedExitRefund, ForcedExitRefundStatus, ForcedExitRequest, ForcedExitTokenExit,
+        ForcedExitTokenStatus,
+    },
+    tx::TxHash,
+    TokenId,
+};
 
 use super::utils;
 
@@ -16,6 +24,7 @@ pub struct DbForcedExitRequest {
     pub created_at: DateTime<Utc>,
     pub fulfilled_by: Option<String>,
     pub fulfilled_at: Option<DateTime<Utc>>,
+    pub payer: Option<String>,
 }
 
 impl From<ForcedExitRequest> for DbForcedExitRequest {
@@ -33,6 +42,7 @@ impl From<ForcedExitRequest> for DbForcedExitRequest {
             created_at: request.created_at,
             fulfilled_at: request.fulfilled_at,
             fulfilled_by,
+            payer: None,
         }
     }
 }
@@ -62,3 +72,56 @@ impl From<DbForcedExitRequest> for ForcedExitRequest {
         }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct DbForcedExitTokenExit {
+    pub token_id: i32,
+    pub status: String,
+    pub tx_hash: Option<String>,
+}
+
+impl From<DbForcedExitTokenExit> for ForcedExitTokenExit {
+    fn from(val: DbForcedExitTokenExit) -> Self {
+        ForcedExitTokenExit {
+            token: TokenId(val.token_id as u32),
+            status: ForcedExitTokenStatus::from_str(&val.status)
+                .expect("Invalid forced exit status has been stored"),
+            tx_hash: val
+                .tx_hash
+                .map(|hash| TxHash::from_str(&hash).expect("Invalid tx hash has been stored")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DbForcedExitRefund {
+    pub request_id: i64,
+    pub recipient: String,
+    pub amount: BigDecimal,
+    pub status: String,
+    pub tx_hash: Option<String>,
+    pub tx: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DbForcedExitRefund> for ForcedExitRefund {
+    fn from(val: DbForcedExitRefund) -> Self {
+        let amount = val
+            .amount
+            .to_bigint()
+            .and_then(|int| int.to_biguint())
+            .expect("Invalid forced exit refund has been stored");
+
+        ForcedExitRefund {
+            request_id: val.request_id,
+            recipient: stored_str_address_to_address(&val.recipient),
+            amount,
+            status: ForcedExitRefundStatus::from_str(&val.status)
+                .expect("Invalid forced exit refund status has been stored"),
+            tx_hash: val
+                .tx_hash
+                .map(|hash| TxHash::from_str(&hash).expect("Invalid tx hash has been stored")),
+            created_at: val.created_at,
+        }
+    }
+}
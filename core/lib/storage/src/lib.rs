@@ -37,6 +37,8 @@
 //! - rootstock, for the data associated with the Rootstock blockchain.
 //! - prover, for the data on prover jobs, proofs, etc.
 //! - tokens, for storing and loading known tokens.
+//! - address_lists, for the addresses allowed or denied to use the API.
+//! - webhooks, for the webhooks of the event listener and their delivery cursors.
 //! - chain - the biggest one, which includes several schemas for the ZKSync sidechain itself.
 //!
 //! The chain module includes the following schemas:
@@ -87,6 +89,7 @@ use crate::connection::{holder::ConnectionHolder, PooledConnection};
 #[cfg(test)]
 mod tests;
 
+pub mod address_lists;
 pub mod chain;
 pub mod config;
 pub mod connection;
@@ -101,6 +104,7 @@ pub mod rootstock;
 pub mod test_data;
 pub mod tokens;
 pub mod utils;
+pub mod webhooks;
 
 use forced_exit_requests::ForcedExitRequestsSchema;
 
@@ -201,6 +205,11 @@ impl<'a> StorageProcessor<'a> {
         }
     }
 
+    /// Gains access to the `AddressLists` schema.
+    pub fn address_lists_schema(&mut self) -> address_lists::AddressListsSchema<'_, 'a> {
+        address_lists::AddressListsSchema(self)
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
@@ -239,6 +248,11 @@ impl<'a> StorageProcessor<'a> {
         event::EventSchema(self)
     }
 
+    /// Gains access to the `Webhooks` schema.
+    pub fn webhooks_schema(&mut self) -> webhooks::WebhooksSchema<'_, 'a> {
+        webhooks::WebhooksSchema(self)
+    }
+
     pub fn misc_schema(&mut self) -> misc::MiscSchema<'_, 'a> {
         misc::MiscSchema(self)
     }
@@ -1,6 +1,7 @@
 // Built-in deps
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use sqlx::types::BigDecimal;
 // Local imports
 
@@ -10,6 +11,9 @@ use num::ToPrimitive;
 
 pub mod records;
 
+/// Key of the advisory lock held by the running server components.
+const SERVER_LOCK_ID: i64 = 0x7a6b_7379_6e63;
+
 /// MiscSchema should be used for various features not directly related to the main zkSync functionality
 /// Please, use this schema if your functionality needs 1-3 methods. Otherwise, it should have a dedicated schema
 #[derive(Debug)]
@@ -64,4 +68,84 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         metrics::histogram!("sql.token.get_total_used_subsidy_for_type", start.elapsed());
         Ok(sum)
     }
+
+    /// Records that the fee quote was used to submit a transaction.
+    /// Returns `false` if the quote has already been used.
+    ///
+    /// Quotes that have expired are removed, since they can't be accepted anyway.
+    pub async fn mark_fee_quote_used(
+        &mut self,
+        quote_id: &str,
+        expires_at: DateTime<Utc>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!("DELETE FROM used_fee_quotes WHERE expires_at < now()")
+            .execute(transaction.conn())
+            .await?;
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO used_fee_quotes (quote_id, expires_at)
+            VALUES ($1, $2)
+            ON CONFLICT (quote_id) DO NOTHING
+            "#,
+            quote_id,
+            expires_at
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected()
+            == 1;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.misc.mark_fee_quote_used", start.elapsed());
+        Ok(inserted)
+    }
+
+    /// Makes the fee quote available again, if the transaction it was used for was rejected.
+    pub async fn release_fee_quote(&mut self, quote_id: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!("DELETE FROM used_fee_quotes WHERE quote_id = $1", quote_id)
+            .execute(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.misc.release_fee_quote", start.elapsed());
+        Ok(())
+    }
+
+    /// Takes the shared server lock, which is held until the connection is closed.
+    /// Server components keep it while running, so the maintenance tools can tell that the server is stopped.
+    /// Returns `false` if the database is locked for maintenance.
+    ///
+    /// The lock belongs to the connection, so it must be taken on the dedicated connection rather than the pooled one.
+    pub async fn try_lock_server(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let locked = sqlx::query!(
+            r#"SELECT pg_try_advisory_lock_shared($1) AS "locked!""#,
+            SERVER_LOCK_ID
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .locked;
+
+        metrics::histogram!("sql.misc.try_lock_server", start.elapsed());
+        Ok(locked)
+    }
+
+    /// Takes the exclusive maintenance lock, which is held until the connection is closed.
+    /// Returns `false` if the server (or another maintenance tool) is running.
+    pub async fn try_lock_maintenance(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let locked = sqlx::query!(
+            r#"SELECT pg_try_advisory_lock($1) AS "locked!""#,
+            SERVER_LOCK_ID
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .locked;
+
+        metrics::histogram!("sql.misc.try_lock_maintenance", start.elapsed());
+        Ok(locked)
+    }
 }
@@ -1,18 +1,24 @@
 // Built-in deps
-use std::time::Instant;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 // External imports
 use anyhow::format_err;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{StorageProverJobQueue, StoredAggregatedProof, StoredProof};
+use self::records::{
+    StorageProverJobAttempt, StorageProverJobQueue, StoredAggregatedProof, StoredProof,
+};
 use crate::chain::operations::OperationsSchema;
 use crate::prover::records::StorageBlockWitness;
 use crate::{QueryResult, StorageProcessor};
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use zksync_crypto::proof::{AggregatedProof, SingleProof};
 use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::prover::{ProverJob, ProverJobStatus, ProverJobType};
+use zksync_types::prover::{
+    ProverJob, ProverJobAttemptOutcome, ProverJobFilter, ProverJobStatus, ProverJobType,
+    ProverJobsStats,
+};
 
 pub mod records;
 
@@ -22,13 +28,14 @@ pub mod records;
 pub struct ProverSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 
 impl<'a, 'c> ProverSchema<'a, 'c> {
-    /// Returns the amount of blocks which await for proof, but have
-    /// no assigned prover run.
+    /// Returns the amount of jobs which await for proof.
+    /// Poisoned jobs are not taken into account, since they won't be handed out to the provers.
     pub async fn pending_jobs_count(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
         let pending_jobs_count = sqlx::query!(
-            "SELECT COUNT(*) FROM prover_job_queue WHERE job_status != $1",
-            ProverJobStatus::Done.to_number()
+            "SELECT COUNT(*) FROM prover_job_queue WHERE job_status != $1 AND job_status != $2",
+            ProverJobStatus::Done.to_number(),
+            ProverJobStatus::Poisoned.to_number(),
         )
         .fetch_one(self.0.conn())
         .await?
@@ -38,6 +45,106 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(pending_jobs_count)
     }
 
+    /// Returns the amount of jobs waiting for a prover and the time since which they are waiting,
+    /// i.e. the latest update of the jobs taken by the provers or the creation of the oldest
+    /// waiting job, whichever is later.
+    pub async fn idle_jobs_stats(&mut self) -> QueryResult<(u32, Option<DateTime<Utc>>)> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"SELECT COUNT(*) FILTER (WHERE job_status = $1) AS "idle_jobs!",
+                GREATEST(
+                    MAX(updated_at) FILTER (WHERE job_status != $1),
+                    MIN(created_at) FILTER (WHERE job_status = $1)
+                ) AS waiting_since
+            FROM prover_job_queue"#,
+            ProverJobStatus::Idle.to_number(),
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+        metrics::histogram!("sql", start.elapsed(), "prover" => "idle_jobs_stats");
+        Ok((record.idle_jobs as u32, record.waiting_since))
+    }
+
+    /// Returns the statistics of the pending and recently proven jobs grouped by the job type and size.
+    ///
+    /// Proving time is averaged over the attempts finished during the last `proving_time_window`.
+    /// Sizes with neither pending nor recently proven jobs are not returned.
+    pub async fn prover_jobs_stats(
+        &mut self,
+        proving_time_window: Duration,
+    ) -> QueryResult<Vec<ProverJobsStats>> {
+        let start = Instant::now();
+        let single_proof = ProverJobType::SingleProof.to_string();
+        let job_type = |job_type: &str| {
+            if job_type == single_proof {
+                ProverJobType::SingleProof
+            } else {
+                ProverJobType::AggregatedProof
+            }
+        };
+        let mut stats = BTreeMap::new();
+
+        let pending_jobs = sqlx::query!(
+            r#"SELECT job_type,
+                CASE WHEN job_type = $1 THEN blocks.block_size ELSE last_block - first_block + 1 END AS "size!",
+                COUNT(*) AS "pending_jobs!", MIN(prover_job_queue.created_at) AS "oldest_job_created_at!"
+            FROM prover_job_queue
+            INNER JOIN blocks ON blocks.number = prover_job_queue.first_block
+            WHERE job_status != $2 AND job_status != $3
+            GROUP BY 1, 2"#,
+            single_proof,
+            ProverJobStatus::Done.to_number(),
+            ProverJobStatus::Poisoned.to_number(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        for row in pending_jobs {
+            let job_type = job_type(&row.job_type);
+            stats.insert(
+                (job_type, row.size as usize),
+                ProverJobsStats {
+                    job_type,
+                    size: row.size as usize,
+                    pending_jobs: row.pending_jobs as u32,
+                    oldest_job_created_at: Some(row.oldest_job_created_at),
+                    avg_proving_time: None,
+                },
+            );
+        }
+
+        let proving_times = sqlx::query!(
+            r#"SELECT job_type,
+                CASE WHEN job_type = $1 THEN blocks.block_size ELSE last_block - first_block + 1 END AS "size!",
+                AVG(EXTRACT(EPOCH FROM finished_at - started_at))::float8 AS "avg_proving_time!"
+            FROM prover_job_attempts
+            INNER JOIN prover_job_queue ON prover_job_queue.id = prover_job_attempts.job_id
+            INNER JOIN blocks ON blocks.number = prover_job_queue.first_block
+            WHERE outcome = $2 AND finished_at > now() - make_interval(secs => $3)
+            GROUP BY 1, 2"#,
+            single_proof,
+            ProverJobAttemptOutcome::Done.to_string(),
+            proving_time_window.as_secs_f64(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        for row in proving_times {
+            let job_type = job_type(&row.job_type);
+            stats
+                .entry((job_type, row.size as usize))
+                .or_insert(ProverJobsStats {
+                    job_type,
+                    size: row.size as usize,
+                    pending_jobs: 0,
+                    oldest_job_created_at: None,
+                    avg_proving_time: None,
+                })
+                .avg_proving_time = Some(Duration::from_secs_f64(row.avg_proving_time.max(0.0)));
+        }
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "prover_jobs_stats");
+        Ok(stats.into_values().collect())
+    }
+
     pub async fn add_prover_job_to_job_queue(
         &mut self,
         first_block: BlockNumber,
@@ -68,24 +175,100 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    pub async fn mark_stale_jobs_as_idle(&mut self) -> QueryResult<()> {
+    /// Returns the jobs with expired leases back to the queue.
+    ///
+    /// Jobs which leases have expired `max_attempts` times are marked as poisoned instead,
+    /// so they are not handed out to the provers anymore.
+    pub async fn mark_stale_jobs_as_idle(&mut self, max_attempts: u32) -> QueryResult<()> {
         let start = Instant::now();
-        let result = sqlx::query!(
-            "UPDATE prover_job_queue SET (job_status, updated_at, updated_by) = ($1, now(), 'server_clean_idle')
-            WHERE job_status = $2 AND (now() - INTERVAL '120 seconds') >= updated_at RETURNING id",
+        let mut transaction = self.0.start_transaction().await?;
+        // Attempts are closed first, so the expired ones are taken into account below.
+        sqlx::query!(
+            "UPDATE prover_job_attempts SET (finished_at, outcome) = (now(), $1)
+            WHERE finished_at IS NULL AND job_id IN (
+                SELECT id FROM prover_job_queue WHERE job_status = $2 AND lease_expires_at <= now()
+            )",
+            ProverJobAttemptOutcome::LeaseExpired.to_string(),
+            ProverJobStatus::InProgress.to_number(),
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        // Only the attempts made since the job was reset (the latest `attempts` ones) are counted.
+        let stale_jobs = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by, lease_expires_at) = (
+                CASE WHEN (
+                    SELECT COUNT(*) FROM (
+                        SELECT outcome FROM prover_job_attempts
+                        WHERE job_id = prover_job_queue.id
+                        ORDER BY id DESC
+                        LIMIT prover_job_queue.attempts
+                    ) AS recent_attempts
+                    WHERE outcome = $1
+                ) >= $2 THEN $3 ELSE $4 END,
+                now(),
+                'server_clean_idle',
+                NULL
+            )
+            WHERE job_status = $5 AND lease_expires_at <= now()
+            RETURNING id, job_status, first_block, last_block",
+            ProverJobAttemptOutcome::LeaseExpired.to_string(),
+            i64::from(max_attempts),
+            ProverJobStatus::Poisoned.to_number(),
             ProverJobStatus::Idle.to_number(),
             ProverJobStatus::InProgress.to_number(),
         )
-        .fetch_all(self.0.conn())
+        .fetch_all(transaction.conn())
         .await?;
-        metrics::counter!("stale_jobs", result.len() as u64);
+        transaction.commit().await?;
+
+        let mut poisoned_jobs = 0;
+        for job in &stale_jobs {
+            if job.job_status == ProverJobStatus::Poisoned.to_number() {
+                vlog::warn!(
+                    "Prover job {} for blocks [{},{}] failed {} times and was marked as poisoned",
+                    job.id,
+                    job.first_block,
+                    job.last_block,
+                    max_attempts
+                );
+                poisoned_jobs += 1;
+            }
+        }
+        metrics::counter!("stale_jobs", stale_jobs.len() as u64);
+        metrics::counter!("poisoned_jobs", poisoned_jobs);
         metrics::histogram!("sql", start.elapsed(), "prover" => "mark_stale_jobs_as_idle");
         Ok(())
     }
 
-    pub async fn get_idle_prover_job_from_job_queue(&mut self) -> QueryResult<Option<ProverJob>> {
+    /// Leases the next job to the prover for the `lease` duration.
+    ///
+    /// Aggregated proofs go first, then the single proofs in the order of blocks: the aggregated
+    /// proof can only be created for a contiguous range of proven blocks, so the lowest unproven
+    /// blocks (e.g. the ones returned to the queue after a failure) block it the most.
+    pub async fn get_idle_prover_job_from_job_queue(
+        &mut self,
+        prover_name: &str,
+        lease: Duration,
+    ) -> QueryResult<Option<ProverJob>> {
+        let jobs = self
+            .get_idle_prover_jobs_from_job_queue(prover_name, lease, 1, ProverJobFilter::any())
+            .await?;
+        Ok(jobs.into_iter().next())
+    }
+
+    /// Leases up to `limit` idle jobs accepted by the `filter` to the prover, in the same order
+    /// as `get_idle_prover_job_from_job_queue`. Block size is only checked for the single proofs.
+    pub async fn get_idle_prover_jobs_from_job_queue(
+        &mut self,
+        prover_name: &str,
+        lease: Duration,
+        limit: u32,
+        filter: ProverJobFilter,
+    ) -> QueryResult<Vec<ProverJob>> {
         let start = Instant::now();
-        // Select the block to prove.
+        // Select the blocks to prove.
         let mut transaction = self.0.start_transaction().await?;
         sqlx::query!("LOCK TABLE prover_job_queue IN EXCLUSIVE MODE")
             .execute(transaction.conn())
@@ -96,80 +279,330 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             r#"
                 SELECT * FROM prover_job_queue
                 WHERE job_status = $1
-                ORDER BY (job_priority, id, first_block)
-                LIMIT 1
+                    AND (job_type = $2 OR $3)
+                    AND (
+                        job_type <> $2 OR $4::bigint IS NULL
+                        OR (SELECT block_size FROM blocks WHERE number = first_block) <= $4
+                    )
+                ORDER BY job_priority, first_block, id
+                LIMIT $5
             "#,
-            ProverJobStatus::Idle.to_number()
+            ProverJobStatus::Idle.to_number(),
+            ProverJobType::SingleProof.to_string(),
+            filter.aggregated_proofs,
+            filter.max_block_chunks.map(|chunks| chunks as i64),
+            i64::from(limit),
         )
-        .fetch_optional(transaction.conn())
+        .fetch_all(transaction.conn())
         .await?;
 
-        let prover_job = if let Some(job) = prover_job_queue {
+        let mut prover_jobs = Vec::with_capacity(prover_job_queue.len());
+        for job in prover_job_queue {
             sqlx::query!(
                 r#"
                 UPDATE prover_job_queue
-                SET (job_status, updated_at, updated_by) = ($1, now(), 'server_give_job')
-                WHERE id = $2;
+                SET (job_status, updated_at, updated_by, attempts, lease_expires_at) =
+                    ($1, now(), $2, attempts + 1, now() + $3::float8 * INTERVAL '1 second')
+                WHERE id = $4;
             "#,
                 ProverJobStatus::InProgress.to_number(),
+                prover_name,
+                lease.as_secs_f64(),
                 job.id,
             )
             .execute(transaction.conn())
             .await?;
+            sqlx::query!(
+                "INSERT INTO prover_job_attempts (job_id, prover_name) VALUES ($1, $2)",
+                job.id,
+                prover_name,
+            )
+            .execute(transaction.conn())
+            .await?;
 
-            Some(ProverJob::new(
+            prover_jobs.push(ProverJob::new(
                 job.id,
                 BlockNumber(job.first_block as u32),
                 BlockNumber(job.last_block as u32),
                 job.job_data,
-            ))
-        } else {
-            None
-        };
+            ));
+        }
         transaction.commit().await?;
-        metrics::histogram!("sql", start.elapsed(), "prover" => "get_idle_prover_job_from_job_queue");
-        Ok(prover_job)
+        metrics::histogram!("sql", start.elapsed(), "prover" => "get_idle_prover_jobs_from_job_queue");
+        Ok(prover_jobs)
     }
 
-    /// Updates the state of ongoing prover job.
+    /// Renews the lease of the job for the prover working on it.
+    ///
+    /// Returns `false` if the job is not leased to this prover (e.g. the lease has
+    /// already expired and the job was handed out to another prover).
     pub async fn record_prover_is_working(
         &mut self,
         job_id: i32,
         prover_name: &str,
-    ) -> QueryResult<()> {
+        lease: Duration,
+    ) -> QueryResult<bool> {
         let start = Instant::now();
-        sqlx::query!(
+        let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, updated_by) = (now(), $1)
-            WHERE id = $2",
-            prover_name.to_string(),
+            SET (updated_at, lease_expires_at) = (now(), now() + $1::float8 * INTERVAL '1 second')
+            WHERE id = $2 AND job_status = $3 AND updated_by = $4",
+            lease.as_secs_f64(),
             job_id,
+            ProverJobStatus::InProgress.to_number(),
+            prover_name,
         )
         .execute(self.0.conn())
-        .await?;
+        .await?
+        .rows_affected();
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_is_working");
-        Ok(())
+        Ok(updated_rows == 1)
     }
 
-    /// Marks the prover as stopped.
+    /// Returns `true` if the job is in progress and leased to the prover.
+    pub async fn is_job_leased_to(&mut self, job_id: i32, prover_name: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let leased = sqlx::query!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM prover_job_queue WHERE id = $1 AND job_status = $2 AND updated_by = $3
+            ) AS "leased!""#,
+            job_id,
+            ProverJobStatus::InProgress.to_number(),
+            prover_name,
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .leased;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "is_job_leased_to");
+        Ok(leased)
+    }
+
+    /// Stores the progress (in percent) of the job reported by the prover working on it.
+    ///
+    /// Returns `false` if the prover has no unfinished attempt for the job.
+    pub async fn record_job_progress(
+        &mut self,
+        job_id: i32,
+        prover_name: &str,
+        progress: u8,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated_rows = sqlx::query!(
+            "UPDATE prover_job_attempts SET progress = $1
+            WHERE job_id = $2 AND prover_name = $3 AND finished_at IS NULL",
+            i16::from(progress),
+            job_id,
+            prover_name,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "record_job_progress");
+        Ok(updated_rows > 0)
+    }
+
+    /// Marks the prover as stopped, its jobs are returned to the queue.
     pub async fn record_prover_stop(&mut self, prover_name: &str) -> QueryResult<()> {
         let start = Instant::now();
-        sqlx::query!(
+        let mut transaction = self.0.start_transaction().await?;
+        let stopped_jobs: Vec<i32> = sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, job_status) = (now(), $1)
-            WHERE updated_by = $2 and job_status = $3",
+            SET (updated_at, job_status, lease_expires_at) = (now(), $1, NULL)
+            WHERE updated_by = $2 and job_status = $3
+            RETURNING id",
             ProverJobStatus::Idle.to_number(),
             prover_name,
             ProverJobStatus::InProgress.to_number()
         )
-        .execute(self.0.conn())
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|job| job.id)
+        .collect();
+
+        sqlx::query!(
+            "UPDATE prover_job_attempts SET (finished_at, outcome) = (now(), $1)
+            WHERE finished_at IS NULL AND job_id = ANY($2)",
+            ProverJobAttemptOutcome::ProverStopped.to_string(),
+            &stopped_jobs,
+        )
+        .execute(transaction.conn())
         .await?;
+        transaction.commit().await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "record_prover_stop");
         Ok(())
     }
 
+    /// Returns the poisoned job back to the queue, giving it another `max_attempts` tries.
+    /// The history of the previous attempts is kept.
+    /// Returns `false` if there is no poisoned job with such ID.
+    pub async fn reset_poisoned_job(&mut self, job_id: i32) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated_rows = sqlx::query!(
+            "UPDATE prover_job_queue
+            SET (job_status, updated_at, updated_by, attempts) = ($1, now(), 'server_reset_job', 0)
+            WHERE id = $2 AND job_status = $3",
+            ProverJobStatus::Idle.to_number(),
+            job_id,
+            ProverJobStatus::Poisoned.to_number(),
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "reset_poisoned_job");
+        Ok(updated_rows == 1)
+    }
+
+    /// Returns the job back to the queue: the job in progress is taken from its prover,
+    /// and the poisoned job is reset as in `reset_poisoned_job`.
+    /// Returns `false` if there is no such job in progress or poisoned.
+    pub async fn requeue_job(&mut self, job_id: i32) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let mut requeued = transaction
+            .prover_schema()
+            .reset_poisoned_job(job_id)
+            .await?;
+        if !requeued {
+            requeued = sqlx::query!(
+                "UPDATE prover_job_queue
+                SET (job_status, updated_at, updated_by, lease_expires_at) = ($1, now(), 'server_requeue_job', NULL)
+                WHERE id = $2 AND job_status = $3",
+                ProverJobStatus::Idle.to_number(),
+                job_id,
+                ProverJobStatus::InProgress.to_number(),
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected()
+                == 1;
+
+            if requeued {
+                transaction
+                    .prover_schema()
+                    .finish_job_attempt(job_id, ProverJobAttemptOutcome::Requeued)
+                    .await?;
+            }
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "requeue_job");
+        Ok(requeued)
+    }
+
+    /// Returns the jobs that failed too many times.
+    pub async fn get_poisoned_jobs(&mut self) -> QueryResult<Vec<StorageProverJobQueue>> {
+        let start = Instant::now();
+        let jobs = sqlx::query_as!(
+            StorageProverJobQueue,
+            "SELECT * FROM prover_job_queue WHERE job_status = $1 ORDER BY first_block, id",
+            ProverJobStatus::Poisoned.to_number(),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "get_poisoned_jobs");
+        Ok(jobs)
+    }
+
+    /// Returns the history of attempts to prove the job, oldest first.
+    pub async fn get_job_attempts(
+        &mut self,
+        job_id: i32,
+    ) -> QueryResult<Vec<StorageProverJobAttempt>> {
+        let start = Instant::now();
+        let attempts = sqlx::query_as!(
+            StorageProverJobAttempt,
+            "SELECT * FROM prover_job_attempts WHERE job_id = $1 ORDER BY id",
+            job_id,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "get_job_attempts");
+        Ok(attempts)
+    }
+
+    /// Returns the latest `limit` attempts made by the prover, newest first.
+    pub async fn get_prover_attempts(
+        &mut self,
+        prover_name: &str,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageProverJobAttempt>> {
+        let start = Instant::now();
+        let attempts = sqlx::query_as!(
+            StorageProverJobAttempt,
+            "SELECT * FROM prover_job_attempts WHERE prover_name = $1 ORDER BY id DESC LIMIT $2",
+            prover_name,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "get_prover_attempts");
+        Ok(attempts)
+    }
+
+    /// Registers the token of the prover, replacing the previous one.
+    /// Token is identified by its hash, the token itself is never stored.
+    pub async fn register_prover_token(
+        &mut self,
+        prover_name: &str,
+        token_hash: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO prover_tokens (prover_name, token_hash)
+            VALUES ($1, $2)
+            ON CONFLICT (prover_name)
+            DO UPDATE SET (token_hash, created_at, revoked_at) = ($2, now(), NULL)",
+            prover_name,
+            token_hash,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "register_prover_token");
+        Ok(())
+    }
+
+    /// Revokes the token of the prover.
+    /// Returns `false` if the prover has no active token.
+    pub async fn revoke_prover_token(&mut self, prover_name: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated_rows = sqlx::query!(
+            "UPDATE prover_tokens SET revoked_at = now()
+            WHERE prover_name = $1 AND revoked_at IS NULL",
+            prover_name,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "revoke_prover_token");
+        Ok(updated_rows == 1)
+    }
+
+    /// Returns the name of the prover owning the active token with such hash.
+    pub async fn get_prover_by_token(&mut self, token_hash: &str) -> QueryResult<Option<String>> {
+        let start = Instant::now();
+        let prover_name = sqlx::query!(
+            "SELECT prover_name FROM prover_tokens WHERE token_hash = $1 AND revoked_at IS NULL",
+            token_hash,
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| row.prover_name);
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "get_prover_by_token");
+        Ok(prover_name)
+    }
+
     /// Stores the proof for a block.
     pub async fn store_proof(
         &mut self,
@@ -181,7 +614,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         let mut transaction = self.0.start_transaction().await?;
         let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, job_status, updated_by) = (now(), $1, 'server_finish_job')
+            SET (updated_at, job_status, updated_by, lease_expires_at) = (now(), $1, 'server_finish_job', NULL)
             WHERE id = $2 AND job_type = $3",
             ProverJobStatus::Done.to_number(),
             job_id,
@@ -194,6 +627,10 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         if updated_rows != 1 {
             return Err(format_err!("Missing job for stored proof"));
         }
+        transaction
+            .prover_schema()
+            .finish_job_attempt(job_id, ProverJobAttemptOutcome::Done)
+            .await?;
 
         sqlx::query!(
             "INSERT INTO proofs (block_number, proof)
@@ -213,6 +650,23 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Records the outcome of the ongoing attempt to prove the job.
+    async fn finish_job_attempt(
+        &mut self,
+        job_id: i32,
+        outcome: ProverJobAttemptOutcome,
+    ) -> QueryResult<()> {
+        sqlx::query!(
+            "UPDATE prover_job_attempts SET (finished_at, outcome) = (now(), $1)
+            WHERE job_id = $2 AND finished_at IS NULL",
+            outcome.to_string(),
+            job_id,
+        )
+        .execute(self.0.conn())
+        .await?;
+        Ok(())
+    }
+
     // Set metrics about stages in block processing
     async fn set_block_processing_metrics(
         &mut self,
@@ -251,7 +705,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         let mut transaction = self.0.start_transaction().await?;
         let updated_rows = sqlx::query!(
             "UPDATE prover_job_queue
-            SET (updated_at, job_status, updated_by) = (now(), $1, 'server_finish_job')
+            SET (updated_at, job_status, updated_by, lease_expires_at) = (now(), $1, 'server_finish_job', NULL)
             WHERE id = $2 AND job_type = $3",
             ProverJobStatus::Done.to_number(),
             job_id,
@@ -264,6 +718,10 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         if updated_rows != 1 {
             return Err(format_err!("Missing job for stored aggregated proof"));
         }
+        transaction
+            .prover_schema()
+            .finish_job_attempt(job_id, ProverJobAttemptOutcome::Done)
+            .await?;
 
         sqlx::query!(
             "INSERT INTO aggregated_proofs (first_block, last_block, proof)
@@ -347,11 +805,160 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(())
     }
 
-    /// Gets stored witness for a block.
-    pub async fn get_witness(
+    /// Claims the lowest block starting from `from_block` that has no witness yet and is not
+    /// leased to another witness generator instance, and leases it to the `instance`.
+    ///
+    /// Blocks with the expired leases were abandoned by the crashed instances, so they can be
+    /// claimed by anyone.
+    pub async fn claim_block_for_witness(
+        &mut self,
+        instance: &str,
+        from_block: BlockNumber,
+        lease: Duration,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!("LOCK TABLE witness_generator_leases IN EXCLUSIVE MODE")
+            .execute(transaction.conn())
+            .await?;
+
+        let block = sqlx::query!(
+            r#"
+                SELECT blocks.number, witness_generator_leases.instance as "abandoned_by?"
+                FROM blocks
+                LEFT JOIN block_witness ON block_witness.block = blocks.number
+                LEFT JOIN witness_generator_leases ON witness_generator_leases.block_number = blocks.number
+                WHERE blocks.number >= $1
+                    AND block_witness.block IS NULL
+                    AND (witness_generator_leases.block_number IS NULL OR witness_generator_leases.lease_expires_at <= now())
+                ORDER BY blocks.number
+                LIMIT 1
+            "#,
+            i64::from(*from_block),
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+
+        let block_number = if let Some(block) = block {
+            sqlx::query!(
+                "INSERT INTO witness_generator_leases (block_number, instance, lease_expires_at)
+                VALUES ($1, $2, now() + $3::float8 * INTERVAL '1 second')
+                ON CONFLICT (block_number)
+                DO UPDATE SET (instance, claimed_at, lease_expires_at) = ($2, now(), now() + $3::float8 * INTERVAL '1 second')",
+                block.number,
+                instance,
+                lease.as_secs_f64(),
+            )
+            .execute(transaction.conn())
+            .await?;
+
+            if let Some(abandoned_by) = block.abandoned_by {
+                vlog::warn!(
+                    "Block {} abandoned by the witness generator '{}' was claimed by '{}'",
+                    block.number,
+                    abandoned_by,
+                    instance
+                );
+                metrics::increment_counter!("witness_generator.abandoned_blocks");
+            }
+            Some(BlockNumber(block.number as u32))
+        } else {
+            None
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "claim_block_for_witness");
+        Ok(block_number)
+    }
+
+    /// Extends the lease of the block claimed by the witness generator `instance`, so the block
+    /// isn't considered abandoned while its witness is being generated.
+    ///
+    /// Returns `false` if the block isn't claimed by the instance anymore.
+    pub async fn renew_witness_block_lease(
+        &mut self,
+        block_number: BlockNumber,
+        instance: &str,
+        lease: Duration,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated_rows = sqlx::query!(
+            "UPDATE witness_generator_leases SET lease_expires_at = now() + $1::float8 * INTERVAL '1 second'
+            WHERE block_number = $2 AND instance = $3",
+            lease.as_secs_f64(),
+            i64::from(*block_number),
+            instance,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "renew_witness_block_lease");
+        Ok(updated_rows == 1)
+    }
+
+    /// Releases the block claimed by the witness generator `instance`,
+    /// either after the witness was stored or after the witness generation has failed.
+    pub async fn release_witness_block(
+        &mut self,
+        block_number: BlockNumber,
+        instance: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM witness_generator_leases WHERE block_number = $1 AND instance = $2",
+            i64::from(*block_number),
+            instance,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "release_witness_block");
+        Ok(())
+    }
+
+    /// Stores the hash of the witness kept in the blob store.
+    pub async fn store_witness_blob(
+        &mut self,
+        block: BlockNumber,
+        blob_hash: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO block_witness (block, blob_hash)
+            VALUES ($1, $2)
+            ON CONFLICT (block)
+            DO NOTHING",
+            i64::from(*block),
+            blob_hash
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "store_witness_blob");
+        Ok(())
+    }
+
+    /// Gets the witness for a block stored in the database itself.
+    ///
+    /// Returns `None` for the witnesses kept in the blob store, use `get_witness_record`
+    /// to get the hashes of their blobs.
+    pub async fn get_inline_witness(
         &mut self,
         block_number: BlockNumber,
     ) -> QueryResult<Option<serde_json::Value>> {
+        let block_witness = self.get_witness_record(block_number).await?;
+
+        Ok(block_witness
+            .and_then(|w| w.witness)
+            .map(|witness| serde_json::from_str(&witness).expect("Failed to deserialize witness")))
+    }
+
+    /// Gets the stored witness for a block, either the witness itself or the hash of its blob.
+    pub async fn get_witness_record(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<StorageBlockWitness>> {
         let start = Instant::now();
         let block_witness = sqlx::query_as!(
             StorageBlockWitness,
@@ -362,8 +969,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?;
 
         metrics::histogram!("sql", start.elapsed(), "prover" => "get_witness");
-        Ok(block_witness
-            .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
+        Ok(block_witness)
     }
 
     pub async fn get_last_block_prover_job_queue(
@@ -53,7 +53,10 @@ pub struct IntegerNumber {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct StorageBlockWitness {
     pub block: i64,
-    pub witness: String,
+    /// `None` if the witness is kept in the blob store.
+    pub witness: Option<String>,
+    /// Hash of the witness blob in the blob store.
+    pub blob_hash: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
@@ -68,4 +71,18 @@ pub struct StorageProverJobQueue {
     pub first_block: i64,
     pub last_block: i64,
     pub job_data: serde_json::Value,
+    pub attempts: i32,
+    pub lease_expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StorageProverJobAttempt {
+    pub id: i32,
+    pub job_id: i32,
+    pub prover_name: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub outcome: Option<String>,
+    /// Progress in percent, as reported by the prover.
+    pub progress: Option<i16>,
 }
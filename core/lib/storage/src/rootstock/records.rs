@@ -15,6 +15,7 @@ pub struct StorageETHOperation {
     pub last_deadline_block: i64,
     pub last_used_gas_price: BigDecimal,
     pub created_at: Option<DateTime<Utc>>,
+    pub last_used_priority_fee: Option<BigDecimal>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -27,6 +28,7 @@ pub struct ETHOperationData {
     pub final_hash: Option<Vec<u8>>,
     pub last_deadline_block: i64,
     pub last_used_gas_price: BigDecimal,
+    pub last_used_priority_fee: Option<BigDecimal>,
     pub agg_op_id: Option<i64>,
     pub arguments: Option<serde_json::Value>,
     pub created_at: Option<DateTime<Utc>>,
@@ -0,0 +1,53 @@
+// External imports
+// Workspace imports
+use zksync_types::{address_lists::AddressListType, Address};
+// Local imports
+use crate::{address_lists::AddressListsSchema, tests::db_test, QueryResult, StorageProcessor};
+
+/// Checks that the addresses are added to and removed from the lists independently.
+#[db_test]
+async fn address_lists(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let denied = Address::random();
+    let allowed = Address::random();
+    let both = Address::random();
+
+    let mut schema = AddressListsSchema(&mut storage);
+    assert!(schema.load_entries().await?.is_empty());
+
+    schema
+        .add_entries(AddressListType::Deny, &[denied, both], "sanctioned")
+        .await?;
+    schema
+        .add_entries(AddressListType::Allow, &[allowed, both], "")
+        .await?;
+    // Adding the listed address again updates the reason.
+    schema
+        .add_entries(AddressListType::Deny, &[denied], "compromised")
+        .await?;
+
+    let entries = schema.load_entries().await?;
+    assert_eq!(entries.len(), 4);
+    let denied_entry = entries
+        .iter()
+        .find(|entry| entry.address == denied)
+        .unwrap();
+    assert_eq!(denied_entry.list, AddressListType::Deny);
+    assert_eq!(denied_entry.reason, "compromised");
+
+    // Removal affects only the specified list.
+    let removed = schema
+        .remove_entries(AddressListType::Deny, &[both, allowed])
+        .await?;
+    assert_eq!(removed, 1);
+
+    let entries = schema.load_entries().await?;
+    assert_eq!(entries.len(), 3);
+    assert!(entries
+        .iter()
+        .any(|entry| entry.address == both && entry.list == AddressListType::Allow));
+    assert!(!entries
+        .iter()
+        .any(|entry| entry.address == both && entry.list == AddressListType::Deny));
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use num::{BigUint, Zero};
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID};
 use zksync_types::{
     aggregated_operations::AggregatedActionType, helpers::apply_updates, AccountId, AccountMap,
-    AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+    AccountUpdate, Address, BlockNumber, Nonce, PubKeyHash, Token, TokenId, TokenKind,
 };
 // Local imports
 use super::block::apply_random_updates;
@@ -323,6 +323,76 @@ async fn test_get_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+#[db_test]
+async fn test_get_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let address = Address::random();
+    let updates1 = vec![
+        (
+            AccountId(1),
+            AccountUpdate::Create {
+                address,
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            AccountId(1),
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(1),
+                balance_update: (TokenId(0), BigUint::zero(), BigUint::from(100u32)),
+            },
+        ),
+    ];
+    let updates2 = vec![(
+        AccountId(1),
+        AccountUpdate::ChangePubKeyHash {
+            old_pub_key_hash: PubKeyHash::default(),
+            new_pub_key_hash: PubKeyHash::default(),
+            old_nonce: Nonce(1),
+            new_nonce: Nonce(2),
+        },
+    )];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(2), &updates1, 0)
+        .await?;
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(3), &updates2, 0)
+        .await?;
+
+    let mut account_schema = storage.chain().account_schema();
+    assert_eq!(
+        account_schema
+            .get_account_nonce_for_block(address, BlockNumber(1))
+            .await?,
+        Nonce(0)
+    );
+    assert_eq!(
+        account_schema
+            .get_account_nonce_for_block(address, BlockNumber(2))
+            .await?,
+        Nonce(1)
+    );
+    assert_eq!(
+        account_schema
+            .get_account_nonce_for_block(address, BlockNumber(4))
+            .await?,
+        Nonce(2)
+    );
+    assert_eq!(
+        account_schema
+            .get_account_nonce_for_block(Address::random(), BlockNumber(4))
+            .await?,
+        Nonce(0)
+    );
+
+    Ok(())
+}
+
 #[db_test]
 async fn test_get_account_nft_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let address = Address::random();
@@ -275,6 +275,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
             )
             .await?;
@@ -318,6 +319,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                         Some((id, op)),
                         100,
                         100u32.into(),
+                        None,
                         Default::default(),
                     )
                     .await?;
@@ -483,6 +485,7 @@ async fn test_block_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
             )
             .await?;
@@ -516,6 +519,7 @@ async fn test_block_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -618,6 +622,7 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                 Some((id, op)),
                 100,
                 100u32.into(),
+                None,
                 Default::default(),
             )
             .await?;
@@ -655,6 +660,7 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                     Some((id, op)),
                     100,
                     100u32.into(),
+                    None,
                     Default::default(),
                 )
                 .await?;
@@ -846,8 +852,25 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
         "Cannot find the pending transaction by hash"
     );
 
+    // The nonce of the pending transaction is reported as used.
+    assert!(
+        BlockSchema(&mut storage)
+            .is_nonce_used_in_pending_block(from_account_id, tx_1.nonce())
+            .await?
+    );
+    assert!(
+        !BlockSchema(&mut storage)
+            .is_nonce_used_in_pending_block(from_account_id, tx_1.nonce() + 1)
+            .await?
+    );
+
     // Finalize the block.
     BlockSchema(&mut storage).save_full_block(block_1).await?;
+    assert!(
+        !BlockSchema(&mut storage)
+            .is_nonce_used_in_pending_block(from_account_id, tx_1.nonce())
+            .await?
+    );
 
     // Ensure that pending block is no more available.
     assert!(
@@ -1053,6 +1076,46 @@ async fn test_is_block_finalized(mut storage: StorageProcessor<'_>) -> QueryResu
     Ok(())
 }
 
+/// Checks that `get_average_block_gas_limit` only takes the latest blocks into account.
+#[db_test]
+async fn test_average_block_gas_limit(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // No blocks yet.
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_average_block_gas_limit(10)
+            .await?,
+        None
+    );
+
+    for block_number in 1..=4 {
+        let mut block = gen_sample_block(
+            BlockNumber(block_number),
+            BLOCK_SIZE_CHUNKS,
+            Default::default(),
+        );
+        block.commit_gas_limit = (u64::from(block_number) * 1_000_000).into();
+        block.verify_gas_limit = 1_000_000.into();
+        BlockSchema(&mut storage).save_full_block(block).await?;
+    }
+
+    // Blocks 3 and 4: (3M + 1M + 4M + 1M) / 2.
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_average_block_gas_limit(2)
+            .await?,
+        Some(4_500_000)
+    );
+    // All the blocks: (2M + 3M + 4M + 5M) / 4.
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_average_block_gas_limit(10)
+            .await?,
+        Some(3_500_000)
+    );
+
+    Ok(())
+}
+
 /// Check that blocks are removed correctly.
 #[db_test]
 async fn test_remove_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1089,6 +1152,21 @@ async fn test_remove_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()
         Some(BlockNumber(6))
     );
 
+    // Rows to revert are counted per table.
+    let rows_to_revert = BlockSchema(&mut storage)
+        .count_rows_to_revert(BlockNumber(2))
+        .await?;
+    let rows = |table: &str| {
+        rows_to_revert
+            .iter()
+            .find(|(name, _)| *name == table)
+            .map(|(_, rows)| *rows)
+    };
+    assert_eq!(rows("blocks"), Some(3));
+    assert_eq!(rows("incomplete_blocks"), Some(1));
+    assert_eq!(rows("aggregate_operations"), Some(3));
+    assert_eq!(rows("pending_block"), Some(0));
+
     // Remove blocks with numbers greater than 2.
     BlockSchema(&mut storage)
         .remove_blocks(BlockNumber(2))
@@ -1111,6 +1189,13 @@ async fn test_remove_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()
             .await?,
         None
     );
+    let rows_to_revert = BlockSchema(&mut storage)
+        .count_rows_to_revert(BlockNumber(2))
+        .await?;
+    assert!(rows_to_revert
+        .iter()
+        .filter(|(table, _)| table.contains("blocks"))
+        .all(|(_, rows)| *rows == 0));
 
     Ok(())
 }
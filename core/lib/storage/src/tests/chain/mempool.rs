@@ -1,21 +1,24 @@
 // External imports
 use chrono::Utc;
+use num::{rational::Ratio, BigUint};
 // Workspace imports
+use zksync_api_types::v02::pagination::{PaginationDirection, PaginationQuery};
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_types::{
-    block::{Block, ExecutedOperations},
+    block::{Block, ExecutedOperations, PendingBlock},
     mempool::SignedTxVariant,
     priority_ops::FullExit,
     tx::{ChangePubKey, Transfer, TxHash, Withdraw},
     AccountId, Address, BlockNumber, ExecutedPriorityOp, ExecutedTx, FullExitOp, Nonce, PriorityOp,
     SignedZkSyncTx, TokenId, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
+use zksync_utils::round_precision;
 // Local imports
 use crate::test_data::gen_eth_sign_data;
 use crate::tests::db_test;
 use crate::{
     chain::{
-        mempool::MempoolSchema,
+        mempool::{MempoolSchema, STORED_FEE_PRIORITY_PRECISION},
         operations::{
             records::{NewExecutedPriorityOperation, NewExecutedTransaction},
             OperationsSchema,
@@ -356,6 +359,356 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
     Ok(())
 }
 
+/// Checks that transactions are counted per account, both for single transactions and batches.
+#[db_test]
+async fn account_txs_count(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Transfers are generated for accounts with IDs 0..5.
+    let txs = gen_transfers(5);
+
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_tx(&txs[0]).await?;
+    mempool.insert_batch(&txs[1..3], Vec::new()).await?;
+    mempool.insert_tx(&txs[3]).await?;
+
+    for id in 0..4 {
+        assert_eq!(mempool.get_account_txs_count(AccountId(id)).await?, 1);
+    }
+    assert_eq!(mempool.get_account_txs_count(AccountId(4)).await?, 0);
+
+    Ok(())
+}
+
+/// Checks that the transactions of the account are purged along with the batches they are part of.
+#[db_test]
+async fn purge_account_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Transfers are generated for accounts with IDs 0..5.
+    let txs = gen_transfers(5);
+
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_tx(&txs[0]).await?;
+    mempool.insert_batch(&txs[1..3], Vec::new()).await?;
+    mempool.insert_tx(&txs[3]).await?;
+    mempool.insert_tx(&txs[4]).await?;
+
+    // Account 1 has no transactions apart from the batch, which is removed as a whole.
+    let mut purged = mempool.purge_account_txs(AccountId(2)).await?;
+    purged.sort();
+    let mut expected: Vec<_> = txs[1..3].iter().map(|tx| tx.hash()).collect();
+    expected.sort();
+    assert_eq!(purged, expected);
+    assert_eq!(mempool.get_account_txs_count(AccountId(1)).await?, 0);
+
+    assert_eq!(
+        mempool.purge_account_txs(AccountId(3)).await?,
+        vec![txs[3].hash()]
+    );
+    assert!(mempool.purge_account_txs(AccountId(3)).await?.is_empty());
+
+    let txs_from_db = mempool.load_txs(&[]).await?;
+    let hashes: Vec<_> = txs_from_db
+        .into_iter()
+        .map(|tx| unwrap_tx(tx).hash())
+        .collect();
+    assert_eq!(hashes, vec![txs[0].hash(), txs[4].hash()]);
+
+    Ok(())
+}
+
+/// Checks that fee priorities are stored for single transactions and batches and loaded along with them.
+#[db_test]
+async fn fee_priority(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(4);
+    let tx_priority = Ratio::new(BigUint::from(1u32), BigUint::from(3u32));
+    let batch_priority = Ratio::from_integer(BigUint::from(25u32));
+
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_tx(&txs[0]).await?;
+    mempool.insert_batch(&txs[1..3], Vec::new()).await?;
+    mempool.insert_tx(&txs[3]).await?;
+
+    mempool
+        .set_fee_priority(&[txs[0].hash()], &tx_priority)
+        .await?;
+    let batch_hashes: Vec<_> = txs[1..3].iter().map(|tx| tx.hash()).collect();
+    mempool
+        .set_fee_priority(&batch_hashes, &batch_priority)
+        .await?;
+
+    let priorities: Vec<_> = mempool
+        .load_prioritized_txs(&[])
+        .await?
+        .into_iter()
+        .map(|(_, priority)| priority)
+        .collect();
+    assert_eq!(
+        priorities,
+        vec![
+            round_precision(&tx_priority, STORED_FEE_PRIORITY_PRECISION),
+            batch_priority,
+            // The priority of the last transaction is not set.
+            Ratio::from_integer(BigUint::from(0u32)),
+        ]
+    );
+
+    Ok(())
+}
+
+/// Creates a transfer of the given account with the given nonce.
+fn transfer_with_nonce(account_id: u32, nonce: u32) -> SignedZkSyncTx {
+    let transfer = Transfer::new(
+        AccountId(account_id),
+        Address::random(),
+        Address::random(),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(nonce),
+        Default::default(),
+        None,
+    );
+
+    SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(transfer)),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    }
+}
+
+/// Checks that only the elements holding the last nonce of their accounts can be evicted,
+/// protected accounts are never touched, and candidates are ordered according to the policy.
+#[db_test]
+async fn eviction_candidates(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let single_txs = vec![
+        transfer_with_nonce(1, 0),
+        transfer_with_nonce(1, 1),
+        transfer_with_nonce(2, 0),
+    ];
+    let batch_1 = vec![transfer_with_nonce(3, 0), transfer_with_nonce(2, 1)];
+    let batch_2 = vec![transfer_with_nonce(4, 0), transfer_with_nonce(3, 1)];
+    let last_txs = vec![transfer_with_nonce(5, 0), transfer_with_nonce(6, 0)];
+
+    let mut mempool = MempoolSchema(&mut storage);
+    for tx in &single_txs {
+        mempool.insert_tx(tx).await?;
+    }
+    mempool.insert_batch(&batch_1, Vec::new()).await?;
+    mempool.insert_batch(&batch_2, Vec::new()).await?;
+    for tx in &last_txs {
+        mempool.insert_tx(tx).await?;
+    }
+
+    let batch_2_hashes: Vec<_> = batch_2.iter().map(|tx| tx.hash()).collect();
+    mempool
+        .set_fee_priority(&[single_txs[1].hash()], &Ratio::from_integer(5u32.into()))
+        .await?;
+    mempool
+        .set_fee_priority(&batch_2_hashes, &Ratio::from_integer(1u32.into()))
+        .await?;
+    mempool
+        .set_fee_priority(&[last_txs[0].hash()], &Ratio::from_integer(3u32.into()))
+        .await?;
+
+    let sorted = |candidates: Vec<Vec<TxHash>>| -> Vec<Vec<TxHash>> {
+        candidates
+            .into_iter()
+            .map(|mut hashes| {
+                hashes.sort();
+                hashes
+            })
+            .collect()
+    };
+    let mut batch_2_hashes = batch_2_hashes;
+    batch_2_hashes.sort();
+
+    // The oldest elements go first.
+    let candidates = mempool
+        .get_eviction_candidates(&[AccountId(6)], None, 10)
+        .await?;
+    assert_eq!(
+        sorted(candidates),
+        vec![
+            vec![single_txs[1].hash()],
+            batch_2_hashes.clone(),
+            vec![last_txs[0].hash()],
+        ]
+    );
+
+    // The cheapest elements go first, and elements paying more than the bound are skipped.
+    let candidates = mempool
+        .get_eviction_candidates(&[AccountId(6)], Some(&Ratio::from_integer(4u32.into())), 10)
+        .await?;
+    assert_eq!(
+        sorted(candidates),
+        vec![batch_2_hashes, vec![last_txs[0].hash()]]
+    );
+
+    // The amount of candidates is limited.
+    let candidates = mempool
+        .get_eviction_candidates(&[AccountId(6)], None, 1)
+        .await?;
+    assert_eq!(candidates, vec![vec![single_txs[1].hash()]]);
+
+    Ok(())
+}
+
+/// Checks that a queued transaction can be found by its nonce and replaced.
+#[db_test]
+async fn replace_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let replaced_tx = txs[0].clone();
+
+    MempoolSchema(&mut storage).insert_tx(&replaced_tx).await?;
+    // Transactions from batches can't be found by nonce.
+    MempoolSchema(&mut storage)
+        .insert_batch(&txs[1..], Vec::new())
+        .await?;
+    assert!(MempoolSchema(&mut storage)
+        .get_queued_tx_by_nonce(AccountId(1), Nonce(10))
+        .await?
+        .is_none());
+
+    let queued_tx = MempoolSchema(&mut storage)
+        .get_queued_tx_by_nonce(AccountId(0), Nonce(10))
+        .await?
+        .expect("Queued transaction must be found");
+    assert_eq!(queued_tx.hash(), replaced_tx.hash());
+
+    // Create the transaction with the same nonce and the higher fee.
+    let mut transfer = match &replaced_tx.tx {
+        ZkSyncTx::Transfer(transfer) => transfer.clone(),
+        _ => unreachable!(),
+    };
+    transfer.fee = 20u32.into();
+    let new_tx = SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(transfer),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    };
+
+    MempoolSchema(&mut storage)
+        .replace_tx(&replaced_tx, &new_tx)
+        .await?;
+
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(replaced_tx.hash())
+            .await?
+    );
+    let queued_tx = MempoolSchema(&mut storage)
+        .get_queued_tx_by_nonce(AccountId(0), Nonce(10))
+        .await?
+        .expect("Queued transaction must be found");
+    assert_eq!(queued_tx.hash(), new_tx.hash());
+    assert_eq!(MempoolSchema(&mut storage).get_mempool_size().await?, 3);
+
+    Ok(())
+}
+
+/// Checks the pagination over the mempool content and the mempool stats.
+#[db_test]
+async fn mempool_content(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    let mut mempool = MempoolSchema(&mut storage);
+    for tx in &txs[..3] {
+        mempool.insert_tx(tx).await?;
+    }
+    mempool.insert_batch(&txs[3..], Vec::new()).await?;
+
+    assert_eq!(
+        mempool.get_last_queued_tx_hash().await?,
+        Some(txs[2].hash())
+    );
+    assert_eq!(mempool.get_queued_txs_count().await?, 3);
+    assert_eq!(mempool.get_queued_batches_count().await?, 1);
+
+    let query = PaginationQuery {
+        from: txs[1].hash(),
+        limit: 2,
+        direction: PaginationDirection::Older,
+    };
+    let page: Vec<TxHash> = mempool
+        .load_queued_txs_page(&query)
+        .await?
+        .expect("Transaction must be found")
+        .into_iter()
+        .map(|tx| tx.tx_hash)
+        .collect();
+    assert_eq!(page, vec![txs[1].hash(), txs[0].hash()]);
+
+    // Batched transactions are not listed among the single ones.
+    let query = PaginationQuery {
+        from: txs[3].hash(),
+        limit: 2,
+        direction: PaginationDirection::Newer,
+    };
+    assert!(mempool.load_queued_txs_page(&query).await?.is_none());
+
+    let batch_hash = TxHash::batch_hash(&[txs[3].hash()]);
+    assert_eq!(
+        mempool.get_last_queued_batch_hash().await?,
+        Some(batch_hash)
+    );
+    let query = PaginationQuery {
+        from: batch_hash,
+        limit: 2,
+        direction: PaginationDirection::Newer,
+    };
+    let batches = mempool
+        .load_queued_batches_page(&query)
+        .await?
+        .expect("Batch must be found");
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].batch_hash, batch_hash);
+
+    let stats = mempool.get_mempool_stats().await?;
+    assert_eq!(stats.queued_txs, 3);
+    assert_eq!(stats.queued_batches, 1);
+    assert_eq!(stats.priority_ops, 0);
+    assert_eq!(stats.count_by_type.get("Transfer"), Some(&2));
+    assert_eq!(stats.count_by_type.get("Withdraw"), Some(&1));
+    assert_eq!(stats.count_by_type.get("ChangePubKey"), Some(&1));
+    assert_eq!(stats.count_by_token.get(&TokenId(0)), Some(&4));
+    assert!(stats.oldest_pending_created_at.is_some());
+    assert_eq!(mempool.get_priority_ops_backlog().await?, (0, None));
+
+    Ok(())
+}
+
+/// Checks that the queued priority operations are listed together with their creation time.
+#[db_test]
+async fn priority_ops_page(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let ops: Vec<_> = (1..=3)
+        .map(|serial_id| PriorityOp {
+            serial_id,
+            data: ZkSyncPriorityOp::FullExit(FullExit {
+                account_id: AccountId(serial_id as u32),
+                eth_address: Address::random(),
+                token: TokenId(0),
+                is_legacy: false,
+            }),
+            deadline_block: 100,
+            eth_hash: H256::random(),
+            eth_block: 10,
+            eth_block_index: Some(serial_id),
+        })
+        .collect();
+    let inserted_after = Utc::now();
+    let mut mempool = MempoolSchema(&mut storage);
+    mempool.insert_priority_ops(&ops, true).await?;
+
+    let page = mempool
+        .get_priority_ops_page(2, 2, PaginationDirection::Newer)
+        .await?;
+    let serial_ids: Vec<_> = page.iter().map(|(op, _)| op.serial_id).collect();
+    assert_eq!(serial_ids, vec![2, 3]);
+    for (_, created_at) in page {
+        assert!(created_at >= inserted_after - chrono::Duration::seconds(1));
+    }
+    assert_eq!(mempool.get_priority_ops_count().await?, 3);
+
+    Ok(())
+}
+
 /// Checks that batch is got from mempool correctly
 #[db_test]
 async fn test_get_batch_info_from_mempool(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -512,3 +865,101 @@ async fn test_return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>)
     assert_eq!(block_tx.variance_name(), "FullExit");
     Ok(())
 }
+
+/// Checks that the operations planned to be reverted are exactly the ones returned to the mempool,
+/// including the ones of the pending block.
+#[db_test]
+async fn test_executed_ops_to_revert(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(5);
+    let executed_tx = |tx: &SignedZkSyncTx, success: bool| ExecutedTx {
+        signed_tx: tx.clone(),
+        success,
+        op: None,
+        fail_reason: None,
+        block_index: if success { Some(0) } else { None },
+        created_at: Utc::now(),
+        batch_id: None,
+    };
+
+    for block_number in 1..=3 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(Block {
+                block_number: BlockNumber(block_number as u32),
+                new_root_hash: Default::default(),
+                fee_account: AccountId(0),
+                block_transactions: vec![ExecutedOperations::Tx(Box::new(executed_tx(
+                    &txs[block_number - 1],
+                    true,
+                )))],
+                processed_priority_ops: (0u64, 1),
+                block_chunks_size: 0usize,
+                commit_gas_limit: Default::default(),
+                verify_gas_limit: Default::default(),
+                block_commitment: Default::default(),
+                timestamp: block_number as u64,
+            })
+            .await?;
+    }
+    storage
+        .chain()
+        .block_schema()
+        .save_pending_block(PendingBlock {
+            number: BlockNumber(4),
+            chunks_left: 10,
+            unprocessed_priority_op_before: 1,
+            pending_block_iteration: 1,
+            success_operations: vec![ExecutedOperations::Tx(Box::new(executed_tx(&txs[3], true)))],
+            failed_txs: vec![executed_tx(&txs[4], false)],
+            timestamp: 4,
+        })
+        .await?;
+
+    let planned: Vec<_> = MempoolSchema(&mut storage)
+        .get_executed_ops_to_revert(BlockNumber(1))
+        .await?
+        .into_iter()
+        .map(|(block_number, operations)| {
+            let tx_hashes: Vec<_> = operations
+                .into_iter()
+                .filter_map(|operation| match operation {
+                    ExecutedOperations::Tx(tx) if tx.success => Some(tx.signed_tx.hash()),
+                    _ => None,
+                })
+                .collect();
+            (block_number, tx_hashes)
+        })
+        .collect();
+    assert_eq!(
+        planned,
+        vec![
+            (BlockNumber(2), vec![txs[1].hash()]),
+            (BlockNumber(3), vec![txs[2].hash()]),
+            (BlockNumber(4), vec![txs[3].hash()]),
+        ]
+    );
+
+    MempoolSchema(&mut storage)
+        .return_executed_txs_to_mempool(BlockNumber(1))
+        .await?;
+    let returned: Vec<_> = MempoolSchema(&mut storage)
+        .get_reverted_blocks(&[380], AccountId(0))
+        .await?
+        .into_iter()
+        .map(|block| {
+            let tx_hashes: Vec<_> = block
+                .block_transactions
+                .into_iter()
+                .filter_map(|operation| match operation {
+                    ExecutedOperations::Tx(tx) => Some(tx.signed_tx.hash()),
+                    ExecutedOperations::PriorityOp(_) => None,
+                })
+                .collect();
+            (block.block_number, tx_hashes)
+        })
+        .collect();
+    assert_eq!(returned, planned);
+
+    Ok(())
+}
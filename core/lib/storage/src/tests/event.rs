@@ -72,6 +72,7 @@ async fn store_operation(
             Some((id, op)),
             100,
             100u32.into(),
+            None,
             Default::default(),
         )
         .await?;
@@ -104,6 +105,7 @@ fn check_block_event(event: &ZkSyncEvent, block_status: BlockStatus, block_numbe
 /// 3. Finalize first 3 blocks then fetch 1 "block committed" event and 3 "block finalized"
 /// in a single query.
 /// 4. Revert all 4 blocks and expect new "block reverted" events.
+/// 5. Fetch all the events in pages.
 #[db_test]
 async fn test_block_events(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let mut last_event_id = EventId(0);
@@ -205,6 +207,22 @@ async fn test_block_events(mut storage: StorageProcessor<'_>) -> QueryResult<()>
         let event = events.next().unwrap();
         check_block_event(&event, BlockStatus::Reverted, block_number);
     }
+    // Events can also be fetched in pages.
+    let all_events = fetch_new_events(&mut storage, EventId(0)).await?;
+    let first_page = storage
+        .event_schema()
+        .fetch_events_page(EventId(0), 3)
+        .await?;
+    assert_eq!(first_page.len(), 3);
+    for (stored_event, event) in first_page.iter().zip(&all_events) {
+        assert_eq!(stored_event.id as u64, *event.id);
+    }
+    let second_page = storage
+        .event_schema()
+        .fetch_events_page(all_events[2].id, 100)
+        .await?;
+    assert_eq!(second_page.len(), all_events.len() - 3);
+    assert_eq!(second_page[0].id as u64, *all_events[3].id);
 
     Ok(())
 }
@@ -10,9 +10,12 @@ use crate::StorageProcessor;
 use chrono::{Duration, Timelike, Utc};
 use num::{BigUint, FromPrimitive};
 use zksync_types::{
-    forced_exit_requests::{ForcedExitRequest, SaveForcedExitRequestQuery},
+    forced_exit_requests::{
+        ForcedExitRefundStatus, ForcedExitRequest, ForcedExitTokenStatus,
+        SaveForcedExitRequestQuery,
+    },
     tx::TxHash,
-    Address,
+    AccountId, Address, Nonce, SignedZkSyncTx, Transfer, ZkSyncTx,
 };
 
 use std::ops::Add;
@@ -159,6 +162,14 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
             // Is valid => should not be deleted
             valid_until: now.sub(day.mul(3)).add(minute.mul(5)),
         },
+        SaveForcedExitRequestQuery {
+            target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+            tokens: vec![TokenId(1)],
+            price_in_wei: BigUint::from_i32(1).unwrap(),
+            created_at: now.sub(day.mul(8)),
+            // Invalid for 6 days, but processed => should not be deleted
+            valid_until: now.sub(day.mul(6)),
+        },
     ];
 
     let stored_requests = store_requests(&mut storage, requests).await;
@@ -175,15 +186,21 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
         .set_fulfilled_by(stored_requests[0].id, Some(vec![transaction_hash]))
         .await?;
 
+    // The last request was processed without sending any transactions
+    // (e.g. it was refunded), so it should not be deleted either
+    ForcedExitRequestsSchema(&mut storage)
+        .set_fulfilled_at(stored_requests[4].id, now)
+        .await?;
+
     ForcedExitRequestsSchema(&mut storage)
         .delete_old_unfulfilled_requests(deleting_threshold)
         .await?;
 
     // true means should not have been deleted
     // false means should have been deleted
-    // Note that we have set the fulfilled_by for the first tx, that's why it should
-    // not have been deleted
-    let should_remain = vec![true, false, true, true];
+    // Note that we have set the fulfilled_by for the first tx and fulfilled_at
+    // for the last one, that's why they should not have been deleted
+    let should_remain = vec![true, false, true, true, true];
 
     for (i, request) in stored_requests.into_iter().enumerate() {
         let stored = ForcedExitRequestsSchema(&mut storage)
@@ -201,3 +218,122 @@ async fn delete_old_requests(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+// Checks that the per-token statuses, the payer and the refund of the request are stored and updated,
+// and that the refund is stored only once
+#[db_test]
+async fn token_exits_and_refunds(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let now = Utc::now().with_nanosecond(0).unwrap();
+
+    let request = store_requests(
+        &mut storage,
+        vec![SaveForcedExitRequestQuery {
+            target: Address::from_str("c0f97CC918C9d6fA4E9fc6be61a6a06589D199b2").unwrap(),
+            tokens: vec![TokenId(0), TokenId(1)],
+            price_in_wei: BigUint::from_i32(200).unwrap(),
+            created_at: now,
+            valid_until: now.add(Duration::days(1)),
+        }],
+    )
+    .await
+    .remove(0);
+
+    let transaction_hash = TxHash::from_str(
+        "sync-tx:796018689b3e323894f44fb0093856ec3832908c626dea357a9bd1b25f9d11bf",
+    )
+    .unwrap();
+
+    let mut schema = ForcedExitRequestsSchema(&mut storage);
+    assert!(schema.get_payer(request.id).await?.is_none());
+    let payer = Address::random();
+    schema.set_payer(request.id, payer).await?;
+    assert_eq!(schema.get_payer(request.id).await?, Some(payer));
+
+    schema
+        .set_token_exit_status(request.id, TokenId(1), ForcedExitTokenStatus::Skipped, None)
+        .await?;
+    schema
+        .set_token_exit_status(
+            request.id,
+            TokenId(0),
+            ForcedExitTokenStatus::Sent,
+            Some(transaction_hash),
+        )
+        .await?;
+    // The hash is kept when only the status is updated
+    schema
+        .set_token_exit_status(
+            request.id,
+            TokenId(0),
+            ForcedExitTokenStatus::Executed,
+            None,
+        )
+        .await?;
+
+    let exits = schema.get_token_exits(request.id).await?;
+    assert_eq!(exits.len(), 2);
+    assert_eq!(exits[0].token, TokenId(0));
+    assert_eq!(exits[0].status, ForcedExitTokenStatus::Executed);
+    assert_eq!(exits[0].tx_hash, Some(transaction_hash));
+    assert_eq!(exits[1].token, TokenId(1));
+    assert_eq!(exits[1].status, ForcedExitTokenStatus::Skipped);
+    assert_eq!(exits[1].tx_hash, None);
+
+    assert!(schema.get_refund(request.id).await?.is_none());
+
+    let recipient = Address::random();
+    schema
+        .store_refund(request.id, recipient, BigUint::from_i32(100).unwrap())
+        .await?;
+    // The second refund is ignored
+    schema
+        .store_refund(
+            request.id,
+            Address::random(),
+            BigUint::from_i32(200).unwrap(),
+        )
+        .await?;
+
+    let refund = schema.get_refund(request.id).await?.unwrap();
+    assert_eq!(refund.recipient, recipient);
+    assert_eq!(refund.amount, BigUint::from_i32(100).unwrap());
+    assert_eq!(refund.status, ForcedExitRefundStatus::Pending);
+    assert_eq!(schema.get_unfinished_refunds().await?, vec![refund]);
+
+    assert!(schema.get_refund_tx(request.id).await?.is_none());
+    let transfer = Transfer::new(
+        AccountId(1),
+        Address::random(),
+        recipient,
+        TokenId(0),
+        100u32.into(),
+        0u32.into(),
+        Nonce(0),
+        Default::default(),
+        None,
+    );
+    let refund_tx = SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(transfer)),
+        eth_sign_data: None,
+        created_at: now,
+    };
+    schema.save_refund_tx(request.id, &refund_tx).await?;
+    assert_eq!(schema.get_unfinished_refunds().await?.len(), 1);
+
+    let refund = schema.get_refund(request.id).await?.unwrap();
+    assert_eq!(refund.status, ForcedExitRefundStatus::Sent);
+    assert_eq!(refund.tx_hash, Some(refund_tx.hash()));
+    let saved_tx = schema.get_refund_tx(request.id).await?.unwrap();
+    assert_eq!(saved_tx.hash(), refund_tx.hash());
+
+    schema
+        .set_refund_status(request.id, ForcedExitRefundStatus::Completed, None)
+        .await?;
+    assert!(schema.get_unfinished_refunds().await?.is_empty());
+
+    let refund = schema.get_refund(request.id).await?.unwrap();
+    assert_eq!(refund.status, ForcedExitRefundStatus::Completed);
+    assert_eq!(refund.tx_hash, Some(refund_tx.hash()));
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use chrono::{Duration, Utc};
 use sqlx::types::BigDecimal;
 use zksync_types::TokenId;
 
@@ -54,3 +55,65 @@ async fn stored_subsidy(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that the server can't be started while the database is locked for maintenance.
+#[db_test]
+async fn server_lock(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut maintenance = StorageProcessor::establish_connection().await?;
+    assert!(MiscSchema(&mut maintenance).try_lock_maintenance().await?);
+
+    assert!(!MiscSchema(&mut storage).try_lock_server().await?);
+    let mut another_maintenance = StorageProcessor::establish_connection().await?;
+    assert!(
+        !MiscSchema(&mut another_maintenance)
+            .try_lock_maintenance()
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks that every fee quote can be used only once.
+#[db_test]
+async fn used_fee_quotes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let expires_at = Utc::now() + Duration::minutes(1);
+
+    assert!(
+        MiscSchema(&mut storage)
+            .mark_fee_quote_used("quote", expires_at)
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .mark_fee_quote_used("quote", expires_at)
+            .await?
+    );
+    assert!(
+        MiscSchema(&mut storage)
+            .mark_fee_quote_used("another_quote", expires_at)
+            .await?
+    );
+
+    // Released quote can be used again.
+    MiscSchema(&mut storage).release_fee_quote("quote").await?;
+    assert!(
+        MiscSchema(&mut storage)
+            .mark_fee_quote_used("quote", expires_at)
+            .await?
+    );
+
+    // Expired quotes are removed from the table.
+    let expired_at = Utc::now() - Duration::minutes(1);
+    assert!(
+        MiscSchema(&mut storage)
+            .mark_fee_quote_used("expired_quote", expired_at)
+            .await?
+    );
+    assert!(
+        MiscSchema(&mut storage)
+            .mark_fee_quote_used("expired_quote", expired_at)
+            .await?
+    );
+
+    Ok(())
+}
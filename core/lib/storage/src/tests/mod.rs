@@ -23,6 +23,7 @@ use tokio::sync::Mutex;
 // Workspace imports
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 
+mod address_lists;
 pub(crate) mod chain;
 mod config;
 mod data_restore;
@@ -32,6 +33,7 @@ mod misc;
 mod prover;
 mod rootstock;
 mod tokens;
+mod webhooks;
 
 pub use db_test_macro::test as db_test;
 
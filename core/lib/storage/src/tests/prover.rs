@@ -1,10 +1,15 @@
+// Built-in imports
+use std::time::Duration;
 // External imports
 use anyhow::format_err;
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 // Workspace imports
 use zksync_types::{
-    prover::{ProverJob, ProverJobType},
+    prover::{
+        ProverJob, ProverJobAttemptOutcome, ProverJobFilter, ProverJobType,
+        AGGREGATED_PROOF_JOB_PRIORITY, SINGLE_PROOF_JOB_PRIORITY,
+    },
     BlockNumber,
 };
 // Local imports
@@ -14,9 +19,11 @@ use crate::{prover::ProverSchema, QueryResult, StorageProcessor};
 
 static MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+const LEASE: Duration = Duration::from_secs(120);
+
 async fn get_idle_job_from_queue(storage: &mut StorageProcessor<'_>) -> QueryResult<ProverJob> {
     let job = ProverSchema(storage)
-        .get_idle_prover_job_from_job_queue()
+        .get_idle_prover_job_from_job_queue("test_prover", LEASE)
         .await?;
 
     job.ok_or_else(|| format_err!("expect idle job from job queue"))
@@ -35,6 +42,416 @@ async fn test_prover_job_queue(mut storage: StorageProcessor<'_>) -> QueryResult
     Ok(())
 }
 
+/// Checks that the jobs with expired leases are returned to the queue and become
+/// poisoned after too many failures, and that every attempt is recorded.
+#[db_test]
+async fn test_prover_job_leases(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+    // `now()` doesn't change within the test transaction, so the zero lease is expired at once.
+    let expired_lease = Duration::from_secs(0);
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("first_prover", expired_lease)
+        .await?
+        .expect("expect idle job from job queue");
+
+    assert!(
+        ProverSchema(&mut storage)
+            .is_job_leased_to(job.job_id, "first_prover")
+            .await?
+    );
+    assert!(
+        !ProverSchema(&mut storage)
+            .is_job_leased_to(job.job_id, "second_prover")
+            .await?
+    );
+
+    // Only the prover holding the lease can renew it.
+    assert!(
+        !ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "second_prover", LEASE)
+            .await?
+    );
+    assert!(
+        ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "first_prover", expired_lease)
+            .await?
+    );
+
+    // The lease has expired, so the job goes back to the queue.
+    ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(2)
+        .await?;
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("second_prover", expired_lease)
+        .await?
+        .expect("expect idle job from job queue");
+
+    // The second failure makes the job poisoned.
+    ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(2)
+        .await?;
+    assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 0);
+    assert!(ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("third_prover", LEASE)
+        .await?
+        .is_none());
+
+    let poisoned_jobs = ProverSchema(&mut storage).get_poisoned_jobs().await?;
+    assert_eq!(poisoned_jobs.len(), 1);
+    assert_eq!(poisoned_jobs[0].id, job.job_id);
+    assert_eq!(poisoned_jobs[0].attempts, 2);
+    assert!(poisoned_jobs[0].lease_expires_at.is_none());
+
+    let attempts = ProverSchema(&mut storage)
+        .get_job_attempts(job.job_id)
+        .await?;
+    let attempts: Vec<_> = attempts
+        .into_iter()
+        .map(|attempt| (attempt.prover_name, attempt.outcome))
+        .collect();
+    let lease_expired = Some(ProverJobAttemptOutcome::LeaseExpired.to_string());
+    assert_eq!(
+        attempts,
+        vec![
+            ("first_prover".to_string(), lease_expired.clone()),
+            ("second_prover".to_string(), lease_expired),
+        ]
+    );
+
+    // Once reset, the job can be proven.
+    assert!(
+        ProverSchema(&mut storage)
+            .reset_poisoned_job(job.job_id)
+            .await?
+    );
+    assert!(
+        !ProverSchema(&mut storage)
+            .reset_poisoned_job(job.job_id)
+            .await?
+    );
+    // The history is kept, but only the failures after the reset are counted.
+    assert_eq!(
+        ProverSchema(&mut storage)
+            .get_job_attempts(job.job_id)
+            .await?
+            .len(),
+        2
+    );
+    ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("second_prover", expired_lease)
+        .await?
+        .expect("expect idle job from job queue");
+    ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(2)
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .get_poisoned_jobs()
+        .await?
+        .is_empty());
+
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("third_prover", LEASE)
+        .await?
+        .expect("expect idle job from job queue");
+    ProverSchema(&mut storage)
+        .mark_stale_jobs_as_idle(2)
+        .await?;
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, job.first_block, &get_sample_single_proof())
+        .await?;
+
+    let attempts = ProverSchema(&mut storage)
+        .get_prover_attempts("third_prover", 10)
+        .await?;
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].job_id, job.job_id);
+    assert_eq!(
+        attempts[0].outcome,
+        Some(ProverJobAttemptOutcome::Done.to_string())
+    );
+    assert!(attempts[0].finished_at.is_some());
+
+    Ok(())
+}
+
+/// Checks that the job in progress can be taken from the prover and returned to the queue.
+#[db_test]
+async fn test_requeue_prover_job(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(1),
+            Default::default(),
+            1,
+            ProverJobType::SingleProof,
+        )
+        .await?;
+    let job = get_idle_job_from_queue(&mut storage).await?;
+    assert!(!ProverSchema(&mut storage).requeue_job(-1).await?);
+
+    // Once requeued, the job is idle and can't be requeued again.
+    assert!(ProverSchema(&mut storage).requeue_job(job.job_id).await?);
+    assert!(!ProverSchema(&mut storage).requeue_job(job.job_id).await?);
+    // The prover that held the job can't renew the lease anymore.
+    assert!(
+        !ProverSchema(&mut storage)
+            .record_prover_is_working(job.job_id, "test_prover", LEASE)
+            .await?
+    );
+
+    let requeued_job = ProverSchema(&mut storage)
+        .get_idle_prover_job_from_job_queue("second_prover", LEASE)
+        .await?
+        .expect("expect idle job from job queue");
+    assert_eq!(requeued_job.job_id, job.job_id);
+
+    let outcomes: Vec<_> = ProverSchema(&mut storage)
+        .get_job_attempts(job.job_id)
+        .await?
+        .into_iter()
+        .map(|attempt| attempt.outcome)
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![Some(ProverJobAttemptOutcome::Requeued.to_string()), None]
+    );
+
+    Ok(())
+}
+
+/// Checks that several jobs can be leased at once, and only the ones accepted by the filter.
+#[db_test]
+async fn test_prover_jobs_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for (block_number, block_size) in [(1, 10), (2, 100)] {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                block_size,
+                Default::default(),
+            ))
+            .await?;
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block_number),
+                BlockNumber(block_number),
+                Default::default(),
+                SINGLE_PROOF_JOB_PRIORITY,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(2),
+            Default::default(),
+            AGGREGATED_PROOF_JOB_PRIORITY,
+            ProverJobType::AggregatedProof,
+        )
+        .await?;
+
+    // Only the small block is accepted.
+    let small_blocks = ProverJobFilter {
+        max_block_chunks: Some(50),
+        aggregated_proofs: false,
+    };
+    let jobs = ProverSchema(&mut storage)
+        .get_idle_prover_jobs_from_job_queue("small_prover", LEASE, 10, small_blocks)
+        .await?;
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].first_block, BlockNumber(1));
+
+    // Aggregated proof goes first.
+    let jobs = ProverSchema(&mut storage)
+        .get_idle_prover_jobs_from_job_queue("big_prover", LEASE, 10, ProverJobFilter::any())
+        .await?;
+    let blocks: Vec<_> = jobs
+        .iter()
+        .map(|job| (job.first_block, job.last_block))
+        .collect();
+    assert_eq!(
+        blocks,
+        vec![
+            (BlockNumber(1), BlockNumber(2)),
+            (BlockNumber(2), BlockNumber(2))
+        ]
+    );
+    assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 3);
+
+    // Progress is recorded for the current attempt of the prover holding the job.
+    let job_id = jobs[1].job_id;
+    assert!(
+        ProverSchema(&mut storage)
+            .record_job_progress(job_id, "big_prover", 42)
+            .await?
+    );
+    assert!(
+        !ProverSchema(&mut storage)
+            .record_job_progress(job_id, "small_prover", 42)
+            .await?
+    );
+    let attempts = ProverSchema(&mut storage).get_job_attempts(job_id).await?;
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].progress, Some(42));
+
+    Ok(())
+}
+
+/// Checks that the pending jobs and the proving time are grouped by the job type and size.
+#[db_test]
+async fn test_prover_jobs_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Lock to prevent database deadlock
+    let _lock = MUTEX.lock().await;
+
+    for (block_number, block_size) in [(1, 10), (2, 10), (3, 100)] {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                block_size,
+                Default::default(),
+            ))
+            .await?;
+        ProverSchema(&mut storage)
+            .add_prover_job_to_job_queue(
+                BlockNumber(block_number),
+                BlockNumber(block_number),
+                Default::default(),
+                SINGLE_PROOF_JOB_PRIORITY,
+                ProverJobType::SingleProof,
+            )
+            .await?;
+    }
+    ProverSchema(&mut storage)
+        .add_prover_job_to_job_queue(
+            BlockNumber(1),
+            BlockNumber(3),
+            Default::default(),
+            AGGREGATED_PROOF_JOB_PRIORITY,
+            ProverJobType::AggregatedProof,
+        )
+        .await?;
+
+    // Prove the big block.
+    let big_blocks = ProverJobFilter {
+        max_block_chunks: Some(100),
+        aggregated_proofs: false,
+    };
+    let job = ProverSchema(&mut storage)
+        .get_idle_prover_jobs_from_job_queue("test_prover", LEASE, 10, big_blocks)
+        .await?
+        .into_iter()
+        .find(|job| job.first_block == BlockNumber(3))
+        .expect("Job for the big block is not handed out");
+    ProverSchema(&mut storage)
+        .store_proof(job.job_id, BlockNumber(3), &get_sample_single_proof())
+        .await?;
+
+    let stats = ProverSchema(&mut storage)
+        .prover_jobs_stats(Duration::from_secs(3600))
+        .await?;
+    let summary: Vec<_> = stats
+        .iter()
+        .map(|stats| {
+            (
+                stats.job_type,
+                stats.size,
+                stats.pending_jobs,
+                stats.oldest_job_created_at.is_some(),
+                stats.avg_proving_time,
+            )
+        })
+        .collect();
+    // `now()` doesn't change within the test transaction, so the proof is created instantly.
+    assert_eq!(
+        summary,
+        vec![
+            (ProverJobType::SingleProof, 10, 2, true, None),
+            (
+                ProverJobType::SingleProof,
+                100,
+                0,
+                false,
+                Some(Duration::from_secs(0))
+            ),
+            (ProverJobType::AggregatedProof, 3, 1, true, None),
+        ]
+    );
+
+    Ok(())
+}
+
+/// Checks that the provers are found by the hashes of their active tokens.
+#[db_test]
+async fn test_prover_tokens(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(ProverSchema(&mut storage)
+        .get_prover_by_token("first_hash")
+        .await?
+        .is_none());
+
+    ProverSchema(&mut storage)
+        .register_prover_token("prover", "first_hash")
+        .await?;
+    assert_eq!(
+        ProverSchema(&mut storage)
+            .get_prover_by_token("first_hash")
+            .await?,
+        Some("prover".to_string())
+    );
+
+    // New token replaces the old one.
+    ProverSchema(&mut storage)
+        .register_prover_token("prover", "second_hash")
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .get_prover_by_token("first_hash")
+        .await?
+        .is_none());
+    assert_eq!(
+        ProverSchema(&mut storage)
+            .get_prover_by_token("second_hash")
+            .await?,
+        Some("prover".to_string())
+    );
+
+    assert!(
+        ProverSchema(&mut storage)
+            .revoke_prover_token("prover")
+            .await?
+    );
+    assert!(
+        !ProverSchema(&mut storage)
+            .revoke_prover_token("prover")
+            .await?
+    );
+    assert!(ProverSchema(&mut storage)
+        .get_prover_by_token("second_hash")
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
 /// Checks that the single and aggregated proof can be stored and loaded.
 async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()> {
     // Attempt to load the proof that was not stored should result in None.
@@ -182,15 +599,21 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     assert_eq!(jobs_count, 3);
 
     // Record prover is working and stopped it.
-    ProverSchema(storage)
-        .record_prover_is_working(first_job.job_id, "test_prover")
-        .await?;
-    ProverSchema(storage)
-        .record_prover_is_working(second_job.job_id, "test_prover")
-        .await?;
-    ProverSchema(storage)
-        .record_prover_is_working(third_job.job_id, "test_prover")
-        .await?;
+    assert!(
+        ProverSchema(storage)
+            .record_prover_is_working(first_job.job_id, "test_prover", LEASE)
+            .await?
+    );
+    assert!(
+        ProverSchema(storage)
+            .record_prover_is_working(second_job.job_id, "test_prover", LEASE)
+            .await?
+    );
+    assert!(
+        ProverSchema(storage)
+            .record_prover_is_working(third_job.job_id, "test_prover", LEASE)
+            .await?
+    );
 
     // Store one proof and then turn off the prover.
     ProverSchema(storage)
@@ -210,6 +633,16 @@ async fn pending_jobs_count(storage: &mut StorageProcessor<'_>) -> QueryResult<(
     let jobs_count = ProverSchema(storage).pending_jobs_count().await?;
     assert_eq!(jobs_count, 2);
 
+    // Unfinished attempts of the stopped prover are closed.
+    let attempts = ProverSchema(storage)
+        .get_job_attempts(first_job.job_id)
+        .await?;
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(
+        attempts[0].outcome,
+        Some(ProverJobAttemptOutcome::ProverStopped.to_string())
+    );
+
     Ok(())
 }
 
@@ -221,7 +654,7 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
     // No witness stored for the block.
     assert!(storage
         .prover_schema()
-        .get_witness(BLOCK_NUMBER)
+        .get_inline_witness(BLOCK_NUMBER)
         .await?
         .is_none());
 
@@ -247,7 +680,7 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
     // Now load it.
     let loaded = storage
         .prover_schema()
-        .get_witness(BLOCK_NUMBER)
+        .get_inline_witness(BLOCK_NUMBER)
         .await?
         .map(|value| serde_json::from_value(value).unwrap());
     assert_eq!(loaded.as_ref(), Some(&expected));
@@ -262,7 +695,7 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     let loaded = storage
         .prover_schema()
-        .get_witness(BLOCK_NUMBER)
+        .get_inline_witness(BLOCK_NUMBER)
         .await?
         .map(|value| serde_json::from_value(value).unwrap());
     assert_ne!(loaded, Some(not_expected));
@@ -271,6 +704,143 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks that the witness kept in the blob store is stored as a reference.
+#[db_test]
+async fn test_store_witness_blob(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCK_NUMBER: BlockNumber = BlockNumber(1);
+    let blob_hash = "ab".repeat(32);
+
+    // FK constraint.
+    storage
+        .chain()
+        .block_schema()
+        .save_full_block(gen_sample_block(BLOCK_NUMBER, 100, Default::default()))
+        .await?;
+
+    storage
+        .prover_schema()
+        .store_witness_blob(BLOCK_NUMBER, &blob_hash)
+        .await?;
+
+    let record = storage
+        .prover_schema()
+        .get_witness_record(BLOCK_NUMBER)
+        .await?
+        .expect("Witness record must exist");
+    assert_eq!(record.witness, None);
+    assert_eq!(record.blob_hash, Some(blob_hash));
+
+    // Witness itself is not in the database.
+    assert!(storage
+        .prover_schema()
+        .get_inline_witness(BLOCK_NUMBER)
+        .await?
+        .is_none());
+
+    // The block still counts as having a witness.
+    assert!(storage
+        .prover_schema()
+        .claim_block_for_witness("instance", BLOCK_NUMBER, LEASE)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks that the blocks are claimed by the witness generators one at a time,
+/// and that the blocks with expired leases can be claimed by another instance.
+#[db_test]
+async fn test_claim_block_for_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    for block_number in 1..=3 {
+        storage
+            .chain()
+            .block_schema()
+            .save_full_block(gen_sample_block(
+                BlockNumber(block_number),
+                100,
+                Default::default(),
+            ))
+            .await?;
+    }
+    let witness = serde_json::to_value(String::from("test")).unwrap();
+    storage
+        .prover_schema()
+        .store_witness(BlockNumber(3), witness)
+        .await?;
+
+    // Blocks are claimed in order, the blocks with a witness are skipped.
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("first", BlockNumber(1), LEASE)
+        .await?;
+    assert_eq!(claimed, Some(BlockNumber(1)));
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("second", BlockNumber(1), LEASE)
+        .await?;
+    assert_eq!(claimed, Some(BlockNumber(2)));
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("third", BlockNumber(1), LEASE)
+        .await?;
+    assert_eq!(claimed, None);
+
+    // Only the instance holding the lease can release the block.
+    storage
+        .prover_schema()
+        .release_witness_block(BlockNumber(1), "second")
+        .await?;
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("third", BlockNumber(1), LEASE)
+        .await?;
+    assert_eq!(claimed, None);
+
+    storage
+        .prover_schema()
+        .release_witness_block(BlockNumber(1), "first")
+        .await?;
+    // `now()` doesn't change within the test transaction, so the zero lease is expired at once.
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("third", BlockNumber(1), Duration::from_secs(0))
+        .await?;
+    assert_eq!(claimed, Some(BlockNumber(1)));
+    // Only the instance holding the lease can renew it.
+    assert!(
+        !storage
+            .prover_schema()
+            .renew_witness_block_lease(BlockNumber(1), "second", LEASE)
+            .await?
+    );
+
+    // The block abandoned by the third instance is taken over.
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("fourth", BlockNumber(1), LEASE)
+        .await?;
+    assert_eq!(claimed, Some(BlockNumber(1)));
+    assert!(
+        !storage
+            .prover_schema()
+            .renew_witness_block_lease(BlockNumber(1), "third", LEASE)
+            .await?
+    );
+    assert!(
+        storage
+            .prover_schema()
+            .renew_witness_block_lease(BlockNumber(1), "fourth", LEASE)
+            .await?
+    );
+    let claimed = storage
+        .prover_schema()
+        .claim_block_for_witness("fifth", BlockNumber(2), LEASE)
+        .await?;
+    assert_eq!(claimed, None);
+
+    Ok(())
+}
+
 /// Checks that block witnesses are removed correctly.
 #[db_test]
 async fn test_remove_witnesses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -300,12 +870,12 @@ async fn test_remove_witnesses(mut storage: StorageProcessor<'_>) -> QueryResult
     // Check that there is a witness for the 3rd block and no witness for the 4th.
     assert!(storage
         .prover_schema()
-        .get_witness(BlockNumber(3))
+        .get_inline_witness(BlockNumber(3))
         .await?
         .is_some());
     assert!(storage
         .prover_schema()
-        .get_witness(BlockNumber(4))
+        .get_inline_witness(BlockNumber(4))
         .await?
         .is_none());
 
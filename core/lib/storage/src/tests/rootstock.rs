@@ -53,6 +53,7 @@ impl RootstockTxParams {
             nonce: nonce.into(),
             last_deadline_block: self.deadline_block,
             last_used_gas_price,
+            last_used_priority_fee: None,
             used_tx_hashes,
             encoded_tx_data: self.raw_tx.clone(),
             confirmed: false,
@@ -116,6 +117,7 @@ async fn rootstock_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            None,
             params.raw_tx.clone(),
         )
         .await?;
@@ -156,6 +158,7 @@ async fn rootstock_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
             params_2.op.clone(),
             params_2.deadline_block as i64,
             params_2.gas_price.clone(),
+            None,
             params_2.raw_tx.clone(),
         )
         .await?;
@@ -270,6 +273,7 @@ async fn rootstock_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult
             params.op.clone(),
             params.deadline_block as i64,
             params.gas_price.clone(),
+            None,
             params.raw_tx.clone(),
         )
         .await?;
@@ -328,6 +332,7 @@ async fn rootstock_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult
             verify_params.op,
             verify_params.deadline_block as i64,
             verify_params.gas_price.clone(),
+            None,
             verify_params.raw_tx.clone(),
         )
         .await?;
@@ -1,7 +1,7 @@
 // Built-in imports
 use std::str::FromStr;
 // External imports
-use chrono::Utc;
+use chrono::{SubsecRound, Utc};
 use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_test_account::ZkSyncAccount;
@@ -179,6 +179,88 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that every ticker price update is kept in the price history,
+/// and the history can be downsampled and pruned.
+#[db_test]
+async fn test_ticker_price_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    // Postgres stores timestamps with microsecond precision, so use whole seconds for comparisons.
+    let now = Utc::now().trunc_subsecs(0);
+    let minutes_ago = |minutes| now - chrono::Duration::minutes(minutes);
+    // Prices updated every 10 minutes during the last 2 hours.
+    let prices: Vec<_> = (0..12)
+        .map(|i| TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(100u32 + i as u32)),
+            last_updated: minutes_ago(110 - i * 10),
+        })
+        .collect();
+    for price in &prices {
+        storage
+            .tokens_schema()
+            .update_historical_ticker_price(TOKEN_ID, price.clone())
+            .await?;
+    }
+
+    // The latest price is still available as the current one.
+    let current = storage
+        .tokens_schema()
+        .get_historical_ticker_price(TOKEN_ID)
+        .await?
+        .expect("couldn't load token price");
+    assert_eq!(current.usd_price, prices[11].usd_price);
+
+    let history = storage
+        .tokens_schema()
+        .get_ticker_price_history(TOKEN_ID, None, None, 100)
+        .await?;
+    assert_eq!(history.len(), prices.len());
+    assert_eq!(history[0].usd_price, prices[11].usd_price);
+    assert_eq!(history[11].usd_price, prices[0].usd_price);
+
+    // The price used 45 minutes ago is the one updated 50 minutes ago.
+    let history = storage
+        .tokens_schema()
+        .get_ticker_price_history(TOKEN_ID, None, Some(minutes_ago(45)), 1)
+        .await?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].usd_price, prices[6].usd_price);
+
+    let history = storage
+        .tokens_schema()
+        .get_ticker_price_history(TOKEN_ID, Some(minutes_ago(35)), Some(minutes_ago(15)), 100)
+        .await?;
+    assert_eq!(history.len(), 2);
+
+    // Keep one price per hour for the prices older than 30 minutes
+    // and remove the prices older than 100 minutes.
+    let removed = storage
+        .tokens_schema()
+        .prune_ticker_price_history(minutes_ago(30), 60 * 60, Some(minutes_ago(100)))
+        .await?;
+    let history = storage
+        .tokens_schema()
+        .get_ticker_price_history(TOKEN_ID, None, None, 100)
+        .await?;
+    assert_eq!(history.len() as u64 + removed, prices.len() as u64);
+    // Recent prices are untouched.
+    assert!(history
+        .iter()
+        .take(3)
+        .zip(prices.iter().rev())
+        .all(|(loaded, price)| loaded.usd_price == price.usd_price));
+    // At most 2 hourly samples remain for the prices updated between 100 and 30 minutes ago.
+    let old_prices = history
+        .iter()
+        .filter(|price| price.last_updated < minutes_ago(30))
+        .count();
+    assert!((1..=2).contains(&old_prices));
+    assert!(history
+        .iter()
+        .all(|price| price.last_updated >= minutes_ago(100)));
+
+    Ok(())
+}
+
 /// Checks the store/load routine for `ticker_market_volume` table and load tokens by market volume.
 #[db_test]
 async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
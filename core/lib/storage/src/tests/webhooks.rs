@@ -0,0 +1,51 @@
+// External imports
+use serde_json::json;
+// Workspace imports
+use zksync_types::event::EventId;
+// Local imports
+use crate::{tests::db_test, webhooks::WebhooksSchema, QueryResult, StorageProcessor};
+
+/// Checks that the webhooks are registered, their cursors are updated and they can be removed.
+#[db_test]
+async fn webhooks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut schema = WebhooksSchema(&mut storage);
+    assert!(schema.load_webhooks().await?.is_empty());
+
+    let filters = json!({ "block": { "status": "committed" } });
+    let first = schema
+        .add_webhook(
+            "http://127.0.0.1:1234/events",
+            "secret",
+            &filters,
+            EventId(10),
+        )
+        .await?;
+    let second = schema
+        .add_webhook(
+            "http://127.0.0.1:1234/blocks",
+            "other",
+            &json!({}),
+            EventId(0),
+        )
+        .await?;
+    assert_eq!(first.url, "http://127.0.0.1:1234/events");
+    assert_eq!(first.secret, "secret");
+    assert_eq!(first.filters, filters);
+    assert_eq!(first.last_delivered_event_id, 10);
+
+    schema.update_delivery_cursor(first.id, EventId(15)).await?;
+    let webhooks = schema.load_webhooks().await?;
+    assert_eq!(webhooks.len(), 2);
+    assert_eq!(webhooks[0].id, first.id);
+    assert_eq!(webhooks[0].last_delivered_event_id, 15);
+    assert_eq!(webhooks[1].id, second.id);
+    assert_eq!(webhooks[1].last_delivered_event_id, 0);
+
+    assert!(schema.remove_webhook(first.id).await?);
+    assert!(!schema.remove_webhook(first.id).await?);
+    let webhooks = schema.load_webhooks().await?;
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0].id, second.id);
+
+    Ok(())
+}
@@ -2,6 +2,7 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
@@ -527,7 +528,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_price.map(|p| p.into()))
     }
 
-    /// Updates price in USD for the given token.
+    /// Updates price in USD for the given token and appends it to the price history.
     ///
     /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
     /// so the number might get rounded.
@@ -538,6 +539,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
     ) -> QueryResult<()> {
         let start = Instant::now();
         let usd_price_rounded = ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION);
+        let mut transaction = self.0.start_transaction().await?;
         sqlx::query!(
             r#"
             INSERT INTO ticker_price ( token_id, usd_price, last_updated )
@@ -550,13 +552,113 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             usd_price_rounded.clone(),
             price.last_updated
         )
-        .fetch_optional(self.0.conn())
+        .fetch_optional(transaction.conn())
         .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO ticker_price_history ( token_id, usd_price, last_updated )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT (token_id, last_updated)
+            DO
+              UPDATE SET usd_price = $2
+            "#,
+            *token_id as i32,
+            usd_price_rounded,
+            price.last_updated
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
 
         metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
         Ok(())
     }
 
+    /// Returns the stored USD prices of the given token updated within the `[from, to]` range,
+    /// starting from the most recent one.
+    ///
+    /// The price used at some moment is the first price returned for the range ending at that moment.
+    pub async fn get_ticker_price_history(
+        &mut self,
+        token_id: TokenId,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: u32,
+    ) -> QueryResult<Vec<TokenPrice>> {
+        let start = Instant::now();
+        let prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT * FROM ticker_price_history
+            WHERE token_id = $1
+                AND last_updated >= COALESCE($2, '-infinity'::timestamptz)
+                AND last_updated <= COALESCE($3, 'infinity'::timestamptz)
+            ORDER BY last_updated DESC
+            LIMIT $4
+            "#,
+            *token_id as i32,
+            from,
+            to,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_ticker_price_history", start.elapsed());
+        Ok(prices.into_iter().map(|price| price.into()).collect())
+    }
+
+    /// Reduces the size of the token price history.
+    ///
+    /// Prices updated before `downsample_before` are downsampled so that only the latest price
+    /// within every `downsample_interval_secs` interval is kept for each token.
+    /// Prices updated before `remove_before` are removed completely.
+    ///
+    /// Returns the number of removed prices.
+    pub async fn prune_ticker_price_history(
+        &mut self,
+        downsample_before: DateTime<Utc>,
+        downsample_interval_secs: u64,
+        remove_before: Option<DateTime<Utc>>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let mut removed = 0;
+        if let Some(remove_before) = remove_before {
+            removed += sqlx::query!(
+                "DELETE FROM ticker_price_history WHERE last_updated < $1",
+                remove_before
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected();
+        }
+        if downsample_interval_secs > 0 {
+            removed += sqlx::query!(
+                r#"
+                DELETE FROM ticker_price_history
+                WHERE last_updated < $1
+                    AND (token_id, last_updated) NOT IN (
+                        SELECT DISTINCT ON (token_id, extract(epoch FROM last_updated)::bigint / $2)
+                            token_id, last_updated
+                        FROM ticker_price_history
+                        WHERE last_updated < $1
+                        ORDER BY token_id, extract(epoch FROM last_updated)::bigint / $2, last_updated DESC
+                    )
+                "#,
+                downsample_before,
+                downsample_interval_secs as i64
+            )
+            .execute(transaction.conn())
+            .await?
+            .rows_affected();
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.prune_ticker_price_history", start.elapsed());
+        Ok(removed)
+    }
+
     pub async fn store_nft_factory(
         &mut self,
         creator_id: AccountId,
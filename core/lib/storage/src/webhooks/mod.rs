@@ -0,0 +1,92 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use serde_json::Value;
+// Workspace imports
+use zksync_types::event::EventId;
+// Local imports
+use self::records::StoredWebhook;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Webhooks schema stores the HTTP endpoints the event listener delivers
+/// the events to, as well as the id of the last event delivered to each of them.
+#[derive(Debug)]
+pub struct WebhooksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WebhooksSchema<'a, 'c> {
+    /// Registers a new webhook, the events after `last_delivered_event_id` will be delivered to it.
+    pub async fn add_webhook(
+        &mut self,
+        url: &str,
+        secret: &str,
+        filters: &Value,
+        last_delivered_event_id: EventId,
+    ) -> QueryResult<StoredWebhook> {
+        let start = Instant::now();
+
+        let webhook = sqlx::query_as!(
+            StoredWebhook,
+            r#"
+            INSERT INTO event_webhooks ( url, secret, filters, last_delivered_event_id )
+            VALUES ( $1, $2, $3, $4 )
+            RETURNING *
+            "#,
+            url,
+            secret,
+            filters,
+            *last_delivered_event_id as i64
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.add_webhook", start.elapsed());
+        Ok(webhook)
+    }
+
+    /// Loads all the registered webhooks.
+    pub async fn load_webhooks(&mut self) -> QueryResult<Vec<StoredWebhook>> {
+        let start = Instant::now();
+
+        let webhooks = sqlx::query_as!(StoredWebhook, "SELECT * FROM event_webhooks ORDER BY id")
+            .fetch_all(self.0.conn())
+            .await?;
+
+        metrics::histogram!("sql.webhooks.load_webhooks", start.elapsed());
+        Ok(webhooks)
+    }
+
+    /// Removes the webhook, returns `false` if there was no webhook with the given id.
+    pub async fn remove_webhook(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!("DELETE FROM event_webhooks WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?
+            .rows_affected();
+
+        metrics::histogram!("sql.webhooks.remove_webhook", start.elapsed());
+        Ok(removed > 0)
+    }
+
+    /// Stores the id of the last event delivered to the webhook.
+    pub async fn update_delivery_cursor(
+        &mut self,
+        id: i64,
+        last_delivered_event_id: EventId,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "UPDATE event_webhooks SET last_delivered_event_id = $2 WHERE id = $1",
+            id,
+            *last_delivered_event_id as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.webhooks.update_delivery_cursor", start.elapsed());
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+// Workspace imports
+// Local imports
+
+#[derive(Debug, Clone)]
+pub struct StoredWebhook {
+    pub id: i64,
+    pub url: String,
+    pub secret: String,
+    pub filters: Value,
+    pub last_delivered_event_id: i64,
+    pub created_at: DateTime<Utc>,
+}
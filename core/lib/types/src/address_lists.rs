@@ -0,0 +1,52 @@
+//! Address lists used by the API to restrict who can submit transactions.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+use zksync_basic_types::Address;
+
+/// Kind of the address list.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressListType {
+    /// If the list is not empty, only the listed addresses can submit transactions.
+    Allow,
+    /// Listed addresses can neither submit transactions nor receive funds.
+    Deny,
+}
+
+impl ToString for AddressListType {
+    fn to_string(&self) -> String {
+        match self {
+            AddressListType::Allow => String::from("ALLOW"),
+            AddressListType::Deny => String::from("DENY"),
+        }
+    }
+}
+
+impl FromStr for AddressListType {
+    type Err = IncorrectAddressListType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ALLOW" => Ok(AddressListType::Allow),
+            "DENY" => Ok(AddressListType::Deny),
+            _ => Err(IncorrectAddressListType(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Incorrect address list type: {0}")]
+pub struct IncorrectAddressListType(pub String);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressListEntry {
+    pub address: Address,
+    pub list: AddressListType,
+    /// Why the address was listed, e.g. the sanctions list it comes from.
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -29,7 +29,8 @@ pub enum EventData {
 pub struct ZkSyncEvent {
     // Id of the event. This value is equal to
     // the id of the corresponding row in the database.
-    #[serde(skip)]
+    // Clients use it as a cursor to resume the stream of events.
+    #[serde(rename = "event_id")]
     pub id: EventId,
     pub block_number: BlockNumber,
     #[serde(flatten)]
@@ -87,6 +87,7 @@ pub fn get_transaction_event(
         tx: Default::default(),
         status,
         fail_reason: None,
+        replaced_by: None,
         created_at: Utc::now(),
         tx_type: OnceCell::from(tx_type),
     };
@@ -6,7 +6,11 @@ use serde::{Deserialize, Serialize};
 // Workspace uses
 // Local uses
 use super::account::AccountStateChangeStatus;
-use crate::{block::ExecutedOperations, AccountId, BlockNumber, TokenId};
+use crate::{
+    block::ExecutedOperations,
+    tx::{SignedZkSyncTx, TxHash},
+    AccountId, BlockNumber, TokenId,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -15,6 +19,9 @@ pub enum TransactionStatus {
     Committed,
     Finalized,
     Rejected,
+    /// Transaction was removed from the mempool in favor of the transaction
+    /// with the same nonce paying a higher fee.
+    Replaced,
 }
 
 /// All possible types of operations in the zkSync network.
@@ -42,6 +49,10 @@ pub struct TransactionEvent {
     pub tx: serde_json::Value,
     pub status: TransactionStatus,
     pub fail_reason: Option<String>,
+    /// Hash of the transaction that replaced this one, set only for
+    /// [`TransactionStatus::Replaced`] events.
+    #[serde(default)]
+    pub replaced_by: Option<String>,
     pub created_at: DateTime<Utc>,
     /// This field is lazily initialized and only used for filtering.
     /// Since the event is shared among server worker threads, it has
@@ -72,6 +83,7 @@ impl TransactionEvent {
                     TransactionStatus::Rejected
                 },
                 fail_reason: exec_tx.fail_reason.clone(),
+                replaced_by: None,
                 created_at: exec_tx.created_at,
                 tx_type: OnceCell::default(),
             },
@@ -83,12 +95,37 @@ impl TransactionEvent {
                 tx: serde_json::to_value(&exec_prior_op.op).unwrap(),
                 status,
                 fail_reason: None,
+                replaced_by: None,
                 created_at: exec_prior_op.created_at,
                 tx_type: OnceCell::default(),
             },
         })
     }
 
+    /// Creates an event for the queued transaction that was replaced by another one
+    /// with the same nonce. `block_number` is the number of the block the transaction
+    /// could have been included into.
+    ///
+    /// Returns [`None`] for [close](crate::tx::Close) operation.
+    pub fn from_replaced_tx(
+        replaced_tx: &SignedZkSyncTx,
+        new_tx_hash: TxHash,
+        block_number: BlockNumber,
+    ) -> Option<Self> {
+        Some(Self {
+            tx_hash: replaced_tx.tx.hash().to_string(),
+            account_id: replaced_tx.account_id().ok()?,
+            token_id: replaced_tx.token_id(),
+            block_number,
+            tx: serde_json::to_value(&replaced_tx.tx).unwrap(),
+            status: TransactionStatus::Replaced,
+            fail_reason: None,
+            replaced_by: Some(new_tx_hash.to_string()),
+            created_at: replaced_tx.created_at,
+            tx_type: OnceCell::default(),
+        })
+    }
+
     pub fn tx_type(&self) -> TransactionType {
         *self
             .tx_type
@@ -9,8 +9,8 @@ use serde::{Deserialize, Serialize};
 pub type ForcedExitRequestId = i64;
 
 use ethabi::{decode, ParamType};
-use std::convert::TryFrom;
-use zksync_basic_types::Log;
+use std::{convert::TryFrom, str::FromStr};
+use zksync_basic_types::{Log, H256};
 
 use crate::tx::TxHash;
 
@@ -28,6 +28,131 @@ pub struct ForcedExitRequest {
     pub fulfilled_at: Option<DateTime<Utc>>,
 }
 
+/// Status of the forced exit of a single token of the request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ForcedExitTokenStatus {
+    /// `ForcedExit` transaction was sent to the mempool.
+    Sent,
+    /// `ForcedExit` transaction was committed successfully.
+    Executed,
+    /// `ForcedExit` transaction was rejected by the state keeper.
+    Failed,
+    /// Target couldn't be exited in this token, so no transaction was sent.
+    Skipped,
+}
+
+impl ForcedExitTokenStatus {
+    /// Whether the part of the payment for this token has to be returned to the payer.
+    pub fn is_refundable(&self) -> bool {
+        matches!(
+            self,
+            ForcedExitTokenStatus::Failed | ForcedExitTokenStatus::Skipped
+        )
+    }
+}
+
+impl ToString for ForcedExitTokenStatus {
+    fn to_string(&self) -> String {
+        match self {
+            ForcedExitTokenStatus::Sent => String::from("SENT"),
+            ForcedExitTokenStatus::Executed => String::from("EXECUTED"),
+            ForcedExitTokenStatus::Failed => String::from("FAILED"),
+            ForcedExitTokenStatus::Skipped => String::from("SKIPPED"),
+        }
+    }
+}
+
+impl FromStr for ForcedExitTokenStatus {
+    type Err = IncorrectForcedExitStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "SENT" => ForcedExitTokenStatus::Sent,
+            "EXECUTED" => ForcedExitTokenStatus::Executed,
+            "FAILED" => ForcedExitTokenStatus::Failed,
+            "SKIPPED" => ForcedExitTokenStatus::Skipped,
+            _ => return Err(IncorrectForcedExitStatus(s.to_string())),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitTokenExit {
+    pub token: TokenId,
+    pub status: ForcedExitTokenStatus,
+    pub tx_hash: Option<TxHash>,
+}
+
+/// Status of the return of the unused part of the request payment.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ForcedExitRefundStatus {
+    /// Refund is recorded, but the transfer is not sent yet.
+    Pending,
+    /// Transfer was sent to the mempool.
+    Sent,
+    /// Transfer was committed successfully.
+    Completed,
+    /// Transfer was rejected by the state keeper, the refund has to be made manually.
+    Failed,
+}
+
+impl ToString for ForcedExitRefundStatus {
+    fn to_string(&self) -> String {
+        match self {
+            ForcedExitRefundStatus::Pending => String::from("PENDING"),
+            ForcedExitRefundStatus::Sent => String::from("SENT"),
+            ForcedExitRefundStatus::Completed => String::from("COMPLETED"),
+            ForcedExitRefundStatus::Failed => String::from("FAILED"),
+        }
+    }
+}
+
+impl FromStr for ForcedExitRefundStatus {
+    type Err = IncorrectForcedExitStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "PENDING" => ForcedExitRefundStatus::Pending,
+            "SENT" => ForcedExitRefundStatus::Sent,
+            "COMPLETED" => ForcedExitRefundStatus::Completed,
+            "FAILED" => ForcedExitRefundStatus::Failed,
+            _ => return Err(IncorrectForcedExitStatus(s.to_string())),
+        })
+    }
+}
+
+/// Return of the part of the payment for the tokens that couldn't be exited.
+/// The refund is an L2 transfer of the base token from the forced exit sender account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRefund {
+    pub request_id: ForcedExitRequestId,
+    /// Sender of the L1 transaction which paid for the request.
+    pub recipient: Address,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    pub status: ForcedExitRefundStatus,
+    pub tx_hash: Option<TxHash>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Forced exit request with the per-token fulfilment status and the refund, if any.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitRequestInfo {
+    #[serde(flatten)]
+    pub request: ForcedExitRequest,
+    pub exits: Vec<ForcedExitTokenExit>,
+    pub refund: Option<ForcedExitRefund>,
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Incorrect forced exit status: {0}")]
+pub struct IncorrectForcedExitStatus(pub String);
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct SaveForcedExitRequestQuery {
     pub target: Address,
@@ -42,6 +167,8 @@ pub struct SaveForcedExitRequestQuery {
 pub struct FundsReceivedEvent {
     pub amount: BigUint,
     pub block_number: u64,
+    /// Hash of the L1 transaction which paid for the request.
+    pub tx_hash: H256,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -65,10 +192,14 @@ impl TryFrom<Log> for FundsReceivedEvent {
             .block_number
             .ok_or(FundsReceivedEventParseError::UnfinalizedBlockAccess)?
             .as_u64();
+        let tx_hash = event
+            .transaction_hash
+            .ok_or(FundsReceivedEventParseError::UnfinalizedBlockAccess)?;
 
         Ok(FundsReceivedEvent {
             amount: BigUint::from(amount.as_u128()),
             block_number,
+            tx_hash,
         })
     }
 }
@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
 use thiserror::Error;
 use zksync_basic_types::BlockNumber;
 
@@ -6,6 +8,8 @@ pub enum ProverJobStatus {
     Idle = 0,
     InProgress = 1,
     Done = 2,
+    /// Job failed too many times and won't be handed out anymore until it's reset manually.
+    Poisoned = 3,
 }
 
 impl ProverJobStatus {
@@ -14,6 +18,7 @@ impl ProverJobStatus {
             ProverJobStatus::Idle => 0,
             ProverJobStatus::InProgress => 1,
             ProverJobStatus::Done => 2,
+            ProverJobStatus::Poisoned => 3,
         }
     }
 
@@ -22,6 +27,7 @@ impl ProverJobStatus {
             0 => Self::Idle,
             1 => Self::InProgress,
             2 => Self::Done,
+            3 => Self::Poisoned,
             _ => return Err(IncorrectProverJobStatus(num)),
         })
     }
@@ -54,7 +60,7 @@ impl ProverJob {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ProverJobType {
     SingleProof,
     AggregatedProof,
@@ -69,6 +75,65 @@ impl ToString for ProverJobType {
     }
 }
 
+/// Result of the single attempt to prove the job.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProverJobAttemptOutcome {
+    /// Proof was received.
+    Done,
+    /// Prover didn't renew the lease in time.
+    LeaseExpired,
+    /// Prover reported that it was stopped.
+    ProverStopped,
+    /// Job was returned to the queue by the operator.
+    Requeued,
+}
+
+impl ToString for ProverJobAttemptOutcome {
+    fn to_string(&self) -> String {
+        match self {
+            ProverJobAttemptOutcome::Done => String::from("DONE"),
+            ProverJobAttemptOutcome::LeaseExpired => String::from("LEASE_EXPIRED"),
+            ProverJobAttemptOutcome::ProverStopped => String::from("PROVER_STOPPED"),
+            ProverJobAttemptOutcome::Requeued => String::from("REQUEUED"),
+        }
+    }
+}
+
+/// Restricts the prover jobs to the ones the prover is able to prove.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProverJobFilter {
+    /// Maximum size (in chunks) of the blocks to prove, `None` if blocks of any size are accepted.
+    pub max_block_chunks: Option<usize>,
+    /// Whether the aggregated proof jobs are accepted.
+    pub aggregated_proofs: bool,
+}
+
+impl ProverJobFilter {
+    /// Filter that accepts any job.
+    pub fn any() -> Self {
+        Self {
+            max_block_chunks: None,
+            aggregated_proofs: true,
+        }
+    }
+}
+
+/// Statistics of the prover jobs of the same type and size, i.e. the jobs which require
+/// the same prover machine type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProverJobsStats {
+    pub job_type: ProverJobType,
+    /// Block size in chunks for the single proofs, amount of blocks for the aggregated proofs.
+    pub size: usize,
+    /// Amount of the jobs waiting for the proof, including the ones in progress.
+    pub pending_jobs: u32,
+    /// Creation time of the oldest pending job.
+    pub oldest_job_created_at: Option<DateTime<Utc>>,
+    /// Average time the provers spent on the recently proven jobs,
+    /// `None` if no such jobs were proven recently.
+    pub avg_proving_time: Option<Duration>,
+}
+
 #[derive(Debug, Error, PartialEq)]
 #[error("Incorrect ProverJobStatus number: {0}")]
 pub struct IncorrectProverJobStatus(pub i32);
@@ -4,6 +4,7 @@
 use thiserror::Error;
 // Local uses
 use crate::aggregated_operations::{AggregatedActionType, AggregatedOperation};
+use crate::gas_counter::GasCounter;
 use zksync_basic_types::{H256, U256};
 
 /// Numerical identifier of the Rootstock operation.
@@ -23,7 +24,11 @@ pub struct ETHOperation {
     /// Deadline block of the last sent transaction.
     pub last_deadline_block: u64,
     /// Gas price used in the last sent transaction.
+    /// For the dynamic fee (EIP-1559) transactions it's the max fee per gas.
     pub last_used_gas_price: U256,
+    /// Priority fee per gas used in the last sent transaction.
+    /// Set only if it was a dynamic fee (EIP-1559) transaction.
+    pub last_used_priority_fee: Option<U256>,
     /// Hashes of all the sent transactions.
     pub used_tx_hashes: Vec<H256>,
     /// Tx payload (not signed).
@@ -45,6 +50,29 @@ impl ETHOperation {
         current_block >= self.last_deadline_block
     }
 
+    /// Calculates the gas limit for transaction to be send, depending on the type of operation.
+    /// This estimation is a higher bound based on a pre-calculated cost of every operation in the block.
+    pub fn gas_limit(&self) -> U256 {
+        let (_, op) = self
+            .op
+            .as_ref()
+            .expect("Operation not found - can't compute gas limit");
+        match op {
+            AggregatedOperation::CommitBlocks(commit) => {
+                GasCounter::commit_gas_limit_aggregated(&commit.blocks)
+            }
+            AggregatedOperation::ExecuteBlocks(execute) => {
+                GasCounter::execute_gas_limit_aggregated(&execute.blocks)
+            }
+            AggregatedOperation::PublishProofBlocksOnchain(_) => {
+                U256::from(GasCounter::BASE_PROOF_BLOCKS_TX_COST)
+            }
+            AggregatedOperation::CreateProofBlocks(_) => {
+                panic!("Can't compute gas limit for CreateProofBlocks")
+            }
+        }
+    }
+
     /// Completes the object state with the data obtained from the database.
     pub fn complete(&mut self, inserted_data: InsertedOperationResponse) {
         self.id = inserted_data.id;
@@ -61,6 +89,7 @@ impl PartialEq for ETHOperation {
         (self.id == other.id)
             && (self.last_deadline_block == other.last_deadline_block)
             && (self.last_used_gas_price == other.last_used_gas_price)
+            && (self.last_used_priority_fee == other.last_used_priority_fee)
             && (self.used_tx_hashes == other.used_tx_hashes)
             && (self.confirmed == other.confirmed)
             && (self.final_hash == other.final_hash)
@@ -57,6 +57,21 @@ pub enum TxAddError {
 
     #[error("Too many Ethereum signatures provided")]
     EthSignaturesLimitExceeded,
+
+    #[error("Account has too many transactions in the mempool")]
+    AccountTxsLimitReached,
+
+    #[error("Mempool is full")]
+    MempoolIsFull,
+
+    #[error("Fee is too low to replace the queued transaction with the same nonce")]
+    ReplacementFeeTooLow,
+
+    #[error("Fee of the replaced or the new transaction can't be converted to USD")]
+    ReplacementFeeUnknown,
+
+    #[error("Transaction with the same nonce is already included into the pending block")]
+    NonceUsedInPendingBlock,
 }
 
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
@@ -1,4 +1,5 @@
 import { Command } from 'commander';
+import { spawnSync } from 'child_process';
 import * as utils from '../utils';
 import * as env from '../env';
 import fetch from 'node-fetch';
@@ -27,6 +28,24 @@ export async function contract() {
     console.log('Successfully inserted contract address into the database');
 }
 
+export async function proverToken(name: string, token: string) {
+    // The values are passed as psql variables and quoted by psql itself, so they are never
+    // interpolated into the query text. Only the hash of the token is stored.
+    const query = `INSERT INTO prover_tokens (prover_name, token_hash)
+                   VALUES (:'name', encode(sha256(:'token'::bytea), 'hex'))
+                   ON CONFLICT (prover_name) DO UPDATE
+                   SET (token_hash, created_at, revoked_at) = (EXCLUDED.token_hash, now(), NULL);`;
+    const psql = spawnSync(
+        'psql',
+        [process.env.DATABASE_URL as string, '-v', 'ON_ERROR_STOP=1', '-v', `name=${name}`, '-v', `token=${token}`],
+        { input: query, stdio: ['pipe', 'inherit', 'inherit'] }
+    );
+    if (psql.status !== 0) {
+        throw new Error(`Failed to register token of the prover ${name}`);
+    }
+    console.log(`Successfully registered token of the prover ${name}`);
+}
+
 export async function ethData() {
     // force read env
     env.reload();
@@ -55,4 +74,8 @@ export const command = new Command('insert').description('insert pre-defined dat
 
 command.command('contract').description('insert contract addresses').action(contract);
 command.command('token <id> <address> <symbol> <precision>').description('insert token information').action(token);
+command
+    .command('prover-token <name> <token>')
+    .description('register the prover API v2 token of the prover')
+    .action(proverToken);
 command.command('eth-data').description('insert info about Rootstock blockchain').action(ethData);
@@ -25,6 +25,7 @@ export async function init(crypto: boolean, withDocker: boolean) {
     await utils.announced('Checking PLONK setup', run.plonkSetup());
     await utils.announced('Unpacking verification  keys', run.verifyKeys.unpack());
     await utils.announced('Setting up database', db.setup());
+    await utils.announced('Registering dev prover token', registerProverToken());
     await utils.announced('Building contracts', contract.build());
     await utils.announced('Deploying localhost ERC20 tokens', run.deployERC20('dev'));
     await utils.announced('Deploying localhost EIP1271 contract', run.deployEIP1271());
@@ -39,16 +40,22 @@ export async function init(crypto: boolean, withDocker: boolean) {
 export async function reinit(withDocker: boolean) {
     await utils.announced('Setting up containers', up());
     await utils.announced('Setting up database', db.setup());
+    await utils.announced('Registering dev prover token', registerProverToken());
     await utils.announced('Building contracts', contract.build());
     await utils.announced('Running server genesis setup', server.genesis(withDocker));
     await utils.announced('Deploying main contracts', contract.redeploy());
     await utils.announced('Restarting dev liquidity watcher', docker.restart('dev-ticker'));
 }
 
+async function registerProverToken() {
+    await db.insert.proverToken('dev-prover', process.env.PROVER_PROVER_AUTH_TOKEN as string);
+}
+
 async function createVolumes() {
     await utils.exec('mkdir -p $ZKSYNC_HOME/volumes/rskj');
     await utils.exec('mkdir -p $ZKSYNC_HOME/volumes/postgres');
     await utils.exec('mkdir -p $ZKSYNC_HOME/volumes/tesseracts');
+    await utils.exec('mkdir -p $ZKSYNC_HOME/volumes/minio');
 }
 
 export const initCommand = new Command('init')